@@ -6,7 +6,7 @@ use r3_support_rza1::sprintln;
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
     // Disable IRQ
-    unsafe { llvm_asm!("cpsid i"::::"volatile") };
+    unsafe { asm!("cpsid i") };
 
     sprintln!("{}", info);
 
@@ -44,7 +44,7 @@
 //!
 //! [pdf]: http://infocenter.arm.com/help/topic/com.arm.doc.dui0471e/DUI0471E_developing_for_arm_processors.pdf
 
-#![feature(llvm_asm)]
+#![feature(asm)]
 #![deny(missing_docs)]
 #![deny(unsupported_naked_functions)]
 #![no_std]
@@ -76,7 +76,7 @@ pub unsafe fn syscall1(_nr: usize, _arg: usize) -> usize {
         #[cfg(all(thumb, arm, not(feature = "no-semihosting")))]
         () => {
             let mut nr = _nr;
-            llvm_asm!("svc 0xAB" : "+{r0}"(nr) : "{r1}"(_arg) : "lr" : "volatile");
+            asm!("svc 0xAB", inout("r0") nr, in("r1") _arg, out("lr") _);
             nr
         }
 
@@ -86,7 +86,7 @@ pub unsafe fn syscall1(_nr: usize, _arg: usize) -> usize {
         #[cfg(all(not(thumb), arm, not(feature = "no-semihosting")))]
         () => {
             let mut nr = _nr;
-            llvm_asm!("svc 0x123456" : "+{r0}"(nr) : "{r1}"(_arg) : "lr" : "volatile");
+            asm!("svc 0x123456", inout("r0") nr, in("r1") _arg, out("lr") _);
             nr
         }
 
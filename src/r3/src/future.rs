@@ -0,0 +1,116 @@
+//! Integration with `async`/`await`.
+//!
+//! This module provides a minimal executor, [`block_on`], which runs a future
+//! to completion in the current task, parking the task while the future is
+//! not ready. The [`Waker`] used by the executor wakes up the task by
+//! [`Task::unpark`].
+//!
+//! This module also provides asynchronous counterparts of some kernel
+//! objects, such as [`AsyncSemaphore`] and [`AsyncEventGroup`]. Their wait
+//! operations return futures instead of blocking the current task, so
+//! multiple wait operations can be combined in a single task.
+//!
+//! [`Task::unpark`]: crate::kernel::Task::unpark
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+
+use crate::kernel::{Id, Kernel, ParkError, Task, UnparkError};
+
+pub mod event_group;
+pub mod semaphore;
+mod waker_list;
+#[doc(no_inline)]
+pub use self::{event_group::AsyncEventGroup, semaphore::AsyncSemaphore};
+
+/// Error type of [`block_on`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum BlockOnError {
+    /// CPU Lock is active, or the current context is not [a task context].
+    ///
+    /// [a task context]: crate#contexts
+    BadContext = ParkError::BadContext as i8,
+}
+
+/// Run a future to completion in the current task.
+///
+/// The current task is parked while the future is not ready. The future is
+/// polled again when the task is unparked, which is done by the [`Waker`]
+/// passed to the future ([`task_waker`]) or possibly by other code calling
+/// [`Task::unpark`] on the current task. Interruption by [`Task::interrupt`]
+/// just causes the future to be polled again.
+///
+/// [`Task::unpark`]: crate::kernel::Task::unpark
+/// [`Task::interrupt`]: crate::kernel::Task::interrupt
+pub fn block_on<System: Kernel, F: Future>(future: F) -> Result<F::Output, BlockOnError> {
+    if System::has_cpu_lock() {
+        return Err(BlockOnError::BadContext);
+    }
+    let task = match Task::<System>::current() {
+        Ok(Some(task)) => task,
+        _ => return Err(BlockOnError::BadContext),
+    };
+
+    let waker = task_waker(task);
+    let mut cx = Context::from_waker(&waker);
+
+    let mut future = future;
+    // Safety: `future` is never moved after this point because it's
+    //         shadowed
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return Ok(output);
+        }
+
+        match System::park() {
+            Ok(()) | Err(ParkError::Interrupted) => {}
+            Err(ParkError::BadContext) => return Err(BlockOnError::BadContext),
+        }
+    }
+}
+
+/// Construct a [`Waker`] that wakes up the specified task by
+/// [`Task::unpark`].
+///
+/// Waking up a task in the Dormant state does nothing. The waker will panic if
+/// it's woken up while CPU Lock is active.
+///
+/// [`Task::unpark`]: crate::kernel::Task::unpark
+pub fn task_waker<System: Kernel>(task: Task<System>) -> Waker {
+    // Safety: `TaskWaker::VTABLE` upholds the `RawWaker` contract
+    unsafe { Waker::from_raw(TaskWaker::<System>::raw_waker(task.id())) }
+}
+
+/// Provides the `RawWakerVTable` for [`task_waker`]. The data pointer of a
+/// `RawWaker` is a task ID.
+struct TaskWaker<System>(PhantomData<System>);
+
+impl<System: Kernel> TaskWaker<System> {
+    const VTABLE: RawWakerVTable =
+        RawWakerVTable::new(Self::clone_waker, Self::wake, Self::wake, Self::drop_waker);
+
+    fn raw_waker(id: Id) -> RawWaker {
+        RawWaker::new(id.get() as *const (), &Self::VTABLE)
+    }
+
+    unsafe fn clone_waker(data: *const ()) -> RawWaker {
+        RawWaker::new(data, &Self::VTABLE)
+    }
+
+    unsafe fn wake(data: *const ()) {
+        // Safety: `data` was created from a valid task ID by `raw_waker`
+        let task = unsafe { Task::<System>::from_id(Id::new_unchecked(data as usize)) };
+        match task.unpark() {
+            Ok(()) | Err(UnparkError::BadObjectState) => {}
+            Err(e) => panic!("failed to wake up {:?}: {:?}", task, e),
+        }
+    }
+
+    unsafe fn drop_waker(_: *const ()) {}
+}
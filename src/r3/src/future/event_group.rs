@@ -0,0 +1,190 @@
+//! Asynchronous event groups
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::waker_list::{RegisterResult, WakerList};
+use crate::kernel::{
+    self,
+    cfg::{CfgBuilder, CfgEventGroupBuilder},
+    EventGroupBits, EventGroupWaitFlags, GetEventGroupError, Kernel, PollEventGroupError,
+    UpdateEventGroupError,
+};
+
+/// Configuration builder type for [`AsyncEventGroup`].
+pub struct Builder<System> {
+    event_group: CfgEventGroupBuilder<System>,
+    max_waiters: usize,
+}
+
+/// An event group whose wait operation returns a future.
+///
+/// This type is implemented using [`r3::kernel::EventGroup`]. A pending
+/// [`wait`](Self::wait) future registers its [`Waker`] to a list, which is
+/// woken up by [`set`](Self::set). Setting bits directly through the
+/// underlying kernel event group doesn't wake up the futures.
+///
+/// The list has a fixed capacity specified by [`Builder::max_waiters`]. When
+/// the list is full, a pending future falls back to busy-polling by waking
+/// itself up immediately.
+///
+/// [`r3::kernel::EventGroup`]: crate::kernel::EventGroup
+/// [`Waker`]: core::task::Waker
+pub struct AsyncEventGroup<System> {
+    event_group: kernel::EventGroup<System>,
+    wakers: WakerList<System>,
+}
+
+impl<System> Clone for AsyncEventGroup<System> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<System> Copy for AsyncEventGroup<System> {}
+
+impl<System: Kernel> fmt::Debug for AsyncEventGroup<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncEventGroup")
+            .field("event_group", &self.event_group)
+            .finish()
+    }
+}
+
+impl<System: Kernel> AsyncEventGroup<System> {
+    /// Construct a `Builder` to define an event group in [a configuration
+    /// function](crate#static-configuration).
+    pub const fn build() -> Builder<System> {
+        Builder {
+            event_group: kernel::EventGroup::build(),
+            max_waiters: 1,
+        }
+    }
+}
+
+impl<System: Kernel> Builder<System> {
+    /// Specify the initial bit pattern.
+    pub const fn initial(self, initial: EventGroupBits) -> Self {
+        Self {
+            event_group: self.event_group.initial(initial),
+            ..self
+        }
+    }
+
+    /// Specify the maximum number of wakers that can be registered at the same
+    /// time without falling back to busy-polling. Defaults to `1`.
+    pub const fn max_waiters(self, max_waiters: usize) -> Self {
+        Self {
+            max_waiters,
+            ..self
+        }
+    }
+
+    /// Complete the definition of an event group, returning a reference to
+    /// the event group.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> AsyncEventGroup<System> {
+        AsyncEventGroup {
+            event_group: self.event_group.finish(cfg),
+            wakers: WakerList::new(cfg, self.max_waiters),
+        }
+    }
+}
+
+impl<System: Kernel> AsyncEventGroup<System> {
+    /// Get the underlying kernel event group.
+    pub const fn event_group(self) -> kernel::EventGroup<System> {
+        self.event_group
+    }
+
+    /// Set the specified bits and wake up the pending [`wait`](Self::wait)
+    /// futures.
+    ///
+    /// This method can be called in any context where CPU Lock is inactive,
+    /// including an interrupt context.
+    pub fn set(self, bits: EventGroupBits) -> Result<(), UpdateEventGroupError> {
+        self.event_group.set(bits)?;
+        self.wakers
+            .wake_all()
+            .map_err(|_| UpdateEventGroupError::BadContext)
+    }
+
+    /// Clear the specified bits, returning the bits that were set before
+    /// clearing.
+    pub fn clear(self, bits: EventGroupBits) -> Result<EventGroupBits, UpdateEventGroupError> {
+        self.event_group.clear(bits)
+    }
+
+    /// Get the currently set bits.
+    pub fn get(self) -> Result<EventGroupBits, GetEventGroupError> {
+        self.event_group.get()
+    }
+
+    /// Construct a future that completes when all or any of the specified
+    /// bits are set, optionally clearing the specified bits. The future
+    /// outputs the bits before clearing.
+    ///
+    /// The future will panic if it's polled while CPU Lock is active.
+    pub fn wait(&self, bits: EventGroupBits, flags: EventGroupWaitFlags) -> Wait<'_, System> {
+        Wait {
+            event_group: self,
+            bits,
+            flags,
+        }
+    }
+}
+
+/// The future returned by [`AsyncEventGroup::wait`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Wait<'a, System> {
+    event_group: &'a AsyncEventGroup<System>,
+    bits: EventGroupBits,
+    flags: EventGroupWaitFlags,
+}
+
+impl<System: Kernel> fmt::Debug for Wait<'_, System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Wait")
+            .field("event_group", &self.event_group)
+            .field("bits", &self.bits)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
+
+impl<System: Kernel> Wait<'_, System> {
+    fn poll_wait(&self) -> Option<EventGroupBits> {
+        match self.event_group.event_group.poll(self.bits, self.flags) {
+            Ok(bits) => Some(bits),
+            Err(PollEventGroupError::Timeout) => None,
+            Err(e) => panic!("failed to poll {:?}: {:?}", self.event_group, e),
+        }
+    }
+}
+
+impl<System: Kernel> Future for Wait<'_, System> {
+    type Output = EventGroupBits;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<EventGroupBits> {
+        if let Some(bits) = self.poll_wait() {
+            return Poll::Ready(bits);
+        }
+
+        match self.event_group.wakers.register(cx.waker()) {
+            RegisterResult::Registered => {}
+            RegisterResult::Full => cx.waker().wake_by_ref(),
+            RegisterResult::BadContext => {
+                panic!("failed to poll {:?}: CPU Lock is active", self.event_group)
+            }
+        }
+
+        // The bits might have been set before the waker was registered
+        if let Some(bits) = self.poll_wait() {
+            Poll::Ready(bits)
+        } else {
+            Poll::Pending
+        }
+    }
+}
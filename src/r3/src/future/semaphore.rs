@@ -0,0 +1,195 @@
+//! Asynchronous semaphores
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use super::waker_list::{RegisterResult, WakerList};
+use crate::kernel::{
+    self,
+    cfg::{CfgBuilder, CfgSemaphoreBuilder},
+    GetSemaphoreError, Kernel, PollSemaphoreError, SemaphoreValue, SignalSemaphoreError,
+};
+
+/// Configuration builder type for [`AsyncSemaphore`].
+pub struct Builder<System> {
+    semaphore: CfgSemaphoreBuilder<System>,
+    max_waiters: usize,
+}
+
+/// A semaphore whose wait operation returns a future.
+///
+/// This type is implemented using [`r3::kernel::Semaphore`]. A pending
+/// [`wait`](Self::wait) future registers its [`Waker`] to a list, which is
+/// woken up by [`signal`](Self::signal). Releasing permits directly through the
+/// underlying kernel semaphore doesn't wake up the futures.
+///
+/// The list has a fixed capacity specified by [`Builder::max_waiters`]. When
+/// the list is full, a pending future falls back to busy-polling by waking
+/// itself up immediately.
+///
+/// [`r3::kernel::Semaphore`]: crate::kernel::Semaphore
+/// [`Waker`]: core::task::Waker
+pub struct AsyncSemaphore<System> {
+    semaphore: kernel::Semaphore<System>,
+    wakers: WakerList<System>,
+}
+
+impl<System> Clone for AsyncSemaphore<System> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<System> Copy for AsyncSemaphore<System> {}
+
+impl<System: Kernel> fmt::Debug for AsyncSemaphore<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncSemaphore")
+            .field("semaphore", &self.semaphore)
+            .finish()
+    }
+}
+
+impl<System: Kernel> AsyncSemaphore<System> {
+    /// Construct a `Builder` to define a semaphore in [a configuration
+    /// function](crate#static-configuration).
+    pub const fn build() -> Builder<System> {
+        Builder {
+            semaphore: kernel::Semaphore::build(),
+            max_waiters: 1,
+        }
+    }
+}
+
+impl<System: Kernel> Builder<System> {
+    /// \[**Required**\] Specify the initial semaphore value.
+    ///
+    /// Must be less than or equal to [`maximum`](Self::maximum).
+    pub const fn initial(self, initial: SemaphoreValue) -> Self {
+        Self {
+            semaphore: self.semaphore.initial(initial),
+            ..self
+        }
+    }
+
+    /// \[**Required**\] Specify the maximum semaphore value.
+    pub const fn maximum(self, maximum: SemaphoreValue) -> Self {
+        Self {
+            semaphore: self.semaphore.maximum(maximum),
+            ..self
+        }
+    }
+
+    /// Specify the maximum number of wakers that can be registered at the same
+    /// time without falling back to busy-polling. Defaults to `1`.
+    pub const fn max_waiters(self, max_waiters: usize) -> Self {
+        Self {
+            max_waiters,
+            ..self
+        }
+    }
+
+    /// Complete the definition of a semaphore, returning a reference to the
+    /// semaphore.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> AsyncSemaphore<System> {
+        AsyncSemaphore {
+            semaphore: self.semaphore.finish(cfg),
+            wakers: WakerList::new(cfg, self.max_waiters),
+        }
+    }
+}
+
+impl<System: Kernel> AsyncSemaphore<System> {
+    /// Get the underlying kernel semaphore.
+    pub const fn semaphore(self) -> kernel::Semaphore<System> {
+        self.semaphore
+    }
+
+    /// Get the number of permits currently held by the semaphore.
+    pub fn get(self) -> Result<SemaphoreValue, GetSemaphoreError> {
+        self.semaphore.get()
+    }
+
+    /// Release `count` permits, returning them to the semaphore, and wake up
+    /// the pending [`wait`](Self::wait) futures.
+    ///
+    /// This method can be called in any context where CPU Lock is inactive,
+    /// including an interrupt context.
+    pub fn signal(self, count: SemaphoreValue) -> Result<(), SignalSemaphoreError> {
+        self.semaphore.signal(count)?;
+        self.wakers
+            .wake_all()
+            .map_err(|_| SignalSemaphoreError::BadContext)
+    }
+
+    /// Release a permit, returning it to the semaphore, and wake up the
+    /// pending [`wait`](Self::wait) futures.
+    pub fn signal_one(self) -> Result<(), SignalSemaphoreError> {
+        self.signal(1)
+    }
+
+    /// Acquire a permit without blocking.
+    pub fn try_wait(self) -> Result<(), PollSemaphoreError> {
+        self.semaphore.poll_one()
+    }
+
+    /// Construct a future that completes when a permit is acquired from the
+    /// semaphore.
+    ///
+    /// The future will panic if it's polled while CPU Lock is active.
+    pub fn wait(&self) -> Wait<'_, System> {
+        Wait { semaphore: self }
+    }
+
+    fn poll_wait(self) -> bool {
+        match self.try_wait() {
+            Ok(()) => true,
+            Err(PollSemaphoreError::Timeout) => false,
+            Err(e) => panic!("failed to poll {:?}: {:?}", self, e),
+        }
+    }
+}
+
+/// The future returned by [`AsyncSemaphore::wait`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Wait<'a, System> {
+    semaphore: &'a AsyncSemaphore<System>,
+}
+
+impl<System: Kernel> fmt::Debug for Wait<'_, System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Wait")
+            .field("semaphore", &self.semaphore)
+            .finish()
+    }
+}
+
+impl<System: Kernel> Future for Wait<'_, System> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let semaphore = *self.semaphore;
+
+        if semaphore.poll_wait() {
+            return Poll::Ready(());
+        }
+
+        match semaphore.wakers.register(cx.waker()) {
+            RegisterResult::Registered => {}
+            RegisterResult::Full => cx.waker().wake_by_ref(),
+            RegisterResult::BadContext => {
+                panic!("failed to poll {:?}: CPU Lock is active", semaphore)
+            }
+        }
+
+        // A permit might have been released before the waker was registered
+        if semaphore.poll_wait() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use core::{cell::UnsafeCell, mem::MaybeUninit, task::Waker};
+
+use crate::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, CpuLockError, Kernel},
+    utils::ZeroInit,
+};
+
+/// A fixed-capacity list of [`Waker`]s to wake up when the state of an
+/// asynchronous kernel object changes. The list is protected by CPU Lock.
+pub(super) struct WakerList<System> {
+    slots: Hunk<System, [WakerSlot]>,
+}
+
+impl<System> Clone for WakerList<System> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<System> Copy for WakerList<System> {}
+
+#[doc(hidden)]
+pub struct WakerSlot {
+    /// `true` if `waker` is initialized. Protected by CPU Lock.
+    occupied: UnsafeCell<bool>,
+    /// Protected by CPU Lock.
+    waker: UnsafeCell<MaybeUninit<Waker>>,
+}
+
+// Safety: All-zero bytes represent an empty slot
+unsafe impl ZeroInit for WakerSlot {}
+
+// Safety: The contents are protected by CPU Lock. `Waker` is `Send + Sync`.
+unsafe impl Sync for WakerSlot {}
+
+/// The result of [`WakerList::register`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RegisterResult {
+    /// The waker was registered or already present.
+    Registered,
+    /// There was no room for the waker.
+    Full,
+    /// CPU Lock is active.
+    BadContext,
+}
+
+impl<System: Kernel> WakerList<System> {
+    /// Define a `WakerList` with the specified capacity.
+    pub(super) const fn new(cfg: &mut CfgBuilder<System>, capacity: usize) -> Self {
+        assert!(capacity > 0, "the waker list capacity must be non-zero");
+        Self {
+            slots: Hunk::build().len(capacity).zeroed().finish(cfg),
+        }
+    }
+
+    /// Register `waker` to be woken up by [`Self::wake_all`].
+    pub(super) fn register(self, waker: &Waker) -> RegisterResult {
+        // Clone the waker outside the CPU Lock section because
+        // `Waker::clone` may run arbitrary code
+        let mut new_waker = Some(waker.clone());
+
+        if System::acquire_cpu_lock().is_err() {
+            return RegisterResult::BadContext;
+        }
+
+        let mut result = RegisterResult::Full;
+        let mut empty_slot = None;
+        for slot in self.slots.iter() {
+            // Safety: CPU Lock active
+            if unsafe { *slot.occupied.get() } {
+                // Safety: CPU Lock active, and `waker` is initialized
+                if unsafe { &*(*slot.waker.get()).as_ptr() }.will_wake(waker) {
+                    result = RegisterResult::Registered;
+                    break;
+                }
+            } else if empty_slot.is_none() {
+                empty_slot = Some(slot);
+            }
+        }
+
+        if result == RegisterResult::Full {
+            if let Some(slot) = empty_slot {
+                // Safety: CPU Lock active
+                unsafe {
+                    *slot.waker.get() = MaybeUninit::new(new_waker.take().unwrap());
+                    *slot.occupied.get() = true;
+                }
+                result = RegisterResult::Registered;
+            }
+        }
+
+        // Safety: CPU Lock was acquired by this method
+        unsafe { System::release_cpu_lock() }.unwrap();
+
+        // `new_waker` (if not used) is dropped outside the CPU Lock section
+        drop(new_waker);
+
+        result
+    }
+
+    /// Wake up and unregister all registered wakers.
+    pub(super) fn wake_all(self) -> Result<(), CpuLockError> {
+        for slot in self.slots.iter() {
+            System::acquire_cpu_lock()?;
+
+            // Safety: CPU Lock active
+            let waker = unsafe {
+                if *slot.occupied.get() {
+                    *slot.occupied.get() = false;
+                    Some((*slot.waker.get()).as_ptr().read())
+                } else {
+                    None
+                }
+            };
+
+            // Safety: CPU Lock was acquired by this method
+            unsafe { System::release_cpu_lock() }.unwrap();
+
+            // Wake up the waker outside the CPU Lock section because
+            // `Waker::wake` may run arbitrary code
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+
+        Ok(())
+    }
+}
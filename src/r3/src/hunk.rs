@@ -51,6 +51,7 @@ impl<System: Kernel, T: ?Sized> Hunk<System, T> {
             _phantom: PhantomData,
             len: 1,
             align: 1,
+            init_fn: None,
         }
     }
 }
@@ -70,8 +71,16 @@ pub struct DefaultInitTag;
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub struct ZeroInitTag;
 
-/// Implemented on [`DefaultInitTag`] and [`ZeroInitTag`] when `T` can be
-/// initialized in this way.
+/// As a generic parameter of [`CfgHunkBuilder`], indicates that the [hunk]
+/// should be initialized with a function specified by
+/// [`CfgHunkBuilder::init_with`].
+///
+/// [hunk]: crate::kernel::Hunk
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct InitWithTag;
+
+/// Implemented on [`DefaultInitTag`], [`ZeroInitTag`], and [`InitWithTag`]
+/// when `T` can be initialized in this way.
 pub trait HunkIniter<T> {
     /// A flag indicating whether [`Self::init`] should be called for
     /// initialization.
@@ -95,9 +104,17 @@ impl<T> HunkIniter<T> for ZeroInitTag {
     }
 }
 
+impl<T> HunkIniter<T> for InitWithTag {
+    const NEEDS_INIT: bool = false;
+    fn init(_: &mut mem::MaybeUninit<T>) {
+        // Do nothing - the initialization is done by `init_with_trampoline`
+    }
+}
+
 /// Configuration builder type for [`Hunk`].
 ///
-/// `InitTag` is either [`DefaultInitTag`] or [`ZeroInitTag`].
+/// `InitTag` is one of [`DefaultInitTag`], [`ZeroInitTag`], and
+/// [`InitWithTag`].
 ///
 /// [`Hunk`]: crate::kernel::Hunk
 #[must_use = "must call `finish()` to complete registration"]
@@ -105,6 +122,9 @@ pub struct CfgHunkBuilder<System, T: ?Sized, InitTag> {
     _phantom: PhantomData<(System, InitTag, T)>,
     len: usize,
     align: usize,
+    /// The function specified by [`Self::init_with`], type-erased by
+    /// [`InitFnCast`]. Must not be called as `fn(usize)`.
+    init_fn: Option<fn(usize)>,
 }
 
 impl<System: Kernel, T: ?Sized, InitTag> CfgHunkBuilder<System, T, InitTag> {
@@ -114,7 +134,8 @@ impl<System: Kernel, T: ?Sized, InitTag> CfgHunkBuilder<System, T, InitTag> {
         Self { len, ..self }
     }
 
-    /// Specify the minimum alignment. Defaults to `1`.
+    /// Specify the minimum alignment. Must be a power of two. Defaults to
+    /// `1`.
     pub const fn align(self, align: usize) -> Self {
         Self { align, ..self }
     }
@@ -139,13 +160,59 @@ impl<System: Kernel, T: ?Sized, InitTag> CfgHunkBuilder<System, T, InitTag> {
             _phantom: PhantomData,
             len: self.len,
             align: self.align,
+            init_fn: None,
+        }
+    }
+}
+
+impl<System: Kernel, T, InitTag> CfgHunkBuilder<System, T, InitTag> {
+    /// Initialize the hunk with the return value of the specified function.
+    ///
+    /// `f` is called during the boot process by a startup hook having the
+    /// priority [`INIT_HOOK_PRIORITY`]. This means the hunk is initialized
+    /// before any startup hooks with non-negative priorities are called.
+    pub const fn init_with(self, f: fn() -> T) -> CfgHunkBuilder<System, T, InitWithTag> {
+        CfgHunkBuilder {
+            _phantom: PhantomData,
+            len: self.len,
+            align: self.align,
+            // Safety: `fn() -> T` and `fn(usize)` have the same size. The
+            // result is converted back to `fn() -> T` before being called.
+            init_fn: Some(unsafe { InitFnCast { typed: f }.erased }),
         }
     }
 }
 
+/// Used to type-erase [`CfgHunkBuilder::init_with`]'s parameter.
+union InitFnCast<T> {
+    typed: fn() -> T,
+    erased: fn(usize),
+    erased_unsafe: unsafe fn(usize),
+}
+
+/// The [startup hook trampoline] for a hunk initialized by
+/// [`CfgHunkBuilder::init_with`]. `f` is the type-erased initialization
+/// function, and `start` is the offset of the hunk.
+///
+/// [startup hook trampoline]: crate::kernel::StartupHookTrampoline
+///
+/// # Safety
+///
+/// `f` must be originally `fn() -> T`. `start` must be the offset of an untyped
+/// hunk large enough to contain `T`.
+unsafe fn init_with_trampoline<System: Kernel, T>(f: unsafe fn(usize), start: usize) {
+    // Safety: `f` is originally `fn() -> T`
+    let f = unsafe { InitFnCast::<T> { erased_unsafe: f }.typed };
+    let untyped_hunk = kernel::Hunk::<System>::from_offset(start).as_ptr();
+    // Safety: The destination is large enough to contain `T`
+    unsafe { (untyped_hunk as *mut T).write(f()) };
+}
+
 impl<System: Kernel, T, InitTag: HunkIniter<T>> CfgHunkBuilder<System, T, InitTag> {
     /// Complete the definition of a hunk, returning a reference to the hunk.
     pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> Hunk<System, T> {
+        assert!(self.align.is_power_of_two(), "`align` is not power of two");
+
         let untyped_hunk = kernel::Hunk::<System>::build()
             .len(mem::size_of::<T>())
             .align(max(mem::align_of::<T>(), self.align))
@@ -156,7 +223,19 @@ impl<System: Kernel, T, InitTag: HunkIniter<T>> CfgHunkBuilder<System, T, InitTa
         let start = untyped_hunk.offset();
 
         // Insert an initializer
-        if InitTag::NEEDS_INIT {
+        if let Some(init_fn) = self.init_fn {
+            // Safety: `init_fn` was created by `init_with` from `fn() -> T`,
+            // which `init_with_trampoline::<System, T>` knows how to call
+            unsafe {
+                StartupHook::build()
+                    .priority(INIT_HOOK_PRIORITY)
+                    .start(init_fn)
+                    .trampoline(init_with_trampoline::<System, T>)
+                    .unchecked()
+                    .param(start)
+                    .finish(cfg);
+            }
+        } else if InitTag::NEEDS_INIT {
             unsafe {
                 StartupHook::build()
                     .priority(INIT_HOOK_PRIORITY)
@@ -1,7 +1,12 @@
 //! The RTOS kernel
-#[cfg(feature = "priority_boost")]
-use core::sync::atomic::{AtomicBool, Ordering};
-use core::{fmt, marker::PhantomData, mem::forget, num::NonZeroUsize, ops::Range};
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::forget,
+    num::NonZeroUsize,
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use crate::{
     time::{Duration, Time},
@@ -10,22 +15,29 @@ use crate::{
 
 #[macro_use]
 pub mod cfg;
+mod cpu_lock;
+mod deferred;
 mod error;
 mod event_group;
 mod hunk;
 mod interrupt;
+mod latency;
 mod mutex;
+mod notification;
 mod semaphore;
+mod shutdown;
 mod startup;
 mod state;
 mod task;
+mod task_local;
 mod timeout;
 mod timer;
 mod utils;
 mod wait;
 pub use self::{
-    error::*, event_group::*, hunk::*, interrupt::*, mutex::*, semaphore::*, startup::*, task::*,
-    timeout::*, timer::*, wait::*,
+    cpu_lock::*, deferred::*, error::*, event_group::*, hunk::*, interrupt::*, latency::*,
+    mutex::*, notification::*, semaphore::*, shutdown::*, startup::*, state::*, task::*,
+    task_local::*, timeout::*, timer::*, wait::*,
 };
 
 /// Numeric value used to identify various kinds of kernel objects.
@@ -50,8 +62,12 @@ pub trait Kernel: Port + KernelCfg2 + Sized + 'static {
     ///
     /// Returns [`BadContext`] if CPU Lock is already active.
     ///
+    /// Consider using [`lock_cpu`] instead to create a critical section. It
+    /// deactivates CPU Lock on scope exit without requiring `unsafe`.
+    ///
     /// [CPU Lock]: crate#system-states
     /// [`BadContext`]: CpuLockError::BadContext
+    /// [`lock_cpu`]: Self::lock_cpu
     fn acquire_cpu_lock() -> Result<(), CpuLockError>;
 
     /// Deactivate [CPU Lock].
@@ -73,6 +89,21 @@ pub trait Kernel: Port + KernelCfg2 + Sized + 'static {
     /// Return a flag indicating whether CPU Lock is currently active.
     fn has_cpu_lock() -> bool;
 
+    /// Activate [CPU Lock] and return an RAII guard that deactivates CPU Lock
+    /// when dropped.
+    ///
+    /// Returns [`BadContext`] if CPU Lock is already active.
+    ///
+    /// Unlike the pair of [`acquire_cpu_lock`] and [`release_cpu_lock`], this
+    /// method makes it impossible to forget to deactivate CPU Lock or to
+    /// deactivate CPU Lock that was activated by someone else.
+    ///
+    /// [CPU Lock]: crate#system-states
+    /// [`BadContext`]: CpuLockError::BadContext
+    /// [`acquire_cpu_lock`]: Self::acquire_cpu_lock
+    /// [`release_cpu_lock`]: Self::release_cpu_lock
+    fn lock_cpu() -> Result<CpuLockGuard<Self>, CpuLockError>;
+
     /// Activate [Priority Boost].
     ///
     /// Returns [`BadContext`] if Priority Boost is already active, the
@@ -84,6 +115,18 @@ pub trait Kernel: Port + KernelCfg2 + Sized + 'static {
     #[doc(cfg(feature = "priority_boost"))]
     fn boost_priority() -> Result<(), BoostPriorityError>;
 
+    /// Return a flag indicating whether [`boost_priority`] would succeed if
+    /// called in the current context, i.e., the calling context is a task
+    /// context, and CPU Lock and [Priority Boost] are both inactive.
+    ///
+    /// This method has no side effects.
+    ///
+    /// [`boost_priority`]: Self::boost_priority
+    /// [Priority Boost]: crate#system-states
+    #[cfg(feature = "priority_boost")]
+    #[doc(cfg(feature = "priority_boost"))]
+    fn can_boost_priority() -> bool;
+
     /// Deactivate [Priority Boost].
     ///
     /// Returns [`BadContext`] if Priority Boost is already inactive, the
@@ -104,6 +147,27 @@ pub trait Kernel: Port + KernelCfg2 + Sized + 'static {
     /// [Priority Boost]: crate#system-states
     fn is_priority_boost_active() -> bool;
 
+    /// Get the kind of [context] the calling thread is running in.
+    ///
+    /// Unlike most system services, this method can be called in any context,
+    /// regardless of whether CPU Lock is active or not.
+    ///
+    /// [context]: crate#contexts
+    fn current_context() -> Context;
+
+    /// Shut down the system.
+    ///
+    /// This method activates CPU Lock (if it's not active yet), calls
+    /// [shutdown hooks], and then spins forever.
+    ///
+    /// This is meant for bare-metal ports. A port running on a hosted
+    /// environment may provide its own way to shut down the system (e.g.,
+    /// `r3_port_std::shutdown`), which should be preferred because it also
+    /// lets the port stop gracefully.
+    ///
+    /// [shutdown hooks]: ShutdownHook
+    fn shutdown() -> !;
+
     /// Get the current [system time].
     ///
     /// [system time]: crate#kernel-timing
@@ -235,7 +299,53 @@ pub trait Kernel: Port + KernelCfg2 + Sized + 'static {
     /// > more predictable.
     fn adjust_time(delta: Duration) -> Result<(), AdjustTimeError>;
 
-    // TODO: get time resolution?
+    /// Get the effective granularity of the [system time] and timeouts,
+    /// derived from [`PortTimer::RESOLUTION`].
+    ///
+    /// A timeout or a sleep shorter than this value might not be honored
+    /// precisely. For example, with a tickful timer driver, timed events are
+    /// only processed at tick boundaries, and therefore the returned value
+    /// equals to the tick period.
+    ///
+    /// This method can be called in any context.
+    ///
+    /// [system time]: crate#kernel-timing
+    fn time_resolution() -> Duration;
+
+    /// Get the time elapsed since boot.
+    ///
+    /// Unlike the [system time], the monotonic time only moves forward at a
+    /// steady rate. It isn't affected by [`set_time`] nor [`adjust_time`],
+    /// making it suitable for measuring intervals.
+    ///
+    /// Unlike [`time`], this method can be called in any context, including
+    /// an interrupt context and a [startup hook]. If CPU Lock is active, this
+    /// method assumes that it's owned by the caller. Like other system calls,
+    /// this method must not be called in an [unmanaged interrupt handler].
+    ///
+    /// <div class="admonition-follows"></div>
+    ///
+    /// > **Rationale:** This method returns [`core::time::Duration`] instead
+    /// > of [`Duration`](crate::time::Duration) because the latter can only
+    /// > represent about 35 minutes.
+    ///
+    /// [system time]: crate#kernel-timing
+    /// [`set_time`]: Self::set_time
+    /// [`adjust_time`]: Self::adjust_time
+    /// [`time`]: Self::time
+    /// [startup hook]: crate::kernel::StartupHook
+    /// [unmanaged interrupt handler]: crate#interrupt-handling-framework
+    fn monotonic_time() -> core::time::Duration;
+
+    /// Get the total execution time of all tasks since boot.
+    ///
+    /// This is the sum of [`Task::cpu_time`] of all tasks. The difference from
+    /// [`monotonic_time`] approximates the time the processor spent idling.
+    ///
+    /// [`monotonic_time`]: Self::monotonic_time
+    #[cfg(feature = "task_stats")]
+    #[doc(cfg(feature = "task_stats"))]
+    fn total_cpu_time() -> Result<core::time::Duration, TotalCpuTimeError>;
 
     /// Terminate the current task, putting it into the Dormant state.
     ///
@@ -272,8 +382,71 @@ pub trait Kernel: Port + KernelCfg2 + Sized + 'static {
     /// [a non-waitable context]: crate#contexts
     fn park_timeout(timeout: Duration) -> Result<(), ParkTimeoutError>;
 
+    /// Non-blocking version of [`park`](Self::park). Consumes the current
+    /// task's token if it's available. Otherwise, returns immediately with
+    /// [`TryParkError::Timeout`].
+    ///
+    /// This method can be called in a task context even if [Priority Boost]
+    /// is active.
+    ///
+    /// [Priority Boost]: crate#system-states
+    fn try_park() -> Result<(), TryParkError>;
+
     /// Block the current task for the specified duration.
     fn sleep(duration: Duration) -> Result<(), SleepError>;
+
+    /// Block the current task until the [system time] reaches `time`.
+    ///
+    /// [system time]: crate#kernel-timing
+    ///
+    /// Returns `Ok(())` immediately (without blocking) if the current system
+    /// time is already equal to or later than `time`. Returns
+    /// [`SleepUntilError::BadParam`] if `time` is so far in the future that
+    /// the duration until `time` is not representable by [`Duration`].
+    ///
+    /// The wake-up time is converted to a relative duration when this method
+    /// is called. This means that changing the system time by [`set_time`]
+    /// while the task is sleeping doesn't affect the actual wake-up time.
+    /// On the other hand, [`adjust_time`] moves both the system time and the
+    /// wake-up time.
+    ///
+    /// [`set_time`]: Self::set_time
+    /// [`adjust_time`]: Self::adjust_time
+    ///
+    /// This system service may block. Therefore, calling this method is not
+    /// allowed in [a non-waitable context] and will return `Err(BadContext)`.
+    ///
+    /// [a non-waitable context]: crate#contexts
+    #[cfg(feature = "system_time")]
+    #[doc(cfg(feature = "system_time"))]
+    fn sleep_until(time: Time) -> Result<(), SleepUntilError>;
+
+    /// Temporarily change the current task's base priority to `priority` while
+    /// calling `f`, and restore the original base priority when `f` returns.
+    ///
+    /// The base priority is used to calculate the task's [effective priority],
+    /// so the new priority composes with the priority raised by a
+    /// [mutex locking protocol]. Changing the priority may cause preemption
+    /// both at the beginning and the end of the scope.
+    ///
+    /// [effective priority]: Task::effective_priority
+    /// [mutex locking protocol]: MutexProtocol
+    ///
+    /// This method will return [`WithPriorityError::BadContext`] when called in
+    /// a non-task context or when CPU Lock is active. The priority must be in
+    /// range `0..`[`num_task_priority_levels`], and it must not violate the
+    /// precondition of the locking protocol of any held mutexes. Otherwise,
+    /// this method will return [`WithPriorityError::BadParam`].
+    /// In these cases, `f` is not called.
+    ///
+    /// [`num_task_priority_levels`]: crate::kernel::cfg::CfgBuilder::num_task_priority_levels
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the original base priority cannot be restored.
+    /// This happens if `f` leaves CPU Lock active or leaves a mutex locked
+    /// whose locking protocol does not allow the original base priority.
+    fn with_priority<R, F: FnOnce() -> R>(priority: usize, f: F) -> Result<R, WithPriorityError>;
 }
 
 impl<T: Port + KernelCfg2 + 'static> Kernel for T {
@@ -304,12 +477,25 @@ impl<T: Port + KernelCfg2 + 'static> Kernel for T {
         Self::is_cpu_lock_active()
     }
 
+    #[inline]
+    fn lock_cpu() -> Result<CpuLockGuard<Self>, CpuLockError> {
+        Self::acquire_cpu_lock()?;
+        // Safety: We just activated CPU Lock
+        Ok(unsafe { CpuLockGuard::new() })
+    }
+
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     #[cfg(feature = "priority_boost")]
     fn boost_priority() -> Result<(), BoostPriorityError> {
         state::boost_priority::<Self>()
     }
 
+    #[inline]
+    #[cfg(feature = "priority_boost")]
+    fn can_boost_priority() -> bool {
+        state::can_boost_priority::<Self>()
+    }
+
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     unsafe fn unboost_priority() -> Result<(), BoostPriorityError> {
         state::unboost_priority::<Self>()
@@ -327,6 +513,25 @@ impl<T: Port + KernelCfg2 + 'static> Kernel for T {
         false
     }
 
+    #[inline]
+    fn current_context() -> Context {
+        state::current_context::<Self>()
+    }
+
+    fn shutdown() -> ! {
+        // Safety: `try_enter_cpu_lock` is only meant to be called by
+        //         the kernel. It's okay if CPU Lock is already active.
+        unsafe { Self::try_enter_cpu_lock() };
+
+        // Safety: Tasks and managed interrupt handlers can't run anymore
+        //         because CPU Lock is active, and we don't return
+        unsafe { <Self as PortToKernel>::call_shutdown_hooks() };
+
+        loop {
+            core::hint::spin_loop();
+        }
+    }
+
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     #[cfg(feature = "system_time")]
     fn time() -> Result<Time, TimeError> {
@@ -341,6 +546,22 @@ impl<T: Port + KernelCfg2 + 'static> Kernel for T {
         timeout::adjust_system_and_event_time::<Self>(delta)
     }
 
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    fn monotonic_time() -> core::time::Duration {
+        timeout::monotonic_time::<Self>()
+    }
+
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    #[cfg(feature = "task_stats")]
+    fn total_cpu_time() -> Result<core::time::Duration, TotalCpuTimeError> {
+        task::total_cpu_time::<Self>()
+    }
+
+    #[inline]
+    fn time_resolution() -> Duration {
+        timeout::time_resolution::<Self>()
+    }
+
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     unsafe fn exit_task() -> Result<!, ExitTaskError> {
         // Safety: Just forwarding the function call
@@ -356,11 +577,27 @@ impl<T: Port + KernelCfg2 + 'static> Kernel for T {
     fn park_timeout(timeout: Duration) -> Result<(), ParkTimeoutError> {
         task::park_current_task_timeout::<Self>(timeout)
     }
+
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    fn try_park() -> Result<(), TryParkError> {
+        task::try_park_current_task::<Self>()
+    }
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     fn sleep(timeout: Duration) -> Result<(), SleepError> {
         task::put_current_task_on_sleep_timeout::<Self>(timeout)
     }
 
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    #[cfg(feature = "system_time")]
+    fn sleep_until(time: Time) -> Result<(), SleepUntilError> {
+        task::put_current_task_on_sleep_until::<Self>(time)
+    }
+
+    #[inline]
+    fn with_priority<R, F: FnOnce() -> R>(priority: usize, f: F) -> Result<R, WithPriorityError> {
+        task::with_current_task_priority::<Self, R, F>(priority, f)
+    }
+
     type DebugPrinter = KernelDebugPrinter<Self>;
 
     /// Get an object that implements [`Debug`](fmt::Debug) for dumping the
@@ -376,6 +613,9 @@ impl<T: Port + KernelCfg2 + 'static> Kernel for T {
 
 /// The object returned by [`Kernel::debug`]. Implements [`fmt::Debug`].
 ///
+/// In a debug build (`debug_assertions`), the output also includes the list of
+/// outstanding timeouts in the order of arrival.
+///
 /// **This type is exempt from the API stability guarantee.**
 pub struct KernelDebugPrinter<T>(PhantomData<T>);
 
@@ -390,17 +630,31 @@ impl<T: Kernel> fmt::Debug for KernelDebugPrinter<T> {
             }
         }
 
-        f.debug_struct("Kernel")
-            .field("state", T::state())
+        let mut s = f.debug_struct("Kernel");
+        s.field("state", T::state())
             .field("task_cb_pool", &PoolPrinter(T::task_cb_pool()))
             .field(
                 "event_group_cb_pool",
                 &PoolPrinter(T::event_group_cb_pool()),
             )
             .field("mutex_cb_pool", &PoolPrinter(T::mutex_cb_pool()))
+            .field(
+                "notification_cb_pool",
+                &PoolPrinter(T::notification_cb_pool()),
+            )
             .field("semaphore_cb_pool", &PoolPrinter(T::semaphore_cb_pool()))
             .field("timer_cb_pool", &PoolPrinter(T::timer_cb_pool()))
-            .finish()
+            .field(
+                "deferred_work_cb_pool",
+                &PoolPrinter(T::deferred_work_cb_pool()),
+            );
+
+        // The list of outstanding timeouts in the order of arrival. This is
+        // omitted from release builds.
+        #[cfg(debug_assertions)]
+        s.field("timeouts", &timeout::debug_fmt_timeouts::<T>());
+
+        s.finish()
     }
 }
 
@@ -435,6 +689,21 @@ pub unsafe trait KernelCfg1: Sized + Send + Sync + 'static {
     /// `TaskPriority::try_from(i).unwrap()` except that the latter doesn't work
     /// in `const fn`.
     const TASK_PRIORITY_LEVELS: &'static [Self::TaskPriority];
+
+    /// The time slice given to each task for round-robin scheduling, measured
+    /// in microseconds. `None` disables round-robin scheduling.
+    ///
+    /// See [`CfgBuilder::round_robin_quantum`] for details.
+    ///
+    /// [`CfgBuilder::round_robin_quantum`]: crate::kernel::cfg::CfgBuilder::round_robin_quantum
+    const ROUND_ROBIN_QUANTUM_US: Option<u64> = None;
+
+    /// Enables the run-time deadlock detection for mutexes.
+    ///
+    /// See [`CfgBuilder::deadlock_detection`] for details.
+    ///
+    /// [`CfgBuilder::deadlock_detection`]: crate::kernel::cfg::CfgBuilder::deadlock_detection
+    const DEADLOCK_DETECTION: bool = false;
 }
 
 /// Implemented by a port. This trait contains items related to low-level
@@ -459,23 +728,54 @@ pub unsafe trait PortThreading: KernelCfg1 {
     /// The default stack size for tasks.
     const STACK_DEFAULT_SIZE: usize = 1024;
 
+    /// The minimum stack size for tasks. The kernel configurator rejects a
+    /// task whose stack region is smaller than this.
+    ///
+    /// A port should set this to the size of the initial context that is
+    /// pushed onto a task's stack upon activation plus a small margin.
+    const STACK_MINIMUM_SIZE: usize = 256;
+
     /// The alignment requirement for task stack regions.
     ///
     /// Both ends of stack regions are aligned by `STACK_ALIGN`. It's
     /// automatically enforced by the kernel configurator for automatically
     /// allocated stack regions (this applies to tasks created without
-    /// [`stack_hunk`]). The kernel configurator does not check the alignemnt
-    /// for manually-allocated stack regions.
+    /// [`stack_hunk`]). For manually-allocated stack regions, the kernel
+    /// configurator checks that the region's offset and length are multiples
+    /// of `STACK_ALIGN`.
     ///
     /// [`stack_hunk`]: crate::kernel::cfg::CfgTaskBuilder::stack_hunk
     /// [`StackHunk`]: crate::kernel::StackHunk
     const STACK_ALIGN: usize = core::mem::size_of::<usize>();
 
+    /// The byte value used to fill task stack regions upon task activation
+    /// when the `stack_usage` feature is enabled. [`Task::stack_usage`]
+    /// measures a task's peak stack usage by looking for the bytes that still
+    /// have this value.
+    ///
+    /// A port may override this to choose a value that is unlikely to be
+    /// written to the stack by a running task.
+    ///
+    /// [`Task::stack_usage`]: crate::kernel::Task::stack_usage
+    const STACK_SENTINEL: u8 = 0xcc;
+
     /// Transfer the control to the dispatcher, discarding the current
     /// (startup) context. `*state.`[`running_task_ptr`]`()` is `None` at this
     /// point. The dispatcher should call [`PortToKernel::choose_running_task`]
     /// to find the next task to run and transfer the control to that task.
     ///
+    /// If `choose_running_task` leaves `*state.running_task_ptr()` as
+    /// `None`, there is no task to run. The kernel doesn't provide an idle
+    /// task, so the port is responsible for idling in this case. The port
+    /// should call [`idle`] with CPU Lock inactive until a task becomes ready.
+    /// An interrupt handler that makes a task ready will call [`yield_cpu`],
+    /// upon which the dispatcher should run again. For example, `r3_port_arm_m`
+    /// does this in an internal idle task, while `r3_port_std` calls `idle`
+    /// once and then lets all worker threads sleep.
+    ///
+    /// [`idle`]: Self::idle
+    /// [`yield_cpu`]: Self::yield_cpu
+    ///
     /// Precondition: CPU Lock active, a boot context
     ///
     /// [`running_task_ptr`]: State::running_task_ptr
@@ -503,6 +803,20 @@ pub unsafe trait PortThreading: KernelCfg1 {
     /// > handler.
     unsafe fn yield_cpu();
 
+    /// Wait for an interrupt, preferably in a low-power state (e.g., by
+    /// executing `wfi`). Called by the port's dispatcher when there is no task
+    /// to run (see [`dispatch_first_task`]). It may be called repeatedly until
+    /// a task becomes ready.
+    ///
+    /// The default implementation returns immediately, which makes the caller
+    /// busy-wait.
+    ///
+    /// Precondition: CPU Lock inactive, not a task context
+    ///
+    /// [`dispatch_first_task`]: Self::dispatch_first_task
+    #[inline]
+    unsafe fn idle() {}
+
     /// Destroy the state of the previously running task (`task`, which has
     /// already been removed from `*state.`[`running_task_ptr`]`()`) and proceed
     /// to the dispatcher.
@@ -594,6 +908,16 @@ pub unsafe trait PortInterrupts: KernelCfg1 {
     /// [managed]: crate#interrupt-handling-framework
     const MANAGED_INTERRUPT_LINES: &'static [InterruptNum] = &[];
 
+    /// The list of interrupt lines which are reserved for
+    /// [`SoftwareInterrupt`]s. They are allocated in order by
+    /// [`CfgSoftwareInterruptBuilder`]. The application must not use them for
+    /// other purposes.
+    ///
+    /// Defaults to `&[]` (empty) when unspecified.
+    ///
+    /// [`CfgSoftwareInterruptBuilder`]: crate::kernel::cfg::CfgSoftwareInterruptBuilder
+    const SOFTWARE_INTERRUPT_LINES: &'static [InterruptNum] = &[];
+
     /// Set the priority of the specified interrupt line.
     ///
     /// Precondition: CPU Lock active. Task context or boot phase.
@@ -604,6 +928,13 @@ pub unsafe trait PortInterrupts: KernelCfg1 {
         Err(SetInterruptLinePriorityError::NotSupported)
     }
 
+    /// Get the current priority of the specified interrupt line.
+    unsafe fn get_interrupt_line_priority(
+        _line: InterruptNum,
+    ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+        Err(GetInterruptLinePriorityError::NotSupported)
+    }
+
     /// Enable the specified interrupt line.
     unsafe fn enable_interrupt_line(_line: InterruptNum) -> Result<(), EnableInterruptLineError> {
         Err(EnableInterruptLineError::NotSupported)
@@ -630,6 +961,21 @@ pub unsafe trait PortInterrupts: KernelCfg1 {
     ) -> Result<bool, QueryInterruptLineError> {
         Err(QueryInterruptLineError::NotSupported)
     }
+
+    /// Read the enable state of the specified interrupt line.
+    unsafe fn is_interrupt_line_enabled(
+        _line: InterruptNum,
+    ) -> Result<bool, QueryInterruptLineError> {
+        Err(QueryInterruptLineError::NotSupported)
+    }
+
+    /// Set the trigger mode of the specified interrupt line.
+    unsafe fn set_interrupt_line_trigger_mode(
+        _line: InterruptNum,
+        _mode: InterruptLineTriggerMode,
+    ) -> Result<(), SetInterruptLineTriggerModeError> {
+        Err(SetInterruptLineTriggerModeError::NotSupported)
+    }
 }
 
 /// Implemented by a port. This trait contains items related to controlling
@@ -660,6 +1006,30 @@ pub trait PortTimer {
     /// [`pend_tick_after`]: Self::pend_tick_after
     const MAX_TIMEOUT: UTicks;
 
+    /// Indicates whether the driver honors [`pend_tick_after`]. Defaults to
+    /// `true`.
+    ///
+    /// A driver that calls [`PortToKernel::timer_tick`] at a steady rate
+    /// (i.e., a “tickful” driver) should set this to `false`. The kernel
+    /// won't call `pend_tick_after` or [`pend_tick`] in this case, saving the
+    /// overhead of calculating the next timeout.
+    ///
+    /// [`pend_tick_after`]: Self::pend_tick_after
+    /// [`pend_tick`]: Self::pend_tick
+    const TICKLESS: bool = true;
+
+    /// The granularity of [`tick_count`], measured in microseconds (ticks).
+    /// Must be greater than zero. Defaults to `1`.
+    ///
+    /// A driver that only updates the tick count at a coarser rate (e.g., a
+    /// tickful driver) should set this to the maximum amount by which the
+    /// tick count can advance at once. This value is used to calculate the
+    /// return value of [`Kernel::time_resolution`] and doesn't affect the
+    /// kernel's timing algorithm.
+    ///
+    /// [`tick_count`]: Self::tick_count
+    const RESOLUTION: UTicks = 1;
+
     /// Read the current tick count (timer value).
     ///
     /// This value steadily increases over time. When it goes past
@@ -757,6 +1127,14 @@ pub trait PortToKernel {
     ///
     /// Precondition: CPU Lock inactive, an interrupt context
     unsafe fn timer_tick();
+
+    /// Call [shutdown hooks].
+    ///
+    /// Should be called for at most once by the port when the system shuts
+    /// down, after the port stops running tasks and interrupt handlers.
+    ///
+    /// [shutdown hooks]: ShutdownHook
+    unsafe fn call_shutdown_hooks();
 }
 
 impl<System: Kernel> PortToKernel for System {
@@ -784,12 +1162,27 @@ impl<System: Kernel> PortToKernel for System {
             System::INTERRUPT_ATTR.init(lock.borrow_mut());
         }
 
+        // Release `CpuLockGuard` (but not CPU Lock itself) before calling
+        // startup hooks. A startup hook may call a system call that assumes
+        // CPU Lock is owned by the caller and creates its own `CpuLockGuard`
+        // (e.g., `monotonic_time`), so we must not hold one here.
+        forget(lock);
+
         // Call startup hooks
         for hook in Self::STARTUP_HOOKS {
             // Safety: This is the intended place to call startup hooks.
-            unsafe { (hook.start)(hook.param) };
+            unsafe { (hook.trampoline)(hook.start, hook.param) };
         }
 
+        // Startup hooks can't deactivate CPU Lock, and they have dropped
+        // their `CpuLockGuard`s (if any) by now
+        // Safety: CPU Lock active, no other `CpuLockGuard` exists
+        let lock = unsafe { utils::assume_cpu_lock::<Self>() };
+
+        // The boot phase is complete. No code will execute in a boot context
+        // from now on.
+        Self::state().boot_complete.store(true, Ordering::Relaxed);
+
         forget(lock);
 
         // Safety: CPU Lock is active, Startup phase
@@ -813,6 +1206,12 @@ impl<System: Kernel> PortToKernel for System {
     unsafe fn timer_tick() {
         timeout::handle_tick::<Self>();
     }
+
+    unsafe fn call_shutdown_hooks() {
+        for hook in Self::SHUTDOWN_HOOKS {
+            (hook.start)(hook.param);
+        }
+    }
 }
 
 /// Associates "system" types with kernel-private data. Use [`build!`] to
@@ -839,6 +1238,14 @@ pub unsafe trait KernelCfg2: Port + Sized {
     #[doc(hidden)]
     const STARTUP_HOOKS: &'static [StartupHookAttr];
 
+    #[doc(hidden)]
+    const SHUTDOWN_HOOKS: &'static [ShutdownHookAttr];
+
+    /// The hunk offsets of the storage of task-local storage slots. The
+    /// length represents the number of task-local storage slots.
+    #[doc(hidden)]
+    const TASK_LOCAL_OFFSETS: &'static [usize];
+
     /// Access the kernel's global state.
     fn state() -> &'static State<Self>;
 
@@ -881,6 +1288,18 @@ pub unsafe trait KernelCfg2: Port + Sized {
         Self::mutex_cb_pool().get(i)
     }
 
+    // FIXME: Waiting for <https://github.com/rust-lang/const-eval/issues/11>
+    //        to be resolved because `NotificationCb` includes interior
+    //        mutability and can't be referred to by `const`
+    #[doc(hidden)]
+    fn notification_cb_pool() -> &'static [NotificationCb<Self>];
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn get_notification_cb(i: usize) -> Option<&'static NotificationCb<Self>> {
+        Self::notification_cb_pool().get(i)
+    }
+
     // FIXME: Waiting for <https://github.com/rust-lang/const-eval/issues/11>
     //        to be resolved because `EventGroupCb` includes interior mutability
     //        and can't be referred to by `const`
@@ -904,6 +1323,18 @@ pub unsafe trait KernelCfg2: Port + Sized {
     fn get_timer_cb(i: usize) -> Option<&'static TimerCb<Self>> {
         Self::timer_cb_pool().get(i)
     }
+
+    // FIXME: Waiting for <https://github.com/rust-lang/const-eval/issues/11>
+    //        to be resolved because `DeferredWorkCb` includes interior
+    //        mutability and can't be referred to by `const`
+    #[doc(hidden)]
+    fn deferred_work_cb_pool() -> &'static [DeferredWorkCb<Self>];
+
+    #[doc(hidden)]
+    #[inline(always)]
+    fn get_deferred_work_cb(i: usize) -> Option<&'static DeferredWorkCb<Self>> {
+        Self::deferred_work_cb_pool().get(i)
+    }
 }
 
 /// Global kernel state.
@@ -929,6 +1360,15 @@ pub struct State<
 
     /// The global state of the timekeeping system.
     timeout: timeout::TimeoutGlobals<System, TimeoutHeap>,
+
+    /// The monotonic time when `running_task` was last updated, measured in
+    /// microseconds. Only maintained if `task_stats` is enabled or
+    /// [`KernelCfg1::ROUND_ROBIN_QUANTUM_US`] is `Some(_)`.
+    task_switch_time: utils::CpuLockCell<System, u64>,
+
+    /// `true` if the boot phase has completed. Used to distinguish a boot
+    /// context from an interrupt context.
+    boot_complete: AtomicBool,
 }
 
 impl<
@@ -945,6 +1385,8 @@ impl<
         #[cfg(feature = "priority_boost")]
         priority_boost: AtomicBool::new(false),
         timeout: Init::INIT,
+        task_switch_time: Init::INIT,
+        boot_complete: AtomicBool::new(false),
     };
 }
 
@@ -970,6 +1412,8 @@ impl<
                 },
             )
             .field("timeout", &self.timeout)
+            .field("task_switch_time", &self.task_switch_time)
+            .field("boot_complete", &self.boot_complete)
             .finish()
     }
 }
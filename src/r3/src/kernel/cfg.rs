@@ -2,20 +2,27 @@
 use core::marker::PhantomData;
 
 use crate::{
-    kernel::Port,
+    kernel::{task, Kernel, Port, Timer},
+    time::Duration,
     utils::{ComptimeVec, FIXED_PRIO_BITMAP_MAX_LEN},
 };
 
+mod deferred;
 mod event_group;
 mod hunk;
 mod interrupt;
+mod latency;
 mod mutex;
+mod notification;
 mod semaphore;
+mod shutdown;
 mod startup;
 mod task;
+mod task_local;
 mod timer;
 pub use self::{
-    event_group::*, hunk::*, interrupt::*, mutex::*, semaphore::*, startup::*, task::*, timer::*,
+    deferred::*, event_group::*, hunk::*, interrupt::*, latency::*, mutex::*, notification::*,
+    semaphore::*, shutdown::*, startup::*, task::*, task_local::*, timer::*,
 };
 
 /// Attach [a configuration function] to a "system" type by implementing
@@ -34,7 +41,9 @@ macro_rules! build {
                 },
                 EventGroupCb, InterruptAttr, InterruptLineInit, KernelCfg1,
                 KernelCfg2, Port, StartupHookAttr, State, TaskAttr, TaskCb, TimeoutRef, TimerAttr,
-                TimerCb, SemaphoreCb, MutexCb, PortThreading, readyqueue,
+                TimerCb, SemaphoreCb, MutexCb, NotificationCb, DeferredWorkAttr, DeferredWorkCb,
+                PortThreading, ShutdownHookAttr,
+                readyqueue,
             },
             staticvec::StaticVec,
             utils::{
@@ -91,6 +100,8 @@ macro_rules! build {
             type TaskPriority = TaskPriority;
             type TaskReadyQueue = TaskReadyQueue;
             const TASK_PRIORITY_LEVELS: &'static [Self::TaskPriority] = &TASK_PRIORITY_LEVELS;
+            const ROUND_ROBIN_QUANTUM_US: Option<u64> = CFG.round_robin_quantum_us;
+            const DEADLOCK_DETECTION: bool = CFG.deadlock_detection;
         }
 
         // Instantiiate task structures
@@ -116,6 +127,13 @@ macro_rules! build {
                     (0..CFG.mutexes.len()).map(|i| CFG.mutexes.get(i).to_state());
         }
 
+        // Instantiiate notification structures
+        $crate::array_item_from_fn! {
+            static NOTIFICATION_CB_POOL:
+                [NotificationCb<$sys>; _] =
+                    (0..CFG.notifications.len()).map(|i| CFG.notifications.get(i).to_state());
+        }
+
         // Instantiiate semaphore structures
         $crate::array_item_from_fn! {
             static SEMAPHORE_CB_POOL:
@@ -132,6 +150,15 @@ macro_rules! build {
                     (0..CFG.timers.len()).map(|i| CFG.timers.get(i).to_state(&TIMER_ATTR_POOL[i], i));
         }
 
+        // Instantiiate deferred work structures
+        $crate::array_item_from_fn! {
+            const DEFERRED_WORK_ATTR_POOL: [DeferredWorkAttr<$sys>; _] =
+                (0..CFG.deferred_works.len()).map(|i| CFG.deferred_works.get(i).to_attr());
+            static DEFERRED_WORK_CB_POOL:
+                [DeferredWorkCb<$sys>; _] =
+                    (0..CFG.deferred_works.len()).map(|i| CFG.deferred_works.get(i).to_state(&DEFERRED_WORK_ATTR_POOL[i]));
+        }
+
         // Instantiate hunks
         static HUNK_POOL: RawCell<AlignedStorage<{ CFG.hunk_pool_len }, { CFG.hunk_pool_align }>> =
             Init::INIT;
@@ -180,6 +207,20 @@ macro_rules! build {
                     (0..CFG.startup_hooks.len()).map(|i| CFG.startup_hooks.get(i).to_attr());
         }
 
+        // Construct a table of shutdown hooks
+        $crate::array_item_from_fn! {
+            const SHUTDOWN_HOOKS:
+                [ShutdownHookAttr; _] =
+                    (0..CFG.shutdown_hooks.len()).map(|i| CFG.shutdown_hooks.get(i).to_attr());
+        }
+
+        // Construct a table of the storage offsets of task-local storage slots
+        $crate::array_item_from_fn! {
+            const TASK_LOCAL_OFFSETS:
+                [usize; _] =
+                    (0..CFG.task_locals.len()).map(|i| CFG.task_locals.get(i).to_offset());
+        }
+
         // Calculate the required storage of the timeout heap
         const TIMEOUT_HEAP_LEN: usize = CFG.tasks.len() + CFG.timers.len();
         type TimeoutHeap = StaticVec<TimeoutRef<$sys>, TIMEOUT_HEAP_LEN>;
@@ -201,6 +242,10 @@ macro_rules! build {
 
             const STARTUP_HOOKS: &'static [StartupHookAttr] = &STARTUP_HOOKS;
 
+            const SHUTDOWN_HOOKS: &'static [ShutdownHookAttr] = &SHUTDOWN_HOOKS;
+
+            const TASK_LOCAL_OFFSETS: &'static [usize] = &TASK_LOCAL_OFFSETS;
+
             #[inline(always)]
             fn hunk_pool_ptr() -> *mut u8 {
                 HUNK_POOL.get() as *mut u8
@@ -221,6 +266,11 @@ macro_rules! build {
                 &MUTEX_CB_POOL
             }
 
+            #[inline(always)]
+            fn notification_cb_pool() -> &'static [NotificationCb<$sys>] {
+                &NOTIFICATION_CB_POOL
+            }
+
             #[inline(always)]
             fn semaphore_cb_pool() -> &'static [SemaphoreCb<$sys>] {
                 &SEMAPHORE_CB_POOL
@@ -230,6 +280,11 @@ macro_rules! build {
             fn timer_cb_pool() -> &'static [TimerCb<$sys>] {
                 &TIMER_CB_POOL
             }
+
+            #[inline(always)]
+            fn deferred_work_cb_pool() -> &'static [DeferredWorkCb<$sys>] {
+                &DEFERRED_WORK_CB_POOL
+            }
         }
 
         id_map()
@@ -278,13 +333,20 @@ pub struct CfgBuilderInner<System> {
     pub hunk_pool_align: usize,
     pub tasks: ComptimeVec<CfgBuilderTask<System>>,
     pub num_task_priority_levels: usize,
+    pub round_robin_quantum_us: Option<u64>,
+    pub deadlock_detection: bool,
     pub interrupt_lines: ComptimeVec<CfgBuilderInterruptLine>,
     pub interrupt_handlers: ComptimeVec<CfgBuilderInterruptHandler>,
+    pub num_software_interrupts: usize,
     pub startup_hooks: ComptimeVec<CfgBuilderStartupHook>,
+    pub shutdown_hooks: ComptimeVec<CfgBuilderShutdownHook>,
     pub event_groups: ComptimeVec<CfgBuilderEventGroup>,
     pub mutexes: ComptimeVec<CfgBuilderMutex>,
+    pub notifications: ComptimeVec<CfgBuilderNotification>,
     pub semaphores: ComptimeVec<CfgBuilderSemaphore>,
     pub timers: ComptimeVec<CfgBuilderTimer>,
+    pub deferred_works: ComptimeVec<CfgBuilderDeferredWork>,
+    pub task_locals: ComptimeVec<CfgBuilderTaskLocal>,
 }
 
 impl<System> CfgBuilder<System> {
@@ -306,13 +368,20 @@ impl<System> CfgBuilder<System> {
                 hunk_pool_align: 1,
                 tasks: ComptimeVec::new(),
                 num_task_priority_levels: 4,
+                round_robin_quantum_us: None,
+                deadlock_detection: false,
                 interrupt_lines: ComptimeVec::new(),
                 interrupt_handlers: ComptimeVec::new(),
+                num_software_interrupts: 0,
                 startup_hooks: ComptimeVec::new(),
+                shutdown_hooks: ComptimeVec::new(),
                 event_groups: ComptimeVec::new(),
                 mutexes: ComptimeVec::new(),
+                notifications: ComptimeVec::new(),
                 semaphores: ComptimeVec::new(),
                 timers: ComptimeVec::new(),
+                deferred_works: ComptimeVec::new(),
+                task_locals: ComptimeVec::new(),
             },
         }
     }
@@ -352,6 +421,26 @@ impl<System> CfgBuilder<System> {
         self.inner.num_task_priority_levels = new_value;
     }
 
+    /// Enable or disable the run-time deadlock detection for mutexes. Defaults
+    /// to `false` when unspecified.
+    ///
+    /// When enabled, [`Mutex::lock`] checks if blocking the current task would
+    /// cause a deadlock before doing so. The check follows the chain of mutex
+    /// owners, each of which may be waiting for another mutex, and panics if
+    /// the chain leads back to the current task. The check is performed with
+    /// CPU Lock active and takes a time proportional to the length of the
+    /// chain, which is bounded by the number of tasks.
+    ///
+    /// [`Mutex::lock_timeout`] isn't checked because such a wait operation
+    /// will eventually time out. Semaphores aren't checked because they don't
+    /// have owners.
+    ///
+    /// [`Mutex::lock`]: crate::kernel::Mutex::lock
+    /// [`Mutex::lock_timeout`]: crate::kernel::Mutex::lock_timeout
+    pub const fn deadlock_detection(&mut self, enable: bool) {
+        self.inner.deadlock_detection = enable;
+    }
+
     /// Finalize the configuration.
     #[doc(hidden)]
     pub const fn finalize(&mut self)
@@ -368,7 +457,58 @@ impl<System> CfgBuilder<System> {
         // Sort handlers by (interrupt number, priority)
         interrupt::sort_handlers(&mut inner.interrupt_handlers);
 
+        interrupt::panic_if_handlers_conflict(&inner.interrupt_handlers);
+
         // Sort startup hooks by priority
         startup::sort_hooks(&mut inner.startup_hooks);
+
+        // Sort shutdown hooks by priority
+        shutdown::sort_hooks(&mut inner.shutdown_hooks);
+
+        // Allocate the storage of task-local storage slots now that the
+        // number of tasks is known
+        task_local::alloc_task_local_storage(self);
+    }
+}
+
+impl<System: Kernel> CfgBuilder<System> {
+    /// Enable round-robin scheduling among tasks having the same priority.
+    ///
+    /// When a task has been running for `quantum` or longer, and there's
+    /// another Ready task having the same [effective priority], the running
+    /// task is preempted and moved to the back of the ready queue of its
+    /// priority level. Tasks having different priorities are scheduled in the
+    /// same way as when round-robin scheduling is disabled.
+    ///
+    /// This method defines a timer that checks the running task periodically.
+    /// The check happens twice as often as `quantum`, so a task may run for up
+    /// to one and a half times `quantum` before being preempted. The timer
+    /// counts toward the number of timers but isn't exposed to the
+    /// application.
+    ///
+    /// Must be positive. This method can be called at most once.
+    ///
+    /// [effective priority]: crate::kernel::Task::effective_priority
+    pub const fn round_robin_quantum(&mut self, quantum: Duration) {
+        if self.inner.round_robin_quantum_us.is_some() {
+            panic!("`round_robin_quantum` is already specified");
+        } else if !quantum.is_positive() {
+            panic!("`round_robin_quantum` must be positive");
+        }
+
+        self.inner.round_robin_quantum_us = Some(quantum.as_micros() as u64);
+
+        let check_period = if quantum.as_micros() >= 2 {
+            Duration::from_micros(quantum.as_micros() / 2)
+        } else {
+            quantum
+        };
+
+        Timer::build()
+            .start(task::round_robin_timer_handler::<System>)
+            .delay(check_period)
+            .period(check_period)
+            .active(true)
+            .finish(self);
     }
 }
@@ -0,0 +1,152 @@
+use core::{marker::PhantomData, num::NonZeroUsize};
+
+use crate::kernel::{cfg::CfgBuilder, deferred, task, utils::CpuLockCell, Kernel, Port};
+
+impl<System: Port> deferred::DeferredWork<System> {
+    /// Construct a `CfgDeferredWorkBuilder` to define a deferred work in [a
+    /// configuration function](crate#static-configuration).
+    pub const fn build() -> CfgDeferredWorkBuilder<System> {
+        CfgDeferredWorkBuilder::new()
+    }
+}
+
+/// Configuration builder type for [`DeferredWork`].
+///
+/// [`DeferredWork`]: crate::kernel::DeferredWork
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgDeferredWorkBuilder<System> {
+    _phantom: PhantomData<System>,
+    start: Option<fn(usize)>,
+    param: usize,
+    stack_size: Option<usize>,
+    priority: Option<usize>,
+}
+
+impl<System: Port> CfgDeferredWorkBuilder<System> {
+    const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+            start: None,
+            param: 0,
+            stack_size: None,
+            priority: None,
+        }
+    }
+
+    /// \[**Required**\] Specify the deferred work's entry point. It will be
+    /// called in a task context.
+    pub const fn start(self, start: fn(usize)) -> Self {
+        Self {
+            start: Some(start),
+            ..self
+        }
+    }
+
+    /// Specify the parameter to `start`. Defaults to `0`.
+    pub const fn param(self, param: usize) -> Self {
+        Self { param, ..self }
+    }
+
+    /// Specify the stack size of the backing task.
+    pub const fn stack_size(self, stack_size: usize) -> Self {
+        Self {
+            stack_size: Some(stack_size),
+            ..self
+        }
+    }
+
+    /// \[**Required**\] Specify the base priority of the backing task. The
+    /// value must be in range `0..`[`num_task_priority_levels`].
+    ///
+    /// [`num_task_priority_levels`]: crate::kernel::cfg::CfgBuilder::num_task_priority_levels
+    pub const fn priority(self, priority: usize) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+}
+
+impl<System: Kernel> CfgDeferredWorkBuilder<System> {
+    /// Complete the definition of a deferred work, returning a reference to
+    /// the deferred work.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> deferred::DeferredWork<System> {
+        // FIXME: Work-around for `Option::expect` being not `const fn`
+        let start = if let Some(x) = self.start {
+            x
+        } else {
+            panic!("`start` (deferred work entry point) is not specified")
+        };
+
+        // FIXME: Work-around for `Option::expect` being not `const fn`
+        let priority = if let Some(x) = self.priority {
+            x
+        } else {
+            panic!("`priority` is not specified")
+        };
+
+        // Define the backing task
+        let task = task::Task::build()
+            .start(deferred::deferred_work_task_body::<System>)
+            .param(cfg.inner.deferred_works.len())
+            .priority(priority);
+        let task = if let Some(stack_size) = self.stack_size {
+            task.stack_size(stack_size)
+        } else {
+            task
+        };
+        let task = task.finish(cfg);
+
+        let inner = &mut cfg.inner;
+
+        inner.deferred_works.push(CfgBuilderDeferredWork {
+            start,
+            param: self.param,
+            task_index: task.id().get() - 1,
+        });
+
+        unsafe {
+            deferred::DeferredWork::from_id(NonZeroUsize::new_unchecked(inner.deferred_works.len()))
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct CfgBuilderDeferredWork {
+    start: fn(usize),
+    param: usize,
+    task_index: usize,
+}
+
+impl Clone for CfgBuilderDeferredWork {
+    fn clone(&self) -> Self {
+        Self {
+            start: self.start,
+            param: self.param,
+            task_index: self.task_index,
+        }
+    }
+}
+
+impl Copy for CfgBuilderDeferredWork {}
+
+impl CfgBuilderDeferredWork {
+    pub const fn to_state<System: Kernel>(
+        &self,
+        attr: &'static deferred::DeferredWorkAttr<System>,
+    ) -> deferred::DeferredWorkCb<System> {
+        deferred::DeferredWorkCb {
+            attr,
+            pending: CpuLockCell::new(false),
+        }
+    }
+
+    pub const fn to_attr<System: Port>(&self) -> deferred::DeferredWorkAttr<System> {
+        deferred::DeferredWorkAttr {
+            entry_point: self.start,
+            entry_param: self.param,
+            task_index: self.task_index,
+            _phantom: PhantomData,
+        }
+    }
+}
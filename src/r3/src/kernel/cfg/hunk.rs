@@ -31,7 +31,8 @@ impl<System: Port> CfgHunkBuilder<System> {
         Self { len, ..self }
     }
 
-    /// Specify the minimum alignment. Defaults to `1`.
+    /// Specify the minimum alignment. Must be a power of two. Defaults to
+    /// `1`.
     pub const fn align(self, align: usize) -> Self {
         Self { align, ..self }
     }
@@ -41,6 +42,8 @@ impl<System: Port> CfgHunkBuilder<System> {
     /// Complete the definition of a hunk, returning a reference to the hunk.
     pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> hunk::Hunk<System> {
         let Self { align, len, .. } = self;
+        assert!(align.is_power_of_two(), "`align` is not power of two");
+
         let inner = &mut cfg.inner;
 
         // Round up `hunk_pool_len`
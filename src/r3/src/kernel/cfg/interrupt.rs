@@ -1,7 +1,7 @@
 use core::marker::PhantomData;
 
 use crate::{
-    kernel::{cfg::CfgBuilder, interrupt, Port, PortThreading},
+    kernel::{cfg::CfgBuilder, interrupt, latency, Kernel, Port, PortThreading},
     utils::{for_times::Nat, ComptimeVec},
 };
 
@@ -27,6 +27,7 @@ pub struct CfgInterruptLineBuilder<System> {
     _phantom: PhantomData<System>,
     line: Option<interrupt::InterruptNum>,
     priority: Option<interrupt::InterruptPriority>,
+    trigger_mode: Option<interrupt::InterruptLineTriggerMode>,
     enabled: bool,
 }
 
@@ -36,6 +37,7 @@ impl<System: Port> CfgInterruptLineBuilder<System> {
             _phantom: PhantomData,
             line: None,
             priority: None,
+            trigger_mode: None,
             enabled: false,
         }
     }
@@ -58,6 +60,20 @@ impl<System: Port> CfgInterruptLineBuilder<System> {
         }
     }
 
+    /// Specify the trigger mode. The system will panic at boot time if the
+    /// port doesn't support the specified trigger mode. Defaults to the
+    /// port-specific default mode when unspecified.
+    pub const fn trigger_mode(self, trigger_mode: interrupt::InterruptLineTriggerMode) -> Self {
+        assert!(
+            self.trigger_mode.is_none(),
+            "`trigger_mode` is specified twice"
+        );
+        Self {
+            trigger_mode: Some(trigger_mode),
+            ..self
+        }
+    }
+
     /// Specify whether the interrupt linie should be enabled at system startup.
     /// Defaults to `false` (don't enable).
     pub const fn enabled(self, enabled: bool) -> Self {
@@ -84,6 +100,7 @@ impl<System: Port> CfgInterruptLineBuilder<System> {
             inner.interrupt_lines.push(CfgBuilderInterruptLine {
                 num: line_num,
                 priority: None,
+                trigger_mode: None,
                 enabled: false,
             });
             inner.interrupt_lines.len() - 1
@@ -100,6 +117,14 @@ impl<System: Port> CfgInterruptLineBuilder<System> {
             cfg_interrupt_line.priority = Some(priority);
         }
 
+        if let Some(trigger_mode) = self.trigger_mode {
+            assert!(
+                cfg_interrupt_line.trigger_mode.is_none(),
+                "`trigger_mode` is already specified for this interrupt line"
+            );
+            cfg_interrupt_line.trigger_mode = Some(trigger_mode);
+        }
+
         if self.enabled {
             cfg_interrupt_line.enabled = true;
         }
@@ -113,6 +138,7 @@ impl<System: Port> CfgInterruptLineBuilder<System> {
 pub struct CfgBuilderInterruptLine {
     num: interrupt::InterruptNum,
     priority: Option<interrupt::InterruptPriority>,
+    trigger_mode: Option<interrupt::InterruptLineTriggerMode>,
     enabled: bool,
 }
 
@@ -133,11 +159,19 @@ impl CfgBuilderInterruptLine {
             line: interrupt::InterruptLine::from_num(self.num),
             // FIXME: `Option::unwrap_or` is not `const fn` yet
             priority: if let Some(i) = self.priority { i } else { 0 },
+            trigger_mode: if let Some(x) = self.trigger_mode {
+                x
+            } else {
+                interrupt::InterruptLineTriggerMode::Edge
+            },
             flags: {
                 let mut f = 0;
                 if self.priority.is_some() {
                     f |= interrupt::InterruptLineInitFlags::SET_PRIORITY.bits();
                 }
+                if self.trigger_mode.is_some() {
+                    f |= interrupt::InterruptLineInitFlags::SET_TRIGGER_MODE.bits();
+                }
                 if self.enabled {
                     f |= interrupt::InterruptLineInitFlags::ENABLE.bits();
                 }
@@ -165,6 +199,8 @@ pub struct CfgInterruptHandlerBuilder<System> {
     param: usize,
     priority: i32,
     unmanaged: bool,
+    chain: bool,
+    latency_probe: Option<(fn(usize), usize)>,
 }
 
 impl<System: Port> CfgInterruptHandlerBuilder<System> {
@@ -176,6 +212,8 @@ impl<System: Port> CfgInterruptHandlerBuilder<System> {
             param: 0,
             priority: 0,
             unmanaged: false,
+            chain: false,
+            latency_probe: None,
         }
     }
 
@@ -204,8 +242,15 @@ impl<System: Port> CfgInterruptHandlerBuilder<System> {
 
     /// Specify the priority. Defaults to `0` when unspecified.
     ///
-    /// When multiple handlers are registered to a single interrupt line, those
-    /// with smaller priority values will execute earlier.
+    /// When multiple handlers are registered to a single interrupt line (see
+    /// [`chain`](Self::chain)), those with smaller priority values will
+    /// execute earlier. Handlers with the same priority value execute in the
+    /// registration order.
+    ///
+    /// All of the handlers attached to an interrupt line are called every time
+    /// the interrupt is taken. When the interrupt line is shared by multiple
+    /// devices, each handler should check whether its device actually needs
+    /// service.
     ///
     /// This should not be confused with [an interrupt line's priority].
     ///
@@ -214,6 +259,19 @@ impl<System: Port> CfgInterruptHandlerBuilder<System> {
         Self { priority, ..self }
     }
 
+    /// Specify whether the interrupt handler may share the interrupt line with
+    /// other interrupt handlers. Defaults to `false` when unspecified.
+    ///
+    /// When multiple interrupt handlers are attached to an interrupt line,
+    /// all but one of them must be marked with `chain(true)`; otherwise,
+    /// configuration will fail. This prevents an interrupt handler from being
+    /// accidentally attached to an interrupt line that is already in use. The
+    /// handlers are called in the order specified by
+    /// [`priority`](Self::priority).
+    pub const fn chain(self, chain: bool) -> Self {
+        Self { chain, ..self }
+    }
+
     /// Indicate that the entry point function is unmanaged-safe (designed to
     /// execute as [an unmanaged interrupt handler]).
     ///
@@ -261,6 +319,8 @@ impl<System: Port> CfgInterruptHandlerBuilder<System> {
             param: self.param,
             priority: self.priority,
             unmanaged: self.unmanaged,
+            chain: self.chain,
+            latency_probe: self.latency_probe,
             order,
         });
 
@@ -268,6 +328,126 @@ impl<System: Port> CfgInterruptHandlerBuilder<System> {
     }
 }
 
+impl<System: Kernel> CfgInterruptHandlerBuilder<System> {
+    /// Attach an [`InterruptLatencyProbe`]. The probe records the time just
+    /// before the entry point is called.
+    ///
+    /// [`InterruptLatencyProbe`]: crate::kernel::InterruptLatencyProbe
+    pub const fn latency_probe(self, probe: latency::InterruptLatencyProbe<System>) -> Self {
+        assert!(
+            self.latency_probe.is_none(),
+            "`latency_probe` is specified twice"
+        );
+        Self {
+            latency_probe: Some((
+                latency::record_interrupt_entry::<System>,
+                probe.hunk().offset(),
+            )),
+            ..self
+        }
+    }
+}
+
+impl<System: Port> interrupt::SoftwareInterrupt<System> {
+    /// Construct a `CfgSoftwareInterruptBuilder` to define a software
+    /// interrupt in [a configuration function](crate#static-configuration).
+    pub const fn build() -> CfgSoftwareInterruptBuilder<System> {
+        CfgSoftwareInterruptBuilder::new()
+    }
+}
+
+/// Configuration builder type for [`SoftwareInterrupt`].
+///
+/// [`SoftwareInterrupt`]: crate::kernel::SoftwareInterrupt
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgSoftwareInterruptBuilder<System> {
+    _phantom: PhantomData<System>,
+    start: Option<fn(usize)>,
+    param: usize,
+    priority: Option<interrupt::InterruptPriority>,
+}
+
+impl<System: Port> CfgSoftwareInterruptBuilder<System> {
+    const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+            start: None,
+            param: 0,
+            priority: None,
+        }
+    }
+
+    /// \[**Required**\] Specify the entry point. It will be called in an
+    /// interrupt context.
+    pub const fn start(self, start: fn(usize)) -> Self {
+        Self {
+            start: Some(start),
+            ..self
+        }
+    }
+
+    /// Specify the parameter to `start`. Defaults to `0`.
+    pub const fn param(self, param: usize) -> Self {
+        Self { param, ..self }
+    }
+
+    /// \[**Required**\] Specify the priority of the allocated interrupt line.
+    /// The value must fall within [a managed range].
+    ///
+    /// [a managed range]: crate::kernel::PortInterrupts::MANAGED_INTERRUPT_PRIORITY_RANGE
+    pub const fn priority(self, priority: interrupt::InterruptPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    /// Complete the definition of a software interrupt, returning a
+    /// `SoftwareInterrupt` object.
+    pub const fn finish(
+        self,
+        cfg: &mut CfgBuilder<System>,
+    ) -> interrupt::SoftwareInterrupt<System> {
+        // Allocate an interrupt line
+        let i = cfg.inner.num_software_interrupts;
+        let lines = System::SOFTWARE_INTERRUPT_LINES;
+        assert!(
+            i < lines.len(),
+            "the port doesn't provide enough software interrupt lines"
+        );
+        let line_num = lines[i];
+        cfg.inner.num_software_interrupts = i + 1;
+
+        // FIXME: Work-around for `Option::expect` being not `const fn`
+        let start = if let Some(x) = self.start {
+            x
+        } else {
+            panic!("`start` is not specified")
+        };
+
+        // FIXME: Work-around for `Option::expect` being not `const fn`
+        let priority = if let Some(x) = self.priority {
+            x
+        } else {
+            panic!("`priority` is not specified")
+        };
+
+        interrupt::InterruptHandler::build()
+            .line(line_num)
+            .start(start)
+            .param(self.param)
+            .finish(cfg);
+
+        let line = interrupt::InterruptLine::build()
+            .line(line_num)
+            .priority(priority)
+            .enabled(true)
+            .finish(cfg);
+
+        interrupt::SoftwareInterrupt::from_line(line)
+    }
+}
+
 #[doc(hidden)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CfgBuilderInterruptHandler {
@@ -276,6 +456,11 @@ pub struct CfgBuilderInterruptHandler {
     param: usize,
     priority: i32,
     unmanaged: bool,
+    /// `true` if the handler may share the interrupt line with other handlers.
+    chain: bool,
+    /// The function to call before `start` and its parameter, used to
+    /// implement [`CfgInterruptHandlerBuilder::latency_probe`].
+    latency_probe: Option<(fn(usize), usize)>,
     /// The registration order.
     order: usize,
 }
@@ -323,6 +508,35 @@ pub(super) const fn panic_if_unmanaged_safety_is_violated<System: Port>(
     }
 }
 
+/// Panic if an interrupt line has two or more interrupt handlers that are not
+/// marked with `chain`.
+///
+/// `interrupt_handlers` must be sorted by interrupt number.
+pub(super) const fn panic_if_handlers_conflict(
+    interrupt_handlers: &ComptimeVec<CfgBuilderInterruptHandler>,
+) {
+    // FIXME: Work-around for `for` being unsupported in `const fn`
+    let mut i = 0;
+    let mut num_exclusive = 0;
+    while i < interrupt_handlers.len() {
+        let handler = interrupt_handlers.get(i);
+        if i > 0 && interrupt_handlers.get(i - 1).line != handler.line {
+            num_exclusive = 0;
+        }
+        i += 1;
+
+        if !handler.chain {
+            num_exclusive += 1;
+        }
+
+        assert!(
+            num_exclusive <= 1,
+            "Multiple interrupt handlers are attached to an interrupt line, \
+            but two or more of them are not marked with `chain`."
+        );
+    }
+}
+
 /// Sort interrupt handlers by (interrupt number, priority, order).
 pub(super) const fn sort_handlers(
     interrupt_handlers: &mut ComptimeVec<CfgBuilderInterruptHandler>,
@@ -474,6 +688,10 @@ impl<System: Port, Handlers: CfgBuilderInterruptHandlerList, const NUM_HANDLERS:
                 fn proto_combined_handler<T: MakeCombinedHandlersTrait, I: Nat>() {
                     let handler = T::HANDLERS[I::N];
 
+                    if let Some((record_entry, probe)) = handler.latency_probe {
+                        record_entry(probe);
+                    }
+
                     (handler.start)(handler.param);
 
                     let next_i = I::N + 1;
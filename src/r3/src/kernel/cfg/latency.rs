@@ -0,0 +1,69 @@
+use core::{marker::PhantomData, mem};
+
+use crate::{
+    hunk::Hunk,
+    kernel::{self, cfg::CfgBuilder, latency, Kernel},
+};
+
+impl<System: Kernel> latency::LatencyProbe<System> {
+    /// Construct a `CfgLatencyProbeBuilder` to define a latency probe in [a
+    /// configuration function](crate#static-configuration).
+    pub const fn build() -> CfgLatencyProbeBuilder<System> {
+        CfgLatencyProbeBuilder {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Configuration builder type for [`LatencyProbe`].
+///
+/// [`LatencyProbe`]: crate::kernel::LatencyProbe
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgLatencyProbeBuilder<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: Kernel> CfgLatencyProbeBuilder<System> {
+    /// Complete the definition of a latency probe, returning a reference to
+    /// the latency probe.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> latency::LatencyProbe<System> {
+        latency::LatencyProbe::from_hunk(Hunk::build().finish(cfg))
+    }
+}
+
+impl<System: Kernel> latency::InterruptLatencyProbe<System> {
+    /// Construct a `CfgInterruptLatencyProbeBuilder` to define an interrupt
+    /// latency probe in [a configuration function](crate#static-configuration).
+    pub const fn build() -> CfgInterruptLatencyProbeBuilder<System> {
+        CfgInterruptLatencyProbeBuilder {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Configuration builder type for [`InterruptLatencyProbe`].
+///
+/// [`InterruptLatencyProbe`]: crate::kernel::InterruptLatencyProbe
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgInterruptLatencyProbeBuilder<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: Kernel> CfgInterruptLatencyProbeBuilder<System> {
+    /// Complete the definition of an interrupt latency probe, returning a
+    /// reference to the interrupt latency probe.
+    pub const fn finish(
+        self,
+        cfg: &mut CfgBuilder<System>,
+    ) -> latency::InterruptLatencyProbe<System> {
+        // An untyped hunk is used so that the offset can be passed to
+        // `record_interrupt_entry`. The hunk pool is zero-initialized, which
+        // is a valid initial state of `InterruptLatencyProbeState`.
+        let hunk = kernel::Hunk::build()
+            .len(mem::size_of::<latency::InterruptLatencyProbeState>())
+            .align(mem::align_of::<latency::InterruptLatencyProbeState>())
+            .finish(cfg);
+
+        latency::InterruptLatencyProbe::from_hunk(hunk)
+    }
+}
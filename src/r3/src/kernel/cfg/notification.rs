@@ -0,0 +1,98 @@
+use core::{marker::PhantomData, num::NonZeroUsize};
+
+use crate::kernel::{cfg::CfgBuilder, notification, utils::CpuLockCell, wait, Port};
+
+impl<System: Port> notification::Notification<System> {
+    /// Construct a `CfgNotificationBuilder` to define a notification in [a
+    /// configuration function](crate#static-configuration).
+    pub const fn build() -> CfgNotificationBuilder<System> {
+        CfgNotificationBuilder::new()
+    }
+}
+
+/// Configuration builder type for [`Notification`].
+///
+/// [`Notification`]: crate::kernel::Notification
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgNotificationBuilder<System> {
+    _phantom: PhantomData<System>,
+    initial_pending: bool,
+    queue_order: wait::QueueOrder,
+}
+
+impl<System: Port> CfgNotificationBuilder<System> {
+    const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+            initial_pending: false,
+            queue_order: wait::QueueOrder::TaskPriority,
+        }
+    }
+
+    /// Specify whether the notification is initially pending. Defaults to
+    /// `false` when unspecified.
+    pub const fn initial_pending(self, initial_pending: bool) -> Self {
+        Self {
+            initial_pending,
+            ..self
+        }
+    }
+
+    /// Specify how tasks are sorted in the wait queue of the notification.
+    /// Defaults to [`QueueOrder::TaskPriority`] when unspecified.
+    ///
+    /// [`QueueOrder::TaskPriority`]: wait::QueueOrder::TaskPriority
+    pub const fn queue_order(self, queue_order: wait::QueueOrder) -> Self {
+        Self {
+            queue_order,
+            ..self
+        }
+    }
+
+    /// Complete the definition of a notification, returning a reference to
+    /// the notification.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> notification::Notification<System> {
+        let inner = &mut cfg.inner;
+
+        inner.notifications.push(CfgBuilderNotification {
+            initial_pending: self.initial_pending,
+            queue_order: self.queue_order,
+        });
+
+        unsafe {
+            notification::Notification::from_id(NonZeroUsize::new_unchecked(
+                inner.notifications.len(),
+            ))
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct CfgBuilderNotification {
+    initial_pending: bool,
+    queue_order: wait::QueueOrder,
+}
+
+impl Clone for CfgBuilderNotification {
+    fn clone(&self) -> Self {
+        Self {
+            initial_pending: self.initial_pending,
+            queue_order: self.queue_order,
+        }
+    }
+}
+
+impl Copy for CfgBuilderNotification {}
+
+impl CfgBuilderNotification {
+    pub const fn to_state<System: Port>(&self) -> notification::NotificationCb<System> {
+        notification::NotificationCb {
+            st: CpuLockCell::new(if self.initial_pending {
+                notification::NotificationSt::Pending
+            } else {
+                notification::NotificationSt::Clear
+            }),
+            wait_queue: wait::WaitQueue::new(self.queue_order),
+        }
+    }
+}
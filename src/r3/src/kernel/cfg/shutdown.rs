@@ -0,0 +1,112 @@
+use core::marker::PhantomData;
+
+use crate::{
+    kernel::{cfg::CfgBuilder, shutdown, Port},
+    utils::ComptimeVec,
+};
+
+impl<System: Port> shutdown::ShutdownHook<System> {
+    /// Construct a `CfgShutdownHookBuilder` to register a shutdown hook in
+    /// [a configuration function](crate#static-configuration).
+    pub const fn build() -> CfgShutdownHookBuilder<System> {
+        CfgShutdownHookBuilder::new()
+    }
+}
+
+/// Configuration builder type for [`ShutdownHook`].
+///
+/// [`ShutdownHook`]: crate::kernel::ShutdownHook
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgShutdownHookBuilder<System> {
+    _phantom: PhantomData<System>,
+    start: Option<fn(usize)>,
+    param: usize,
+    priority: i32,
+}
+
+impl<System: Port> CfgShutdownHookBuilder<System> {
+    const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+            start: None,
+            param: 0,
+            priority: 0,
+        }
+    }
+
+    /// \[**Required**\] Specify the entry point.
+    pub const fn start(self, start: fn(usize)) -> Self {
+        Self {
+            start: Some(start),
+            ..self
+        }
+    }
+
+    /// Specify the parameter to `start`. Defaults to `0`.
+    pub const fn param(self, param: usize) -> Self {
+        Self { param, ..self }
+    }
+
+    /// Specify the priority. Defaults to `0` when unspecified.
+    ///
+    /// Shutdown hooks will execute in the descending order of priority.
+    /// Shutdown hooks with identical priority values will execute in the
+    /// reverse registration order.
+    pub const fn priority(self, priority: i32) -> Self {
+        Self { priority, ..self }
+    }
+
+    /// Complete the registration of a shutdown hook, returning an
+    /// `ShutdownHook` object.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> shutdown::ShutdownHook<System> {
+        let inner = &mut cfg.inner;
+
+        let order = inner.shutdown_hooks.len();
+        inner.shutdown_hooks.push(CfgBuilderShutdownHook {
+            // FIXME: Work-around for `Option::expect` being not `const fn`
+            start: if let Some(x) = self.start {
+                x
+            } else {
+                panic!("`start` is not specified")
+            },
+            param: self.param,
+            priority: self.priority,
+            order,
+        });
+
+        shutdown::ShutdownHook::new()
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CfgBuilderShutdownHook {
+    start: fn(usize),
+    param: usize,
+    priority: i32,
+    /// The registration order.
+    order: usize,
+}
+
+/// Sort shutdown hooks by (priority, order) in the descending order, which is
+/// the order in which they are called.
+pub(super) const fn sort_hooks(shutdown_hooks: &mut ComptimeVec<CfgBuilderShutdownHook>) {
+    sort_unstable_by!(
+        shutdown_hooks.len(),
+        |i| shutdown_hooks.get_mut(i),
+        |x, y| if x.priority != y.priority {
+            x.priority > y.priority
+        } else {
+            x.order > y.order
+        }
+    );
+}
+
+impl CfgBuilderShutdownHook {
+    pub const fn to_attr(&self) -> shutdown::ShutdownHookAttr {
+        shutdown::ShutdownHookAttr {
+            start: self.start,
+            param: self.param,
+        }
+    }
+}
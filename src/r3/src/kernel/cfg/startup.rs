@@ -23,6 +23,7 @@ pub struct CfgStartupHookBuilder<System> {
     param: usize,
     priority: i32,
     unchecked: bool,
+    trampoline: startup::StartupHookTrampoline,
 }
 
 impl<System: Port> CfgStartupHookBuilder<System> {
@@ -33,6 +34,7 @@ impl<System: Port> CfgStartupHookBuilder<System> {
             param: 0,
             priority: 0,
             unchecked: false,
+            trampoline: startup::call_start,
         }
     }
 
@@ -76,6 +78,19 @@ impl<System: Port> CfgStartupHookBuilder<System> {
         }
     }
 
+    /// Specify the function used to call the entry point. This allows `start`
+    /// to be a type-erased function pointer of a different signature.
+    ///
+    /// # Safety
+    ///
+    /// `trampoline` must be able to call `start` safely.
+    pub(crate) const unsafe fn trampoline(
+        self,
+        trampoline: startup::StartupHookTrampoline,
+    ) -> Self {
+        Self { trampoline, ..self }
+    }
+
     /// Complete the registration of a startup hook, returning an `StartupHook`
     /// object.
     pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> startup::StartupHook<System> {
@@ -96,6 +111,7 @@ impl<System: Port> CfgStartupHookBuilder<System> {
             param: self.param,
             priority: self.priority,
             order,
+            trampoline: self.trampoline,
         });
 
         startup::StartupHook::new()
@@ -110,6 +126,7 @@ pub struct CfgBuilderStartupHook {
     priority: i32,
     /// The registration order.
     order: usize,
+    trampoline: startup::StartupHookTrampoline,
 }
 
 /// Sort startup hooks by (priority, order).
@@ -130,6 +147,7 @@ impl CfgBuilderStartupHook {
         startup::StartupHookAttr {
             start: self.start,
             param: self.param,
+            trampoline: self.trampoline,
         }
     }
 }
@@ -58,7 +58,12 @@ impl<System: Port> CfgTaskBuilder<System> {
         Self { param, ..self }
     }
 
-    /// Specify the task's stack size.
+    /// Specify the task's stack size. The size is rounded up to a multiple
+    /// of [`STACK_ALIGN`] and must not be smaller than
+    /// [`STACK_MINIMUM_SIZE`].
+    ///
+    /// [`STACK_ALIGN`]: crate::kernel::PortThreading::STACK_ALIGN
+    /// [`STACK_MINIMUM_SIZE`]: crate::kernel::PortThreading::STACK_MINIMUM_SIZE
     pub const fn stack_size(self, stack_size: usize) -> Self {
         assert!(
             self.stack.is_none(),
@@ -128,6 +133,15 @@ impl<System: Port> CfgTaskBuilder<System> {
             TaskStack::Hunk(hunk) => hunk,
         };
 
+        // Validate the stack region
+        let (stack_hunk, stack_len) = stack.into_inner();
+        if stack_len < System::STACK_MINIMUM_SIZE {
+            panic!("the task's stack is smaller than `PortThreading::STACK_MINIMUM_SIZE`");
+        }
+        if stack_hunk.offset() % System::STACK_ALIGN != 0 || stack_len % System::STACK_ALIGN != 0 {
+            panic!("the task's stack region is not aligned to `PortThreading::STACK_ALIGN`");
+        }
+
         let inner = &mut cfg.inner;
 
         inner.tasks.push(CfgBuilderTask {
@@ -194,6 +208,8 @@ impl<System: Port> CfgBuilderTask<System> {
             wait: Init::INIT,
             park_token: CpuLockCell::new(false),
             last_mutex_held: CpuLockCell::new(None),
+            #[cfg(feature = "task_stats")]
+            cpu_time: CpuLockCell::new(0),
         }
     }
 
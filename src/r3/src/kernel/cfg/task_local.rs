@@ -0,0 +1,95 @@
+use core::{marker::PhantomData, mem};
+
+use crate::{
+    hunk::INIT_HOOK_PRIORITY,
+    kernel::{cfg::CfgBuilder, hunk, task_local, Kernel, Port, StartupHook},
+    utils::Init,
+};
+
+impl<System: Kernel, T: Send + Init + 'static> task_local::TaskLocal<System, T> {
+    /// Construct a `CfgTaskLocalBuilder` to define a task-local storage slot
+    /// in [a configuration function](crate#static-configuration).
+    pub const fn build() -> CfgTaskLocalBuilder<System, T> {
+        CfgTaskLocalBuilder {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Configuration builder type for [`TaskLocal`].
+///
+/// [`TaskLocal`]: crate::kernel::TaskLocal
+#[must_use = "must call `finish()` to complete registration"]
+pub struct CfgTaskLocalBuilder<System, T> {
+    _phantom: PhantomData<(System, T)>,
+}
+
+impl<System: Kernel, T: Send + Init + 'static> CfgTaskLocalBuilder<System, T> {
+    /// Complete the definition of a task-local storage slot, returning a
+    /// reference to the slot.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> task_local::TaskLocal<System, T> {
+        let inner = &mut cfg.inner;
+
+        let index = inner.task_locals.len();
+        inner.task_locals.push(CfgBuilderTaskLocal {
+            size: mem::size_of::<T>(),
+            align: mem::align_of::<T>(),
+            offset: 0,
+        });
+
+        // Initialize the values of all tasks. The storage is allocated by
+        // `CfgBuilder::finalize`, so the initializer looks up the offset at
+        // runtime.
+        unsafe {
+            StartupHook::build()
+                .priority(INIT_HOOK_PRIORITY)
+                .start(|index| {
+                    let offset = System::TASK_LOCAL_OFFSETS[index];
+                    let slots = hunk::Hunk::<System>::from_offset(offset).as_ptr() as *mut T;
+                    for i in 0..System::task_cb_pool().len() {
+                        // Safety: The storage is large enough to contain a
+                        //         value for every task
+                        slots.wrapping_add(i).write(T::INIT);
+                    }
+                })
+                .unchecked()
+                .param(index)
+                .finish(cfg);
+        }
+
+        task_local::TaskLocal::from_index(index)
+    }
+}
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CfgBuilderTaskLocal {
+    size: usize,
+    align: usize,
+    /// The offset of the storage, which is assigned by
+    /// [`alloc_task_local_storage`].
+    offset: usize,
+}
+
+impl CfgBuilderTaskLocal {
+    pub const fn to_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Allocate the storage of all task-local storage slots. Must be called after
+/// all tasks are defined.
+pub(super) const fn alloc_task_local_storage<System: Port>(cfg: &mut CfgBuilder<System>) {
+    let num_tasks = cfg.inner.tasks.len();
+
+    let mut i = 0;
+    while i < cfg.inner.task_locals.len() {
+        let task_local = *cfg.inner.task_locals.get(i);
+        let storage = hunk::Hunk::<System>::build()
+            .len(task_local.size * num_tasks)
+            .align(task_local.align)
+            .finish(cfg);
+        cfg.inner.task_locals.get_mut(i).offset = storage.offset();
+        i += 1;
+    }
+}
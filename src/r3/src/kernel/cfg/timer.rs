@@ -177,6 +177,7 @@ impl CfgBuilderTimer {
             entry_point: self.start,
             entry_param: self.param,
             init_active: self.active,
+            init_delay: self.delay,
             _phantom: PhantomData,
         }
     }
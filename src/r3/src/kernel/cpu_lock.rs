@@ -0,0 +1,58 @@
+//! CPU Lock guards
+use core::{fmt, marker::PhantomData};
+
+use super::Kernel;
+
+/// An RAII guard of [CPU Lock]. When this structure is dropped, CPU Lock will
+/// be deactivated.
+///
+/// This structure is created by [`Kernel::lock_cpu`]. It isn't [`Send`]
+/// because CPU Lock is a property of the current execution context.
+///
+/// [CPU Lock]: crate#system-states
+///
+/// # Examples
+///
+/// ```rust
+/// use r3::prelude::*;
+///
+/// fn increment_shared_counter<System: Kernel>(counter: &core::cell::Cell<u32>) {
+///     let _guard = System::lock_cpu().unwrap();
+///     counter.set(counter.get() + 1);
+///     // CPU Lock is deactivated here
+/// }
+/// ```
+#[must_use = "if unused CPU Lock will immediately be deactivated"]
+pub struct CpuLockGuard<System: Kernel> {
+    _phantom: PhantomData<(System, *mut ())>,
+}
+
+impl<System: Kernel> CpuLockGuard<System> {
+    /// Construct a `CpuLockGuard`.
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock must have been activated by the caller.
+    pub(super) unsafe fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<System: Kernel> fmt::Debug for CpuLockGuard<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CpuLockGuard")
+    }
+}
+
+/// The destructor of `CpuLockGuard` that deactivates CPU Lock. It will panic
+/// if CPU Lock has already been deactivated by other means.
+impl<System: Kernel> Drop for CpuLockGuard<System> {
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: CPU Lock was activated by `Kernel::lock_cpu`, and it's
+        //         owned by `self`
+        unsafe { System::release_cpu_lock() }.unwrap();
+    }
+}
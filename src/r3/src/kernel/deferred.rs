@@ -0,0 +1,229 @@
+//! Deferred works
+use core::{fmt, hash, marker::PhantomData};
+
+use super::{
+    task::{self, TaskSt},
+    utils::{lock_cpu, CpuLockCell},
+    BadIdError, DeferredWorkError, Id, Kernel,
+};
+use crate::utils::Init;
+
+/// Represents a single deferred work in a system.
+///
+/// A deferred work is a function that is scheduled to run in a task context at
+/// a later time. It's useful for off-loading time-consuming operations from
+/// an interrupt handler or a timer callback function, keeping interrupt latency
+/// short.
+///
+/// Each deferred work is backed by a dedicated task, which is created at
+/// configuration time and kept in the Dormant state while there's no pending
+/// work. [`schedule`] marks the work as pending and activates the backing task
+/// as needed. The backing task calls the entry point repeatedly until there
+/// are no more pending requests and then exits, returning to the Dormant
+/// state.
+///
+/// Calling [`schedule`] more than once before the entry point starts
+/// executing has the same effect as calling it once. If [`schedule`] is called
+/// while the entry point is executing, the entry point will be called once
+/// again after the current execution completes.
+///
+/// This type is ABI-compatible with [`Id`].
+///
+/// <div class="admonition-follows"></div>
+///
+/// > **Relation to Other Specifications:** Similar to *tasklets* and *work
+/// > queues* from Linux and *deferred procedure calls* from Windows NT.
+///
+/// [`schedule`]: DeferredWork::schedule
+///
+/// # Examples
+///
+/// ```rust
+/// # #![feature(const_fn)]
+/// # #![feature(const_mut_refs)]
+/// # #![feature(const_fn_fn_ptr_basics)]
+/// use r3::kernel::{cfg::CfgBuilder, DeferredWork, Kernel};
+///
+/// const fn configure<System: Kernel>(b: &mut CfgBuilder<System>) -> DeferredWork<System> {
+///     DeferredWork::build()
+///         .start(|_| dbg!())
+///         .priority(2)
+///         .finish(b)
+/// }
+/// ```
+#[doc(include = "../common.md")]
+#[repr(transparent)]
+pub struct DeferredWork<System>(Id, PhantomData<System>);
+
+impl<System> Clone for DeferredWork<System> {
+    fn clone(&self) -> Self {
+        Self(self.0, self.1)
+    }
+}
+
+impl<System> Copy for DeferredWork<System> {}
+
+impl<System> PartialEq for DeferredWork<System> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<System> Eq for DeferredWork<System> {}
+
+impl<System> hash::Hash for DeferredWork<System> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        hash::Hash::hash(&self.0, state);
+    }
+}
+
+impl<System> fmt::Debug for DeferredWork<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("DeferredWork").field(&self.0).finish()
+    }
+}
+
+impl<System> DeferredWork<System> {
+    /// Construct a `DeferredWork` from `Id`.
+    ///
+    /// # Safety
+    ///
+    /// The kernel can handle invalid IDs without a problem. However, the
+    /// constructed `DeferredWork` may point to an object that is not intended
+    /// to be manipulated except by its creator. This is usually prevented by
+    /// making `DeferredWork` an opaque handle, but this safeguard can be
+    /// circumvented by this method.
+    pub const unsafe fn from_id(id: Id) -> Self {
+        Self(id, PhantomData)
+    }
+
+    /// Get the raw `Id` value representing this deferred work.
+    pub const fn id(self) -> Id {
+        self.0
+    }
+}
+
+impl<System: Kernel> DeferredWork<System> {
+    /// Get the `DeferredWorkCb` for this deferred work.
+    fn deferred_work_cb(self) -> Result<&'static DeferredWorkCb<System>, BadIdError> {
+        System::get_deferred_work_cb(self.0.get() - 1).ok_or(BadIdError::BadId)
+    }
+
+    /// Schedule the execution of the deferred work.
+    ///
+    /// This method can be called from any context except when CPU Lock is
+    /// active. If the work is already pending, this method does nothing.
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn schedule(self) -> Result<(), DeferredWorkError> {
+        let mut lock = lock_cpu::<System>()?;
+        let deferred_work_cb = self.deferred_work_cb()?;
+
+        if deferred_work_cb.pending.replace(&mut *lock, true) {
+            // The work is already pending; it will be picked up by the backing
+            // task
+            return Ok(());
+        }
+
+        let task_cb = System::get_task_cb(deferred_work_cb.attr.task_index).unwrap();
+
+        if *task_cb.st.read(&*lock) == TaskSt::Dormant {
+            // Wake up the backing task
+            task::activate(lock, task_cb).unwrap();
+        } else {
+            // The backing task is still running the previous execution. It
+            // will notice the new request when it's done.
+        }
+
+        Ok(())
+    }
+}
+
+/// *Deferred work control block* - the state data of a deferred work.
+#[doc(hidden)]
+pub struct DeferredWorkCb<System: Kernel> {
+    pub(super) attr: &'static DeferredWorkAttr<System>,
+
+    /// `true` if the deferred work is scheduled and the entry point hasn't
+    /// started executing yet.
+    pub(super) pending: CpuLockCell<System, bool>,
+}
+
+impl<System: Kernel> Init for DeferredWorkCb<System> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        attr: &Init::INIT,
+        pending: Init::INIT,
+    };
+}
+
+impl<System: Kernel> fmt::Debug for DeferredWorkCb<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeferredWorkCb")
+            .field("self", &(self as *const _))
+            .field("attr", &self.attr)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+/// The static properties of a deferred work.
+#[doc(hidden)]
+pub struct DeferredWorkAttr<System> {
+    /// The entry point of the deferred work.
+    pub(super) entry_point: fn(usize),
+
+    /// The parameter supplied for `entry_point`.
+    pub(super) entry_param: usize,
+
+    /// The index of the backing task in
+    /// [`super::KernelCfg2::task_cb_pool`].
+    pub(super) task_index: usize,
+
+    pub(super) _phantom: PhantomData<System>,
+}
+
+impl<System> Init for DeferredWorkAttr<System> {
+    const INIT: Self = Self {
+        entry_point: |_| {},
+        entry_param: 0,
+        task_index: 0,
+        _phantom: PhantomData,
+    };
+}
+
+impl<System: Kernel> fmt::Debug for DeferredWorkAttr<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeferredWorkAttr")
+            .field("entry_point", &self.entry_point)
+            .field("entry_param", &self.entry_param)
+            .field("task_index", &self.task_index)
+            .finish()
+    }
+}
+
+/// The entry point of a deferred work's backing task. `i` is an index into
+/// [`super::KernelCfg2::deferred_work_cb_pool`].
+pub(super) fn deferred_work_task_body<System: Kernel>(i: usize) {
+    let deferred_work_cb = System::get_deferred_work_cb(i).unwrap();
+
+    loop {
+        let mut lock = lock_cpu::<System>().unwrap();
+
+        if !deferred_work_cb.pending.replace(&mut *lock, false) {
+            // There's no more work to do. Exit the task without releasing CPU
+            // Lock so that a request made in the meantime can't be lost.
+            core::mem::forget(lock);
+
+            // Safety: The task stack doesn't contain any data that outlives
+            // this task
+            unsafe { System::exit_task() }.unwrap();
+        }
+
+        drop(lock);
+
+        (deferred_work_cb.attr.entry_point)(deferred_work_cb.attr.entry_param);
+    }
+}
@@ -278,7 +278,7 @@ define_error! {
     ///
     /// [`Task::current`]: super::Task::current
     pub enum GetCurrentTaskError: BadContextError {
-        /// CPU Lock is active.
+        /// CPU Lock is active, and the current context is not a boot context.
         BadContext,
     }
 }
@@ -298,6 +298,37 @@ define_error! {
     }
 }
 
+define_error! {
+    mod suspend_task_error {}
+    /// Error type for [`Task::suspend`].
+    ///
+    /// [`Task::suspend`]: super::Task::suspend
+    pub enum SuspendTaskError: BadContextError, BadIdError, BadObjectStateError {
+        /// The task ID is out of range.
+        BadId,
+        /// CPU Lock is active, or the task is the currently running task and
+        /// Priority Boost is active.
+        BadContext,
+        /// The task is not in the Ready or Running state.
+        BadObjectState,
+    }
+}
+
+define_error! {
+    mod resume_task_error {}
+    /// Error type for [`Task::resume`].
+    ///
+    /// [`Task::resume`]: super::Task::resume
+    pub enum ResumeTaskError: BadContextError, BadIdError, BadObjectStateError {
+        /// The task ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+        /// The task is not in the Suspended state.
+        BadObjectState,
+    }
+}
+
 define_error! {
     mod set_task_priority_error {}
     /// Error type for [`Task::set_priority`].
@@ -334,6 +365,49 @@ define_error! {
     }
 }
 
+define_error! {
+    mod get_task_stack_usage_error {}
+    /// Error type for [`Task::stack_usage`].
+    ///
+    /// [`Task::stack_usage`]: super::Task::stack_usage
+    pub enum GetTaskStackUsageError: BadContextError, BadIdError {
+        /// The task ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
+
+define_error! {
+    mod get_task_cpu_time_error {}
+    /// Error type for [`Task::cpu_time`].
+    ///
+    /// [`Task::cpu_time`]: super::Task::cpu_time
+    pub enum GetTaskCpuTimeError: BadContextError, BadIdError {
+        /// The task ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
+
+define_error! {
+    mod with_priority_error {}
+    /// Error type for [`Kernel::with_priority`].
+    ///
+    /// [`Kernel::with_priority`]: super::Kernel::with_priority
+    pub enum WithPriorityError: BadContextError {
+        /// CPU Lock is active, or the current context is not a task context.
+        BadContext,
+        /// The priority is out of range, or the task owns a mutex created with
+        /// with the protocol attribute having the value [`Ceiling`] and the
+        /// new priority is higher than the mutex's priority ceiling.
+        ///
+        /// [`Ceiling`]: crate::kernel::MutexProtocol::Ceiling
+        BadParam,
+    }
+}
+
 define_error! {
     mod exit_task_error {}
     /// Error type for [`Kernel::exit_task`].
@@ -385,6 +459,17 @@ define_error! {
     }
 }
 
+define_error! {
+    mod total_cpu_time_error {}
+    /// Error type for [`Kernel::total_cpu_time`].
+    ///
+    /// [`Kernel::total_cpu_time`]: super::Kernel::total_cpu_time
+    pub enum TotalCpuTimeError: BadContextError {
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
+
 define_error! {
     mod adjust_time_error {}
     /// Error type for [`Kernel::adjust_time`].
@@ -477,6 +562,21 @@ define_error! {
     }
 }
 
+define_error! {
+    mod try_park_error {}
+    /// Error type for [`Kernel::try_park`].
+    ///
+    /// [`Kernel::try_park`]: super::Kernel::try_park
+    pub enum TryParkError: BadContextError {
+        /// CPU Lock is active, or the current context is not a [task context].
+        ///
+        /// [task context]: crate#contexts
+        BadContext,
+        /// The task doesn't have a token.
+        Timeout,
+    }
+}
+
 define_error! {
     mod unpark_exact_error {}
     /// Error type for [`Task::unpark_exact`].
@@ -487,7 +587,8 @@ define_error! {
         BadContext,
         /// The task ID is out of range.
         BadId,
-        /// The task already has a token.
+        /// The task already has a token. The token count saturates at one, so
+        /// the call had no effect.
         QueueOverflow,
         /// The task is in the Dormant state.
         BadObjectState,
@@ -509,6 +610,26 @@ define_error! {
         BadParam,
     }
 }
+
+define_error! {
+    mod sleep_until_error {}
+    /// Error type for [`Kernel::sleep_until`].
+    ///
+    /// [`Kernel::sleep_until`]: super::Kernel::sleep_until
+    pub enum SleepUntilError: BadContextError, BadParamError {
+        /// CPU Lock is active, or the current context is not [waitable].
+        ///
+        /// [waitable]: crate#contexts
+        BadContext,
+        Interrupted,
+        /// The specified time is too far in the future. The maximum
+        /// representable sleep duration is [`Duration::MAX`].
+        ///
+        /// [`Duration::MAX`]: crate::time::Duration::MAX
+        BadParam,
+    }
+}
+
 define_error! {
     mod update_event_group_error {}
     /// Error type for [`EventGroup::set`] and [`EventGroup::clear`].
@@ -656,6 +777,25 @@ define_error! {
     }
 }
 
+define_error! {
+    mod wait_semaphore_n_error {}
+    /// Error type for [`Semaphore::wait_n`].
+    ///
+    /// [`Semaphore::wait_n`]: super::Semaphore::wait_n
+    pub enum WaitSemaphoreNError: BadContextError, BadIdError, WaitError, BadParamError {
+        /// The semaphore ID is out of range.
+        BadId,
+        /// CPU Lock is active, or the current context is not [waitable].
+        ///
+        /// [waitable]: crate#contexts
+        BadContext,
+        Interrupted,
+        /// The requested number of permits exceeds the semaphore's maximum
+        /// value.
+        BadParam,
+    }
+}
+
 define_error! {
     mod wait_semaphore_timeout_error {}
     /// Error type for [`Semaphore::wait_one_timeout`].
@@ -675,6 +815,68 @@ define_error! {
     }
 }
 
+define_error! {
+    mod notify_notification_error {}
+    /// Error type for [`Notification::notify`].
+    ///
+    /// [`Notification::notify`]: super::Notification::notify
+    pub enum NotifyNotificationError: BadContextError, BadIdError {
+        /// The notification ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
+
+define_error! {
+    mod poll_notification_error {}
+    /// Error type for [`Notification::poll`].
+    ///
+    /// [`Notification::poll`]: super::Notification::poll
+    pub enum PollNotificationError: BadContextError, BadIdError {
+        /// The notification ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+        Timeout,
+    }
+}
+
+define_error! {
+    mod wait_notification_error {}
+    /// Error type for [`Notification::wait`].
+    ///
+    /// [`Notification::wait`]: super::Notification::wait
+    pub enum WaitNotificationError: BadContextError, BadIdError, WaitError {
+        /// The notification ID is out of range.
+        BadId,
+        /// CPU Lock is active, or the current context is not [waitable].
+        ///
+        /// [waitable]: crate#contexts
+        BadContext,
+        Interrupted,
+    }
+}
+
+define_error! {
+    mod wait_notification_timeout_error {}
+    /// Error type for [`Notification::wait_timeout`].
+    ///
+    /// [`Notification::wait_timeout`]: super::Notification::wait_timeout
+    pub enum WaitNotificationTimeoutError: BadContextError, BadIdError, WaitTimeoutError, BadParamError {
+        /// The notification ID is out of range.
+        BadId,
+        /// CPU Lock is active, or the current context is not [waitable].
+        ///
+        /// [waitable]: crate#contexts
+        BadContext,
+        Interrupted,
+        Timeout,
+        /// The timeout duration is negative.
+        BadParam,
+    }
+}
+
 define_error! {
     mod query_mutex_error {}
     /// Error type for [`Mutex::is_locked`].
@@ -849,6 +1051,20 @@ define_error! {
     }
 }
 
+define_error! {
+    mod get_interrupt_line_priority_error {}
+    /// Error type for [`InterruptLine::get_priority`].
+    ///
+    /// [`InterruptLine::get_priority`]: super::InterruptLine::get_priority
+    pub enum GetInterruptLinePriorityError: BadParamError {
+        /// Reading an interrupt line priority is not supported by the port.
+        NotSupported,
+        /// Reading the priority of the specified interrupt line is not
+        /// supported.
+        BadParam,
+    }
+}
+
 define_error! {
     mod enable_interrupt_line_error {}
     /// Error type for [`InterruptLine::enable`] and [`InterruptLine::disable`].
@@ -903,16 +1119,34 @@ define_error! {
     }
 }
 
+define_error! {
+    mod set_interrupt_line_trigger_mode_error {}
+    /// Error type for [`InterruptLine::set_trigger_mode`].
+    ///
+    /// [`InterruptLine::set_trigger_mode`]: super::InterruptLine::set_trigger_mode
+    pub enum SetInterruptLineTriggerModeError: BadParamError {
+        /// The operation is not supported by the port, or the port can't
+        /// model the specified trigger mode.
+        NotSupported,
+        /// Changing the trigger mode of the specified interrupt line is not
+        /// supported.
+        BadParam,
+    }
+}
+
 define_error! {
     mod query_interrupt_line_error {}
-    /// Error type for [`InterruptLine::is_pending`].
+    /// Error type for [`InterruptLine::is_pending`] and
+    /// [`InterruptLine::is_enabled`].
     ///
     /// [`InterruptLine::is_pending`]: super::InterruptLine::is_pending
+    /// [`InterruptLine::is_enabled`]: super::InterruptLine::is_enabled
     pub enum QueryInterruptLineError: BadParamError {
-        /// Reading a pending flag is not supported by the port.
+        /// Reading a pending flag or an enable state is not supported by the
+        /// port.
         NotSupported,
-        /// Reading the pending flag of the specified interrupt line is not
-        /// supported.
+        /// Reading the pending flag or the enable state of the specified
+        /// interrupt line is not supported.
         BadParam,
     }
 }
@@ -943,6 +1177,32 @@ define_error! {
     }
 }
 
+define_error! {
+    mod reset_timer_error {}
+    /// Error type for [`Timer::reset`].
+    ///
+    /// [`Timer::reset`]: super::Timer::reset
+    pub enum ResetTimerError: BadContextError, BadIdError {
+        /// The timer ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
+
+define_error! {
+    mod query_timer_error {}
+    /// Error type for [`Timer::is_active`].
+    ///
+    /// [`Timer::is_active`]: super::Timer::is_active
+    pub enum QueryTimerError: BadContextError, BadIdError {
+        /// The timer ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
+
 define_error! {
     mod set_timer_delay_error {}
     /// Error type for [`Timer::set_delay`].
@@ -972,3 +1232,16 @@ define_error! {
         BadParam,
     }
 }
+
+define_error! {
+    mod deferred_work_error {}
+    /// Error type for [`DeferredWork::schedule`].
+    ///
+    /// [`DeferredWork::schedule`]: super::DeferredWork::schedule
+    pub enum DeferredWorkError: BadContextError, BadIdError {
+        /// The deferred work ID is out of range.
+        BadId,
+        /// CPU Lock is active.
+        BadContext,
+    }
+}
@@ -9,10 +9,29 @@ use super::{
 };
 use crate::{time::Duration, utils::Init};
 
-// TODO: Support changing `EventGroupBits`?
 /// Unsigned integer type backing event groups.
+///
+/// This is `u32` by default and `u64` if the `event_group_64bit` feature is
+/// enabled.
+///
+/// <div class="admonition-follows"></div>
+///
+/// > **Rationale:** Event group bits are only accessed while CPU Lock is
+/// > active, so the choice of the data type isn't limited by the target's
+/// > native atomic operation width. However, a 64-bit data type increases the
+/// > code size and the runtime overhead on 32-bit targets, so it's opt-in.
+#[cfg(not(feature = "event_group_64bit"))]
+#[doc(include = "../common.md")]
 pub type EventGroupBits = u32;
 
+/// Unsigned integer type backing event groups.
+///
+/// This is `u32` by default and `u64` if the `event_group_64bit` feature is
+/// enabled.
+#[cfg(feature = "event_group_64bit")]
+#[doc(include = "../common.md")]
+pub type EventGroupBits = u64;
+
 /// Represents a single event group in a system.
 ///
 /// An event group is a set of bits that can be updated and waited for to be
@@ -106,16 +125,23 @@ impl<System: Kernel> EventGroup<System> {
         Ok(())
     }
 
-    /// Clear the specified bits.
+    /// Clear the specified bits, returning the bits that were set before
+    /// clearing.
+    ///
+    /// The read and the update are performed atomically, so this method can be
+    /// used to consume a set of events without racing with
+    /// [`set`](Self::set).
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
-    pub fn clear(self, bits: EventGroupBits) -> Result<(), UpdateEventGroupError> {
+    pub fn clear(self, bits: EventGroupBits) -> Result<EventGroupBits, UpdateEventGroupError> {
         let mut lock = utils::lock_cpu::<System>()?;
         let event_group_cb = self.event_group_cb()?;
-        event_group_cb.bits.replace_with(&mut *lock, |b| *b & !bits);
-        Ok(())
+        Ok(event_group_cb.bits.replace_with(&mut *lock, |b| *b & !bits))
     }
 
     /// Get the currently set bits.
+    ///
+    /// This method doesn't block or modify the bits, so it can be used in any
+    /// context, including interrupt handlers.
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn get(self) -> Result<EventGroupBits, GetEventGroupError> {
         let lock = utils::lock_cpu::<System>()?;
@@ -146,7 +172,46 @@ impl<System: Kernel> EventGroup<System> {
         wait(event_group_cb, lock, bits, flags)
     }
 
+    /// Wait for any bit to be set. Equivalent to calling [`wait`](Self::wait)
+    /// with all bits set in `bits` and an empty `flags`.
+    ///
+    /// Returns the currently set bits, which include at least one set bit.
+    /// This method doesn't clear any bits. Use `wait` with
+    /// `EventGroupWaitFlags::CLEAR` to consume the observed bits atomically.
+    ///
+    /// This system service may block. Therefore, calling this method is not
+    /// allowed in [a non-waitable context] and will return `Err(BadContext)`.
+    ///
+    /// [a non-waitable context]: crate#contexts
+    #[inline]
+    pub fn wait_any(self) -> Result<EventGroupBits, WaitEventGroupError> {
+        self.wait(EventGroupBits::MAX, EventGroupWaitFlags::empty())
+    }
+
+    /// Wait for all of the specified bits to be set. Equivalent to calling
+    /// [`wait`](Self::wait) with `EventGroupWaitFlags::ALL`.
+    ///
+    /// Returns the currently set bits. This method doesn't clear any bits.
+    ///
+    /// This system service may block. Therefore, calling this method is not
+    /// allowed in [a non-waitable context] and will return `Err(BadContext)`.
+    ///
+    /// [a non-waitable context]: crate#contexts
+    #[inline]
+    pub fn wait_all(self, bits: EventGroupBits) -> Result<EventGroupBits, WaitEventGroupError> {
+        self.wait(bits, EventGroupWaitFlags::ALL)
+    }
+
     /// [`wait`](Self::wait) with timeout.
+    ///
+    /// The wait condition is checked before the timeout is taken into
+    /// consideration. Thus, this method succeeds without blocking if the
+    /// condition is already satisfied, even if `timeout` is zero.
+    ///
+    /// On timeout, the calling task is removed from the event group's wait
+    /// queue, and the event group's bits are left unchanged. Bits set after
+    /// that point remain set, so they will be observed by a subsequent wait
+    /// operation.
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn wait_timeout(
         self,
@@ -165,6 +230,12 @@ impl<System: Kernel> EventGroup<System> {
     /// Non-blocking version of [`wait`](Self::wait). Returns immediately with
     /// [`PollEventGroupError::Timeout`] if the unblocking condition is not
     /// satisfied.
+    ///
+    /// Unless `EventGroupWaitFlags::CLEAR` is specified, this method doesn't
+    /// modify the bits. Combined with an empty `flags`, this can be used to
+    /// check if any of the specified bits are set without consuming them.
+    /// Unlike `wait`, this method can be called in a non-waitable context,
+    /// such as an interrupt handler.
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn poll(
         self,
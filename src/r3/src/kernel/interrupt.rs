@@ -1,8 +1,9 @@
 use core::{fmt, hash, marker::PhantomData};
 
 use super::{
-    utils, ClearInterruptLineError, EnableInterruptLineError, Kernel, PendInterruptLineError, Port,
-    QueryInterruptLineError, SetInterruptLinePriorityError,
+    utils, ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError,
+    Kernel, PendInterruptLineError, Port, QueryInterruptLineError, SetInterruptLinePriorityError,
+    SetInterruptLineTriggerModeError,
 };
 use crate::utils::Init;
 
@@ -15,6 +16,24 @@ pub type InterruptNum = usize;
 /// Priority value for an interrupt line.
 pub type InterruptPriority = i16;
 
+/// Specifies the type of signal transition that pends an interrupt line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterruptLineTriggerMode {
+    /// The interrupt line is pended as long as the interrupt signal is
+    /// asserted. [Clearing] the pending flag has no lasting effect unless the
+    /// interrupt source is deasserted, i.e., the interrupt line is pended
+    /// again immediately.
+    ///
+    /// [Clearing]: InterruptLine::clear
+    Level,
+    /// The interrupt line is pended upon detection of an edge of the interrupt
+    /// signal. The pending flag remains set until the interrupt is taken or
+    /// [cleared].
+    ///
+    /// [cleared]: InterruptLine::clear
+    Edge,
+}
+
 /// Refers to an interrupt line in a system.
 pub struct InterruptLine<System>(InterruptNum, PhantomData<System>);
 
@@ -145,6 +164,76 @@ impl<System: Kernel> InterruptLine<System> {
         unsafe { System::set_interrupt_line_priority(self.0, value) }
     }
 
+    /// Get the current priority of the interrupt line.
+    #[inline]
+    pub fn get_priority(self) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+        // Safety: We are the kernel, so it's okay to call `Port`'s methods
+        unsafe { System::get_interrupt_line_priority(self.0) }
+    }
+
+    /// Set the priority of the interrupt line to `value` temporarily while
+    /// calling the given closure, restoring the original priority when the
+    /// closure returns or unwinds.
+    ///
+    /// The new priority must fall within [a managed range]. The original
+    /// priority is read by [`get_priority`]. If the port doesn't support
+    /// reading interrupt line priorities, this method returns
+    /// [`SetInterruptLinePriorityError::NotSupported`] without changing the
+    /// priority or calling the closure.
+    ///
+    /// The priority is restored even if the closure leaves CPU Lock active.
+    ///
+    /// [a managed range]: crate::kernel::PortInterrupts::MANAGED_INTERRUPT_PRIORITY_RANGE
+    /// [`get_priority`]: Self::get_priority
+    pub fn with_raised_priority<R>(
+        self,
+        value: InterruptPriority,
+        f: impl FnOnce() -> R,
+    ) -> Result<R, SetInterruptLinePriorityError> {
+        let old_value = self.get_priority().map_err(|e| match e {
+            GetInterruptLinePriorityError::NotSupported => {
+                SetInterruptLinePriorityError::NotSupported
+            }
+            GetInterruptLinePriorityError::BadParam => SetInterruptLinePriorityError::BadParam,
+        })?;
+
+        self.set_priority(value)?;
+
+        struct RestorePriority<System: Kernel> {
+            line: InterruptLine<System>,
+            value: InterruptPriority,
+        }
+
+        impl<System: Kernel> Drop for RestorePriority<System> {
+            #[inline]
+            fn drop(&mut self) {
+                // `self.value` is the priority the interrupt line had before,
+                // so restoring it doesn't create a new unmanaged interrupt
+                // handler. We are still in the task context that called
+                // `set_priority` successfully.
+                if let Ok(mut lock) = utils::lock_cpu::<System>() {
+                    // Safety: See above
+                    let _ = unsafe {
+                        self.line
+                            .set_priority_unchecked_inner(self.value, lock.borrow_mut())
+                    };
+                } else {
+                    // CPU Lock was left active by the closure.
+                    // Safety: (1) We are the kernel, so it's okay to call
+                    //         `Port`'s methods. (2) CPU Lock active
+                    let _ = unsafe { System::set_interrupt_line_priority(self.line.0, self.value) };
+                }
+            }
+        }
+
+        let _guard = RestorePriority {
+            line: self,
+            value: old_value,
+        };
+
+        Ok(f())
+    }
+
     /// Enable the interrupt line.
     #[inline]
     pub fn enable(self) -> Result<(), EnableInterruptLineError> {
@@ -180,6 +269,29 @@ impl<System: Kernel> InterruptLine<System> {
         unsafe { System::is_interrupt_line_pending(self.0) }
     }
 
+    /// Read the enable state of the interrupt line, i.e., whether it's
+    /// currently unmasked.
+    #[inline]
+    pub fn is_enabled(self) -> Result<bool, QueryInterruptLineError> {
+        // Safety: We are the kernel, so it's okay to call `Port`'s methods
+        unsafe { System::is_interrupt_line_enabled(self.0) }
+    }
+
+    /// Set the trigger mode of the interrupt line.
+    ///
+    /// Returns [`NotSupported`] if the port or the interrupt controller can't
+    /// model the specified trigger mode.
+    ///
+    /// [`NotSupported`]: SetInterruptLineTriggerModeError::NotSupported
+    #[inline]
+    pub fn set_trigger_mode(
+        self,
+        mode: InterruptLineTriggerMode,
+    ) -> Result<(), SetInterruptLineTriggerModeError> {
+        // Safety: We are the kernel, so it's okay to call `Port`'s methods
+        unsafe { System::set_interrupt_line_trigger_mode(self.0, mode) }
+    }
+
     // TODO: port-specific attributes
 }
 
@@ -195,12 +307,85 @@ impl<System> InterruptHandler<System> {
     }
 }
 
+/// Represents a software interrupt in a system.
+///
+/// A software interrupt is an interrupt line reserved by a port for the
+/// application (see [`PortInterrupts::SOFTWARE_INTERRUPT_LINES`]), which is
+/// only triggered by [`pend`]. Its handler runs at the specified interrupt
+/// priority and preempts tasks, so it can be used to defer processing from a
+/// higher-priority interrupt handler or to serialize operations performed by
+/// multiple tasks.
+///
+/// [`PortInterrupts::SOFTWARE_INTERRUPT_LINES`]: crate::kernel::PortInterrupts::SOFTWARE_INTERRUPT_LINES
+/// [`pend`]: SoftwareInterrupt::pend
+pub struct SoftwareInterrupt<System>(InterruptLine<System>);
+
+impl<System> Clone for SoftwareInterrupt<System> {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<System> Copy for SoftwareInterrupt<System> {}
+
+impl<System> PartialEq for SoftwareInterrupt<System> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<System> Eq for SoftwareInterrupt<System> {}
+
+impl<System> hash::Hash for SoftwareInterrupt<System> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        hash::Hash::hash(&self.0, state);
+    }
+}
+
+impl<System> fmt::Debug for SoftwareInterrupt<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SoftwareInterrupt")
+            .field(&self.0.num())
+            .finish()
+    }
+}
+
+impl<System> SoftwareInterrupt<System> {
+    pub(super) const fn from_line(line: InterruptLine<System>) -> Self {
+        Self(line)
+    }
+
+    /// Get the interrupt line allocated for this software interrupt.
+    pub const fn line(self) -> InterruptLine<System> {
+        self.0
+    }
+}
+
+impl<System: Kernel> SoftwareInterrupt<System> {
+    /// Signal the software interrupt. This method can be called from any
+    /// context.
+    #[inline]
+    pub fn pend(self) -> Result<(), PendInterruptLineError> {
+        self.0.pend()
+    }
+
+    /// Clear the pending flag of the software interrupt.
+    #[inline]
+    pub fn clear(self) -> Result<(), ClearInterruptLineError> {
+        self.0.clear()
+    }
+}
+
 /// Initialization parameter for an interrupt line.
 #[doc(hidden)]
 #[derive(Debug, Clone, Copy)]
 pub struct InterruptLineInit<System> {
     pub(super) line: InterruptLine<System>,
     pub(super) priority: InterruptPriority,
+    pub(super) trigger_mode: InterruptLineTriggerMode,
     pub(super) flags: InterruptLineInitFlags,
 }
 
@@ -208,6 +393,7 @@ impl<System> Init for InterruptLineInit<System> {
     const INIT: Self = Self {
         line: InterruptLine::from_num(0),
         priority: Init::INIT,
+        trigger_mode: InterruptLineTriggerMode::Edge,
         flags: InterruptLineInitFlags::empty(),
     };
 }
@@ -218,6 +404,7 @@ bitflags::bitflags! {
     pub struct InterruptLineInitFlags: u32 {
         const ENABLE = 1 << 0;
         const SET_PRIORITY = 1 << 1;
+        const SET_TRIGGER_MODE = 1 << 2;
     }
 }
 
@@ -239,6 +426,15 @@ impl<System: Kernel> InterruptAttr<System> {
     /// Can be called only during a boot phase.
     pub(super) unsafe fn init(&self, mut lock: utils::CpuLockTokenRefMut<System>) {
         for line_init in self.line_inits {
+            if line_init
+                .flags
+                .contains(InterruptLineInitFlags::SET_TRIGGER_MODE)
+            {
+                line_init
+                    .line
+                    .set_trigger_mode(line_init.trigger_mode)
+                    .unwrap();
+            }
             if line_init
                 .flags
                 .contains(InterruptLineInitFlags::SET_PRIORITY)
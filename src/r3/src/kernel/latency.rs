@@ -0,0 +1,335 @@
+//! Latency probes
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+use super::{hunk, Kernel};
+use crate::{hunk::Hunk, utils::Init};
+
+/// A measurement tool for the latency between two points in a program, such as
+/// the dispatch latency of a task.
+///
+/// [`arm`](Self::arm) records the current time, and
+/// [`read_and_reset`](Self::read_and_reset) returns the time elapsed since
+/// then. For example, calling `arm` just before waking up a task and
+/// `read_and_reset` in the woken task measures the time taken by the kernel to
+/// dispatch the task.
+///
+/// The time is measured in microseconds by [`Kernel::monotonic_time`], so the
+/// precision depends on the port's timer driver. Both methods can be called in
+/// any context.
+///
+/// A probe is meant to be used by one context at a time. The methods don't
+/// provide atomicity across them, so concurrent calls to `arm` and
+/// `read_and_reset` may produce an inaccurate result.
+///
+/// # Examples
+///
+/// ```rust
+/// # #![feature(const_fn)]
+/// # #![feature(const_mut_refs)]
+/// use r3::kernel::{cfg::CfgBuilder, Kernel, LatencyProbe, Task};
+///
+/// const fn configure<System: Kernel>(b: &mut CfgBuilder<System>) -> LatencyProbe<System> {
+///     LatencyProbe::build().finish(b)
+/// }
+///
+/// fn wake_up<System: Kernel>(probe: LatencyProbe<System>, task: Task<System>) {
+///     probe.arm();
+///     task.unpark().unwrap();
+/// }
+///
+/// // Called by `task`
+/// fn woken_up<System: Kernel>(probe: LatencyProbe<System>) {
+///     if let Some(latency_us) = probe.read_and_reset() {
+///         dbg!(latency_us);
+///     }
+/// }
+/// ```
+#[doc(include = "../common.md")]
+pub struct LatencyProbe<System>(Hunk<System, LatencyProbeState>);
+
+impl<System> Clone for LatencyProbe<System> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<System> Copy for LatencyProbe<System> {}
+
+impl<System: Kernel> fmt::Debug for LatencyProbe<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LatencyProbe").field(&*self.0).finish()
+    }
+}
+
+/// The state data of [`LatencyProbe`].
+#[doc(hidden)]
+pub struct LatencyProbeState {
+    /// `true` if the probe is armed.
+    armed: AtomicBool,
+    /// The time when the probe was armed, measured in microseconds.
+    start: AtomicU32,
+    /// The maximum latency ever measured, measured in microseconds.
+    worst_case: AtomicU32,
+}
+
+impl Init for LatencyProbeState {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        armed: AtomicBool::new(false),
+        start: AtomicU32::new(0),
+        worst_case: AtomicU32::new(0),
+    };
+}
+
+impl fmt::Debug for LatencyProbeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LatencyProbeState")
+            .field("armed", &self.armed)
+            .field("start", &self.start)
+            .field("worst_case", &self.worst_case)
+            .finish()
+    }
+}
+
+impl<System> LatencyProbe<System> {
+    pub(super) const fn from_hunk(hunk: Hunk<System, LatencyProbeState>) -> Self {
+        Self(hunk)
+    }
+}
+
+impl<System: Kernel> LatencyProbe<System> {
+    /// Record the current time as the starting point of a measurement.
+    ///
+    /// If the probe is already armed, this method overwrites the starting
+    /// point.
+    pub fn arm(self) {
+        let now = now::<System>();
+        self.0.start.store(now, Ordering::Relaxed);
+        self.0.armed.store(true, Ordering::Release);
+    }
+
+    /// Get the time elapsed since the last call to [`arm`](Self::arm),
+    /// measured in microseconds, and disarm the probe.
+    ///
+    /// Returns `None` if the probe isn't armed. The returned value is also
+    /// accounted for by [`worst_case`](Self::worst_case).
+    pub fn read_and_reset(self) -> Option<u32> {
+        let now = now::<System>();
+        if !self.0.armed.load(Ordering::Acquire) {
+            return None;
+        }
+        self.0.armed.store(false, Ordering::Relaxed);
+
+        let latency = now.wrapping_sub(self.0.start.load(Ordering::Relaxed));
+
+        if latency > self.0.worst_case.load(Ordering::Relaxed) {
+            self.0.worst_case.store(latency, Ordering::Relaxed);
+        }
+
+        Some(latency)
+    }
+
+    /// Get the maximum latency ever returned by
+    /// [`read_and_reset`](Self::read_and_reset), measured in microseconds.
+    pub fn worst_case(self) -> u32 {
+        self.0.worst_case.load(Ordering::Relaxed)
+    }
+}
+
+/// A measurement tool for the interrupt latency, the time taken by the system
+/// to start executing an interrupt handler after an interrupt is requested.
+///
+/// An interrupt latency probe is attached to interrupt handlers by
+/// [`CfgInterruptHandlerBuilder::latency_probe`]. [`arm`](Self::arm) records
+/// the current time, and the kernel records the time again just before calling
+/// the first attached interrupt handler after that.
+/// [`read_and_reset`](Self::read_and_reset) returns the difference between
+/// them.
+///
+/// When an interrupt line has multiple handlers, the recorded time includes
+/// the execution time of the handlers preceding the one the probe is attached
+/// to.
+///
+/// The time is measured in microseconds by [`Kernel::monotonic_time`], so the
+/// precision depends on the port's timer driver. All methods can be called in
+/// any context.
+///
+/// [`CfgInterruptHandlerBuilder::latency_probe`]: crate::kernel::cfg::CfgInterruptHandlerBuilder::latency_probe
+///
+/// # Examples
+///
+/// ```rust
+/// # #![feature(const_fn)]
+/// # #![feature(const_fn_fn_ptr_basics)]
+/// # #![feature(const_mut_refs)]
+/// use r3::kernel::{
+///     cfg::CfgBuilder, InterruptHandler, InterruptLatencyProbe, InterruptLine, InterruptNum,
+///     Kernel,
+/// };
+///
+/// const fn configure<System: Kernel>(
+///     b: &mut CfgBuilder<System>,
+///     line: InterruptNum,
+/// ) -> InterruptLatencyProbe<System> {
+///     let probe = InterruptLatencyProbe::build().finish(b);
+///     InterruptHandler::build()
+///         .line(line)
+///         .start(|_| {})
+///         .latency_probe(probe)
+///         .finish(b);
+///     probe
+/// }
+///
+/// fn measure<System: Kernel>(probe: InterruptLatencyProbe<System>, line: InterruptLine<System>) {
+///     probe.arm();
+///     line.pend().unwrap();
+///     if let Some(latency_us) = probe.read_and_reset() {
+///         dbg!(latency_us);
+///     }
+/// }
+/// ```
+#[doc(include = "../common.md")]
+pub struct InterruptLatencyProbe<System>(hunk::Hunk<System>);
+
+impl<System> Clone for InterruptLatencyProbe<System> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<System> Copy for InterruptLatencyProbe<System> {}
+
+impl<System: Kernel> fmt::Debug for InterruptLatencyProbe<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("InterruptLatencyProbe")
+            .field(self.state())
+            .finish()
+    }
+}
+
+/// The state data of [`InterruptLatencyProbe`]. All-zero bytes represent the
+/// initial state.
+#[doc(hidden)]
+pub struct InterruptLatencyProbeState {
+    /// `ARMED` or `ENTERED` if the probe is armed.
+    state: AtomicU32,
+    /// The time when the probe was armed, measured in microseconds.
+    arm_time: AtomicU32,
+    /// The time when an interrupt handler was entered, measured in
+    /// microseconds.
+    entry_time: AtomicU32,
+    /// The maximum latency ever measured, measured in microseconds.
+    worst_case: AtomicU32,
+}
+
+/// [`InterruptLatencyProbeState::state`]: The probe is not armed.
+const IDLE: u32 = 0;
+/// [`InterruptLatencyProbeState::state`]: The probe is armed and waiting for
+/// an interrupt handler to be entered.
+const ARMED: u32 = 1;
+/// [`InterruptLatencyProbeState::state`]: The probe is armed and
+/// [`InterruptLatencyProbeState::entry_time`] has been recorded.
+const ENTERED: u32 = 2;
+
+impl fmt::Debug for InterruptLatencyProbeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("InterruptLatencyProbeState")
+            .field("state", &self.state)
+            .field("arm_time", &self.arm_time)
+            .field("entry_time", &self.entry_time)
+            .field("worst_case", &self.worst_case)
+            .finish()
+    }
+}
+
+impl<System> InterruptLatencyProbe<System> {
+    pub(super) const fn from_hunk(hunk: hunk::Hunk<System>) -> Self {
+        Self(hunk)
+    }
+
+    /// Get the hunk holding the probe's state.
+    pub(super) const fn hunk(self) -> hunk::Hunk<System> {
+        self.0
+    }
+}
+
+impl<System: Kernel> InterruptLatencyProbe<System> {
+    /// Record the current time as the starting point of a measurement. Call
+    /// this just before doing something that causes an interrupt request.
+    ///
+    /// If the probe is already armed, this method overwrites the starting
+    /// point.
+    pub fn arm(self) {
+        let state = self.state();
+        state.state.store(IDLE, Ordering::Relaxed);
+        state.arm_time.store(now::<System>(), Ordering::Relaxed);
+        state.state.store(ARMED, Ordering::Release);
+    }
+
+    /// Get the time elapsed between the last call to [`arm`](Self::arm) and
+    /// the entry to an interrupt handler, measured in microseconds, and disarm
+    /// the probe.
+    ///
+    /// Returns `None` if the probe isn't armed or no interrupt handler has
+    /// been entered since the probe was armed. The returned value is also
+    /// accounted for by [`worst_case`](Self::worst_case).
+    pub fn read_and_reset(self) -> Option<u32> {
+        let state = self.state();
+        if state.state.load(Ordering::Acquire) != ENTERED {
+            return None;
+        }
+        state.state.store(IDLE, Ordering::Relaxed);
+
+        let latency = state
+            .entry_time
+            .load(Ordering::Relaxed)
+            .wrapping_sub(state.arm_time.load(Ordering::Relaxed));
+
+        if latency > state.worst_case.load(Ordering::Relaxed) {
+            state.worst_case.store(latency, Ordering::Relaxed);
+        }
+
+        Some(latency)
+    }
+
+    /// Get the maximum latency ever returned by
+    /// [`read_and_reset`](Self::read_and_reset), measured in microseconds.
+    pub fn worst_case(self) -> u32 {
+        self.state().worst_case.load(Ordering::Relaxed)
+    }
+
+    /// Record the entry to an interrupt handler if the probe is armed.
+    fn record_entry(self) {
+        let now = now::<System>();
+        let state = self.state();
+        if state.state.load(Ordering::Acquire) == ARMED {
+            state.entry_time.store(now, Ordering::Relaxed);
+            state.state.store(ENTERED, Ordering::Release);
+        }
+    }
+
+    #[inline]
+    fn state(self) -> &'static InterruptLatencyProbeState {
+        // Safety: The hunk is large enough and suitably aligned to hold
+        //         `InterruptLatencyProbeState` (ensured by the configuration
+        //         builder). The hunk pool is zero-initialized, and all-zero
+        //         bytes represent a valid `InterruptLatencyProbeState`.
+        unsafe { &*(self.0.as_ptr() as *const InterruptLatencyProbeState) }
+    }
+}
+
+/// The function called by a combined interrupt handler before calling an
+/// interrupt handler with an attached [`InterruptLatencyProbe`]. `offset`
+/// specifies the offset of [`InterruptLatencyProbe::hunk`].
+pub(super) fn record_interrupt_entry<System: Kernel>(offset: usize) {
+    InterruptLatencyProbe::<System>::from_hunk(hunk::Hunk::from_offset(offset)).record_entry();
+}
+
+#[inline]
+fn now<System: Kernel>() -> u32 {
+    // Truncating is okay because we only need differences
+    System::monotonic_time().as_micros() as u32
+}
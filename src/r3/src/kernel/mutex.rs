@@ -2,11 +2,11 @@
 // FIXME: `debug_assert_matches` needing `assert_matches` is fixed by
 //        <https://github.com/murarth/assert_matches/pull/9>
 use assert_matches::{assert_matches, debug_assert_matches};
-use core::{fmt, hash, marker::PhantomData};
+use core::{fmt, hash, marker::PhantomData, mem};
 
 use super::{
     state, task, timeout, utils,
-    wait::{WaitPayload, WaitQueue},
+    wait::{self, WaitPayload, WaitQueue},
     BadIdError, Id, Kernel, KernelCfg1, LockMutexError, LockMutexPrecheckError,
     LockMutexTimeoutError, MarkConsistentMutexError, PortThreading, QueryMutexError,
     TryLockMutexError, UnlockMutexError,
@@ -45,10 +45,19 @@ pub enum MutexProtocol {
     /// [the immediate priority ceiling protocol]. The inner value specifies the
     /// priority ceiling.
     ///
-    /// The value must be in range `0..`[`num_task_priority_levels`].
+    /// The value must be in range `0..`[`num_task_priority_levels`]. This is
+    /// checked at configuration time.
+    ///
+    /// The priority ceiling is a [task priority]. Holding the mutex prevents
+    /// the owning task from being preempted by tasks whose priorities are
+    /// lower than or equal to the priority ceiling, but doesn't prevent
+    /// interrupt handlers from running. (Interrupt handlers can't lock mutexes
+    /// anyway.) Use [CPU Lock] to protect data shared with interrupt handlers.
     ///
     /// [`num_task_priority_levels`]: crate::kernel::cfg::CfgBuilder::num_task_priority_levels
     /// [the immediate priority ceiling protocol]: https://en.wikipedia.org/wiki/Priority_ceiling_protocol
+    /// [task priority]: crate::kernel::cfg::CfgTaskBuilder::priority
+    /// [CPU Lock]: crate::kernel::Kernel::lock_cpu
     Ceiling(usize),
 }
 
@@ -659,6 +668,10 @@ fn lock_mutex<System: Kernel>(
     let running_task = precheck_and_get_running_task(lock.borrow_mut(), mutex_cb)?;
 
     if !poll_core(mutex_cb, running_task, lock.borrow_mut()) {
+        if System::DEADLOCK_DETECTION {
+            panic_if_wait_would_deadlock(&*lock, running_task, mutex_cb);
+        }
+
         // The current state does not satify the wait condition. In this case,
         // start waiting. The wake-upper is responsible for using `poll_core`
         // to complete the effect of the wait operation.
@@ -674,6 +687,106 @@ fn lock_mutex<System: Kernel>(
     }
 }
 
+/// Panic if making `task` wait for `mutex_cb` would cause a deadlock, i.e.,
+/// if following the chain of mutex owners and the mutexes they are waiting for
+/// leads back to `task`.
+///
+/// This is only used when [`KernelCfg1::DEADLOCK_DETECTION`] is enabled.
+#[inline(never)]
+fn panic_if_wait_would_deadlock<System: Kernel>(
+    lock: &utils::CpuLockToken<System>,
+    task: &'static task::TaskCb<System>,
+    mutex_cb: &'static MutexCb<System>,
+) {
+    /// The chain of the wait-for graph starting from `task` waiting for
+    /// `mutex_cb`. Implements `Display` to print the chain.
+    struct WaitForChain<'a, System: Kernel> {
+        lock: &'a utils::CpuLockToken<System>,
+        task: &'static task::TaskCb<System>,
+        mutex_cb: &'static MutexCb<System>,
+    }
+
+    impl<System: Kernel> WaitForChain<'_, System> {
+        /// Call `f` for each mutex in the chain and its owner. Returns `true`
+        /// if the chain leads back to `self.task`.
+        fn walk(
+            &self,
+            mut f: impl FnMut(&'static MutexCb<System>, &'static task::TaskCb<System>),
+        ) -> bool {
+            let mut mutex_cb = self.mutex_cb;
+
+            // Each task appears at most once in the chain unless there's a
+            // cycle not involving `self.task`, which can be created by
+            // `lock_timeout`. Stop when all tasks have been visited to bound
+            // the execution time.
+            for _ in 0..System::task_cb_pool().len() {
+                let owning_task = if let Some(owning_task) = mutex_cb.owning_task.get(self.lock) {
+                    owning_task
+                } else {
+                    return false;
+                };
+
+                f(mutex_cb, owning_task);
+
+                if core::ptr::eq(owning_task, self.task) {
+                    return true;
+                }
+
+                mutex_cb = if let Some(next) = wait::current_wait_mutex(self.lock, owning_task) {
+                    next
+                } else {
+                    return false;
+                };
+            }
+
+            false
+        }
+    }
+
+    impl<System: Kernel> fmt::Display for WaitForChain<'_, System> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "task {}", task_id(self.task))?;
+
+            let mut result = Ok(());
+            self.walk(|mutex_cb, owning_task| {
+                if result.is_ok() {
+                    result = write!(
+                        f,
+                        " -> mutex {} -> task {}",
+                        mutex_id(mutex_cb),
+                        task_id(owning_task)
+                    );
+                }
+            });
+            result
+        }
+    }
+
+    /// Calculate the task ID from a task control block pointer.
+    fn task_id<System: Kernel>(task_cb: &task::TaskCb<System>) -> usize {
+        let offset_bytes =
+            task_cb as *const task::TaskCb<_> as usize - System::task_cb_pool().as_ptr() as usize;
+        offset_bytes / mem::size_of::<task::TaskCb<System>>() + 1
+    }
+
+    /// Calculate the mutex ID from a mutex control block pointer.
+    fn mutex_id<System: Kernel>(mutex_cb: &MutexCb<System>) -> usize {
+        let offset_bytes =
+            mutex_cb as *const MutexCb<_> as usize - System::mutex_cb_pool().as_ptr() as usize;
+        offset_bytes / mem::size_of::<MutexCb<System>>() + 1
+    }
+
+    let chain = WaitForChain {
+        lock,
+        task,
+        mutex_cb,
+    };
+
+    if chain.walk(|_, _| {}) {
+        panic!("deadlock detected (task -> mutex -> owner): {}", chain);
+    }
+}
+
 fn try_lock_mutex<System: Kernel>(
     mutex_cb: &'static MutexCb<System>,
     mut lock: utils::CpuLockGuard<System>,
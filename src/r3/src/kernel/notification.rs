@@ -0,0 +1,264 @@
+//! Notifications
+use core::{fmt, hash, marker::PhantomData};
+
+use super::{
+    state, task, timeout, utils,
+    wait::{WaitPayload, WaitQueue},
+    BadIdError, Id, Kernel, NotifyNotificationError, PollNotificationError, Port,
+    WaitNotificationError, WaitNotificationTimeoutError,
+};
+use crate::{time::Duration, utils::Init};
+
+/// Represents a single notification in a system.
+///
+/// A notification is a dataless, one-shot signal. [`notify`] makes the
+/// notification *pending*, and a wait operation (e.g., [`wait`]) returns when
+/// the notification is pending, atomically consuming it. This is similar to
+/// a [semaphore] with the maximum value of one or an [event group] with one
+/// bit, but is cheaper than both and has a simpler interface.
+///
+/// Notifications *coalesce*. If the notification is already pending when
+/// `notify` is called, nothing is queued, and the notification remains
+/// pending. In this case, the notification remembers that some notifications
+/// were lost, and the next wait operation to consume the notification
+/// reports this by returning `Ok(true)`.
+///
+/// This type is ABI-compatible with [`Id`].
+///
+/// [`notify`]: Notification::notify
+/// [`wait`]: Notification::wait
+/// [semaphore]: crate::kernel::Semaphore
+/// [event group]: crate::kernel::EventGroup
+///
+/// <div class="admonition-follows"></div>
+///
+/// > **Relation to Other Specifications:** Similar to task notifications in
+/// > FreeRTOS and binary semaphores in many operating systems. The overflow
+/// > reporting is unique to this kernel.
+#[doc(include = "../common.md")]
+#[repr(transparent)]
+pub struct Notification<System>(Id, PhantomData<System>);
+
+impl<System> Clone for Notification<System> {
+    fn clone(&self) -> Self {
+        Self(self.0, self.1)
+    }
+}
+
+impl<System> Copy for Notification<System> {}
+
+impl<System> PartialEq for Notification<System> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<System> Eq for Notification<System> {}
+
+impl<System> hash::Hash for Notification<System> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        hash::Hash::hash(&self.0, state);
+    }
+}
+
+impl<System> fmt::Debug for Notification<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Notification").field(&self.0).finish()
+    }
+}
+
+impl<System> Notification<System> {
+    /// Construct a `Notification` from `Id`.
+    ///
+    /// # Safety
+    ///
+    /// The kernel can handle invalid IDs without a problem. However, the
+    /// constructed `Notification` may point to an object that is not intended
+    /// to be manipulated except by its creator. This is usually prevented by
+    /// making `Notification` an opaque handle, but this safeguard can be
+    /// circumvented by this method.
+    pub const unsafe fn from_id(id: Id) -> Self {
+        Self(id, PhantomData)
+    }
+
+    /// Get the raw `Id` value representing this notification.
+    pub const fn id(self) -> Id {
+        self.0
+    }
+}
+
+impl<System: Kernel> Notification<System> {
+    fn notification_cb(self) -> Result<&'static NotificationCb<System>, BadIdError> {
+        System::get_notification_cb(self.0.get() - 1).ok_or(BadIdError::BadId)
+    }
+
+    /// Make the notification pending. If there's a task waiting on the
+    /// notification, the notification is consumed by the task, and the task
+    /// is woken up.
+    ///
+    /// If the notification is already pending, this method marks the
+    /// notification as overflowed and leaves it pending.
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn notify(self) -> Result<(), NotifyNotificationError> {
+        let lock = utils::lock_cpu::<System>()?;
+        let notification_cb = self.notification_cb()?;
+        notify(notification_cb, lock);
+        Ok(())
+    }
+
+    /// Wait until the notification is pending and consume it.
+    ///
+    /// Returns `true` if the consumed notification has overflowed, i.e., two
+    /// or more calls to [`notify`](Self::notify) were coalesced into it.
+    ///
+    /// This system service may block. Therefore, calling this method is not
+    /// allowed in [a non-waitable context] and will return `Err(BadContext)`.
+    ///
+    /// [a non-waitable context]: crate#contexts
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn wait(self) -> Result<bool, WaitNotificationError> {
+        let mut lock = utils::lock_cpu::<System>()?;
+        state::expect_waitable_context::<System>()?;
+        let notification_cb = self.notification_cb()?;
+
+        if let Some(overflowed) = poll_core(notification_cb.st.write(&mut *lock)) {
+            Ok(overflowed)
+        } else {
+            // The current state does not satify the wait condition. In this
+            // case, start waiting. The wake-upper is responsible for completing
+            // the effect of the wait operation. Since the notification wasn't
+            // pending, it couldn't have overflowed.
+            notification_cb
+                .wait_queue
+                .wait(lock.borrow_mut(), WaitPayload::Notification)?;
+
+            Ok(false)
+        }
+    }
+
+    /// [`wait`](Self::wait) with timeout.
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn wait_timeout(self, timeout: Duration) -> Result<bool, WaitNotificationTimeoutError> {
+        let time32 = timeout::time32_from_duration(timeout)?;
+        let mut lock = utils::lock_cpu::<System>()?;
+        state::expect_waitable_context::<System>()?;
+        let notification_cb = self.notification_cb()?;
+
+        if let Some(overflowed) = poll_core(notification_cb.st.write(&mut *lock)) {
+            Ok(overflowed)
+        } else {
+            // See `wait`
+            notification_cb.wait_queue.wait_timeout(
+                lock.borrow_mut(),
+                WaitPayload::Notification,
+                time32,
+            )?;
+
+            Ok(false)
+        }
+    }
+
+    /// Non-blocking version of [`wait`](Self::wait). Returns immediately with
+    /// [`PollNotificationError::Timeout`] if the notification is not pending.
+    ///
+    /// Unlike `wait`, this method can be called in any context where CPU Lock
+    /// is inactive, including an interrupt context.
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn poll(self) -> Result<bool, PollNotificationError> {
+        let mut lock = utils::lock_cpu::<System>()?;
+        let notification_cb = self.notification_cb()?;
+
+        poll_core(notification_cb.st.write(&mut *lock)).ok_or(PollNotificationError::Timeout)
+    }
+}
+
+/// The state of a notification.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationSt {
+    /// The notification is not pending.
+    Clear,
+    /// The notification is pending.
+    Pending,
+    /// The notification is pending, and one or more notifications were
+    /// coalesced into it.
+    Overflowed,
+}
+
+impl Init for NotificationSt {
+    const INIT: Self = Self::Clear;
+}
+
+/// *Notification control block* - the state data of a notification.
+#[doc(hidden)]
+pub struct NotificationCb<System: Port> {
+    pub(super) st: utils::CpuLockCell<System, NotificationSt>,
+
+    pub(super) wait_queue: WaitQueue<System>,
+}
+
+impl<System: Port> Init for NotificationCb<System> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        st: Init::INIT,
+        wait_queue: Init::INIT,
+    };
+}
+
+impl<System: Kernel> fmt::Debug for NotificationCb<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NotificationCb")
+            .field("self", &(self as *const _))
+            .field("st", &self.st)
+            .field("wait_queue", &self.wait_queue)
+            .finish()
+    }
+}
+
+/// Check if the current state of a notification, `st`, satisfies the wait
+/// condition.
+///
+/// If `st` satisfies the wait condition, this function clears `st` and returns
+/// `Some(overflowed)`. Otherwise, it returns `None`.
+#[inline]
+fn poll_core(st: &mut NotificationSt) -> Option<bool> {
+    match *st {
+        NotificationSt::Clear => None,
+        NotificationSt::Pending => {
+            *st = NotificationSt::Clear;
+            Some(false)
+        }
+        NotificationSt::Overflowed => {
+            *st = NotificationSt::Clear;
+            Some(true)
+        }
+    }
+}
+
+fn notify<System: Kernel>(
+    notification_cb: &'static NotificationCb<System>,
+    mut lock: utils::CpuLockGuard<System>,
+) {
+    let st = notification_cb.st.write(&mut *lock);
+    match *st {
+        NotificationSt::Clear => {}
+        NotificationSt::Pending | NotificationSt::Overflowed => {
+            // Coalesce the notification. There can't be a waiting task
+            // because the notification is pending.
+            *st = NotificationSt::Overflowed;
+            return;
+        }
+    }
+
+    if notification_cb.wait_queue.wake_up_one(lock.borrow_mut()) {
+        // We just woke up a task, which consumed the notification
+        task::unlock_cpu_and_check_preemption(lock);
+    } else {
+        notification_cb
+            .st
+            .replace(&mut *lock, NotificationSt::Pending);
+    }
+}
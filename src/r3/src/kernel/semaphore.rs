@@ -5,7 +5,8 @@ use super::{
     state, task, timeout, utils,
     wait::{WaitPayload, WaitQueue},
     BadIdError, DrainSemaphoreError, GetSemaphoreError, Id, Kernel, PollSemaphoreError, Port,
-    SignalSemaphoreError, WaitSemaphoreError, WaitSemaphoreTimeoutError,
+    PortThreading, SignalSemaphoreError, WaitError, WaitSemaphoreError, WaitSemaphoreNError,
+    WaitSemaphoreTimeoutError,
 };
 use crate::{time::Duration, utils::Init};
 
@@ -108,6 +109,7 @@ impl<System: Kernel> Semaphore<System> {
     }
 
     /// Get the number of permits currently held by the semaphore.
+    #[doc(alias = "value")]
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn get(self) -> Result<SemaphoreValue, GetSemaphoreError> {
         let lock = utils::lock_cpu::<System>()?;
@@ -116,6 +118,12 @@ impl<System: Kernel> Semaphore<System> {
     }
 
     /// Release `count` permits, returning them to the semaphore.
+    ///
+    /// The released permits are handed over to the tasks waiting on the
+    /// semaphore in the order of the wait queue. This continues until there
+    /// are not enough permits left to satisfy the first waiting task, in which
+    /// case the remaining permits are held by the semaphore.
+    #[doc(alias = "signal_n")]
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn signal(self, count: SemaphoreValue) -> Result<(), SignalSemaphoreError> {
         let lock = utils::lock_cpu::<System>()?;
@@ -135,27 +143,65 @@ impl<System: Kernel> Semaphore<System> {
     /// allowed in [a non-waitable context] and will return `Err(BadContext)`.
     ///
     /// [a non-waitable context]: crate#contexts
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn wait_one(self) -> Result<(), WaitSemaphoreError> {
+        let lock = utils::lock_cpu::<System>()?;
+        state::expect_waitable_context::<System>()?;
+        let semaphore_cb = self.semaphore_cb()?;
+
+        wait(semaphore_cb, lock, 1)?;
+
+        Ok(())
+    }
+
+    /// Acquire `count` permits at once, potentially blocking the calling
+    /// thread until they are available.
+    ///
+    /// The permits are acquired atomically, i.e., the calling task doesn't
+    /// hold any of them while waiting. This is unlike calling
+    /// [`wait_one`](Self::wait_one) `count` times, which could leave the
+    /// calling task holding some but not all of the permits (and possibly
+    /// cause a deadlock). Returns [`WaitSemaphoreNError::BadParam`] if `count`
+    /// exceeds the semaphore's maximum value.
+    ///
+    /// A waiting task blocks all tasks behind it in the wait queue until it
+    /// acquires the permits it's waiting for, so that a task waiting for many
+    /// permits will not be starved by tasks waiting for fewer permits.
+    /// However, a task that calls this method when enough permits are
+    /// available acquires them immediately regardless of the wait queue's
+    /// contents.
+    ///
+    /// This system service may block. Therefore, calling this method is not
+    /// allowed in [a non-waitable context] and will return `Err(BadContext)`.
+    ///
+    /// [a non-waitable context]: crate#contexts
     ///
     /// <div class="admonition-follows"></div>
     ///
-    /// > **Rationale:** Multi-wait (waiting for more than one permit) is not
-    /// > supported because it introduces additional complexity to the wait
-    /// > queue mechanism by requiring it to reevaluate wait conditions after
-    /// > reordering the queue.
-    /// >
-    /// > The support for multi-wait is relatively rare among operating systems.
-    /// > It's not supported by POSIX, RTEMS, TOPPERS, VxWorks, nor Win32. The
-    /// > rare exception is μT-Kernel.
+    /// > **Relation to Other Specifications:** The support for multi-wait is
+    /// > relatively rare among operating systems. It's not supported by POSIX,
+    /// > RTEMS, TOPPERS, VxWorks, nor Win32. The rare exception is μT-Kernel.
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
-    pub fn wait_one(self) -> Result<(), WaitSemaphoreError> {
+    pub fn wait_n(self, count: SemaphoreValue) -> Result<(), WaitSemaphoreNError> {
         let lock = utils::lock_cpu::<System>()?;
         state::expect_waitable_context::<System>()?;
         let semaphore_cb = self.semaphore_cb()?;
 
-        wait_one(semaphore_cb, lock)
+        if count > semaphore_cb.max_value {
+            return Err(WaitSemaphoreNError::BadParam);
+        }
+
+        wait(semaphore_cb, lock, count)?;
+
+        Ok(())
     }
 
     /// [`wait_one`](Self::wait_one) with timeout.
+    ///
+    /// Returns [`WaitSemaphoreTimeoutError::Timeout`] if no permit could be
+    /// acquired before the timeout expires. On timeout, the calling task is
+    /// removed from the semaphore's wait queue.
+    #[doc(alias = "wait_timeout")]
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn wait_one_timeout(self, timeout: Duration) -> Result<(), WaitSemaphoreTimeoutError> {
         let time32 = timeout::time32_from_duration(timeout)?;
@@ -169,6 +215,10 @@ impl<System: Kernel> Semaphore<System> {
     /// Non-blocking version of [`wait_one`](Self::wait_one). Returns
     /// immediately with [`PollSemaphoreError::Timeout`] if the unblocking
     /// condition is not satisfied.
+    ///
+    /// Unlike `wait_one`, this method can be called in any context where CPU
+    /// Lock is inactive, including an interrupt context.
+    #[doc(alias = "try_wait")]
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn poll_one(self) -> Result<(), PollSemaphoreError> {
         let lock = utils::lock_cpu::<System>()?;
@@ -180,7 +230,7 @@ impl<System: Kernel> Semaphore<System> {
 
 /// *Semaphore control block* - the state data of an event group.
 #[doc(hidden)]
-pub struct SemaphoreCb<System: Port> {
+pub struct SemaphoreCb<System: PortThreading> {
     pub(super) value: utils::CpuLockCell<System, SemaphoreValue>,
     pub(super) max_value: SemaphoreValue,
 
@@ -211,26 +261,31 @@ fn poll_one<System: Kernel>(
     semaphore_cb: &'static SemaphoreCb<System>,
     mut lock: utils::CpuLockGuard<System>,
 ) -> Result<(), PollSemaphoreError> {
-    if poll_core(semaphore_cb.value.write(&mut *lock)) {
+    if poll_core(semaphore_cb.value.write(&mut *lock), 1) {
         Ok(())
     } else {
         Err(PollSemaphoreError::Timeout)
     }
 }
 
-fn wait_one<System: Kernel>(
+fn wait<System: Kernel>(
     semaphore_cb: &'static SemaphoreCb<System>,
     mut lock: utils::CpuLockGuard<System>,
-) -> Result<(), WaitSemaphoreError> {
-    if poll_core(semaphore_cb.value.write(&mut *lock)) {
+    count: SemaphoreValue,
+) -> Result<(), WaitError> {
+    if poll_core(semaphore_cb.value.write(&mut *lock), count) {
         Ok(())
     } else {
         // The current state does not satify the wait condition. In this case,
         // start waiting. The wake-upper is responsible for using `poll_core`
         // to complete the effect of the wait operation.
-        semaphore_cb
-            .wait_queue
-            .wait(lock.borrow_mut(), WaitPayload::Semaphore)?;
+        semaphore_cb.wait_queue.wait(
+            lock.borrow_mut(),
+            WaitPayload::Semaphore {
+                semaphore_cb,
+                count,
+            },
+        )?;
 
         Ok(())
     }
@@ -241,29 +296,34 @@ fn wait_one_timeout<System: Kernel>(
     mut lock: utils::CpuLockGuard<System>,
     time32: timeout::Time32,
 ) -> Result<(), WaitSemaphoreTimeoutError> {
-    if poll_core(semaphore_cb.value.write(&mut *lock)) {
+    if poll_core(semaphore_cb.value.write(&mut *lock), 1) {
         Ok(())
     } else {
         // The current state does not satify the wait condition. In this case,
         // start waiting. The wake-upper is responsible for using `poll_core`
         // to complete the effect of the wait operation.
-        semaphore_cb
-            .wait_queue
-            .wait_timeout(lock.borrow_mut(), WaitPayload::Semaphore, time32)?;
+        semaphore_cb.wait_queue.wait_timeout(
+            lock.borrow_mut(),
+            WaitPayload::Semaphore {
+                semaphore_cb,
+                count: 1,
+            },
+            time32,
+        )?;
 
         Ok(())
     }
 }
 
 /// Check if the current state of a semaphore, `value`, satisfies the wait
-/// condition.
+/// condition of acquiring `count` permits.
 ///
 /// If `value` satisfies the wait condition, this function updates `value` and
 /// returns `true`. Otherwise, it returns `false`.
 #[inline]
-fn poll_core(value: &mut SemaphoreValue) -> bool {
-    if *value > 0 {
-        *value -= 1;
+fn poll_core(value: &mut SemaphoreValue, count: SemaphoreValue) -> bool {
+    if *value >= count {
+        *value -= count;
         true
     } else {
         false
@@ -273,7 +333,7 @@ fn poll_core(value: &mut SemaphoreValue) -> bool {
 fn signal<System: Kernel>(
     semaphore_cb: &'static SemaphoreCb<System>,
     mut lock: utils::CpuLockGuard<System>,
-    mut count: SemaphoreValue,
+    count: SemaphoreValue,
 ) -> Result<(), SignalSemaphoreError> {
     let value = semaphore_cb.value.get(&*lock);
 
@@ -281,27 +341,46 @@ fn signal<System: Kernel>(
         return Err(SignalSemaphoreError::QueueOverflow);
     }
 
-    let orig_count = count;
-
-    // This is equivalent to using `wake_up_all_conditional` and calling
-    // `poll_core` for each waiting task, but is (presumably) more efficient
-    while count > 0 {
-        if semaphore_cb.wait_queue.wake_up_one(lock.borrow_mut()) {
-            // We just woke up a task. Give one permit to that task.
-            count -= 1;
-        } else {
-            // There's no more task to wake up; deposit the remaining permits to
-            // the semaphore
-            semaphore_cb.value.replace(&mut *lock, value + count);
-            break;
-        }
-    }
+    semaphore_cb.value.replace(&mut *lock, value + count);
 
     // If we woke up at least one task in the process, call
     // `unlock_cpu_and_check_preemption`
-    if count != orig_count {
+    if wake_up_waiters(lock.borrow_mut(), semaphore_cb) {
         task::unlock_cpu_and_check_preemption(lock);
     }
 
     Ok(())
 }
+
+/// Wake up the tasks waiting on a semaphore in the order of the wait queue,
+/// giving each of them the permits it's waiting for, until the semaphore
+/// doesn't hold enough permits to satisfy the first waiting task. Returns
+/// `true` if it has woken up at least one task.
+///
+/// This method may make a task Ready, but doesn't yield the processor.
+/// Call `unlock_cpu_and_check_preemption` as needed.
+pub(super) fn wake_up_waiters<System: Kernel>(
+    mut lock: utils::CpuLockTokenRefMut<'_, System>,
+    semaphore_cb: &'static SemaphoreCb<System>,
+) -> bool {
+    let mut woke_up = false;
+
+    loop {
+        let mut value = semaphore_cb.value.get(&*lock);
+
+        let woke_up_one =
+            semaphore_cb
+                .wait_queue
+                .wake_up_one_conditional(lock.borrow_mut(), |payload| match payload {
+                    WaitPayload::Semaphore { count, .. } => poll_core(&mut value, *count),
+                    _ => unreachable!(),
+                });
+
+        if !woke_up_one {
+            return woke_up;
+        }
+
+        semaphore_cb.value.replace(&mut *lock, value);
+        woke_up = true;
+    }
+}
@@ -0,0 +1,54 @@
+use core::marker::PhantomData;
+
+use crate::utils::Init;
+
+/// Represents a registered shutdown hook in a system.
+///
+/// There are no operations defined for shutdown hooks, so this type
+/// is only used for static configuration.
+///
+/// Shutdown hooks execute when the system shuts down, either by
+/// [`Kernel::shutdown`] or by a port-specific mechanism (e.g.,
+/// `r3_port_std::shutdown`). They are useful for flushing output buffers or
+/// stopping DMA transfers. Their execution order is the reverse of the order
+/// determined by their [priority values], ties being broken by the reverse
+/// registration order. This mirrors [startup hooks], so a shutdown hook can
+/// undo what a startup hook with the same priority value did.
+///
+/// Shutdown hooks must not call system services.
+///
+/// [`Kernel::shutdown`]: crate::kernel::Kernel::shutdown
+/// [priority values]: crate::kernel::cfg::CfgShutdownHookBuilder::priority
+/// [startup hooks]: crate::kernel::StartupHook
+///
+/// <div class="admonition-follows"></div>
+///
+/// > **Relation to Other Specifications:** `ShutdownHook` (AUTOSAR OS,
+/// > OSEK/VDX), termination routine (μITRON4.0).
+///
+#[doc(include = "../common.md")]
+pub struct ShutdownHook<System>(PhantomData<System>);
+
+impl<System> ShutdownHook<System> {
+    pub(super) const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// A shutdown hook.
+///
+/// This type isn't technically public but needs to be `pub` so that it can be
+/// referred to by a macro.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct ShutdownHookAttr {
+    pub(super) start: fn(usize),
+    pub(super) param: usize,
+}
+
+impl Init for ShutdownHookAttr {
+    const INIT: Self = Self {
+        start: |_| {},
+        param: 0,
+    };
+}
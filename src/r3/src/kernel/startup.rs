@@ -9,8 +9,12 @@ use crate::utils::Init;
 ///
 /// Startup hooks execute during the boot process with [CPU Lock] active, after
 /// initializing kernel structures and before scheduling the first task.
+/// Their execution order is determined by their [priority values], ties being
+/// broken by the registration order. Therefore, the order is deterministic and
+/// doesn't depend on implementation details of the configurator.
 ///
 /// [CPU Lock]: crate#system-states
+/// [priority values]: crate::kernel::cfg::CfgStartupHookBuilder::priority
 ///
 /// <div class="admonition-follows"></div>
 ///
@@ -35,11 +39,27 @@ impl<System> StartupHook<System> {
 pub struct StartupHookAttr {
     pub(super) start: unsafe fn(usize),
     pub(super) param: usize,
+    /// The function used to call `start`.
+    pub(super) trampoline: StartupHookTrampoline,
 }
 
 impl Init for StartupHookAttr {
     const INIT: Self = Self {
         start: |_| {},
         param: 0,
+        trampoline: call_start,
     };
 }
+
+/// A function that calls a startup hook's entry point (the first parameter)
+/// with the parameter (the second parameter). This makes it possible for
+/// the kernel to call an entry point of a different signature, which the
+/// trampoline function knows how to call.
+#[doc(hidden)]
+pub type StartupHookTrampoline = unsafe fn(unsafe fn(usize), usize);
+
+/// The default [`StartupHookTrampoline`], which just calls `start(param)`.
+pub(super) unsafe fn call_start(start: unsafe fn(usize), param: usize) {
+    // Safety: Upheld by the caller
+    unsafe { start(param) };
+}
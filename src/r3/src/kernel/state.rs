@@ -1,10 +1,51 @@
-#[cfg(feature = "priority_boost")]
 use core::sync::atomic::Ordering;
 
 #[cfg(feature = "priority_boost")]
 use super::{task, utils};
 use super::{BadContextError, BoostPriorityError, Kernel};
 
+/// Identifies the kind of [context] a thread is running in. Returned by
+/// [`Kernel::current_context`].
+///
+/// There's no variant for the idle state (when no task is ready to run)
+/// because no application code can observe it. The processor runs only
+/// port-provided code ([`PortThreading::idle`]) while idle, and anything that
+/// interrupts it, such as an interrupt handler, runs in an interrupt context.
+///
+/// [context]: crate#contexts
+/// [`PortThreading::idle`]: crate::kernel::PortThreading::idle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Context {
+    /// [A boot context]. [Startup hooks] run in this context.
+    ///
+    /// [A boot context]: crate#contexts
+    /// [Startup hooks]: crate::kernel::StartupHook
+    Boot,
+    /// [A task context].
+    ///
+    /// [A task context]: crate#contexts
+    Task,
+    /// [An interrupt context]. This includes [interrupt handlers], [timer]
+    /// callbacks, and other kernel-provided callbacks that aren't run by a
+    /// task.
+    ///
+    /// [An interrupt context]: crate#contexts
+    /// [interrupt handlers]: crate::kernel::InterruptHandler
+    /// [timer]: crate::kernel::Timer
+    Interrupt,
+}
+
+/// Implements `Kernel::current_context`.
+pub(super) fn current_context<System: Kernel>() -> Context {
+    if !System::state().boot_complete.load(Ordering::Relaxed) {
+        Context::Boot
+    } else if System::is_task_context() {
+        Context::Task
+    } else {
+        Context::Interrupt
+    }
+}
+
 /// If the current context is not a task context, return `Err(BadContext)`.
 pub(super) fn expect_task_context<System: Kernel>() -> Result<(), BadContextError> {
     if !System::is_task_context() {
@@ -23,13 +64,18 @@ pub(super) fn expect_waitable_context<System: Kernel>() -> Result<(), BadContext
     }
 }
 
+/// Implements `Kernel::can_boost_priority`.
+#[cfg(feature = "priority_boost")]
+pub(super) fn can_boost_priority<System: Kernel>() -> bool {
+    !System::is_cpu_lock_active()
+        && System::is_task_context()
+        && !System::is_priority_boost_active()
+}
+
 /// Implements `Kernel::boost_priority`.
 #[cfg(feature = "priority_boost")]
 pub(super) fn boost_priority<System: Kernel>() -> Result<(), BoostPriorityError> {
-    if System::is_cpu_lock_active()
-        || !System::is_task_context()
-        || System::is_priority_boost_active()
-    {
+    if !can_boost_priority::<System>() {
         Err(BoostPriorityError::BadContext)
     } else {
         System::state()
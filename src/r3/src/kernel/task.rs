@@ -4,12 +4,21 @@ use core::sync::atomic::Ordering;
 use core::{convert::TryFrom, fmt, hash, marker::PhantomData, mem};
 use num_traits::ToPrimitive;
 
+#[cfg(feature = "stack_usage")]
+use super::GetTaskStackUsageError;
+#[cfg(feature = "system_time")]
+use super::SleepUntilError;
 use super::{
     hunk::Hunk, mutex, state, timeout, utils, wait, ActivateTaskError, BadIdError, ExitTaskError,
     GetCurrentTaskError, GetTaskPriorityError, Id, InterruptTaskError, Kernel, KernelCfg1,
-    ParkError, ParkTimeoutError, PortThreading, SetTaskPriorityError, SleepError, UnparkError,
-    UnparkExactError, WaitTimeoutError,
+    ParkError, ParkTimeoutError, PortThreading, ResumeTaskError, SetTaskPriorityError, SleepError,
+    SuspendTaskError, TryParkError, UnparkError, UnparkExactError, WaitTimeoutError,
+    WithPriorityError,
 };
+#[cfg(feature = "task_stats")]
+use super::{GetTaskCpuTimeError, TotalCpuTimeError};
+#[cfg(feature = "system_time")]
+use crate::time::Time;
 use crate::{time::Duration, utils::Init};
 
 #[doc(hidden)]
@@ -42,28 +51,44 @@ use self::readyqueue::Queue as _;
 ///  - **Waiting** — The task has an associated execution thread, which is
 ///    currently blocked by a blocking operation
 ///
+///  - **Suspended** — The task has an associated execution thread, which is
+///    excluded from scheduling until it's [resumed]. A Ready or Running task
+///    transitions into this state when it's [suspended]. A Waiting task can't
+///    be suspended. Unlike Waiting, a task never leaves this state on its
+///    own.
+///
 /// <center>
 /// ```svgbob
-///                     ,-------,
-///    ,--------------->| Ready |<--------------,
-///    |                '-------'               |
-///    |          dispatch | ^                  |
-///    |                   | |                  |
-///    | release           | |                  | activate
-/// ,---------,            | |           ,---------,
-/// | Waiting |            | |           | Dormant |
-/// '---------'            | |           '---------'
-///    ^                   | |                  ^
-///    |                   | |                  |
-///    |                   v | preempt          |
-///    |          wait ,---------,              |
-///    '---------------| Running |--------------'
-///                    '---------' exit
+///                  ,-----------,
+///                  | Suspended |<----------------------------,
+///                  '-----------'                             |
+///                resume |  ^ suspend                         |
+///                       |  |                                 |
+///                       v  |                                 |
+///                     ,-------,                              |
+///    ,--------------->| Ready |<--------------,              |
+///    |                '-------'               |              |
+///    |          dispatch | ^                  |              |
+///    |                   | |                  |              |
+///    | release           | |                  | activate     |
+/// ,---------,            | |           ,---------,           |
+/// | Waiting |            | |           | Dormant |           |
+/// '---------'            | |           '---------'           |
+///    ^                   | |                  ^              |
+///    |                   | |                  |              |
+///    |                   v | preempt          |              |
+///    |          wait ,---------,              |              |
+///    '---------------| Running |--------------'              |
+///                    '---------' exit                        |
+///                         |                          suspend |
+///                         '----------------------------------'
 /// ```
 /// </center>
 ///
 /// [thread]: crate#threads
 /// [activated]: Task::activate
+/// [resumed]: Task::resume
+/// [suspended]: Task::suspend
 #[doc(include = "../common.md")]
 #[repr(transparent)]
 pub struct Task<System>(Id, PhantomData<System>);
@@ -127,12 +152,22 @@ impl<System: Kernel> Task<System> {
     ///
     /// In a task context, this method returns the currently running task.
     ///
-    /// In an interrupt context, the result is unreliable because scheduling is
-    /// deferred until the control returns to a task, but the current interrupt
-    /// handler could be interrupted by another interrrupt, which might do
-    /// scheduling on return (whether this happens or not is unspecified).
+    /// In an interrupt context, this method returns the task that was running
+    /// when the interrupt was taken, i.e., the task that will resume when all
+    /// interrupt handlers return, or `None` if no task was running (the system
+    /// was idle). The result is unreliable because scheduling is deferred
+    /// until the control returns to a task, but the current interrupt handler
+    /// could be interrupted by another interrrupt, which might do scheduling
+    /// on return (whether this happens or not is unspecified).
+    ///
+    /// In a boot context, this method returns `None` because no task has been
+    /// dispatched yet.
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn current() -> Result<Option<Self>, GetCurrentTaskError> {
+        if state::current_context::<System>() == state::Context::Boot {
+            return Ok(None);
+        }
+
         let mut lock = utils::lock_cpu::<System>()?;
         let task_cb = if let Some(cb) = System::state().running_task(lock.borrow_mut()) {
             cb
@@ -187,6 +222,40 @@ impl<System: Kernel> Task<System> {
         Ok(())
     }
 
+    /// Suspend the task, transitioning it from the Ready or Running state
+    /// into the Suspended state. The task will not be scheduled until it's
+    /// resumed by [`Task::resume`].
+    ///
+    /// If the task is the currently running task, the processor is
+    /// relinquished to another task (if any) before this method returns.
+    ///
+    /// This method will return [`SuspendTaskError::BadObjectState`] if the
+    /// task is not in the Ready or Running state. This includes the case
+    /// where the task is already suspended.
+    ///
+    /// This method will return [`SuspendTaskError::BadContext`] if the task
+    /// is the currently running task and [Priority Boost] is active.
+    ///
+    /// [Priority Boost]: crate::kernel::Kernel::boost_priority
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn suspend(self) -> Result<(), SuspendTaskError> {
+        let lock = utils::lock_cpu::<System>()?;
+        let task_cb = self.task_cb()?;
+        suspend(lock, task_cb)
+    }
+
+    /// Resume the task, transitioning it from the Suspended state into the
+    /// Ready state.
+    ///
+    /// This method will return [`ResumeTaskError::BadObjectState`] if the
+    /// task is not in the Suspended state.
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn resume(self) -> Result<(), ResumeTaskError> {
+        let lock = utils::lock_cpu::<System>()?;
+        let task_cb = self.task_cb()?;
+        resume(lock, task_cb)
+    }
+
     /// Make the task's token available, unblocking [`Kernel::park`] now or in
     /// the future.
     ///
@@ -285,6 +354,52 @@ impl<System: Kernel> Task<System> {
             Ok(task_cb.effective_priority.read(&*lock).to_usize().unwrap())
         }
     }
+
+    /// Estimate the task's peak stack usage, measured in bytes.
+    ///
+    /// The kernel fills the task's stack region with [`STACK_SENTINEL`] every
+    /// time the task is activated. This method looks for the longest prefix of
+    /// the stack region (in the ascending order of addresses) that still has
+    /// this value and returns the size of the remaining part. This assumes a
+    /// descending stack, which is the case for all supported architectures.
+    ///
+    /// The result is an estimate. It might underestimate the usage if the task
+    /// wrote [`STACK_SENTINEL`] to the stack. Also, it's meaningless if the
+    /// port doesn't run tasks on [`StackHunk`] (e.g., `r3_port_std`, which
+    /// runs tasks on host threads) or if the task has never been activated.
+    ///
+    /// This method scans the whole stack region with CPU Lock active, so it
+    /// might increase the interrupt latency.
+    ///
+    /// [`STACK_SENTINEL`]: crate::kernel::PortThreading::STACK_SENTINEL
+    #[cfg(feature = "stack_usage")]
+    #[doc(cfg(feature = "stack_usage"))]
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn stack_usage(self) -> Result<usize, GetTaskStackUsageError> {
+        let _lock = utils::lock_cpu::<System>()?;
+        let task_cb = self.task_cb()?;
+        Ok(stack_usage(task_cb))
+    }
+
+    /// Get the task's accumulated execution time since boot.
+    ///
+    /// The kernel measures the execution time of each task by recording the
+    /// [monotonic time] on every context switch. The time spent by interrupt
+    /// handlers is attributed to the task that was running when they were
+    /// taken. The value isn't reset when the task is activated again.
+    ///
+    /// [monotonic time]: crate::kernel::Kernel::monotonic_time
+    #[cfg(feature = "task_stats")]
+    #[doc(cfg(feature = "task_stats"))]
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn cpu_time(self) -> Result<core::time::Duration, GetTaskCpuTimeError> {
+        let mut lock = utils::lock_cpu::<System>()?;
+        let task_cb = self.task_cb()?;
+        Ok(core::time::Duration::from_micros(cpu_time(
+            lock.borrow_mut(),
+            task_cb,
+        )))
+    }
 }
 
 /// [`Hunk`] for a task stack.
@@ -328,7 +443,7 @@ impl<System> StackHunk<System> {
     }
 
     /// Get the inner `Hunk` and the length, consuming `self`.
-    pub fn into_inner(self) -> (Hunk<System>, usize) {
+    pub const fn into_inner(self) -> (Hunk<System>, usize) {
         (self.0, self.1)
     }
 }
@@ -406,6 +521,14 @@ pub struct TaskCb<
 
     /// The last mutex locked by the task.
     pub(super) last_mutex_held: utils::CpuLockCell<System, Option<&'static mutex::MutexCb<System>>>,
+
+    #[cfg(feature = "task_stats")]
+    /// The accumulated execution time of the task, measured in microseconds.
+    /// If the task is currently running, this doesn't include the time
+    /// elapsed since [`State::task_switch_time`].
+    ///
+    /// [`State::task_switch_time`]: crate::kernel::State::task_switch_time
+    pub(super) cpu_time: utils::CpuLockCell<System, u64>,
 }
 
 impl<
@@ -425,6 +548,10 @@ impl<
             .field("st", &self.st)
             .field("ready_queue_data", &self.ready_queue_data)
             .field("wait", &self.wait)
+            .field(
+                "wait_state",
+                &self.wait.debug_fmt_state(self as *const _ as *const ()),
+            )
             .field(
                 "last_mutex_held",
                 // Don't print the content of the mutex. It'll be printed
@@ -434,6 +561,15 @@ impl<
                     .debug_fmt_with(|x, f| x.map(|x| x as *const _).fmt(f)),
             )
             .field("park_token", &self.park_token)
+            .field(
+                "cpu_time",
+                match () {
+                    #[cfg(feature = "task_stats")]
+                    () => &self.cpu_time,
+                    #[cfg(not(feature = "task_stats"))]
+                    () => &(),
+                },
+            )
             .finish()
     }
 }
@@ -487,6 +623,9 @@ pub enum TaskSt {
     /// The task is in the Waiting state.
     Waiting,
 
+    /// The task is in the Suspended state.
+    Suspended,
+
     /// The task should be activated at startup. This will transition into
     /// `Ready` or `Running` before the first task is scheduled.
     PendingActivation,
@@ -532,6 +671,9 @@ pub(super) unsafe fn exit_current_task<System: Kernel>() -> Result<!, ExitTaskEr
     running_task.st.replace(&mut *lock, TaskSt::Dormant);
 
     // Erase `running_task`
+    if cfg!(feature = "task_stats") || System::ROUND_ROBIN_QUANTUM_US.is_some() {
+        update_task_switch_time(lock.borrow_mut(), Some(running_task));
+    }
     System::state().running_task.replace(&mut *lock, None);
 
     core::mem::forget(lock);
@@ -553,6 +695,13 @@ pub(super) fn init_task<System: Kernel>(
         // `PendingActivation` is equivalent to `Dormant` but serves as a marker
         // indicating tasks that should be activated by `init_task`.
 
+        // Prepare for `Task::stack_usage`
+        // Safety: CPU Lock active, the task is (essentially) in the Dormant state
+        #[cfg(feature = "stack_usage")]
+        unsafe {
+            fill_stack(task_cb)
+        };
+
         // Safety: CPU Lock active, the task is (essentially) in the Dormant state
         unsafe { System::initialize_task_state(task_cb) };
 
@@ -563,7 +712,7 @@ pub(super) fn init_task<System: Kernel>(
 }
 
 /// Implements `Task::activate`.
-fn activate<System: Kernel>(
+pub(super) fn activate<System: Kernel>(
     mut lock: utils::CpuLockGuard<System>,
     task_cb: &'static TaskCb<System>,
 ) -> Result<(), ActivateTaskError> {
@@ -574,6 +723,13 @@ fn activate<System: Kernel>(
     // Discard a park token if the task has one
     task_cb.park_token.replace(&mut *lock, false);
 
+    // Prepare for `Task::stack_usage`
+    // Safety: CPU Lock active, the task is in the Dormant state
+    #[cfg(feature = "stack_usage")]
+    unsafe {
+        fill_stack(task_cb)
+    };
+
     // Safety: CPU Lock active, the task is in the Dormant state
     unsafe { System::initialize_task_state(task_cb) };
 
@@ -595,6 +751,91 @@ fn activate<System: Kernel>(
     Ok(())
 }
 
+/// Fill the task's stack region with [`PortThreading::STACK_SENTINEL`].
+///
+/// # Safety
+///
+/// CPU Lock active, the task is in the Dormant state. (Thus, no code is
+/// using the stack region.)
+#[cfg(feature = "stack_usage")]
+unsafe fn fill_stack<System: Kernel>(task_cb: &'static TaskCb<System>) {
+    let (hunk, len) = task_cb.attr.stack.into_inner();
+    // Safety: The caller ensures the stack region is not in use
+    unsafe { core::ptr::write_bytes(hunk.as_ptr(), System::STACK_SENTINEL, len) };
+}
+
+/// Implements [`Task::stack_usage`].
+#[cfg(feature = "stack_usage")]
+fn stack_usage<System: Kernel>(task_cb: &'static TaskCb<System>) -> usize {
+    let (hunk, len) = task_cb.attr.stack.into_inner();
+    let start = hunk.as_ptr();
+
+    // Count the untouched bytes. Use volatile reads because the task may be
+    // concurrently using the stack from the compiler's point of view.
+    let num_untouched = (0..len)
+        // Safety: `i` is in bounds
+        .take_while(|&i| unsafe { start.add(i).read_volatile() } == System::STACK_SENTINEL)
+        .count();
+
+    len - num_untouched
+}
+
+/// Implements [`Task::suspend`].
+fn suspend<System: Kernel>(
+    mut lock: utils::CpuLockGuard<System>,
+    task_cb: &'static TaskCb<System>,
+) -> Result<(), SuspendTaskError> {
+    match *task_cb.st.read(&*lock) {
+        TaskSt::Ready => {
+            // Remove the task from the ready queue.
+            //
+            // Safety: The task is in the Ready state, so it's in the ready
+            //         queue
+            unsafe {
+                System::state()
+                    .task_ready_queue
+                    .remove_task(lock.borrow_mut().into(), task_cb);
+            }
+            task_cb.st.replace(&mut *lock, TaskSt::Suspended);
+            Ok(())
+        }
+        TaskSt::Running => {
+            // The running task can't be preempted while Priority Boost is
+            // active
+            if System::is_priority_boost_active() {
+                return Err(SuspendTaskError::BadContext);
+            }
+
+            task_cb.st.replace(&mut *lock, TaskSt::Suspended);
+
+            // The running task is not in the Running state anymore, so this
+            // will call `yield_cpu`, which will choose the next task to run
+            unlock_cpu_and_check_preemption(lock);
+            Ok(())
+        }
+        _ => Err(SuspendTaskError::BadObjectState),
+    }
+}
+
+/// Implements [`Task::resume`].
+fn resume<System: Kernel>(
+    mut lock: utils::CpuLockGuard<System>,
+    task_cb: &'static TaskCb<System>,
+) -> Result<(), ResumeTaskError> {
+    if *task_cb.st.read(&*lock) != TaskSt::Suspended {
+        return Err(ResumeTaskError::BadObjectState);
+    }
+
+    // Safety: The previous state is Suspended, which doesn't require any
+    //         cleanup
+    unsafe { make_ready(lock.borrow_mut(), task_cb) };
+
+    // If `task_cb` has a higher priority, perform a context switch.
+    unlock_cpu_and_check_preemption(lock);
+
+    Ok(())
+}
+
 /// Transition the task into the Ready state. This function doesn't do any
 /// proper cleanup for a previous state. If the previous state is `Dormant`, the
 /// caller must initialize the task state first by calling
@@ -666,6 +907,67 @@ pub(super) fn unlock_cpu_and_check_preemption<System: Kernel>(
     }
 }
 
+/// The callback function of the timer that implements round-robin scheduling
+/// (see [`CfgBuilder::round_robin_quantum`]).
+///
+/// If the running task has used up its time slice and there's another Ready
+/// task having the same priority, this function moves the running task to the
+/// back of the ready queue and calls `Port::yield_cpu`.
+///
+/// [`CfgBuilder::round_robin_quantum`]: crate::kernel::cfg::CfgBuilder::round_robin_quantum
+pub(super) fn round_robin_timer_handler<System: Kernel>(_: usize) {
+    let quantum = if let Some(x) = System::ROUND_ROBIN_QUANTUM_US {
+        x
+    } else {
+        return;
+    };
+
+    // The timer callback is called with CPU Lock inactive
+    let mut lock = utils::lock_cpu::<System>().unwrap();
+
+    // The running task can't be preempted while Priority Boost is active
+    if System::is_priority_boost_active() {
+        return;
+    }
+
+    let running_task = match System::state().running_task(lock.borrow_mut()) {
+        Some(task_cb) if *task_cb.st.read(&*lock) == TaskSt::Running => task_cb,
+        _ => return,
+    };
+
+    // Has the running task used up its time slice?
+    let now = timeout::current_mono_time(lock.borrow_mut());
+    if now - System::state().task_switch_time.get(&*lock) < quantum {
+        return;
+    }
+
+    // Is there a task to rotate the running task with?
+    let priority = running_task
+        .effective_priority
+        .read(&*lock)
+        .to_usize()
+        .unwrap();
+    if !System::state()
+        .task_ready_queue
+        .has_ready_task_in_priority_range(lock.borrow_mut().into(), ..priority + 1)
+    {
+        return;
+    }
+
+    // Move the running task to the back of the ready queue. The dispatcher
+    // will choose the next task because the running task is not in the
+    // Running state anymore.
+    //
+    // Safety: The previous state is Running, so this is safe
+    unsafe { make_ready(lock.borrow_mut(), running_task) };
+
+    // Relinquish CPU Lock
+    drop(lock);
+
+    // Safety: CPU Lock inactive
+    unsafe { System::yield_cpu() };
+}
+
 /// Implements `PortToKernel::choose_running_task`.
 #[inline]
 pub(super) fn choose_next_running_task<System: Kernel>(
@@ -756,15 +1058,72 @@ pub(super) fn choose_next_running_task<System: Kernel>(
             TaskSt::Ready => {
                 // `prev_running_task` stays in Ready state.
             }
+            TaskSt::Suspended => {
+                // `prev_running_task` stays in Suspended state.
+            }
             _ => unreachable!(),
         }
     }
 
+    if cfg!(feature = "task_stats") || System::ROUND_ROBIN_QUANTUM_US.is_some() {
+        update_task_switch_time(lock.borrow_mut(), prev_running_task);
+    }
     System::state()
         .running_task
         .replace(&mut *lock, next_running_task);
 }
 
+/// Update [`State::task_switch_time`] and add the time elapsed since the last
+/// update of `running_task` to `prev_running_task`'s execution time. The
+/// caller is about to update `running_task`.
+///
+/// [`State::task_switch_time`]: crate::kernel::State::task_switch_time
+fn update_task_switch_time<System: Kernel>(
+    mut lock: utils::CpuLockTokenRefMut<System>,
+    prev_running_task: Option<&'static TaskCb<System>>,
+) {
+    let now = timeout::current_mono_time(lock.borrow_mut());
+    let last_switch_time = System::state().task_switch_time.replace(&mut *lock, now);
+
+    #[cfg(feature = "task_stats")]
+    if let Some(task_cb) = prev_running_task {
+        task_cb
+            .cpu_time
+            .replace_with(&mut *lock, |x| *x + (now - last_switch_time));
+    }
+
+    #[cfg(not(feature = "task_stats"))]
+    let _ = (prev_running_task, last_switch_time);
+}
+
+/// Get `task_cb`'s execution time, including the time elapsed since the last
+/// context switch if the task is currently running.
+#[cfg(feature = "task_stats")]
+fn cpu_time<System: Kernel>(
+    mut lock: utils::CpuLockTokenRefMut<System>,
+    task_cb: &'static TaskCb<System>,
+) -> u64 {
+    let cpu_time = task_cb.cpu_time.get(&*lock);
+    let running_task = System::state().running_task(lock.borrow_mut());
+    if ptr_from_option_ref(running_task) == task_cb {
+        let now = timeout::current_mono_time(lock.borrow_mut());
+        cpu_time + (now - System::state().task_switch_time.get(&*lock))
+    } else {
+        cpu_time
+    }
+}
+
+/// Implements [`Kernel::total_cpu_time`].
+#[cfg(feature = "task_stats")]
+pub(super) fn total_cpu_time<System: Kernel>() -> Result<core::time::Duration, TotalCpuTimeError> {
+    let mut lock = utils::lock_cpu::<System>()?;
+    let total = System::task_cb_pool()
+        .iter()
+        .map(|task_cb| cpu_time(lock.borrow_mut(), task_cb))
+        .sum();
+    Ok(core::time::Duration::from_micros(total))
+}
+
 #[inline]
 fn ptr_from_option_ref<T>(x: Option<&T>) -> *const T {
     if let Some(x) = x {
@@ -850,6 +1209,20 @@ pub(super) fn park_current_task_timeout<System: Kernel>(
     Ok(())
 }
 
+/// Implements [`Kernel::try_park`].
+pub(super) fn try_park_current_task<System: Kernel>() -> Result<(), TryParkError> {
+    let mut lock = utils::lock_cpu::<System>()?;
+    state::expect_task_context::<System>()?;
+
+    let running_task = System::state().running_task(lock.borrow_mut()).unwrap();
+
+    if running_task.park_token.replace(&mut *lock, false) {
+        Ok(())
+    } else {
+        Err(TryParkError::Timeout)
+    }
+}
+
 /// Implements [`Task::unpark_exact`].
 fn unpark_exact<System: Kernel>(
     mut lock: utils::CpuLockGuard<System>,
@@ -900,6 +1273,60 @@ pub(super) fn put_current_task_on_sleep_timeout<System: Kernel>(
     }
 }
 
+/// Implements [`Kernel::sleep_until`].
+#[cfg(feature = "system_time")]
+pub(super) fn put_current_task_on_sleep_until<System: Kernel>(
+    time: Time,
+) -> Result<(), SleepUntilError> {
+    let mut lock = utils::lock_cpu::<System>()?;
+    state::expect_waitable_context::<System>()?;
+
+    let time32 = if let Some(x) = timeout::time32_until_sys_time(lock.borrow_mut(), time)? {
+        x
+    } else {
+        // `time` is already past
+        return Ok(());
+    };
+
+    // Wait until woken up by timeout
+    match wait::wait_no_queue_timeout(lock.borrow_mut(), wait::WaitPayload::Sleep, time32) {
+        Ok(_) => unreachable!(),
+        Err(WaitTimeoutError::Interrupted) => Err(SleepUntilError::Interrupted),
+        Err(WaitTimeoutError::Timeout) => Ok(()),
+    }
+}
+
+/// Implements [`Kernel::with_priority`].
+pub(super) fn with_current_task_priority<System: Kernel, R, F: FnOnce() -> R>(
+    priority: usize,
+    f: F,
+) -> Result<R, WithPriorityError> {
+    let mut lock = utils::lock_cpu::<System>()?;
+    state::expect_task_context::<System>()?;
+
+    let running_task = System::state().running_task(lock.borrow_mut()).unwrap();
+    let old_priority = running_task.base_priority.read(&*lock).to_usize().unwrap();
+
+    set_task_base_priority(lock, running_task, priority).map_err(|e| match e {
+        SetTaskPriorityError::BadParam => WithPriorityError::BadParam,
+        // The running task can't be Dormant, and its ID is always valid
+        SetTaskPriorityError::BadId
+        | SetTaskPriorityError::BadContext
+        | SetTaskPriorityError::BadObjectState => unreachable!(),
+    })?;
+
+    let output = f();
+
+    // Restore the original base priority
+    let lock = utils::lock_cpu::<System>()
+        .unwrap_or_else(|_| panic!("CPU Lock was left active in `with_priority`"));
+    if set_task_base_priority(lock, running_task, old_priority).is_err() {
+        panic!("unable to restore the original priority in `with_priority`");
+    }
+
+    Ok(output)
+}
+
 /// Implements [`Task::set_priority`].
 fn set_task_base_priority<System: Kernel>(
     mut lock: utils::CpuLockGuard<System>,
@@ -992,7 +1419,7 @@ fn set_task_base_priority<System: Kernel>(
                 old_effective_priority,
             );
         },
-        TaskSt::Running => {}
+        TaskSt::Running | TaskSt::Suspended => {}
         TaskSt::Waiting => {
             // Reposition the task in a wait queue if the task is currently waiting
             wait::reorder_wait_of_task(lock.borrow_mut(), task_cb);
@@ -1000,12 +1427,14 @@ fn set_task_base_priority<System: Kernel>(
         TaskSt::Dormant | TaskSt::PendingActivation => unreachable!(),
     }
 
-    if let TaskSt::Running | TaskSt::Ready = st {
+    if let TaskSt::Running | TaskSt::Ready | TaskSt::Waiting = st {
         // - If `st == TaskSt::Running`, `task_cb` is the currently running
         //   task. If the priority was lowered, it could be preempted by
         //   a task in the Ready state.
         // - If `st == TaskSt::Ready` and the priority was raised, it could
         //   preempt the currently running task.
+        // - If `st == TaskSt::Waiting`, repositioning the task in a
+        //   semaphore's wait queue might have woken up some tasks.
         unlock_cpu_and_check_preemption(lock);
     }
 
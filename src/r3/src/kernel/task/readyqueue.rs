@@ -122,6 +122,16 @@ pub trait Queue<System>: Send + Sync + fmt::Debug + Init + 'static + private::Se
         old_effective_priority: usize,
     ) where
         System: Kernel;
+
+    /// Remove the specified task from the ready queue.
+    ///
+    /// # Safety
+    ///
+    /// This method will cause an undefined behavior if `task_cb` is not
+    /// included in the queue.
+    unsafe fn remove_task(&self, ctx: Ctx<'_, System>, task_cb: &'static TaskCb<System>)
+    where
+        System: Kernel;
 }
 
 /// Implements [the sealed trait pattern], which prevents [`Queue`] against
@@ -379,6 +389,28 @@ where
             task_ready_bitmap.clear(old_effective_priority);
         }
     }
+
+    #[inline]
+    unsafe fn remove_task(
+        &self,
+        Ctx { mut lock }: Ctx<'_, System>,
+        task_cb: &'static TaskCb<System>,
+    ) {
+        let pri = task_cb.effective_priority.read(&*lock).to_usize().unwrap();
+
+        let pri_empty = {
+            let mut accessor = list_accessor!(&self.queues[pri], lock.borrow_mut());
+            // Safety:  `task_cb` is definitely linked to this list, so `remove`
+            //          shouldn't return `ItemError::NotLinked`.
+            unsafe { accessor.remove(Ident(task_cb)).unwrap_unchecked() };
+            accessor.is_empty()
+        };
+
+        // Update `bitmap` accordingly
+        if pri_empty {
+            self.bitmap.write(&mut *lock).clear(pri);
+        }
+    }
 }
 
 impl<
@@ -0,0 +1,132 @@
+//! Task-local storage
+use core::{fmt, hash, marker::PhantomData, mem};
+
+use super::{Hunk, Kernel, TaskCb};
+
+/// Represents a single task-local storage slot in a system.
+///
+/// A `TaskLocal` holds a separate value of type `T` for every task defined in
+/// the system. The values are stored in a [hunk] allocated when the
+/// configuration is finalized, and they are initialized with [`Init`] during
+/// the boot process. The value of a task persists across activations of the
+/// task.
+///
+/// The value can only be accessed in a task context, and only the value of
+/// the current task is accessible. This makes it impossible for a task to
+/// observe another task's value without explicit sharing. To prevent
+/// aliasing, `TaskLocal` doesn't hand out references to the value; instead, it
+/// provides an interface similar to [`Cell`].
+///
+/// [hunk]: crate::kernel::Hunk
+/// [`Init`]: crate::utils::Init
+/// [`Cell`]: core::cell::Cell
+///
+/// <div class="admonition-follows"></div>
+///
+/// > **Relation to Other Specifications:** Thread-local storage in POSIX
+/// > and thread local storage pointers in FreeRTOS.
+#[doc(include = "../common.md")]
+pub struct TaskLocal<System, T>(usize, PhantomData<(System, fn(T) -> T)>);
+
+impl<System, T> Clone for TaskLocal<System, T> {
+    fn clone(&self) -> Self {
+        Self(self.0, self.1)
+    }
+}
+
+impl<System, T> Copy for TaskLocal<System, T> {}
+
+impl<System, T> PartialEq for TaskLocal<System, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<System, T> Eq for TaskLocal<System, T> {}
+
+impl<System, T> hash::Hash for TaskLocal<System, T> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: hash::Hasher,
+    {
+        hash::Hash::hash(&self.0, state);
+    }
+}
+
+impl<System, T> fmt::Debug for TaskLocal<System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TaskLocal").field(&self.0).finish()
+    }
+}
+
+impl<System, T> TaskLocal<System, T> {
+    pub(super) const fn from_index(index: usize) -> Self {
+        Self(index, PhantomData)
+    }
+}
+
+impl<System: Kernel, T: Send + 'static> TaskLocal<System, T> {
+    /// Get a raw pointer to the current task's value.
+    ///
+    /// Panics if the current context is not a task context.
+    fn slot_ptr(self) -> *mut T {
+        assert!(
+            System::is_task_context(),
+            "`TaskLocal` can only be accessed in a task context"
+        );
+
+        // Safety: It's safe to read `running_task` in a task context
+        let task_cb = unsafe { *System::state().running_task_ptr() }.unwrap();
+        let task_index = (task_cb as *const TaskCb<System> as usize
+            - System::task_cb_pool().as_ptr() as usize)
+            / mem::size_of::<TaskCb<System>>();
+
+        let hunk = Hunk::<System>::from_offset(System::TASK_LOCAL_OFFSETS[self.0]);
+        (hunk.as_ptr() as *mut T).wrapping_add(task_index)
+    }
+
+    /// Replace the current task's value with `value`, returning the old value.
+    ///
+    /// Panics if the current context is not a task context.
+    #[inline]
+    pub fn replace(self, value: T) -> T {
+        // Safety: The slot is initialized and is only accessed by the current
+        //         task. No references to the slot exist outside this method.
+        unsafe { mem::replace(&mut *self.slot_ptr(), value) }
+    }
+
+    /// Set the current task's value to `value`.
+    ///
+    /// Panics if the current context is not a task context.
+    #[inline]
+    pub fn set(self, value: T) {
+        // Drop the old value after the slot is no longer borrowed so that
+        // `Drop::drop` can access the slot
+        drop(self.replace(value));
+    }
+
+    /// Get a copy of the current task's value.
+    ///
+    /// Panics if the current context is not a task context.
+    #[inline]
+    pub fn get(self) -> T
+    where
+        T: Copy,
+    {
+        // Safety: The slot is initialized and is only accessed by the current
+        //         task
+        unsafe { *self.slot_ptr() }
+    }
+
+    /// Take the current task's value, leaving `Default::default()` in its
+    /// place.
+    ///
+    /// Panics if the current context is not a task context.
+    #[inline]
+    pub fn take(self) -> T
+    where
+        T: Default,
+    {
+        self.replace(T::default())
+    }
+}
@@ -130,7 +130,7 @@ use core::{fmt, marker::PhantomPinned, pin::Pin, ptr::NonNull};
 use super::{
     state::expect_task_context,
     task,
-    utils::{lock_cpu, CpuLockCell, CpuLockGuard, CpuLockTokenRefMut},
+    utils::{assume_cpu_lock, lock_cpu, CpuLockCell, CpuLockGuard, CpuLockTokenRefMut},
     AdjustTimeError, BadParamError, Kernel, TimeError, UTicks,
 };
 use crate::{
@@ -182,6 +182,11 @@ pub(super) struct TimeoutGlobals<System, TimeoutHeap: 'static> {
     #[cfg(feature = "system_time")]
     last_tick_sys_time: CpuLockCell<System, Time64>,
 
+    /// The monotonic time on the previous “tick”, i.e., the total duration of
+    /// the ticks created since boot, measured in microseconds. This is not
+    /// affected by [`set_system_time`] nor [`adjust_system_and_event_time`].
+    last_tick_mono_time: CpuLockCell<System, u64>,
+
     /// The gap between the frontier and the previous tick.
     ///
     /// This value only can be increased by [`adjust_system_and_event_time`].
@@ -214,6 +219,7 @@ impl<System, TimeoutHeap: Init + 'static> Init for TimeoutGlobals<System, Timeou
         last_tick_time: Init::INIT,
         #[cfg(feature = "system_time")]
         last_tick_sys_time: Init::INIT,
+        last_tick_mono_time: Init::INIT,
         frontier_gap: Init::INIT,
         heap_and_prop_token: CpuLockCell::new(TimeoutHeapAndPropToken {
             heap: Init::INIT,
@@ -243,6 +249,7 @@ impl<System: Kernel, TimeoutHeap: fmt::Debug> fmt::Debug for TimeoutGlobals<Syst
                     () => &(),
                 },
             )
+            .field("last_tick_mono_time", &self.last_tick_mono_time)
             .field("frontier_gap", &self.frontier_gap)
             .field("heap_and_prop_token", &self.heap_and_prop_token)
             .field("handle_tick_in_progress", &self.handle_tick_in_progress)
@@ -640,8 +647,10 @@ impl<System: Kernel, TimeoutHeap> TimeoutGlobals<System, TimeoutHeap> {
 
         // Schedule the next tick. There are no timeouts registered at the
         // moment, so use `MAX_TIMEOUT`.
-        // Safety: CPU Lock active
-        unsafe { System::pend_tick_after(System::MAX_TIMEOUT) };
+        if System::TICKLESS {
+            // Safety: CPU Lock active
+            unsafe { System::pend_tick_after(System::MAX_TIMEOUT) };
+        }
     }
 }
 
@@ -654,14 +663,42 @@ pub(super) fn system_time<System: Kernel>() -> Result<Time, TimeError> {
     expect_task_context::<System>()?;
     let mut lock = lock_cpu::<System>()?;
 
+    let cur_sys_time = current_sys_time(lock.borrow_mut());
+
+    // Convert `Time64` to a public type
+    Ok(sys_time_from_time64(cur_sys_time))
+}
+
+/// Get the current system time.
+#[cfg(feature = "system_time")]
+fn current_sys_time<System: Kernel>(mut lock: CpuLockTokenRefMut<'_, System>) -> Time64 {
     let (duration_since_last_tick, _) = duration_since_last_tick(lock.borrow_mut());
     let last_tick_sys_time = System::g_timeout()
         .last_tick_sys_time
         .get(&*lock.borrow_mut());
-    let cur_sys_time = last_tick_sys_time.wrapping_add(duration_since_last_tick as Time64);
+    last_tick_sys_time.wrapping_add(duration_since_last_tick as Time64)
+}
 
-    // Convert `Time64` to a public type
-    Ok(sys_time_from_time64(cur_sys_time))
+/// Calculate the duration between the current system time and `sys_time`.
+///
+/// Returns `None` if `sys_time` is equal to or older than the current system
+/// time. Returns `Err(BadParam)` if the duration can't be represented by
+/// [`Duration`].
+#[cfg(feature = "system_time")]
+pub(super) fn time32_until_sys_time<System: Kernel>(
+    mut lock: CpuLockTokenRefMut<'_, System>,
+    sys_time: Time,
+) -> Result<Option<Time32>, BadParamError> {
+    let cur_sys_time = current_sys_time(lock.borrow_mut());
+    let delta = time64_from_sys_time(sys_time).wrapping_sub(cur_sys_time) as i64;
+
+    if delta <= 0 {
+        Ok(None)
+    } else if delta > Duration::MAX.as_micros() as i64 {
+        Err(BadParamError::BadParam)
+    } else {
+        Ok(Some(delta as Time32))
+    }
 }
 
 /// Implements [`Kernel::set_time`].
@@ -785,6 +822,51 @@ pub(super) fn adjust_system_and_event_time<System: Kernel>(
     Ok(())
 }
 
+/// Implements [`Kernel::monotonic_time`].
+pub(super) fn monotonic_time<System: Kernel>() -> core::time::Duration {
+    let cur_mono_time = if let Ok(mut lock) = lock_cpu::<System>() {
+        current_mono_time(lock.borrow_mut())
+    } else {
+        // CPU Lock is already active. System calls are disallowed in an
+        // unmanaged interrupt handler, so it's owned by the caller, not by an
+        // interrupted kernel function. The kernel doesn't hold `CpuLockGuard`
+        // while calling application code (e.g., `PortToKernel::boot` releases
+        // it before calling startup hooks), so we can create a temporary one.
+        // Safety: CPU Lock active, no other `CpuLockGuard` exists
+        let mut lock = unsafe { assume_cpu_lock::<System>() };
+        let cur_mono_time = current_mono_time(lock.borrow_mut());
+
+        // CPU Lock is owned by the caller
+        core::mem::forget(lock);
+
+        cur_mono_time
+    };
+
+    core::time::Duration::from_micros(cur_mono_time)
+}
+
+/// Get the current monotonic time, measured in microseconds.
+pub(super) fn current_mono_time<System: Kernel>(mut lock: CpuLockTokenRefMut<'_, System>) -> u64 {
+    let (duration_since_last_tick, _) = duration_since_last_tick(lock.borrow_mut());
+    let last_tick_mono_time = System::g_timeout().last_tick_mono_time.get(&*lock);
+    last_tick_mono_time + duration_since_last_tick as u64
+}
+
+/// Implements [`Kernel::time_resolution`].
+#[inline]
+pub(super) fn time_resolution<System: Kernel>() -> Duration {
+    let resolution = System::RESOLUTION;
+    debug_assert_ne!(resolution, 0);
+
+    // `Duration` can't represent the full range of `UTicks`. A resolution
+    // this coarse is meaningless anyway, so just saturate it.
+    if resolution > i32::MAX as UTicks {
+        Duration::MAX
+    } else {
+        Duration::from_micros(resolution as i32)
+    }
+}
+
 /// Calculate the elapsed time since the last tick.
 ///
 /// Returns two values:
@@ -847,6 +929,11 @@ fn mark_tick<System: Kernel>(mut lock: CpuLockTokenRefMut<'_, System>) {
         .replace_with(&mut *lock, |old_value| {
             old_value.wrapping_add(duration_since_last_tick as Time64)
         });
+    g_timeout
+        .last_tick_mono_time
+        .replace_with(&mut *lock, |old_value| {
+            old_value + duration_since_last_tick as u64
+        });
 
     g_timeout
         .frontier_gap
@@ -942,6 +1029,12 @@ fn current_time<System: Kernel>(mut lock: CpuLockTokenRefMut<'_, System>) -> Tim
 
 /// Schedule the next tick.
 fn pend_next_tick<System: Kernel>(lock: CpuLockTokenRefMut<'_, System>, current_time: Time32) {
+    if !System::TICKLESS {
+        // The driver calls `timer_tick` at a steady rate, so there's no point
+        // in finding the next timeout
+        return;
+    }
+
     let mut delay = System::MAX_TIMEOUT;
 
     let TimeoutHeapAndPropToken { heap, prop_token } =
@@ -1009,6 +1102,166 @@ fn saturating_duration_before_timeout_exhausting_user_headroom<System: Kernel>(
     duration_until_violating_critical_point.saturating_sub(HARD_HEADROOM)
 }
 
+/// Find a registered timeout whose callback parameter is `callback_param` and
+/// calculate the duration until it's reached. Returns `None` if there's no such
+/// timeout.
+///
+/// This does a linear search over the heap, so it's only meant to be used for
+/// debug printing.
+pub(super) fn saturating_duration_until_timeout_with_param<System: Kernel>(
+    mut lock: CpuLockTokenRefMut<'_, System>,
+    callback_param: usize,
+) -> Option<Time32> {
+    let current_time = current_time(lock.borrow_mut());
+
+    let heap_and_prop_token = System::g_timeout().heap_and_prop_token.read(&*lock);
+
+    heap_and_prop_token.heap.iter().find_map(|timeout_ref| {
+        // Safety: `timeout_ref` is in the heap, so the pointee must be valid
+        let timeout = unsafe { timeout_ref.0.as_ref() };
+
+        if timeout.callback_param == callback_param {
+            Some(saturating_duration_until_timeout(
+                timeout,
+                current_time,
+                heap_and_prop_token.prop_token.borrow(),
+            ))
+        } else {
+            None
+        }
+    })
+}
+
+/// Get an object that implements [`Debug`](fmt::Debug) for dumping the
+/// outstanding timeouts in the order of arrival, along with the remaining
+/// duration and the object that each timeout belongs to.
+///
+/// This does a quadratic-time search over the heap, so it's only meant to be
+/// used for debug printing.
+#[cfg(debug_assertions)]
+pub(super) fn debug_fmt_timeouts<System: Kernel>() -> impl fmt::Debug {
+    struct TimeoutsPrinter<System>(core::marker::PhantomData<System>);
+
+    impl<System: Kernel> fmt::Debug for TimeoutsPrinter<System> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let mut lock = if let Ok(lock) = lock_cpu::<System>() {
+                lock
+            } else {
+                return f.write_str("< locked >");
+            };
+
+            let current_time = current_time(lock.borrow_mut());
+            let critical_point = critical_point(current_time);
+
+            let TimeoutHeapAndPropToken { heap, prop_token } =
+                System::g_timeout().heap_and_prop_token.read(&*lock);
+
+            let mut list = f.debug_list();
+
+            // Find the timeouts one by one in the same order as the one used
+            // by `TimeoutHeapCtx`, breaking ties by the heap position
+            let mut last_key = None;
+            loop {
+                let next = heap
+                    .iter()
+                    .enumerate()
+                    .map(|(i, timeout_ref)| {
+                        // Safety: `timeout_ref` is in the heap, so the pointee
+                        //         must be valid
+                        let timeout = unsafe { timeout_ref.0.as_ref() };
+                        let at = *timeout.at.read(prop_token);
+                        ((at.wrapping_sub(critical_point), i), timeout)
+                    })
+                    .filter(|&(key, _)| last_key.map_or(true, |last_key| key > last_key))
+                    .min_by_key(|&(key, _)| key);
+
+                let (key, timeout) = if let Some(next) = next {
+                    next
+                } else {
+                    break;
+                };
+                last_key = Some(key);
+
+                let remaining =
+                    saturating_duration_until_timeout(timeout, current_time, prop_token.borrow());
+
+                list.entry(&TimeoutPrinter {
+                    remaining: core::time::Duration::from_micros(remaining.into()),
+                    owner: TimeoutOwner::new(&*lock, timeout),
+                });
+            }
+
+            list.finish()
+        }
+    }
+
+    struct TimeoutPrinter {
+        remaining: core::time::Duration,
+        owner: TimeoutOwner,
+    }
+
+    impl fmt::Debug for TimeoutPrinter {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_struct("Timeout")
+                .field("remaining", &self.remaining)
+                .field("owner", &self.owner)
+                .finish()
+        }
+    }
+
+    enum TimeoutOwner {
+        Task { id: usize, reason: &'static str },
+        Timer { id: usize },
+        Unknown,
+    }
+
+    impl TimeoutOwner {
+        fn new<System: Kernel>(
+            lock: &super::utils::CpuLockToken<System>,
+            timeout: &Timeout<System>,
+        ) -> Self {
+            let param = timeout.callback_param;
+
+            // A timer's timeout object has the timer's index as its parameter
+            // (see `CfgBuilderTimer::to_state`)
+            if timeout.callback as usize == super::timer::timer_timeout_handler::<System> as usize {
+                return Self::Timer { id: param + 1 };
+            }
+
+            // A task's timeout object has the task's address as its parameter
+            // (see `new_timeout_object_for_task`)
+            let task_cb_pool = System::task_cb_pool();
+            let offset = param.wrapping_sub(task_cb_pool.as_ptr() as usize);
+            if offset < core::mem::size_of_val(task_cb_pool) {
+                let i = offset / core::mem::size_of::<task::TaskCb<System>>();
+                let reason = super::wait::current_wait_reason(lock, &task_cb_pool[i]);
+                return Self::Task {
+                    id: i + 1,
+                    reason: reason.unwrap_or("NotWaiting"),
+                };
+            }
+
+            Self::Unknown
+        }
+    }
+
+    impl fmt::Debug for TimeoutOwner {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Self::Task { id, reason } => f
+                    .debug_struct("Task")
+                    .field("id", id)
+                    .field("reason", &format_args!("{}", reason))
+                    .finish(),
+                Self::Timer { id } => f.debug_struct("Timer").field("id", id).finish(),
+                Self::Unknown => f.write_str("Unknown"),
+            }
+        }
+    }
+
+    TimeoutsPrinter::<System>(core::marker::PhantomData)
+}
+
 /// Register the specified timeout.
 pub(super) fn insert_timeout<System: Kernel>(
     mut lock: CpuLockTokenRefMut<'_, System>,
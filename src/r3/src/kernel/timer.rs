@@ -4,8 +4,8 @@ use core::{fmt, hash, marker::PhantomData, mem::ManuallyDrop};
 use super::{
     timeout,
     utils::{assume_cpu_lock, lock_cpu, CpuLockCell, CpuLockGuard, CpuLockTokenRefMut},
-    BadIdError, Id, Kernel, SetTimerDelayError, SetTimerPeriodError, StartTimerError,
-    StopTimerError,
+    BadIdError, Id, Kernel, QueryTimerError, ResetTimerError, SetTimerDelayError,
+    SetTimerPeriodError, StartTimerError, StopTimerError,
 };
 use crate::{
     time::Duration,
@@ -164,7 +164,13 @@ use crate::{
 /// Another way to stop a timer is to [set the delay or the period to `None`
 /// (infinity)](#infinite-delay-andor-period).
 ///
+/// A timer can also be [reset], which sets the delay back to the initial delay
+/// specified at configuration time. If the timer is in the Active state, the
+/// delay is measured from the current time; otherwise, it's measured from the
+/// next activation. This can be used to implement a software watchdog timer.
+///
 /// [stopped]: Timer::stop
+/// [reset]: Timer::reset
 ///
 /// ## Dynamic Period
 ///
@@ -399,13 +405,53 @@ impl<System: Kernel> Timer<System> {
         Ok(())
     }
 
+    /// Reset the delay to the initial delay specified at configuration time.
+    ///
+    /// This is equivalent to calling [`set_delay`] with the initial delay. The
+    /// timer's state (Active or Dormant) and period are left unchanged.
+    ///
+    /// [`set_delay`]: Self::set_delay
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn reset(self) -> Result<(), ResetTimerError> {
+        let mut lock = lock_cpu::<System>()?;
+        let timer_cb = self.timer_cb()?;
+        set_timer_delay(lock.borrow_mut(), timer_cb, timer_cb.attr.init_delay);
+        Ok(())
+    }
+
+    /// Get a flag indicating whether the timer is in the Active state.
+    ///
+    /// Note that an Active timer doesn't necessarily have a scheduled tick.
+    /// For example, a timer with an infinite delay never fires even when it's
+    /// in the Active state.
+    #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
+    pub fn is_active(self) -> Result<bool, QueryTimerError> {
+        let lock = lock_cpu::<System>()?;
+        let timer_cb = self.timer_cb()?;
+        Ok(timer_cb.active.get(&*lock))
+    }
+
     /// Set the duration before the next tick.
     ///
+    /// The duration is measured from the current time, and the period is not
+    /// taken into account. If this method is called inside the timer's
+    /// callback function, the period-based rescheduling that took place before
+    /// the call is overridden.
+    ///
     /// If the timer is currently in the Dormant state, this method specifies
     /// the duration between the next activation and the first tick
     /// following the activation.
     ///
     /// `None` means infinity (the timer will never fire).
+    ///
+    /// <div class="admonition-follows"></div>
+    ///
+    /// > **Relation to Other Specifications:** Combined with [`start`], this
+    /// > method can be used like `sta_alm` from μITRON4.0 and
+    /// > [the TOPPERS 3rd generation kernels].
+    ///
+    /// [`start`]: Self::start
+    /// [the TOPPERS 3rd generation kernels]: https://www.toppers.jp/index.html
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn set_delay(self, delay: Option<Duration>) -> Result<(), SetTimerDelayError> {
         let time32 = if let Some(x) = delay {
@@ -422,7 +468,16 @@ impl<System: Kernel> Timer<System> {
     /// Set the timer period, which is a quantity to be added to the timer's
     /// absolute arrival time on every tick.
     ///
-    /// `None` means infinity.
+    /// `None` means infinity. A timer with an infinite period works as a
+    /// one-shot timer: it stops firing after the next tick but stays in the
+    /// Active state, and it can be rescheduled by [setting the delay].
+    ///
+    /// The period of the next tick is determined before the callback function
+    /// is called. This means that, if this method is called inside the
+    /// timer's callback function, the new value takes effect after the next
+    /// tick.
+    ///
+    /// [setting the delay]: Self::set_delay
     #[cfg_attr(not(feature = "inline_syscall"), inline(never))]
     pub fn set_period(self, period: Option<Duration>) -> Result<(), SetTimerPeriodError> {
         let time32 = if let Some(x) = period {
@@ -435,6 +490,13 @@ impl<System: Kernel> Timer<System> {
         set_timer_period(lock.borrow_mut(), timer_cb, time32);
         Ok(())
     }
+
+    /// Set the timer period to infinity, making the timer a one-shot timer.
+    ///
+    /// This is equivalent to `set_period(None)`.
+    pub fn clear_period(self) -> Result<(), SetTimerPeriodError> {
+        self.set_period(None)
+    }
 }
 
 /// *Timer control block* - the state data of a timer.
@@ -509,6 +571,9 @@ pub struct TimerAttr<System> {
     /// The initial state of the timer.
     pub(super) init_active: bool,
 
+    /// The initial delay of the timer, restored by [`Timer::reset`].
+    pub(super) init_delay: timeout::Time32,
+
     pub(super) _phantom: PhantomData<System>,
 }
 
@@ -517,6 +582,7 @@ impl<System> Init for TimerAttr<System> {
         entry_point: |_| {},
         entry_param: 0,
         init_active: false,
+        init_delay: timeout::BAD_DURATION32,
         _phantom: PhantomData,
     };
 }
@@ -1,7 +1,7 @@
 use core::{cell::Cell, fmt, ops, ptr::NonNull};
 
 use super::{
-    event_group, mutex, task,
+    event_group, mutex, semaphore, task,
     task::{TaskCb, TaskSt},
     timeout,
     utils::{CpuLockCell, CpuLockGuard, CpuLockTokenRef, CpuLockTokenRefMut},
@@ -125,7 +125,11 @@ pub(super) enum WaitPayload<System: PortThreading> {
         flags: event_group::EventGroupWaitFlags,
         orig_bits: CpuLockCell<System, Cell<event_group::EventGroupBits>>,
     },
-    Semaphore,
+    Semaphore {
+        semaphore_cb: &'static semaphore::SemaphoreCb<System>,
+        count: semaphore::SemaphoreValue,
+    },
+    Notification,
     Mutex(&'static mutex::MutexCb<System>),
     Park,
     Sleep,
@@ -133,6 +137,20 @@ pub(super) enum WaitPayload<System: PortThreading> {
 }
 
 impl<T: PortThreading> WaitPayload<T> {
+    /// Get the name of the variant, which describes what the waiting task is
+    /// blocked on.
+    fn variant_name(&self) -> &'static str {
+        match self {
+            Self::EventGroupBits { .. } => "EventGroupBits",
+            Self::Semaphore { .. } => "Semaphore",
+            Self::Notification => "Notification",
+            Self::Mutex(_) => "Mutex",
+            Self::Park => "Park",
+            Self::Sleep => "Sleep",
+            Self::__Nonexhaustive => unreachable!(),
+        }
+    }
+
     /// Return `self`.
     ///
     /// This might look redundant but is actually very important to maximize
@@ -153,7 +171,14 @@ impl<T: PortThreading> WaitPayload<T> {
                 flags,
                 orig_bits,
             },
-            Self::Semaphore => Self::Semaphore,
+            Self::Semaphore {
+                semaphore_cb,
+                count,
+            } => Self::Semaphore {
+                semaphore_cb,
+                count,
+            },
+            Self::Notification => Self::Notification,
             Self::Mutex(x) => Self::Mutex(x),
             Self::Park => Self::Park,
             Self::Sleep => Self::Sleep,
@@ -480,6 +505,46 @@ impl<System: Kernel> WaitQueue<System> {
         true
     }
 
+    /// Wake up the first waiting task if `cond` returns `true` for its wait
+    /// payload. Returns `true` if it has successfully woken up a task.
+    ///
+    /// This method may make a task Ready, but doesn't yield the processor.
+    /// Call `unlock_cpu_and_check_preemption` as needed.
+    pub(super) fn wake_up_one_conditional(
+        &self,
+        mut lock: CpuLockTokenRefMut<'_, System>,
+        cond: impl FnOnce(&WaitPayload<System>) -> bool,
+    ) -> bool {
+        // Get the first wait object
+        let mut accessor = wait_queue_accessor!(&self.waits, lock.borrow_mut());
+        let Ok(wait_ref) = accessor.front();
+
+        let wait_ref = if let Some(wait_ref) = wait_ref {
+            wait_ref
+        } else {
+            return false;
+        };
+
+        // Safety: `wait_ref` points to a valid `Wait` because `wait_ref` is
+        // in `self.waits`.
+        let wait = unsafe { wait_ref.0.as_ref() };
+
+        assert!(core::ptr::eq(wait.wait_queue.unwrap(), self));
+
+        // Should this task be woken up?
+        if !cond(&wait.payload) {
+            return false;
+        }
+
+        // Safety: `wait_ref` is still linked, so it shouldn't return
+        //         `ItemError::Unlinked`.
+        unsafe { accessor.remove(wait_ref).unwrap_unchecked() };
+
+        complete_wait(lock.borrow_mut(), wait, Ok(()));
+
+        true
+    }
+
     /// Conditionally wake up waiting tasks.
     ///
     /// This method may make a task Ready, but doesn't yield the processor.
@@ -556,7 +621,15 @@ impl<System: Kernel> fmt::Debug for WaitPayload<System> {
                 .field("flags", flags)
                 .field("orig_bits", orig_bits)
                 .finish(),
-            Self::Semaphore => f.write_str("Semaphore"),
+            Self::Semaphore {
+                semaphore_cb,
+                count,
+            } => f
+                .debug_struct("Semaphore")
+                .field("semaphore_cb", &(*semaphore_cb as *const _))
+                .field("count", count)
+                .finish(),
+            Self::Notification => f.write_str("Notification"),
             Self::Mutex(mutex) => write!(f, "Mutex({:p})", mutex),
             Self::Park => f.write_str("Park"),
             Self::Sleep => f.write_str("Sleep"),
@@ -608,6 +681,61 @@ impl<System: Kernel> fmt::Debug for TaskWait<System> {
     }
 }
 
+impl<System: Kernel> TaskWait<System> {
+    /// Return a debug formatting proxy summarizing what the owning task is
+    /// waiting for and how long it will take for the wait operation to time
+    /// out.
+    ///
+    /// `task_cb` must point to the [`TaskCb`] containing `self`.
+    pub(super) fn debug_fmt_state(&self, task_cb: *const ()) -> impl fmt::Debug + '_ {
+        struct WaitStatePrinter<'a, System: PortThreading> {
+            wait: &'a TaskWait<System>,
+            task_cb: *const (),
+        }
+
+        impl<System: Kernel> fmt::Debug for WaitStatePrinter<'_, System> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut lock = if let Ok(lock) = super::utils::lock_cpu::<System>() {
+                    lock
+                } else {
+                    return f.write_str("< locked >");
+                };
+
+                let wait_ref = if let Some(wait_ref) = self.wait.current_wait.get(&*lock) {
+                    wait_ref
+                } else {
+                    return f.write_str("NotWaiting");
+                };
+
+                // Safety: `wait_ref` must point to an existing `Wait`
+                let reason = unsafe { wait_ref.0.as_ref() }.payload.variant_name();
+
+                // The timeout object for the task has the task's address as
+                // its parameter (see `new_timeout_object_for_task`)
+                let timeout = timeout::saturating_duration_until_timeout_with_param(
+                    lock.borrow_mut(),
+                    self.task_cb as usize,
+                );
+
+                drop(lock);
+
+                f.debug_struct("Waiting")
+                    .field("reason", &format_args!("{}", reason))
+                    .field(
+                        "timeout",
+                        &timeout.map(|x| core::time::Duration::from_micros(x.into())),
+                    )
+                    .finish()
+            }
+        }
+
+        WaitStatePrinter {
+            wait: self,
+            task_cb,
+        }
+    }
+}
+
 /// Access the specified task's current wait payload object in the supplied
 /// closure.
 ///
@@ -627,6 +755,32 @@ pub(super) fn with_current_wait_payload<System: Kernel, R>(
     f(wait)
 }
 
+/// Get the mutex that the specified task is currently waiting for.
+pub(super) fn current_wait_mutex<System: Kernel>(
+    lock: &super::utils::CpuLockToken<System>,
+    task_cb: &TaskCb<System>,
+) -> Option<&'static mutex::MutexCb<System>> {
+    let wait_ref = task_cb.wait.current_wait.get(lock)?;
+
+    // Safety: `wait_ref` must point to an existing `Wait`
+    match unsafe { &wait_ref.0.as_ref().payload } {
+        WaitPayload::Mutex(mutex_cb) => Some(*mutex_cb),
+        _ => None,
+    }
+}
+
+/// Get the name of what the specified task is currently waiting for. Returns
+/// `None` if the task is not in the Waiting state.
+pub(super) fn current_wait_reason<System: Kernel>(
+    lock: &super::utils::CpuLockToken<System>,
+    task_cb: &TaskCb<System>,
+) -> Option<&'static str> {
+    let wait_ref = task_cb.wait.current_wait.get(lock)?;
+
+    // Safety: `wait_ref` must point to an existing `Wait`
+    Some(unsafe { wait_ref.0.as_ref() }.payload.variant_name())
+}
+
 /// Reposition the given task's wait object within the wait queue. This is
 /// necessary after changing the task's priority because some wait queues are
 /// configured to sort wait objects by task priority
@@ -634,8 +788,11 @@ pub(super) fn with_current_wait_payload<System: Kernel, R>(
 ///
 /// This function does nothing if the task is currently not in the Waiting state
 /// or the wait object is not associated with any wait queue.
+///
+/// This method may make a task Ready, but doesn't yield the processor.
+/// Call `unlock_cpu_and_check_preemption` as needed.
 pub(super) fn reorder_wait_of_task<System: Kernel>(
-    lock: CpuLockTokenRefMut<'_, System>,
+    mut lock: CpuLockTokenRefMut<'_, System>,
     task_cb: &TaskCb<System>,
 ) {
     if let Some(wait_ref) = task_cb.wait.current_wait.get(&*lock) {
@@ -643,7 +800,14 @@ pub(super) fn reorder_wait_of_task<System: Kernel>(
         let wait = unsafe { &*wait_ref.0.as_ptr() };
 
         if let Some(wait_queue) = wait.wait_queue {
-            wait_queue.reorder_wait(lock, wait);
+            wait_queue.reorder_wait(lock.borrow_mut(), wait);
+
+            // A different task might be at the front of a semaphore's wait
+            // queue now, and it might be able to acquire the permits it's
+            // waiting for
+            if let WaitPayload::Semaphore { semaphore_cb, .. } = wait.payload {
+                semaphore::wake_up_waiters(lock, semaphore_cb);
+            }
         }
     }
 }
@@ -815,9 +979,22 @@ pub(super) fn interrupt_task<System: Kernel>(
                 unsafe { accessor.remove(wait_ref).unwrap_unchecked() };
             }
 
+            let semaphore_cb = if let WaitPayload::Semaphore { semaphore_cb, .. } = wait.payload {
+                Some(semaphore_cb)
+            } else {
+                None
+            };
+
             // Wake up the task
             complete_wait(lock.borrow_mut(), wait, wait_result);
 
+            // If `wait` was blocking the tasks behind it in a semaphore's wait
+            // queue, they might be able to acquire the permits they're waiting
+            // for now
+            if let Some(semaphore_cb) = semaphore_cb {
+                semaphore::wake_up_waiters(lock.borrow_mut(), semaphore_cb);
+            }
+
             Ok(())
         }
         _ => Err(BadObjectStateError::BadObjectState),
@@ -66,6 +66,7 @@ pub extern crate staticvec;
 pub mod utils;
 #[macro_use]
 pub mod kernel;
+pub mod future;
 pub mod hunk;
 pub mod sync;
 pub mod time;
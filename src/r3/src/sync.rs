@@ -1,5 +1,13 @@
 //! Safe synchronization primitives.
+pub mod condvar;
+pub mod memory_pool;
 pub mod mutex;
+pub mod queue;
 pub mod recursive_mutex;
+pub mod rwlock;
+pub mod semaphore;
 #[doc(no_inline)]
-pub use self::{mutex::Mutex, recursive_mutex::RecursiveMutex};
+pub use self::{
+    condvar::Condvar, memory_pool::MemoryPool, mutex::Mutex, queue::Queue,
+    recursive_mutex::RecursiveMutex, rwlock::RwLock, semaphore::Semaphore,
+};
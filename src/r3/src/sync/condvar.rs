@@ -0,0 +1,263 @@
+use core::{cell::Cell, fmt};
+
+use super::mutex::{LockError, MutexGuard};
+use crate::{
+    hunk::Hunk,
+    kernel::{
+        self, cfg::CfgBuilder, CpuLockError, LockMutexError, SemaphoreValue, SignalSemaphoreError,
+        WaitSemaphoreError,
+    },
+    prelude::*,
+};
+
+/// A condition variable, which can be used to block a task while waiting for
+/// a condition protected by a [`Mutex`] to become true.
+///
+/// This type is implemented using [`r3::kernel::Semaphore`]. The number of
+/// waiting tasks is tracked by a counter protected by CPU Lock, and a
+/// notification is delivered by releasing a permit to the semaphore. The
+/// permit stays in the semaphore until it's taken by a waiting task, so a
+/// notification made between the release of the mutex and the start of the
+/// wait operation in [`Condvar::wait`] is not lost.
+///
+/// Like `std::sync::Condvar`, a waiting task may be woken up without a
+/// matching notification (*a spurious wakeup*), e.g., when a task is
+/// interrupted while waiting and leaves behind a permit intended for it.
+/// Therefore, the condition should always be checked in a loop.
+///
+/// [`Mutex`]: super::Mutex
+/// [`r3::kernel::Semaphore`]: crate::kernel::Semaphore
+///
+/// # Examples
+///
+/// ```rust
+/// #![feature(const_fn_fn_ptr_basics)]
+/// #![feature(const_mut_refs)]
+/// use r3::{
+///     kernel::cfg::CfgBuilder,
+///     prelude::*,
+///     sync::{Condvar, Mutex},
+/// };
+///
+/// struct Objects<System> {
+///     mutex: Mutex<System, bool>,
+///     condvar: Condvar<System>,
+/// }
+///
+/// const fn configure<System: Kernel>(b: &mut CfgBuilder<System>) -> Objects<System> {
+///     Objects {
+///         mutex: Mutex::build().finish(b),
+///         condvar: Condvar::build().finish(b),
+///     }
+/// }
+///
+/// fn wait_for_ready<System: Kernel>(objects: &Objects<System>) {
+///     let mut guard = objects.mutex.lock().unwrap();
+///     while !*guard {
+///         guard = objects.condvar.wait(guard).unwrap();
+///     }
+/// }
+///
+/// fn set_ready<System: Kernel>(objects: &Objects<System>) {
+///     *objects.mutex.lock().unwrap() = true;
+///     objects.condvar.notify_all().unwrap();
+/// }
+/// ```
+pub struct Condvar<System> {
+    /// The number of tasks waiting on the condition variable that haven't
+    /// been notified yet. Protected by CPU Lock.
+    num_waiters: Hunk<System, Cell<usize>>,
+    /// Holds permits for notified tasks.
+    semaphore: kernel::Semaphore<System>,
+}
+
+unsafe impl<System: Kernel> Send for Condvar<System> {}
+unsafe impl<System: Kernel> Sync for Condvar<System> {}
+
+/// Configuration builder type for [`Condvar`].
+pub struct Builder<System> {
+    _phantom: core::marker::PhantomData<System>,
+}
+
+/// Type alias for the result of [`Condvar::wait`].
+pub type WaitResult<Guard> = Result<Guard, WaitError<Guard>>;
+
+/// Error type of [`Condvar::wait`].
+#[repr(i8)]
+pub enum WaitError<Guard> {
+    /// CPU Lock is active, or the current context is not [waitable]. The mutex
+    /// is not released.
+    ///
+    /// [waitable]: crate#contexts
+    BadContext(Guard) = WaitSemaphoreError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`]. The mutex
+    /// was re-acquired.
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted(Guard) = WaitSemaphoreError::Interrupted as i8,
+    /// The previous owning task of the mutex exited while holding the mutex
+    /// lock. *The current task shall hold the mutex lock*, but is up to make
+    /// the state consistent.
+    Abandoned(Guard) = LockMutexError::Abandoned as i8,
+}
+
+impl<Guard> fmt::Debug for WaitError<Guard> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BadContext(_) => "BadContext",
+            Self::Interrupted(_) => "Interrupted",
+            Self::Abandoned(_) => "Abandoned",
+        })
+    }
+}
+
+/// Error type of [`Condvar::notify_one`] and [`Condvar::notify_all`].
+#[derive(Debug)]
+#[repr(i8)]
+pub enum NotifyError {
+    /// CPU Lock is active.
+    BadContext = SignalSemaphoreError::BadContext as i8,
+}
+
+impl<System: Kernel> Condvar<System> {
+    /// Construct a `Builder` to define a condition variable in [a
+    /// configuration function](crate#static-configuration).
+    pub const fn build() -> Builder<System> {
+        Builder {
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<System: Kernel> Builder<System> {
+    /// Complete the definition of a condition variable, returning a reference
+    /// to the condition variable.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> Condvar<System> {
+        Condvar {
+            num_waiters: Hunk::build().finish(cfg),
+            semaphore: kernel::Semaphore::build()
+                .initial(0)
+                .maximum(SemaphoreValue::MAX)
+                .finish(cfg),
+        }
+    }
+}
+
+impl<System: Kernel> Condvar<System> {
+    /// Release the mutex guarded by `guard` and block the current task until
+    /// this condition variable receives a notification. The mutex is
+    /// re-acquired before this method returns.
+    ///
+    /// This method will panic if the mutex was not the one most recently
+    /// locked by the current task because mutexes must be unlocked in a
+    /// lock-reverse order.
+    pub fn wait<'a, T: 'static>(
+        &self,
+        guard: MutexGuard<'a, System, T>,
+    ) -> WaitResult<MutexGuard<'a, System, T>> {
+        // Register the current task as a waiter. This must happen before
+        // releasing the mutex so that a notifier who observed the condition
+        // being false is guaranteed to see this task.
+        if self.update_num_waiters(|n| n + 1).is_err() {
+            return Err(WaitError::BadContext(guard));
+        }
+
+        let mutex = guard.mutex;
+        drop(guard);
+
+        // If a notification arrives after the mutex is released, the permit
+        // stays in the semaphore, so it's not lost
+        let interrupted = match self.semaphore.wait_one() {
+            Ok(()) => false,
+            Err(WaitSemaphoreError::BadId) => unreachable!(),
+            // `guard` proves that this is a task context, and CPU Lock was
+            // checked by `update_num_waiters`
+            Err(WaitSemaphoreError::BadContext) => unreachable!(),
+            Err(WaitSemaphoreError::Interrupted) => {
+                // Unregister the current task. If a notification intended
+                // for this task is in flight, its permit will cause
+                // a spurious wakeup of another task instead.
+                self.update_num_waiters(|n| n.saturating_sub(1)).unwrap();
+                true
+            }
+        };
+
+        // Re-acquire the mutex. `Interrupted` is merged into the result.
+        let mut interrupted_while_locking = false;
+        let result = loop {
+            match mutex.lock() {
+                Err(LockError::Interrupted) => interrupted_while_locking = true,
+                result => break result,
+            }
+        };
+
+        match result {
+            Ok(guard) if interrupted || interrupted_while_locking => {
+                Err(WaitError::Interrupted(guard))
+            }
+            Ok(guard) => Ok(guard),
+            Err(LockError::Abandoned(guard)) => Err(WaitError::Abandoned(guard)),
+            // `Interrupted` is handled by the loop. The other errors are
+            // impossible because the mutex was owned by this task just
+            // a moment ago.
+            Err(e) => panic!("failed to re-acquire the mutex: {:?}", e),
+        }
+    }
+
+    /// Wake up one of the tasks waiting on this condition variable.
+    ///
+    /// This method can be called in any context where CPU Lock is inactive,
+    /// including an interrupt context. It does nothing if there are no
+    /// waiting tasks.
+    pub fn notify_one(&self) -> Result<(), NotifyError> {
+        self.notify(1)
+    }
+
+    /// Wake up all tasks waiting on this condition variable.
+    ///
+    /// This method can be called in any context where CPU Lock is inactive,
+    /// including an interrupt context.
+    pub fn notify_all(&self) -> Result<(), NotifyError> {
+        self.notify(usize::MAX)
+    }
+
+    /// Wake up at most `max_count` waiting tasks.
+    fn notify(&self, max_count: usize) -> Result<(), NotifyError> {
+        let mut count = 0;
+        self.update_num_waiters(|n| {
+            count = n.min(max_count);
+            n - count
+        })
+        .map_err(|_| NotifyError::BadContext)?;
+
+        if count > 0 {
+            match self.semaphore.signal(count) {
+                Ok(()) => {}
+                Err(SignalSemaphoreError::BadId) => unreachable!(),
+                Err(SignalSemaphoreError::BadContext) => return Err(NotifyError::BadContext),
+                // The semaphore's value never exceeds the number of waiters
+                Err(SignalSemaphoreError::QueueOverflow) => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the waiter count by `f` inside a CPU Lock section.
+    fn update_num_waiters(&self, f: impl FnOnce(usize) -> usize) -> Result<(), CpuLockError> {
+        System::acquire_cpu_lock()?;
+        let num_waiters = &*self.num_waiters;
+        num_waiters.set(f(num_waiters.get()));
+        // Safety: CPU Lock was acquired by this function
+        unsafe { System::release_cpu_lock() }.unwrap();
+        Ok(())
+    }
+}
+
+impl<System: Kernel> fmt::Debug for Condvar<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Condvar")
+            .field("semaphore", &self.semaphore)
+            .finish()
+    }
+}
@@ -0,0 +1,348 @@
+use core::{
+    cell::{Cell, UnsafeCell},
+    fmt,
+    marker::PhantomData,
+    ops,
+};
+
+use crate::{
+    hunk::Hunk,
+    kernel::{
+        self, cfg::CfgBuilder, PollSemaphoreError, QueueOrder, SemaphoreValue, WaitSemaphoreError,
+        WaitSemaphoreTimeoutError,
+    },
+    prelude::*,
+    time::Duration,
+    utils::Init,
+};
+
+/// Configuration builder type for [`MemoryPool`].
+pub struct Builder<System, T, const N: usize> {
+    _phantom: PhantomData<(System, T)>,
+    queue_order: QueueOrder,
+}
+
+/// A pool of `N` fixed-size blocks, each storing a value of type `T`.
+///
+/// The blocks are allocated from a [hunk] and initialized with [`Init`] during
+/// the boot process. [`allocate`] removes a block from the pool and returns
+/// [`PoolBox`], a handle providing exclusive access to the block, blocking the
+/// current task if all blocks are in use. Dropping `PoolBox` returns the block
+/// to the pool. The block's contents are left intact, so a value written to a
+/// block might be observed by the next owner of the block.
+///
+/// Free blocks are managed by a first-in first-out list, and the number of
+/// free blocks is tracked by a [`r3::kernel::Semaphore`], which the blocking
+/// operations wait on. Therefore, blocked tasks are woken up in the order
+/// specified by [`QueueOrder`].
+///
+/// [hunk]: crate::hunk::Hunk
+/// [`r3::kernel::Semaphore`]: crate::kernel::Semaphore
+/// [`allocate`]: MemoryPool::allocate
+///
+/// # Examples
+///
+/// ```rust
+/// #![feature(const_fn_fn_ptr_basics)]
+/// #![feature(const_mut_refs)]
+/// use r3::{kernel::cfg::CfgBuilder, prelude::*, sync::MemoryPool};
+///
+/// const fn configure<System: Kernel>(
+///     b: &mut CfgBuilder<System>,
+/// ) -> MemoryPool<System, [u8; 64], 4> {
+///     MemoryPool::build().finish(b)
+/// }
+///
+/// fn task_body<System: Kernel>(pool: &MemoryPool<System, [u8; 64], 4>) {
+///     let mut block = pool.allocate().unwrap();
+///     block[0] = 42;
+///     // `block` is returned to the pool here
+/// }
+/// ```
+pub struct MemoryPool<System, T, const N: usize> {
+    /// The storage of the blocks.
+    blocks: Hunk<System, [Block<T>; N]>,
+    /// The list of free blocks. Protected by CPU Lock.
+    state: Hunk<System, PoolState<N>>,
+    /// Holds as many permits as the number of free blocks.
+    free: kernel::Semaphore<System>,
+}
+
+unsafe impl<System: Kernel, T: 'static + Send, const N: usize> Send for MemoryPool<System, T, N> {}
+unsafe impl<System: Kernel, T: 'static + Send, const N: usize> Sync for MemoryPool<System, T, N> {}
+
+struct Block<T>(UnsafeCell<T>);
+
+impl<T: Init> Init for Block<T> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self(Init::INIT);
+}
+
+struct PoolState<const N: usize> {
+    /// The indices of free blocks, forming a ring buffer.
+    free_list: [Cell<usize>; N],
+    /// The position of the oldest entry in `free_list`.
+    head: Cell<usize>,
+    /// The number of entries in `free_list`.
+    len: Cell<usize>,
+}
+
+impl<const N: usize> PoolState<N> {
+    /// Construct a `PoolState` with all blocks being free.
+    fn new() -> Self {
+        let free_list: [Cell<usize>; N] = Init::INIT;
+        for (i, entry) in free_list.iter().enumerate() {
+            entry.set(i);
+        }
+        Self {
+            free_list,
+            head: Cell::new(0),
+            len: Cell::new(N),
+        }
+    }
+}
+
+/// An owning handle to a block allocated from a [`MemoryPool`]. When this
+/// structure is dropped, the block will be returned to the pool.
+///
+/// This structure is created by the [`allocate`], [`try_allocate`], and
+/// [`allocate_timeout`] methods of [`MemoryPool`].
+///
+/// [`allocate`]: MemoryPool::allocate
+/// [`try_allocate`]: MemoryPool::try_allocate
+/// [`allocate_timeout`]: MemoryPool::allocate_timeout
+#[must_use = "if unused the block will immediately be returned to the pool"]
+pub struct PoolBox<'a, System: Kernel, T: 'static, const N: usize> {
+    pool: &'a MemoryPool<System, T, N>,
+    index: usize,
+}
+
+unsafe impl<System: Kernel, T: 'static + Sync, const N: usize> Sync for PoolBox<'_, System, T, N> {}
+
+/// Error type of [`MemoryPool::allocate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum AllocError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = WaitSemaphoreError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = WaitSemaphoreError::Interrupted as i8,
+}
+
+/// Error type of [`MemoryPool::try_allocate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum TryAllocError {
+    /// CPU Lock is active.
+    BadContext = PollSemaphoreError::BadContext as i8,
+    /// All blocks are in use.
+    WouldBlock = PollSemaphoreError::Timeout as i8,
+}
+
+/// Error type of [`MemoryPool::allocate_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum AllocTimeoutError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = WaitSemaphoreTimeoutError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = WaitSemaphoreTimeoutError::Interrupted as i8,
+    /// All blocks remained in use until the timeout expired.
+    Timeout = WaitSemaphoreTimeoutError::Timeout as i8,
+    /// The timeout duration is negative.
+    BadParam = WaitSemaphoreTimeoutError::BadParam as i8,
+}
+
+impl<System: Kernel, T: 'static + Init, const N: usize> MemoryPool<System, T, N> {
+    /// Construct a `Builder` to define a memory pool in [a configuration
+    /// function](crate#static-configuration).
+    pub const fn build() -> Builder<System, T, N> {
+        Builder {
+            _phantom: PhantomData,
+            queue_order: QueueOrder::TaskPriority,
+        }
+    }
+}
+
+impl<System: Kernel, T: 'static + Init, const N: usize> Builder<System, T, N> {
+    /// Specify how tasks are sorted in the wait queue of the memory pool.
+    /// Defaults to [`QueueOrder::TaskPriority`] when unspecified.
+    pub const fn queue_order(self, queue_order: QueueOrder) -> Self {
+        Self {
+            queue_order,
+            ..self
+        }
+    }
+
+    /// Complete the definition of a memory pool, returning a reference to the
+    /// memory pool.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> MemoryPool<System, T, N> {
+        assert!(N > 0, "`N` must be non-zero");
+
+        MemoryPool {
+            blocks: Hunk::build().finish(cfg),
+            state: Hunk::build().init_with(PoolState::new).finish(cfg),
+            free: kernel::Semaphore::build()
+                .initial(N as SemaphoreValue)
+                .maximum(N as SemaphoreValue)
+                .queue_order(self.queue_order)
+                .finish(cfg),
+        }
+    }
+}
+
+impl<System: Kernel, T: 'static, const N: usize> MemoryPool<System, T, N> {
+    /// Allocate a block, blocking the current task until a free block is
+    /// available.
+    pub fn allocate(&self) -> Result<PoolBox<'_, System, T, N>, AllocError> {
+        match self.free.wait_one() {
+            Ok(()) => {}
+            Err(WaitSemaphoreError::BadId) => unreachable!(),
+            Err(WaitSemaphoreError::BadContext) => return Err(AllocError::BadContext),
+            Err(WaitSemaphoreError::Interrupted) => return Err(AllocError::Interrupted),
+        }
+
+        // Safety: We took a permit from `free`
+        Ok(unsafe { self.take_free_block() })
+    }
+
+    /// Attempt to allocate a block without blocking.
+    ///
+    /// Unlike `allocate`, this method can be called in any context where CPU
+    /// Lock is inactive, including an interrupt context.
+    pub fn try_allocate(&self) -> Result<PoolBox<'_, System, T, N>, TryAllocError> {
+        match self.free.poll_one() {
+            Ok(()) => {}
+            Err(PollSemaphoreError::BadId) => unreachable!(),
+            Err(PollSemaphoreError::BadContext) => return Err(TryAllocError::BadContext),
+            Err(PollSemaphoreError::Timeout) => return Err(TryAllocError::WouldBlock),
+        }
+
+        // Safety: We took a permit from `free`
+        Ok(unsafe { self.take_free_block() })
+    }
+
+    /// [`allocate`](Self::allocate) with timeout.
+    pub fn allocate_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<PoolBox<'_, System, T, N>, AllocTimeoutError> {
+        match self.free.wait_one_timeout(timeout) {
+            Ok(()) => {}
+            Err(WaitSemaphoreTimeoutError::BadId) => unreachable!(),
+            Err(WaitSemaphoreTimeoutError::BadContext) => {
+                return Err(AllocTimeoutError::BadContext)
+            }
+            Err(WaitSemaphoreTimeoutError::Interrupted) => {
+                return Err(AllocTimeoutError::Interrupted)
+            }
+            Err(WaitSemaphoreTimeoutError::Timeout) => return Err(AllocTimeoutError::Timeout),
+            Err(WaitSemaphoreTimeoutError::BadParam) => return Err(AllocTimeoutError::BadParam),
+        }
+
+        // Safety: We took a permit from `free`
+        Ok(unsafe { self.take_free_block() })
+    }
+
+    /// Remove the oldest entry from the free list.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have taken a permit from `self.free`, which guarantees
+    /// the existence of a free block.
+    unsafe fn take_free_block(&self) -> PoolBox<'_, System, T, N> {
+        // The caller has checked that CPU Lock is inactive
+        System::acquire_cpu_lock().unwrap();
+
+        let state = &*self.state;
+        let head = state.head.get();
+        debug_assert!(state.len.get() > 0);
+        let index = state.free_list[head].get();
+        state.head.set((head + 1) % N);
+        state.len.set(state.len.get() - 1);
+
+        // Safety: CPU Lock was acquired by this method
+        unsafe { System::release_cpu_lock() }.unwrap();
+
+        PoolBox { pool: self, index }
+    }
+
+    /// Append the specified block to the free list and make it available to
+    /// other tasks.
+    ///
+    /// # Safety
+    ///
+    /// The block must have been allocated and must not be accessed anymore.
+    unsafe fn return_block(&self, index: usize) {
+        // This will panic if CPU Lock is active
+        System::acquire_cpu_lock().unwrap();
+
+        let state = &*self.state;
+        debug_assert!(state.len.get() < N);
+        state.free_list[(state.head.get() + state.len.get()) % N].set(index);
+        state.len.set(state.len.get() + 1);
+
+        // Safety: CPU Lock was acquired by this method
+        unsafe { System::release_cpu_lock() }.unwrap();
+
+        // `QueueOverflow` is impossible because the number of free blocks
+        // never exceeds `N`
+        self.free.signal_one().unwrap();
+    }
+}
+
+impl<System: Kernel, T: 'static, const N: usize> fmt::Debug for MemoryPool<System, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MemoryPool")
+            .field("capacity", &N)
+            .field("free", &self.free)
+            .finish()
+    }
+}
+
+impl<System: Kernel, T: 'static, const N: usize> ops::Deref for PoolBox<'_, System, T, N> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // Safety: `self` owns the block
+        unsafe { &*self.pool.blocks[self.index].0.get() }
+    }
+}
+
+impl<System: Kernel, T: 'static, const N: usize> ops::DerefMut for PoolBox<'_, System, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: `self` owns the block
+        unsafe { &mut *self.pool.blocks[self.index].0.get() }
+    }
+}
+
+impl<System: Kernel, T: 'static + fmt::Debug, const N: usize> fmt::Debug
+    for PoolBox<'_, System, T, N>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PoolBox")
+            .field("index", &self.index)
+            .field("value", &&**self)
+            .finish()
+    }
+}
+
+/// The destructor of `PoolBox` that returns the block to the pool. It will
+/// panic if CPU Lock is active.
+impl<System: Kernel, T: 'static, const N: usize> Drop for PoolBox<'_, System, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: `self` owns the block, which won't be accessed anymore
+        unsafe { self.pool.return_block(self.index) };
+    }
+}
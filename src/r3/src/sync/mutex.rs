@@ -52,7 +52,7 @@ unsafe impl<System: Kernel, T: 'static + Send> Sync for Mutex<System, T> {}
 /// [`try_lock`]: Mutex::try_lock
 #[must_use = "if unused the Mutex will immediately unlock"]
 pub struct MutexGuard<'a, System: Kernel, T: 'static> {
-    mutex: &'a Mutex<System, T>,
+    pub(super) mutex: &'a Mutex<System, T>,
     _no_send_sync: PhantomData<*mut ()>,
 }
 
@@ -0,0 +1,441 @@
+use core::{
+    cell::{Cell, UnsafeCell},
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+};
+
+use crate::{
+    hunk::Hunk,
+    kernel::{
+        self, cfg::CfgBuilder, PollSemaphoreError, QueueOrder, SemaphoreValue, WaitSemaphoreError,
+        WaitSemaphoreTimeoutError,
+    },
+    prelude::*,
+    time::Duration,
+    utils::{Init, ZeroInit},
+};
+
+/// Configuration builder type for [`Queue`].
+pub struct Builder<System, T> {
+    _phantom: PhantomData<(System, T)>,
+    capacity: Option<usize>,
+    queue_order: QueueOrder,
+}
+
+/// A fixed-capacity first-in first-out queue for passing values between
+/// tasks (a *message queue* or *mailbox*).
+///
+/// The values are stored in a ring buffer allocated from a [hunk]. The number
+/// of free slots and the number of stored values are tracked by two
+/// [`r3::kernel::Semaphore`]s, which the blocking operations wait on.
+/// Therefore, blocked senders and receivers are woken up in the order
+/// specified by [`QueueOrder`].
+///
+/// The non-blocking operations ([`try_send`] and [`try_recv`]) can be used in
+/// an interrupt context.
+///
+/// [hunk]: crate::hunk::Hunk
+/// [`r3::kernel::Semaphore`]: crate::kernel::Semaphore
+/// [`try_send`]: Queue::try_send
+/// [`try_recv`]: Queue::try_recv
+///
+/// # Examples
+///
+/// ```rust
+/// #![feature(const_fn_fn_ptr_basics)]
+/// #![feature(const_mut_refs)]
+/// use r3::{kernel::cfg::CfgBuilder, prelude::*, sync::Queue};
+///
+/// const fn configure<System: Kernel>(b: &mut CfgBuilder<System>) -> Queue<System, u32> {
+///     Queue::build().capacity(4).finish(b)
+/// }
+///
+/// fn producer<System: Kernel>(queue: &Queue<System, u32>) {
+///     for i in 0..8 {
+///         queue.send(i).unwrap();
+///     }
+/// }
+///
+/// fn consumer<System: Kernel>(queue: &Queue<System, u32>) {
+///     for i in 0..8 {
+///         assert_eq!(queue.recv().unwrap(), i);
+///     }
+/// }
+/// ```
+pub struct Queue<System, T> {
+    /// The ring buffer.
+    slots: Hunk<System, [Slot<T>]>,
+    /// The position of the ring buffer. Protected by CPU Lock.
+    state: Hunk<System, QueueState>,
+    /// Holds as many permits as the number of free slots.
+    free: kernel::Semaphore<System>,
+    /// Holds as many permits as the number of stored values.
+    filled: kernel::Semaphore<System>,
+}
+
+unsafe impl<System: Kernel, T: 'static + Send> Send for Queue<System, T> {}
+unsafe impl<System: Kernel, T: 'static + Send> Sync for Queue<System, T> {}
+
+struct Slot<T>(UnsafeCell<MaybeUninit<T>>);
+
+// Safety: `MaybeUninit` doesn't require its contents to be valid
+unsafe impl<T> ZeroInit for Slot<T> {}
+
+struct QueueState {
+    /// The index of the oldest value.
+    head: Cell<usize>,
+    /// The number of stored values.
+    len: Cell<usize>,
+}
+
+impl Init for QueueState {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        head: Cell::new(0),
+        len: Cell::new(0),
+    };
+}
+
+/// Error type of [`Queue::send`]. The value being sent is returned back.
+#[repr(i8)]
+pub enum SendError<T> {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext(T) = WaitSemaphoreError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted(T) = WaitSemaphoreError::Interrupted as i8,
+}
+
+/// Error type of [`Queue::try_send`]. The value being sent is returned back.
+#[repr(i8)]
+pub enum TrySendError<T> {
+    /// CPU Lock is active.
+    BadContext(T) = PollSemaphoreError::BadContext as i8,
+    /// The queue is full.
+    Full(T) = PollSemaphoreError::Timeout as i8,
+}
+
+/// Error type of [`Queue::send_timeout`]. The value being sent is returned
+/// back.
+#[repr(i8)]
+pub enum SendTimeoutError<T> {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext(T) = WaitSemaphoreTimeoutError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted(T) = WaitSemaphoreTimeoutError::Interrupted as i8,
+    /// The queue remained full until the timeout expired.
+    Timeout(T) = WaitSemaphoreTimeoutError::Timeout as i8,
+    /// The timeout duration is negative.
+    BadParam(T) = WaitSemaphoreTimeoutError::BadParam as i8,
+}
+
+/// Error type of [`Queue::recv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum RecvError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = WaitSemaphoreError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = WaitSemaphoreError::Interrupted as i8,
+}
+
+/// Error type of [`Queue::try_recv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum TryRecvError {
+    /// CPU Lock is active.
+    BadContext = PollSemaphoreError::BadContext as i8,
+    /// The queue is empty.
+    Empty = PollSemaphoreError::Timeout as i8,
+}
+
+/// Error type of [`Queue::recv_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum RecvTimeoutError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = WaitSemaphoreTimeoutError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = WaitSemaphoreTimeoutError::Interrupted as i8,
+    /// The queue remained empty until the timeout expired.
+    Timeout = WaitSemaphoreTimeoutError::Timeout as i8,
+    /// The timeout duration is negative.
+    BadParam = WaitSemaphoreTimeoutError::BadParam as i8,
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BadContext(_) => "BadContext",
+            Self::Interrupted(_) => "Interrupted",
+        })
+    }
+}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BadContext(_) => "BadContext",
+            Self::Full(_) => "Full",
+        })
+    }
+}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::BadContext(_) => "BadContext",
+            Self::Interrupted(_) => "Interrupted",
+            Self::Timeout(_) => "Timeout",
+            Self::BadParam(_) => "BadParam",
+        })
+    }
+}
+
+impl<System: Kernel, T: 'static> Queue<System, T> {
+    /// Construct a `Builder` to define a queue in [a configuration
+    /// function](crate#static-configuration).
+    pub const fn build() -> Builder<System, T> {
+        Builder {
+            _phantom: PhantomData,
+            capacity: None,
+            queue_order: QueueOrder::TaskPriority,
+        }
+    }
+}
+
+impl<System: Kernel, T: 'static> Builder<System, T> {
+    /// \[**Required**\] Specify the maximum number of values the queue can
+    /// hold. Must be non-zero.
+    pub const fn capacity(self, capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..self
+        }
+    }
+
+    /// Specify how tasks are sorted in the wait queues of the queue.
+    /// Defaults to [`QueueOrder::TaskPriority`] when unspecified.
+    pub const fn queue_order(self, queue_order: QueueOrder) -> Self {
+        Self {
+            queue_order,
+            ..self
+        }
+    }
+
+    /// Complete the definition of a queue, returning a reference to the
+    /// queue.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> Queue<System, T> {
+        // FIXME: Work-around for `Option::expect` being not `const fn`
+        let capacity = if let Some(x) = self.capacity {
+            x
+        } else {
+            panic!("`capacity` is not specified")
+        };
+        assert!(capacity > 0, "`capacity` must be non-zero");
+
+        Queue {
+            slots: Hunk::build().len(capacity).zeroed().finish(cfg),
+            state: Hunk::build().finish(cfg),
+            free: kernel::Semaphore::build()
+                .initial(capacity as SemaphoreValue)
+                .maximum(capacity as SemaphoreValue)
+                .queue_order(self.queue_order)
+                .finish(cfg),
+            filled: kernel::Semaphore::build()
+                .initial(0)
+                .maximum(capacity as SemaphoreValue)
+                .queue_order(self.queue_order)
+                .finish(cfg),
+        }
+    }
+}
+
+impl<System: Kernel, T: 'static> Queue<System, T> {
+    /// Send a value to the queue, blocking the current task until a free slot
+    /// is available.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        match self.free.wait_one() {
+            Ok(()) => {}
+            Err(WaitSemaphoreError::BadId) => unreachable!(),
+            Err(WaitSemaphoreError::BadContext) => return Err(SendError::BadContext(value)),
+            Err(WaitSemaphoreError::Interrupted) => return Err(SendError::Interrupted(value)),
+        }
+
+        // Safety: We took a permit from `free`
+        unsafe { self.push(value) };
+        Ok(())
+    }
+
+    /// Attempt to send a value to the queue without blocking.
+    ///
+    /// Unlike `send`, this method can be called in any context where CPU
+    /// Lock is inactive, including an interrupt context.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        match self.free.poll_one() {
+            Ok(()) => {}
+            Err(PollSemaphoreError::BadId) => unreachable!(),
+            Err(PollSemaphoreError::BadContext) => return Err(TrySendError::BadContext(value)),
+            Err(PollSemaphoreError::Timeout) => return Err(TrySendError::Full(value)),
+        }
+
+        // Safety: We took a permit from `free`
+        unsafe { self.push(value) };
+        Ok(())
+    }
+
+    /// [`send`](Self::send) with timeout.
+    pub fn send_timeout(&self, value: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        match self.free.wait_one_timeout(timeout) {
+            Ok(()) => {}
+            Err(WaitSemaphoreTimeoutError::BadId) => unreachable!(),
+            Err(WaitSemaphoreTimeoutError::BadContext) => {
+                return Err(SendTimeoutError::BadContext(value))
+            }
+            Err(WaitSemaphoreTimeoutError::Interrupted) => {
+                return Err(SendTimeoutError::Interrupted(value))
+            }
+            Err(WaitSemaphoreTimeoutError::Timeout) => {
+                return Err(SendTimeoutError::Timeout(value))
+            }
+            Err(WaitSemaphoreTimeoutError::BadParam) => {
+                return Err(SendTimeoutError::BadParam(value))
+            }
+        }
+
+        // Safety: We took a permit from `free`
+        unsafe { self.push(value) };
+        Ok(())
+    }
+
+    /// Receive the oldest value from the queue, blocking the current task
+    /// until a value is available.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        match self.filled.wait_one() {
+            Ok(()) => {}
+            Err(WaitSemaphoreError::BadId) => unreachable!(),
+            Err(WaitSemaphoreError::BadContext) => return Err(RecvError::BadContext),
+            Err(WaitSemaphoreError::Interrupted) => return Err(RecvError::Interrupted),
+        }
+
+        // Safety: We took a permit from `filled`
+        Ok(unsafe { self.pop() })
+    }
+
+    /// Attempt to receive the oldest value from the queue without blocking.
+    ///
+    /// Unlike `recv`, this method can be called in any context where CPU
+    /// Lock is inactive, including an interrupt context.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match self.filled.poll_one() {
+            Ok(()) => {}
+            Err(PollSemaphoreError::BadId) => unreachable!(),
+            Err(PollSemaphoreError::BadContext) => return Err(TryRecvError::BadContext),
+            Err(PollSemaphoreError::Timeout) => return Err(TryRecvError::Empty),
+        }
+
+        // Safety: We took a permit from `filled`
+        Ok(unsafe { self.pop() })
+    }
+
+    /// [`recv`](Self::recv) with timeout.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match self.filled.wait_one_timeout(timeout) {
+            Ok(()) => {}
+            Err(WaitSemaphoreTimeoutError::BadId) => unreachable!(),
+            Err(WaitSemaphoreTimeoutError::BadContext) => return Err(RecvTimeoutError::BadContext),
+            Err(WaitSemaphoreTimeoutError::Interrupted) => {
+                return Err(RecvTimeoutError::Interrupted)
+            }
+            Err(WaitSemaphoreTimeoutError::Timeout) => return Err(RecvTimeoutError::Timeout),
+            Err(WaitSemaphoreTimeoutError::BadParam) => return Err(RecvTimeoutError::BadParam),
+        }
+
+        // Safety: We took a permit from `filled`
+        Ok(unsafe { self.pop() })
+    }
+
+    /// Append `value` to the ring buffer and make it available to receivers.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have taken a permit from `self.free`, which guarantees
+    /// the existence of a free slot.
+    unsafe fn push(&self, value: T) {
+        // The caller has checked that CPU Lock is inactive
+        System::acquire_cpu_lock().unwrap();
+
+        let state = &*self.state;
+        let i = (state.head.get() + state.len.get()) % self.slots.len();
+        debug_assert!(state.len.get() < self.slots.len());
+        state.len.set(state.len.get() + 1);
+
+        // Safety: The slot is free, and no one else can access it while CPU
+        //         Lock is active
+        unsafe { *self.slots[i].0.get() = MaybeUninit::new(value) };
+
+        // Safety: CPU Lock was acquired by this method
+        unsafe { System::release_cpu_lock() }.unwrap();
+
+        // `QueueOverflow` is impossible because the number of stored values
+        // never exceeds the capacity
+        self.filled.signal_one().unwrap();
+    }
+
+    /// Remove the oldest value from the ring buffer and make the slot
+    /// available to senders.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have taken a permit from `self.filled`, which
+    /// guarantees the existence of a stored value.
+    unsafe fn pop(&self) -> T {
+        // The caller has checked that CPU Lock is inactive
+        System::acquire_cpu_lock().unwrap();
+
+        let state = &*self.state;
+        let i = state.head.get();
+        debug_assert!(state.len.get() > 0);
+        state.head.set((i + 1) % self.slots.len());
+        state.len.set(state.len.get() - 1);
+
+        // Safety: The slot contains a value, which we are moving out of the
+        //         slot. No one else can access it while CPU Lock is active.
+        let value = unsafe { (*self.slots[i].0.get()).as_ptr().read() };
+
+        // Safety: CPU Lock was acquired by this method
+        unsafe { System::release_cpu_lock() }.unwrap();
+
+        // `QueueOverflow` is impossible because the number of free slots
+        // never exceeds the capacity
+        self.free.signal_one().unwrap();
+
+        value
+    }
+}
+
+impl<System: Kernel, T: 'static> fmt::Debug for Queue<System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Queue")
+            .field("capacity", &self.slots.len())
+            .field("free", &self.free)
+            .field("filled", &self.filled)
+            .finish()
+    }
+}
@@ -0,0 +1,415 @@
+use core::{cell::UnsafeCell, fmt, marker::PhantomData};
+
+use super::{
+    condvar::{self, Condvar},
+    mutex::{self, Mutex, MutexGuard},
+};
+use crate::{
+    hunk::{CfgHunkBuilder, DefaultInitTag, Hunk, HunkIniter},
+    kernel::{cfg::CfgBuilder, LockMutexError, TryLockMutexError},
+    prelude::*,
+};
+
+/// Configuration builder type for [`RwLock`].
+pub struct Builder<System, T, InitTag> {
+    hunk: CfgHunkBuilder<System, UnsafeCell<T>, InitTag>,
+    policy: RwLockPolicy,
+}
+
+/// A reader-writer lock, which allows multiple readers or at most one writer
+/// at any point in time.
+///
+/// This type is implemented using [`Mutex`] and [`Condvar`]. The mutex
+/// protects the lock state (the number of readers and whether a writer holds
+/// the lock), and the condition variable is used to wait for the lock state to
+/// change. The mutex is only held for a short period of time during a lock or
+/// unlock operation.
+///
+/// Which of readers and writers are given precedence is specified by
+/// [`RwLockPolicy`].
+pub struct RwLock<System, T> {
+    hunk: Hunk<System, UnsafeCell<T>>,
+    state: Mutex<System, RwLockState>,
+    cond: Condvar<System>,
+    policy: RwLockPolicy,
+}
+
+unsafe impl<System: Kernel, T: 'static + Send> Send for RwLock<System, T> {}
+unsafe impl<System: Kernel, T: 'static + Send + Sync> Sync for RwLock<System, T> {}
+
+/// Specifies which of readers and writers are given precedence by
+/// [`RwLock`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RwLockPolicy {
+    /// A reader can acquire the lock as long as no writer holds the lock, even
+    /// if there are writers waiting for the lock. Writers might starve if
+    /// readers keep holding the lock.
+    ReaderPreferring,
+    /// A reader can't acquire the lock while there are writers waiting for the
+    /// lock. Readers might starve if writers keep requesting the lock.
+    ///
+    /// Because of this, a task that already holds a read lock should not try
+    /// to acquire another read lock, which might cause a deadlock.
+    WriterPreferring,
+}
+
+/// The lock state of [`RwLock`].
+struct RwLockState {
+    /// The number of readers holding the lock.
+    num_readers: usize,
+    /// The number of writers waiting for the lock.
+    num_waiting_writers: usize,
+    /// `true` if a writer holds the lock.
+    writer: bool,
+}
+
+impl Init for RwLockState {
+    const INIT: Self = Self {
+        num_readers: 0,
+        num_waiting_writers: 0,
+        writer: false,
+    };
+}
+
+/// RAII structure used to release the shared read access of a lock when
+/// dropped.
+///
+/// This structure is created by the [`read`] and [`try_read`] methods of
+/// [`RwLock`].
+///
+/// [`read`]: RwLock::read
+/// [`try_read`]: RwLock::try_read
+#[must_use = "if unused the RwLock will immediately unlock"]
+pub struct RwLockReadGuard<'a, System: Kernel, T: 'static> {
+    lock: &'a RwLock<System, T>,
+    _no_send_sync: PhantomData<*mut ()>,
+}
+
+unsafe impl<System: Kernel, T: 'static + Sync> Sync for RwLockReadGuard<'_, System, T> {}
+
+/// RAII structure used to release the exclusive write access of a lock when
+/// dropped.
+///
+/// This structure is created by the [`write`] and [`try_write`] methods of
+/// [`RwLock`].
+///
+/// [`write`]: RwLock::write
+/// [`try_write`]: RwLock::try_write
+#[must_use = "if unused the RwLock will immediately unlock"]
+pub struct RwLockWriteGuard<'a, System: Kernel, T: 'static> {
+    lock: &'a RwLock<System, T>,
+    _no_send_sync: PhantomData<*mut ()>,
+}
+
+unsafe impl<System: Kernel, T: 'static + Sync> Sync for RwLockWriteGuard<'_, System, T> {}
+
+/// Error type of [`RwLock::read`] and [`RwLock::write`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum LockError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = LockMutexError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = LockMutexError::Interrupted as i8,
+}
+
+/// Error type of [`RwLock::try_read`] and [`RwLock::try_write`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum TryLockError {
+    /// CPU Lock is active, or the current context is not [a task context].
+    ///
+    /// [a task context]: crate#contexts
+    BadContext = TryLockMutexError::BadContext as i8,
+    /// The lock could not be acquire at this time because the operation would
+    /// otherwise block.
+    WouldBlock = TryLockMutexError::Timeout as i8,
+}
+
+impl<System: Kernel, T: 'static> RwLock<System, T> {
+    /// Construct a `Builder` to define a reader-writer lock in [a
+    /// configuration function](crate#static-configuration).
+    pub const fn build() -> Builder<System, T, DefaultInitTag> {
+        Builder {
+            hunk: Hunk::build(),
+            policy: RwLockPolicy::WriterPreferring,
+        }
+    }
+}
+
+impl<System: Kernel, T: 'static, InitTag> Builder<System, T, InitTag> {
+    /// Specify which of readers and writers are given precedence. Defaults to
+    /// [`RwLockPolicy::WriterPreferring`] when unspecified.
+    pub const fn policy(self, policy: RwLockPolicy) -> Self {
+        Self { policy, ..self }
+    }
+}
+
+impl<System: Kernel, T: 'static, InitTag: HunkIniter<UnsafeCell<T>>> Builder<System, T, InitTag> {
+    /// Complete the definition of a reader-writer lock, returning a reference
+    /// to the reader-writer lock.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> RwLock<System, T> {
+        RwLock {
+            hunk: self.hunk.finish(cfg),
+            state: Mutex::build().finish(cfg),
+            cond: Condvar::build().finish(cfg),
+            policy: self.policy,
+        }
+    }
+}
+
+impl<System: Kernel, T: 'static> RwLock<System, T> {
+    /// Acquire the lock with shared read access, blocking the current task
+    /// until it is able to do so.
+    pub fn read(&self) -> Result<RwLockReadGuard<'_, System, T>, LockError> {
+        let mut state = self.lock_state()?;
+        while !self.can_read(&state) {
+            state = self.wait(state)?;
+        }
+        state.num_readers += 1;
+        Ok(RwLockReadGuard {
+            lock: self,
+            _no_send_sync: PhantomData,
+        })
+    }
+
+    /// Attempt to acquire the lock with shared read access.
+    pub fn try_read(&self) -> Result<RwLockReadGuard<'_, System, T>, TryLockError> {
+        let mut state = self.try_lock_state()?;
+        if !self.can_read(&state) {
+            return Err(TryLockError::WouldBlock);
+        }
+        state.num_readers += 1;
+        Ok(RwLockReadGuard {
+            lock: self,
+            _no_send_sync: PhantomData,
+        })
+    }
+
+    /// Acquire the lock with exclusive write access, blocking the current task
+    /// until it is able to do so.
+    pub fn write(&self) -> Result<RwLockWriteGuard<'_, System, T>, LockError> {
+        let mut state = self.lock_state()?;
+        state.num_waiting_writers += 1;
+        while state.writer || state.num_readers > 0 {
+            state = match self.wait(state) {
+                Ok(state) => state,
+                Err(e) => {
+                    // Readers might have been waiting for this writer to
+                    // give up
+                    let mut state = self.lock_state_uninterruptible();
+                    state.num_waiting_writers -= 1;
+                    drop(state);
+                    self.notify_all();
+                    return Err(e);
+                }
+            };
+        }
+        state.num_waiting_writers -= 1;
+        state.writer = true;
+        Ok(RwLockWriteGuard {
+            lock: self,
+            _no_send_sync: PhantomData,
+        })
+    }
+
+    /// Attempt to acquire the lock with exclusive write access.
+    pub fn try_write(&self) -> Result<RwLockWriteGuard<'_, System, T>, TryLockError> {
+        let mut state = self.try_lock_state()?;
+        if state.writer || state.num_readers > 0 {
+            return Err(TryLockError::WouldBlock);
+        }
+        state.writer = true;
+        Ok(RwLockWriteGuard {
+            lock: self,
+            _no_send_sync: PhantomData,
+        })
+    }
+
+    /// Get a raw pointer to the contained data.
+    #[inline]
+    pub fn get_ptr(&self) -> *mut T {
+        self.hunk.get()
+    }
+
+    fn can_read(&self, state: &RwLockState) -> bool {
+        !state.writer
+            && (self.policy == RwLockPolicy::ReaderPreferring || state.num_waiting_writers == 0)
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<'_, System, RwLockState>, LockError> {
+        match self.state.lock() {
+            Ok(state) => Ok(state),
+            Err(mutex::LockError::BadContext) => Err(LockError::BadContext),
+            Err(mutex::LockError::Interrupted) => Err(LockError::Interrupted),
+            // The internal mutex is never held by user code, and the task
+            // can't exit while holding it
+            Err(
+                mutex::LockError::WouldDeadlock
+                | mutex::LockError::BadParam
+                | mutex::LockError::Abandoned(_),
+            ) => unreachable!(),
+        }
+    }
+
+    fn try_lock_state(&self) -> Result<MutexGuard<'_, System, RwLockState>, TryLockError> {
+        match self.state.try_lock() {
+            Ok(state) => Ok(state),
+            Err(mutex::TryLockError::BadContext) => Err(TryLockError::BadContext),
+            Err(mutex::TryLockError::WouldBlock) => Err(TryLockError::WouldBlock),
+            Err(
+                mutex::TryLockError::WouldDeadlock
+                | mutex::TryLockError::BadParam
+                | mutex::TryLockError::Abandoned(_),
+            ) => unreachable!(),
+        }
+    }
+
+    /// Lock the internal mutex, ignoring [`Task::interrupt`]. This is used to
+    /// release the lock, which must not fail.
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    fn lock_state_uninterruptible(&self) -> MutexGuard<'_, System, RwLockState> {
+        loop {
+            match self.lock_state() {
+                Ok(state) => break state,
+                Err(LockError::Interrupted) => {}
+                Err(LockError::BadContext) => {
+                    panic!("failed to unlock RwLock: CPU Lock is active")
+                }
+            }
+        }
+    }
+
+    fn wait<'a>(
+        &'a self,
+        state: MutexGuard<'a, System, RwLockState>,
+    ) -> Result<MutexGuard<'a, System, RwLockState>, LockError> {
+        match self.cond.wait(state) {
+            Ok(state) => Ok(state),
+            Err(condvar::WaitError::BadContext(_)) => Err(LockError::BadContext),
+            Err(condvar::WaitError::Interrupted(_)) => Err(LockError::Interrupted),
+            Err(condvar::WaitError::Abandoned(_)) => unreachable!(),
+        }
+    }
+
+    fn notify_all(&self) {
+        // `BadContext` is impossible because the internal mutex was held
+        // a moment ago
+        self.cond.notify_all().unwrap();
+    }
+}
+
+impl<System: Kernel, T: fmt::Debug + 'static> fmt::Debug for RwLock<System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.try_read() {
+            Ok(guard) => f.debug_struct("RwLock").field("data", &&*guard).finish(),
+            Err(TryLockError::BadContext) => {
+                struct BadContextPlaceholder;
+                impl fmt::Debug for BadContextPlaceholder {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("<bad context>")
+                    }
+                }
+
+                f.debug_struct("RwLock")
+                    .field("data", &BadContextPlaceholder)
+                    .finish()
+            }
+            Err(TryLockError::WouldBlock) => {
+                struct LockedPlaceholder;
+                impl fmt::Debug for LockedPlaceholder {
+                    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        f.write_str("<locked>")
+                    }
+                }
+
+                f.debug_struct("RwLock")
+                    .field("data", &LockedPlaceholder)
+                    .finish()
+            }
+        }
+    }
+}
+
+impl<System: Kernel, T: fmt::Debug + 'static> fmt::Debug for RwLockReadGuard<'_, System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<System: Kernel, T: fmt::Display + 'static> fmt::Display for RwLockReadGuard<'_, System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<System: Kernel, T: fmt::Debug + 'static> fmt::Debug for RwLockWriteGuard<'_, System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<System: Kernel, T: fmt::Display + 'static> fmt::Display for RwLockWriteGuard<'_, System, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// The destructor of `RwLockReadGuard` that releases the lock. It will panic
+/// if CPU Lock is active.
+impl<System: Kernel, T: 'static> Drop for RwLockReadGuard<'_, System, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.lock_state_uninterruptible();
+        state.num_readers -= 1;
+        let wake_writers = state.num_readers == 0 && state.num_waiting_writers > 0;
+        drop(state);
+
+        if wake_writers {
+            self.lock.notify_all();
+        }
+    }
+}
+
+/// The destructor of `RwLockWriteGuard` that releases the lock. It will panic
+/// if CPU Lock is active.
+impl<System: Kernel, T: 'static> Drop for RwLockWriteGuard<'_, System, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.lock_state_uninterruptible();
+        state.writer = false;
+        drop(state);
+
+        self.lock.notify_all();
+    }
+}
+
+impl<System: Kernel, T: 'static> core::ops::Deref for RwLockReadGuard<'_, System, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // Safety: `RwLockReadGuard` represents a shared read access, which
+        //         excludes writers
+        unsafe { &*self.lock.hunk.get() }
+    }
+}
+
+impl<System: Kernel, T: 'static> core::ops::Deref for RwLockWriteGuard<'_, System, T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // Safety: `RwLockWriteGuard` represents an exclusive write access
+        unsafe { &*self.lock.hunk.get() }
+    }
+}
+
+impl<System: Kernel, T: 'static> core::ops::DerefMut for RwLockWriteGuard<'_, System, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: `RwLockWriteGuard` represents an exclusive write access
+        unsafe { &mut *self.lock.hunk.get() }
+    }
+}
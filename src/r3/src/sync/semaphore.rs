@@ -0,0 +1,217 @@
+use core::fmt;
+
+use crate::{
+    kernel::{
+        self,
+        cfg::{CfgBuilder, CfgSemaphoreBuilder},
+        GetSemaphoreError, PollSemaphoreError, QueueOrder, SemaphoreValue, WaitSemaphoreError,
+        WaitSemaphoreTimeoutError,
+    },
+    prelude::*,
+    time::Duration,
+};
+
+/// Configuration builder type for [`Semaphore`].
+pub struct Builder<System> {
+    semaphore: CfgSemaphoreBuilder<System>,
+}
+
+/// A counting semaphore useful for limiting concurrent access to a pool of
+/// resources.
+///
+/// This type is implemented using [`r3::kernel::Semaphore`], the low-level
+/// synchronization primitive. Unlike the low-level one, a permit acquired from
+/// this type is represented by [`SemaphoreGuard`], which releases the permit
+/// when dropped. This makes it impossible to forget to release a permit or to
+/// release a permit that wasn't acquired.
+///
+/// [`r3::kernel::Semaphore`]: crate::kernel::Semaphore
+pub struct Semaphore<System> {
+    semaphore: kernel::Semaphore<System>,
+}
+
+/// An RAII implementation of a permit acquired from a semaphore. When this
+/// structure is dropped, the permit will be released back to the semaphore.
+///
+/// This structure is created by the [`acquire`], [`try_acquire`], and
+/// [`acquire_timeout`] methods of [`Semaphore`].
+///
+/// [`acquire`]: Semaphore::acquire
+/// [`try_acquire`]: Semaphore::try_acquire
+/// [`acquire_timeout`]: Semaphore::acquire_timeout
+#[must_use = "if unused the permit will immediately be released"]
+pub struct SemaphoreGuard<'a, System: Kernel> {
+    semaphore: &'a Semaphore<System>,
+}
+
+/// Error type of [`Semaphore::acquire`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum AcquireError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = WaitSemaphoreError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = WaitSemaphoreError::Interrupted as i8,
+}
+
+/// Error type of [`Semaphore::try_acquire`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum TryAcquireError {
+    /// CPU Lock is active.
+    BadContext = PollSemaphoreError::BadContext as i8,
+    /// No permits are available at this time.
+    WouldBlock = PollSemaphoreError::Timeout as i8,
+}
+
+/// Error type of [`Semaphore::acquire_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum AcquireTimeoutError {
+    /// CPU Lock is active, or the current context is not [waitable].
+    ///
+    /// [waitable]: crate#contexts
+    BadContext = WaitSemaphoreTimeoutError::BadContext as i8,
+    /// The wait operation was interrupted by [`Task::interrupt`].
+    ///
+    /// [`Task::interrupt`]: crate::kernel::Task::interrupt
+    Interrupted = WaitSemaphoreTimeoutError::Interrupted as i8,
+    /// No permits became available before the timeout expired.
+    Timeout = WaitSemaphoreTimeoutError::Timeout as i8,
+    /// The timeout duration is negative.
+    BadParam = WaitSemaphoreTimeoutError::BadParam as i8,
+}
+
+/// Error type of [`Semaphore::available_permits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i8)]
+pub enum AvailablePermitsError {
+    /// CPU Lock is active.
+    BadContext = GetSemaphoreError::BadContext as i8,
+}
+
+impl<System: Kernel> Semaphore<System> {
+    /// Construct a `Builder` to define a semaphore in [a configuration
+    /// function](crate#static-configuration).
+    pub const fn build() -> Builder<System> {
+        Builder {
+            semaphore: kernel::Semaphore::build(),
+        }
+    }
+}
+
+impl<System: Kernel> Builder<System> {
+    /// \[**Required**\] Specify the number of permits initially held by the
+    /// semaphore.
+    ///
+    /// Must be less than or equal to [`maximum`](Self::maximum).
+    pub const fn initial(self, initial: SemaphoreValue) -> Self {
+        Self {
+            semaphore: self.semaphore.initial(initial),
+        }
+    }
+
+    /// \[**Required**\] Specify the maximum number of permits held by the
+    /// semaphore. This is usually equal to the initial value.
+    pub const fn maximum(self, maximum: SemaphoreValue) -> Self {
+        Self {
+            semaphore: self.semaphore.maximum(maximum),
+        }
+    }
+
+    /// Specify how tasks are sorted in the wait queue of the semaphore.
+    /// Defaults to [`QueueOrder::TaskPriority`] when unspecified.
+    pub const fn queue_order(self, queue_order: QueueOrder) -> Self {
+        Self {
+            semaphore: self.semaphore.queue_order(queue_order),
+        }
+    }
+
+    /// Complete the definition of a semaphore, returning a reference to the
+    /// semaphore.
+    pub const fn finish(self, cfg: &mut CfgBuilder<System>) -> Semaphore<System> {
+        Semaphore {
+            semaphore: self.semaphore.finish(cfg),
+        }
+    }
+}
+
+impl<System: Kernel> Semaphore<System> {
+    /// Acquire a permit, blocking the current task until one is available.
+    pub fn acquire(&self) -> Result<SemaphoreGuard<'_, System>, AcquireError> {
+        match self.semaphore.wait_one() {
+            Ok(()) => Ok(SemaphoreGuard { semaphore: self }),
+            Err(WaitSemaphoreError::BadId) => unreachable!(),
+            Err(WaitSemaphoreError::BadContext) => Err(AcquireError::BadContext),
+            Err(WaitSemaphoreError::Interrupted) => Err(AcquireError::Interrupted),
+        }
+    }
+
+    /// Attempt to acquire a permit without blocking.
+    ///
+    /// Unlike `acquire`, this method can be called in any context where CPU
+    /// Lock is inactive, including an interrupt context.
+    pub fn try_acquire(&self) -> Result<SemaphoreGuard<'_, System>, TryAcquireError> {
+        match self.semaphore.poll_one() {
+            Ok(()) => Ok(SemaphoreGuard { semaphore: self }),
+            Err(PollSemaphoreError::BadId) => unreachable!(),
+            Err(PollSemaphoreError::BadContext) => Err(TryAcquireError::BadContext),
+            Err(PollSemaphoreError::Timeout) => Err(TryAcquireError::WouldBlock),
+        }
+    }
+
+    /// [`acquire`](Self::acquire) with timeout.
+    pub fn acquire_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<SemaphoreGuard<'_, System>, AcquireTimeoutError> {
+        match self.semaphore.wait_one_timeout(timeout) {
+            Ok(()) => Ok(SemaphoreGuard { semaphore: self }),
+            Err(WaitSemaphoreTimeoutError::BadId) => unreachable!(),
+            Err(WaitSemaphoreTimeoutError::BadContext) => Err(AcquireTimeoutError::BadContext),
+            Err(WaitSemaphoreTimeoutError::Interrupted) => Err(AcquireTimeoutError::Interrupted),
+            Err(WaitSemaphoreTimeoutError::Timeout) => Err(AcquireTimeoutError::Timeout),
+            Err(WaitSemaphoreTimeoutError::BadParam) => Err(AcquireTimeoutError::BadParam),
+        }
+    }
+
+    /// Get the number of permits currently available.
+    pub fn available_permits(&self) -> Result<SemaphoreValue, AvailablePermitsError> {
+        self.semaphore.get().map_err(|e| match e {
+            GetSemaphoreError::BadId => unreachable!(),
+            GetSemaphoreError::BadContext => AvailablePermitsError::BadContext,
+        })
+    }
+}
+
+impl<System: Kernel> fmt::Debug for Semaphore<System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("semaphore", &self.semaphore)
+            .field("available_permits", &self.available_permits())
+            .finish()
+    }
+}
+
+impl<System: Kernel> fmt::Debug for SemaphoreGuard<'_, System> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SemaphoreGuard")
+            .field("semaphore", &self.semaphore.semaphore)
+            .finish()
+    }
+}
+
+/// The destructor of `SemaphoreGuard` that releases the permit. It will panic
+/// if CPU Lock is active.
+impl<System: Kernel> Drop for SemaphoreGuard<'_, System> {
+    #[inline]
+    fn drop(&mut self) {
+        // `QueueOverflow` is impossible because the permit was taken from the
+        // semaphore
+        self.semaphore.semaphore.signal_one().unwrap();
+    }
+}
@@ -9,6 +9,11 @@ use crate::utils::{Init, ZeroInit};
 ///
 /// `Duration` is backed by `i32` and can represent the range
 /// [-35′47.483648″, +35′47.483647″] with microsecond precision.
+///
+/// The arithmetic operators (`+`, `-`, `*`, `/`) are checked and panic on
+/// overflow regardless of whether debug assertions are enabled. Use the
+/// `checked_*` or `saturating_*` methods if overflow is a possibility that
+/// should be handled.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Duration {
@@ -194,11 +199,84 @@ impl Duration {
             None
         }
     }
+
+    /// Add the specified value to `self`, saturating at the numeric bounds
+    /// instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use r3::time::Duration;
+    ///
+    /// assert_eq!(
+    ///     Duration::from_secs(1).saturating_add(Duration::from_secs(2)),
+    ///     Duration::from_secs(3),
+    /// );
+    /// assert_eq!(
+    ///     Duration::MAX.saturating_add(Duration::from_micros(1)),
+    ///     Duration::MAX,
+    /// );
+    /// assert_eq!(
+    ///     Duration::MIN.saturating_add(Duration::from_micros(-1)),
+    ///     Duration::MIN,
+    /// );
+    /// ```
+    #[inline]
+    pub const fn saturating_add(self, other: Self) -> Self {
+        Self::from_micros(self.micros.saturating_add(other.micros))
+    }
+
+    /// Subtract the specified value from `self`, saturating at the numeric
+    /// bounds instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use r3::time::Duration;
+    ///
+    /// assert_eq!(
+    ///     Duration::from_secs(1).saturating_sub(Duration::from_secs(2)),
+    ///     Duration::from_secs(-1),
+    /// );
+    /// assert_eq!(
+    ///     Duration::MIN.saturating_sub(Duration::from_micros(1)),
+    ///     Duration::MIN,
+    /// );
+    /// assert_eq!(
+    ///     Duration::ZERO.saturating_sub(Duration::MIN),
+    ///     Duration::MAX,
+    /// );
+    /// ```
+    #[inline]
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self::from_micros(self.micros.saturating_sub(other.micros))
+    }
+
+    /// Multiply `self` by the specified value, saturating at the numeric
+    /// bounds instead of overflowing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use r3::time::Duration;
+    ///
+    /// assert_eq!(
+    ///     Duration::from_secs(2).saturating_mul(3),
+    ///     Duration::from_secs(6),
+    /// );
+    /// assert_eq!(Duration::from_secs(60).saturating_mul(60), Duration::MAX);
+    /// assert_eq!(Duration::from_secs(60).saturating_mul(-60), Duration::MIN);
+    /// assert_eq!(Duration::MIN.saturating_mul(-1), Duration::MAX);
+    /// ```
+    #[inline]
+    pub const fn saturating_mul(self, other: i32) -> Self {
+        Self::from_micros(self.micros.saturating_mul(other))
+    }
 }
 
 /// Error type returned when a checked duration type conversion fails.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct TryFromDurationError(());
+pub struct TryFromDurationError(pub(super) ());
 
 impl TryFrom<core::time::Duration> for Duration {
     type Error = TryFromDurationError;
@@ -207,7 +285,23 @@ impl TryFrom<core::time::Duration> for Duration {
     /// Returns an error if the specified `Duration` overflows the representable
     /// range of the destination type.
     ///
-    /// The sub-microsecond part is rounded by truncating.
+    /// The sub-microsecond part is rounded by truncating (i.e., toward zero).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{convert::TryFrom, time::Duration as CoreDuration};
+    /// use r3::time::Duration;
+    /// assert_eq!(
+    ///     Duration::try_from(CoreDuration::from_nanos(123_456_999)),
+    ///     Ok(Duration::from_micros(123_456)),
+    /// );
+    /// assert_eq!(
+    ///     Duration::try_from(CoreDuration::from_micros(0x7fff_ffff)),
+    ///     Ok(Duration::MAX),
+    /// );
+    /// assert!(Duration::try_from(CoreDuration::from_micros(0x8000_0000)).is_err());
+    /// ```
     fn try_from(value: core::time::Duration) -> Result<Self, Self::Error> {
         Ok(Self::from_micros(
             value
@@ -224,6 +318,20 @@ impl TryFrom<Duration> for core::time::Duration {
     /// Try to construct a `core::time::Duration` from the specified `Duration`.
     /// Returns an error if the specified `Duration` represents a negative time
     /// span.
+    ///
+    /// This conversion is lossless if it succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{convert::TryFrom, time::Duration as CoreDuration};
+    /// use r3::time::Duration;
+    /// assert_eq!(
+    ///     CoreDuration::try_from(Duration::from_micros(123_456)),
+    ///     Ok(CoreDuration::from_micros(123_456)),
+    /// );
+    /// assert!(CoreDuration::try_from(Duration::from_micros(-1)).is_err());
+    /// ```
     fn try_from(value: Duration) -> Result<Self, Self::Error> {
         if value.micros < 0 {
             Err(TryFromDurationError(()))
@@ -405,6 +513,115 @@ impl From<Duration> for chrono::Duration {
     }
 }
 
-// TODO: Add more tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE: Duration = Duration::from_micros(1);
+    const MINUS_ONE: Duration = Duration::from_micros(-1);
+    /// `2³⁰` microseconds. Doubling this overflows `i32`.
+    const HALF: Duration = Duration::from_micros(0x4000_0000);
+
+    #[test]
+    fn checked_add_boundary() {
+        assert_eq!(
+            Duration::from_micros(i32::MAX - 1).checked_add(ONE),
+            Some(Duration::MAX)
+        );
+        assert_eq!(Duration::MAX.checked_add(ONE), None);
+        assert_eq!(Duration::MIN.checked_add(MINUS_ONE), None);
+        assert_eq!(Duration::MIN.checked_add(Duration::MAX), Some(MINUS_ONE));
+    }
+
+    #[test]
+    fn checked_sub_boundary() {
+        assert_eq!(Duration::MIN.checked_sub(ONE), None);
+        assert_eq!(Duration::ZERO.checked_sub(Duration::MIN), None);
+        assert_eq!(
+            Duration::ZERO.checked_sub(Duration::MAX),
+            Some(Duration::from_micros(i32::MIN + 1))
+        );
+    }
+
+    #[test]
+    fn checked_mul_boundary() {
+        assert_eq!(HALF.checked_mul(2), None);
+        assert_eq!(HALF.checked_mul(-2), Some(Duration::MIN));
+        assert_eq!(Duration::MIN.checked_mul(-1), None);
+        assert_eq!(
+            Duration::MAX.checked_mul(-1),
+            Some(Duration::from_micros(i32::MIN + 1))
+        );
+        assert_eq!(Duration::MAX.checked_mul(1), Some(Duration::MAX));
+    }
+
+    #[test]
+    fn checked_div_boundary() {
+        assert_eq!(Duration::MIN.checked_div(-1), None);
+        assert_eq!(Duration::MIN.checked_div(1), Some(Duration::MIN));
+        assert_eq!(
+            Duration::MAX.checked_div(-1),
+            Some(Duration::from_micros(-i32::MAX))
+        );
+        assert_eq!(ONE.checked_div(0), None);
+        assert_eq!(Duration::ZERO.checked_div(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_overflow() {
+        let _ = Duration::MAX + ONE;
+    }
+
+    #[test]
+    #[should_panic]
+    fn sub_overflow() {
+        let _ = Duration::MIN - ONE;
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_overflow() {
+        let _ = HALF * 2;
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_overflow() {
+        let _ = Duration::MIN / -1;
+    }
+
+    #[test]
+    fn saturating_boundary() {
+        assert_eq!(Duration::MAX.saturating_add(ONE), Duration::MAX);
+        assert_eq!(Duration::MAX.saturating_add(Duration::MIN), MINUS_ONE);
+        assert_eq!(Duration::MIN.saturating_sub(ONE), Duration::MIN);
+        assert_eq!(Duration::MAX.saturating_sub(MINUS_ONE), Duration::MAX);
+        assert_eq!(HALF.saturating_mul(2), Duration::MAX);
+        assert_eq!(HALF.saturating_mul(-2), Duration::MIN);
+        assert_eq!(Duration::MIN.saturating_mul(-1), Duration::MAX);
+        assert_eq!(Duration::MIN.saturating_mul(0), Duration::ZERO);
+    }
+
+    #[test]
+    fn core_duration_conversion_boundary() {
+        // The representable range ends at `i32::MAX` microseconds, which is
+        // smaller than `u32::MAX` microseconds
+        assert_eq!(
+            Duration::try_from(core::time::Duration::from_micros(i32::MAX as u64)),
+            Ok(Duration::MAX)
+        );
+        assert!(
+            Duration::try_from(core::time::Duration::from_micros(i32::MAX as u64 + 1)).is_err()
+        );
+        assert!(Duration::try_from(core::time::Duration::from_micros(u32::MAX as u64)).is_err());
+        assert_eq!(
+            core::time::Duration::try_from(Duration::MAX),
+            Ok(core::time::Duration::from_micros(i32::MAX as u64))
+        );
+        assert!(core::time::Duration::try_from(Duration::MIN).is_err());
+    }
+}
+
 // TODO: Maybe add macros to construct a `Duration` with compile-time overflow
 //       check
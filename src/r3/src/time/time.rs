@@ -1,9 +1,10 @@
-#[cfg(feature = "chrono")]
-use core::convert::TryFrom;
-use core::{convert::TryInto, fmt, ops};
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt, ops,
+};
 
 use crate::{
-    time::Duration,
+    time::{Duration, TryFromDurationError},
     utils::{Init, ZeroInit},
 };
 
@@ -139,10 +140,24 @@ impl Time {
 
     /// Get the duration since the specified timestamp as
     /// [`::core::time::Duration`]. Returns `None` if `self` < `reference`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration as CoreDuration;
+    /// use r3::time::Time;
+    /// assert_eq!(
+    ///     Time::from_secs(5).core_duration_since(Time::from_secs(3)),
+    ///     Some(CoreDuration::from_secs(2)),
+    /// );
+    /// assert_eq!(Time::from_secs(3).core_duration_since(Time::from_secs(5)), None);
+    /// ```
     #[inline]
     pub const fn core_duration_since(self, reference: Self) -> Option<core::time::Duration> {
         if self.micros >= reference.micros {
-            Some(core::time::Duration::from_micros(self.micros))
+            Some(core::time::Duration::from_micros(
+                self.micros - reference.micros,
+            ))
         } else {
             None
         }
@@ -216,6 +231,52 @@ impl ops::SubAssign<Duration> for Time {
     }
 }
 
+impl From<Time> for core::time::Duration {
+    /// Get the duration since the origin. Equivalent to
+    /// [`Time::core_duration_since_origin`].
+    ///
+    /// This conversion is lossless.
+    #[inline]
+    fn from(value: Time) -> Self {
+        value.core_duration_since_origin()
+    }
+}
+
+impl TryFrom<core::time::Duration> for Time {
+    type Error = TryFromDurationError;
+
+    /// Try to construct a `Time` that is the specified
+    /// `core::time::Duration` after the origin. Returns an error if the result
+    /// overflows the representable range of `Time`.
+    ///
+    /// The sub-microsecond part is rounded by truncating (i.e., toward the
+    /// origin).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{convert::TryFrom, time::Duration as CoreDuration};
+    /// use r3::time::Time;
+    /// assert_eq!(
+    ///     Time::try_from(CoreDuration::from_nanos(123_456_999)),
+    ///     Ok(Time::from_micros(123_456)),
+    /// );
+    /// assert_eq!(
+    ///     Time::try_from(CoreDuration::from_micros(u64::MAX)),
+    ///     Ok(Time::MAX),
+    /// );
+    /// assert!(Time::try_from(CoreDuration::from_secs(u64::MAX)).is_err());
+    /// ```
+    fn try_from(value: core::time::Duration) -> Result<Self, Self::Error> {
+        Ok(Self::from_micros(
+            value
+                .as_micros()
+                .try_into()
+                .map_err(|_| TryFromDurationError(()))?,
+        ))
+    }
+}
+
 /// Error type returned when a checked timestamp type conversion fails.
 #[cfg(feature = "chrono")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
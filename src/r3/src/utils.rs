@@ -38,6 +38,10 @@ pub use self::{
     aligned_storage::*, init::*, int::*, prio_bitmap::*, rawcell::*, vec::*, zeroinit::*,
 };
 
+/// Derive macro generating an implementation of [`Init`]. See
+/// [`r3_derive::Init`] for details.
+pub use r3_derive::Init;
+
 /// A "type function" producing a type.
 #[doc(hidden)]
 pub trait TypeFn {
@@ -0,0 +1,125 @@
+//! Procedural macros for [R3](https://crates.io/crates/r3). This crate is
+//! re-exported by `r3`, so you don't need to depend on it directly.
+extern crate proc_macro;
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    spanned::Spanned,
+    Data, DeriveInput, Expr, Fields, GenericParam, Token,
+};
+
+/// Derive an implementation of `r3::utils::Init`.
+///
+/// The derived `INIT` is constructed by taking `Init::INIT` of every field.
+/// Every field must therefore implement `Init`, and every type parameter is
+/// given an `Init` bound. Structs with named fields, tuple structs, and unit
+/// structs are supported.
+///
+/// The initial value of an individual field can be overridden by the
+/// `#[init = expr]` attribute, where `expr` is a constant expression. (Using
+/// a non-literal expression here requires
+/// `#![feature(extended_key_value_attributes)]` on older compilers.)
+///
+/// # Examples
+///
+/// ```
+/// use r3::utils::Init;
+///
+/// #[derive(Init)]
+/// struct Counter {
+///     count: u32,
+///     #[init = 10]
+///     limit: u32,
+///     name: &'static str,
+/// }
+///
+/// const C: Counter = Counter::INIT;
+/// assert_eq!(C.count, 0);
+/// assert_eq!(C.limit, 10);
+/// assert_eq!(C.name, "");
+/// ```
+#[proc_macro_derive(Init, attributes(init))]
+pub fn derive_init(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_init_inner(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn derive_init_inner(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span(),
+                "`Init` can't be derived for enums",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span(),
+                "`Init` can't be derived for unions",
+            ))
+        }
+    };
+
+    // Construct the initializer of each field
+    let mut field_inits = Vec::with_capacity(fields.len());
+    for field in fields.iter() {
+        let mut init = None;
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("init")) {
+            if init.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "duplicate `#[init]` attribute",
+                ));
+            }
+            let InitAttr(expr) = syn::parse2(attr.tokens.clone())?;
+            init = Some(quote! { #expr });
+        }
+
+        let ty = &field.ty;
+        field_inits.push(init.unwrap_or_else(|| {
+            quote_spanned! { ty.span() => <#ty as ::r3::utils::Init>::INIT }
+        }));
+    }
+
+    let body = match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|f| &f.ident);
+            quote! { Self { #( #names: #field_inits, )* } }
+        }
+        Fields::Unnamed(_) => quote! { Self ( #( #field_inits, )* ) },
+        Fields::Unit => quote! { Self },
+    };
+
+    // Require `T: Init` for every type parameter `T`
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::r3::utils::Init));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::r3::utils::Init for #name #ty_generics #where_clause {
+            #[allow(clippy::declare_interior_mutable_const)]
+            const INIT: Self = #body;
+        }
+    })
+}
+
+/// The contents of `#[init = expr]` following the path.
+struct InitAttr(Expr);
+
+impl Parse for InitAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![=]>()?;
+        Ok(Self(input.parse()?))
+    }
+}
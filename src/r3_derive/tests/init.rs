@@ -0,0 +1,106 @@
+//! Checks the code generated by `#[derive(Init)]`.
+use r3::utils::Init;
+use std::{cell::Cell, mem, slice};
+
+/// The fields are sorted by alignment in a descending order, so there's no
+/// padding between them.
+#[derive(Init)]
+#[repr(C)]
+struct Primitives {
+    a: u128,
+    b: i128,
+    c: u64,
+    d: i64,
+    e: f64,
+    f: usize,
+    g: isize,
+    h: u32,
+    i: i32,
+    j: f32,
+    k: char,
+    l: u16,
+    m: i16,
+    n: u8,
+    o: i8,
+    p: bool,
+}
+
+#[derive(Init)]
+struct Nested {
+    primitives: Primitives,
+    array: [u16; 4],
+    option: Option<&'static str>,
+    cell: Cell<u32>,
+}
+
+#[derive(Init)]
+struct Tuple(u32, #[init = 42] u32, &'static str);
+
+#[derive(Init)]
+struct Unit;
+
+#[derive(Init)]
+struct Overridden {
+    #[init = 1]
+    a: u32,
+    b: u32,
+    #[init = "hello"]
+    c: &'static str,
+}
+
+#[derive(Init)]
+struct Generic<T, const N: usize> {
+    values: [T; N],
+    value: T,
+}
+
+#[test]
+fn primitives_are_zero() {
+    static VALUE: Primitives = Primitives::INIT;
+
+    // Exclude the trailing padding
+    let len = {
+        let v = &VALUE;
+        &v.p as *const _ as usize + mem::size_of_val(&v.p) - v as *const _ as usize
+    };
+    let bytes = unsafe { slice::from_raw_parts(&VALUE as *const _ as *const u8, len) };
+
+    assert!(bytes.iter().all(|&b| b == 0), "{:?}", bytes);
+}
+
+#[test]
+fn nested() {
+    let value = Nested::INIT;
+    assert_eq!(value.primitives.h, 0);
+    assert_eq!(value.array, [0; 4]);
+    assert_eq!(value.option, None);
+    assert_eq!(value.cell.get(), 0);
+}
+
+#[test]
+fn tuple_struct() {
+    const VALUE: Tuple = Tuple::INIT;
+    assert_eq!(VALUE.0, 0);
+    assert_eq!(VALUE.1, 42);
+    assert_eq!(VALUE.2, "");
+}
+
+#[test]
+fn unit_struct() {
+    let Unit = Unit::INIT;
+}
+
+#[test]
+fn override_fields() {
+    const VALUE: Overridden = Overridden::INIT;
+    assert_eq!(VALUE.a, 1);
+    assert_eq!(VALUE.b, 0);
+    assert_eq!(VALUE.c, "hello");
+}
+
+#[test]
+fn generic() {
+    const VALUE: Generic<u8, 3> = Generic::INIT;
+    assert_eq!(VALUE.values, [0; 3]);
+    assert_eq!(VALUE.value, 0);
+}
@@ -1,5 +1,5 @@
 //! The public interface of the GIC driver.
-use r3::kernel::InterruptNum;
+use r3::kernel::{InterruptLineTriggerMode, InterruptNum, SetInterruptLineTriggerModeError};
 use register::FieldValue;
 
 use super::{gic_regs, imp::GicRegs};
@@ -26,9 +26,10 @@ macro_rules! use_gic {
                 core::ops::Range,
                 gic::imp,
                 r3::kernel::{
-                    ClearInterruptLineError, EnableInterruptLineError, InterruptNum,
-                    InterruptPriority, PendInterruptLineError, PortInterrupts,
+                    ClearInterruptLineError, EnableInterruptLineError, InterruptLineTriggerMode,
+                    InterruptNum, InterruptPriority, PendInterruptLineError, PortInterrupts,
                     QueryInterruptLineError, SetInterruptLinePriorityError,
+                    SetInterruptLineTriggerModeError,
                 },
                 Gic, InterruptController,
             };
@@ -85,6 +86,14 @@ macro_rules! use_gic {
                 ) -> Result<bool, QueryInterruptLineError> {
                     imp::is_interrupt_line_pending::<Self>(line)
                 }
+
+                #[inline]
+                unsafe fn set_interrupt_line_trigger_mode(
+                    line: InterruptNum,
+                    mode: InterruptLineTriggerMode,
+                ) -> Result<(), SetInterruptLineTriggerModeError> {
+                    imp::set_interrupt_line_trigger_mode::<Self>(line, mode)
+                }
             }
 
             impl InterruptController for $sys {
@@ -107,23 +116,6 @@ macro_rules! use_gic {
     };
 }
 
-/// Specifies the type of signal transition that pends an interrupt.
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-pub enum InterruptLineTriggerMode {
-    /// Asserts an interrupt whenever the interrupt signal level is active and
-    /// deasserts whenever the level is not active.
-    Level = 0,
-    /// Asserts an interrupt upon detection of a rising edge of an interrupt
-    /// signal.
-    RisingEdge = 1,
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum SetInterruptLineTriggerModeError {
-    /// The interrupt number is out of range.
-    BadParam,
-}
-
 /// The options for [`use_gic!`].
 pub trait GicOptions {
     /// The base address of GIC distributor registers.
@@ -147,6 +139,7 @@ pub unsafe trait Gic {
     }
 
     /// Set the trigger mode of the specified interrupt line.
+    /// [`InterruptLineTriggerMode::Edge`] selects the rising edge.
     fn set_interrupt_line_trigger_mode(
         num: InterruptNum,
         mode: InterruptLineTriggerMode,
@@ -158,7 +151,10 @@ pub unsafe trait Gic {
             return Err(SetInterruptLineTriggerModeError::BadParam);
         }
 
-        let int_config = mode as u32 * 2;
+        let int_config = match mode {
+            InterruptLineTriggerMode::Level => 0b00,
+            InterruptLineTriggerMode::Edge => 0b10,
+        };
         distributor.ICFGR[num / 16].modify(FieldValue::<u32, ()>::new(
             0b10,
             (num % 16) * 2,
@@ -1,7 +1,8 @@
 //! Under the hood
 use r3::kernel::{
-    ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-    PendInterruptLineError, QueryInterruptLineError, SetInterruptLinePriorityError,
+    ClearInterruptLineError, EnableInterruptLineError, InterruptLineTriggerMode, InterruptNum,
+    InterruptPriority, PendInterruptLineError, QueryInterruptLineError,
+    SetInterruptLinePriorityError, SetInterruptLineTriggerModeError,
 };
 
 use super::{
@@ -201,3 +202,11 @@ pub fn is_interrupt_line_pending<System: Gic>(
 
     Ok((distributor.ISPEND[line / 32].get() & (1 << (line % 32))) != 0)
 }
+
+/// Implements [`r3::kernel::PortInterrupts::set_interrupt_line_trigger_mode`].
+pub fn set_interrupt_line_trigger_mode<System: Gic>(
+    line: InterruptNum,
+    mode: InterruptLineTriggerMode,
+) -> Result<(), SetInterruptLineTriggerModeError> {
+    <System as Gic>::set_interrupt_line_trigger_mode(line, mode)
+}
@@ -31,6 +31,8 @@ pub mod rt {
     pub mod cfg;
     #[cfg(target_os = "none")]
     pub mod imp;
+    #[cfg(target_os = "none")]
+    pub mod semihosting;
 }
 
 /// The tickful [`r3::kernel::PortTimer`] implementation based on SysTick.
@@ -41,7 +43,23 @@ pub mod systick_tickful {
     pub mod imp;
 }
 
-pub use self::{systick_tickful::cfg::*, threading::cfg::*};
+/// The tickless [`r3::kernel::PortTimer`] implementation based on SysTick.
+#[doc(hidden)]
+pub mod systick_tickless {
+    pub mod cfg;
+    #[cfg(target_os = "none")]
+    pub mod imp;
+}
+
+/// The Memory Protection Unit support.
+#[doc(hidden)]
+pub mod mpu {
+    pub mod cfg;
+    #[cfg(target_os = "none")]
+    pub mod imp;
+}
+
+pub use self::{mpu::cfg::*, systick_tickful::cfg::*, systick_tickless::cfg::*, threading::cfg::*};
 
 /// Used by `use_port!`
 #[doc(hidden)]
@@ -50,6 +68,9 @@ pub extern crate core;
 /// Used by `use_port!`
 #[doc(hidden)]
 pub extern crate r3;
+/// Used by `use_systick_tickless!`
+#[doc(hidden)]
+pub extern crate r3_portkit;
 /// Used by `use_port!`
 #[doc(hidden)]
 #[cfg(target_os = "none")]
@@ -0,0 +1,145 @@
+use r3::kernel::Id;
+
+/// A memory region to be configured in the Memory Protection Unit (PMSAv7).
+///
+/// See [the crate-level documentation](crate#memory-protection-unit).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MpuRegion {
+    /// The base address of the region. Must be aligned to the region size.
+    pub base: u32,
+    /// The binary logarithm of the region size in bytes. Must be in range
+    /// `5..=32`.
+    pub size_log2: u8,
+    /// The access permissions and memory attributes of the region.
+    pub attrs: MpuRegionAttrs,
+}
+
+/// The access permissions and memory attributes of an [`MpuRegion`],
+/// represented in the `MPU_RASR` format.
+///
+/// The values can be combined by [`MpuRegionAttrs::union`]. The result must
+/// include exactly one access permission (e.g., [`RW`]) and at most one memory
+/// type (e.g., [`NORMAL`]).
+///
+/// [`RW`]: Self::RW
+/// [`NORMAL`]: Self::NORMAL
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct MpuRegionAttrs(pub u32);
+
+impl MpuRegionAttrs {
+    /// Read/write by privileged software only.
+    pub const PRIV_RW: Self = Self(0b001 << 24);
+    /// Read/write by any software.
+    pub const RW: Self = Self(0b011 << 24);
+    /// Read-only by privileged software only.
+    pub const PRIV_RO: Self = Self(0b101 << 24);
+    /// Read-only by any software.
+    pub const RO: Self = Self(0b110 << 24);
+    /// Prevents instruction fetches from the region.
+    pub const EXECUTE_NEVER: Self = Self(1 << 28);
+
+    /// Strongly-ordered memory.
+    pub const STRONGLY_ORDERED: Self = Self(0);
+    /// Device memory.
+    pub const DEVICE: Self = Self(0b000_0_0_1 << 16);
+    /// Normal memory, outer and inner write-back, write and read allocate.
+    pub const NORMAL: Self = Self(0b001_0_1_1 << 16);
+
+    /// Combine two sets of attributes.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+/// The configuration of the Memory Protection Unit support.
+///
+/// See [the crate-level documentation](crate#memory-protection-unit).
+pub trait MpuOptions {
+    /// The number of MPU regions (starting from region `0`) that the port
+    /// reprograms on every context switch. Defaults to `4`.
+    ///
+    /// The remaining regions are left untouched, so the application can
+    /// configure them for global use.
+    const NUM_TASK_REGIONS: u8 = 4;
+
+    /// Automatically configure an MPU region covering a task's stack with
+    /// [`RW`] and [`EXECUTE_NEVER`]. Defaults to `true`.
+    ///
+    /// This region is created only if the task's stack is representable as an
+    /// MPU region, i.e., its size is a power of two and it's aligned to its
+    /// size. The region occupies region `0`, so it takes precedence over
+    /// nothing but the default memory map.
+    ///
+    /// [`RW`]: MpuRegionAttrs::RW
+    /// [`EXECUTE_NEVER`]: MpuRegionAttrs::EXECUTE_NEVER
+    const PROTECT_TASK_STACKS: bool = true;
+
+    /// Get the task-specific regions for the task identified by `task`.
+    /// Defaults to returning an empty slice.
+    ///
+    /// The number of returned regions must not exceed `NUM_TASK_REGIONS`
+    /// (`NUM_TASK_REGIONS - 1` if a stack region was created).
+    fn task_regions(task: Id) -> &'static [MpuRegion] {
+        let _ = task;
+        &[]
+    }
+}
+
+/// Enable the support for the Memory Protection Unit. **Requires
+/// [`MpuOptions`].**
+///
+/// You should also do the following:
+///
+///  - Implement [`MpuOptions`] manually.
+///  - Call `$ty::configure_mpu()` in your configuration function.
+///    See the following example.
+///
+/// ```rust,ignore
+/// r3_port_arm_m::use_mpu!(unsafe System);
+///
+/// impl r3_port_arm_m::MpuOptions for System {
+///     fn task_regions(task: Id) -> &'static [MpuRegion] {
+///         if task == COTTAGE.task1.id() {
+///             &[MpuRegion {
+///                 base: 0x2000_8000,
+///                 size_log2: 10,
+///                 attrs: MpuRegionAttrs::RO
+///                     .union(MpuRegionAttrs::NORMAL)
+///                     .union(MpuRegionAttrs::EXECUTE_NEVER),
+///             }]
+///         } else {
+///             &[]
+///         }
+///     }
+/// }
+///
+/// const fn configure_app(b: &mut CfgBuilder<System>) -> Objects {
+///     System::configure_mpu(b);
+///     /* ... */
+/// }
+/// ```
+///
+/// # Safety
+///
+///  - The target must really be a bare-metal Armv6-M or Armv7-M environment
+///    with an MPU.
+///  - The MPU must not be reconfigured by anything other than the port.
+///
+#[macro_export]
+macro_rules! use_mpu {
+    (unsafe $sys:ty) => {
+        const _: () = {
+            use $crate::{mpu::imp, r3::kernel::cfg::CfgBuilder};
+
+            // Safety: Only `use_mpu!` is allowed to `impl` this
+            unsafe impl imp::MpuInstance for $sys {}
+
+            impl $sys {
+                pub const fn configure_mpu(b: &mut CfgBuilder<Self>) {
+                    imp::configure(b);
+                }
+            }
+        };
+    };
+}
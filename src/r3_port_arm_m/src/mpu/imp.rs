@@ -0,0 +1,170 @@
+//! The implementation of the Memory Protection Unit support.
+use core::mem;
+use r3::kernel::{cfg::CfgBuilder, Id, KernelCfg2, StartupHook, TaskCb};
+
+use crate::{threading::imp::PortInstance, MpuOptions, MpuRegion};
+
+/// `MPU_CTRL.ENABLE`
+const MPU_CTRL_ENABLE: u32 = 1 << 0;
+/// `MPU_CTRL.PRIVDEFENA`
+const MPU_CTRL_PRIVDEFENA: u32 = 1 << 2;
+/// `MPU_RBAR.VALID`
+const MPU_RBAR_VALID: u32 = 1 << 4;
+/// `MPU_RASR.ENABLE`
+const MPU_RASR_ENABLE: u32 = 1 << 0;
+
+/// Implemented on a system type by [`use_mpu!`].
+///
+/// # Safety
+///
+/// Only meant to be implemented by [`use_mpu!`].
+pub unsafe trait MpuInstance: PortInstance + MpuOptions {}
+
+/// The configuration function.
+pub const fn configure<System: MpuInstance>(b: &mut CfgBuilder<System>) {
+    if cfg!(armv8m) {
+        panic!("`use_mpu!` doesn't support Armv8-M (PMSAv8) yet");
+    }
+
+    StartupHook::build()
+        .start(
+            #[inline]
+            |_| unsafe { init::<System>() },
+        )
+        .finish(b);
+}
+
+/// Enable the MPU and install the dispatch hook.
+///
+/// # Safety
+///
+/// CPU Lock active, boot phase
+unsafe fn init<System: MpuInstance>() {
+    // Safety: We have the control of the MPU
+    let peripherals = unsafe { cortex_m::Peripherals::steal() };
+
+    assert!(
+        (peripherals.MPU._type.read() >> 8) & 0xff >= System::NUM_TASK_REGIONS as u32,
+        "the MPU doesn't have `NUM_TASK_REGIONS` regions"
+    );
+
+    // Disable the task-specific regions for now. They will be configured by
+    // `switch_regions` when a task is dispatched.
+    for i in 0..System::NUM_TASK_REGIONS as u32 {
+        // Safety: We have the control of the MPU
+        unsafe {
+            peripherals.MPU.rnr.write(i);
+            peripherals.MPU.rasr.write(0);
+        }
+    }
+
+    // Privileged software (including tasks and interrupt handlers) can access
+    // memory not covered by any regions using the default memory map
+    // (`PRIVDEFENA`)
+    // Safety: We have the control of the MPU
+    unsafe {
+        peripherals
+            .MPU
+            .ctrl
+            .write(MPU_CTRL_ENABLE | MPU_CTRL_PRIVDEFENA)
+    };
+
+    // Escalate MPU faults to MemManage (which is not available on Armv6-M)
+    // instead of HardFault
+    #[cfg(not(armv6m))]
+    // Safety: We have the control of `SHCSR.MEMFAULTENA`
+    unsafe {
+        peripherals.SCB.shcsr.modify(|x| x | (1 << 16))
+    };
+
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+
+    // Safety: We are in the boot phase, and the dispatcher doesn't run yet
+    unsafe { System::port_state().set_dispatch_hook(switch_regions::<System>) };
+}
+
+/// Reprogram the task-specific regions for the task that is about to be
+/// dispatched. Called by the dispatcher.
+///
+/// # Safety
+///
+/// CPU Lock active, called by the dispatcher
+unsafe fn switch_regions<System: MpuInstance>() {
+    // Safety: CPU Lock active
+    let task_cb: Option<&'static TaskCb<System>> = unsafe { *System::state().running_task_ptr() };
+
+    let mut region_i = 0;
+    let mut set_region = |region: &MpuRegion| {
+        debug_assert!(region_i < System::NUM_TASK_REGIONS as u32);
+        debug_assert!((5..=32).contains(&region.size_log2));
+        debug_assert_eq!(
+            region.base as u64 % (1u64 << region.size_log2),
+            0,
+            "the region base must be aligned to the region size"
+        );
+
+        // Safety: We have the control of the MPU
+        unsafe {
+            let mpu = &*cortex_m::peripheral::MPU::ptr();
+            mpu.rbar.write(region.base | MPU_RBAR_VALID | region_i);
+            mpu.rasr
+                .write(region.attrs.0 | ((region.size_log2 as u32 - 1) << 1) | MPU_RASR_ENABLE);
+        }
+
+        region_i += 1;
+    };
+
+    if let Some(task_cb) = task_cb {
+        // Create a region for the stack
+        if System::PROTECT_TASK_STACKS {
+            if let Some(region) = stack_region(task_cb) {
+                set_region(&region);
+            }
+        }
+
+        // Calculate an `Id` from the task CB pointer
+        let offset_bytes =
+            task_cb as *const TaskCb<_> as usize - System::task_cb_pool().as_ptr() as usize;
+        let offset = offset_bytes / mem::size_of::<TaskCb<System>>();
+        let id = Id::new(offset + 1).unwrap();
+
+        for region in System::task_regions(id) {
+            set_region(region);
+        }
+    }
+
+    // Disable the remaining regions
+    for i in region_i..System::NUM_TASK_REGIONS as u32 {
+        // Safety: We have the control of the MPU
+        unsafe {
+            let mpu = &*cortex_m::peripheral::MPU::ptr();
+            mpu.rnr.write(i);
+            mpu.rasr.write(0);
+        }
+    }
+
+    // The new configuration will be in effect after the exception return,
+    // which is a context synchronization event
+    cortex_m::asm::dsb();
+}
+
+/// Get an MPU region exactly covering the given task's stack if the stack is
+/// representable as an MPU region.
+fn stack_region<System: MpuInstance>(task_cb: &'static TaskCb<System>) -> Option<MpuRegion> {
+    let stack = task_cb.attr.stack.as_ptr();
+    let base = stack as *mut u8 as usize;
+    let size = stack.len();
+
+    if !size.is_power_of_two() || size < 32 || base % size != 0 {
+        return None;
+    }
+
+    Some(MpuRegion {
+        base: base as u32,
+        size_log2: size.trailing_zeros() as u8,
+        attrs: crate::MpuRegionAttrs::RW
+            .union(crate::MpuRegionAttrs::NORMAL)
+            .union(crate::MpuRegionAttrs::EXECUTE_NEVER),
+    })
+}
@@ -10,9 +10,24 @@
 ///  - The SysTick handler (`SysTick` global symbol).
 ///  - The PendSV handler (`PendSV` global symbol).
 ///  - Interrupt handlers and the vector table (`__INTERRUPTS` global symbol).
+///  - The panic handler (`#[panic_handler]`) if `panic_handler` is specified
+///    as in `use_rt!(unsafe System, panic_handler)`. **Requires the
+///    `panic-handler` Cargo feature.** See [the crate-level documentation]
+///    for its behavior.
 ///
+/// [the crate-level documentation]: crate#panic-handler
 #[macro_export]
 macro_rules! use_rt {
+    (unsafe $sys:ty, panic_handler) => {
+        $crate::use_rt!(unsafe $sys);
+
+        const _: () = {
+            #[panic_handler]
+            fn panic(info: &$crate::core::panic::PanicInfo) -> ! {
+                $crate::rt::imp::handle_panic(info)
+            }
+        };
+    };
     (unsafe $sys:ty) => {
         const _: () = {
             use $crate::{
@@ -66,3 +66,29 @@ impl ExceptionTrampoline {
         }
     }
 }
+
+/// Used by `use_rt!`
+#[cfg(feature = "panic-handler")]
+#[inline]
+pub fn handle_panic(info: &core::panic::PanicInfo) -> ! {
+    // Mask all configurable-priority exceptions. The kernel state might be
+    // inconsistent, so no kernel code should run from now on.
+    cortex_m::interrupt::disable();
+
+    #[cfg(feature = "panic-semihosting")]
+    {
+        use core::fmt::Write;
+        if let Ok(mut hstdout) = cortex_m_semihosting::hio::hstdout() {
+            let _ = writeln!(hstdout, "{}", info);
+        }
+    }
+
+    #[cfg(not(feature = "panic-semihosting"))]
+    let _ = info;
+
+    // Halt. Don't use `wfi` here because it can interfere with debugger
+    // connection.
+    loop {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,69 @@
+//! A minimal semihosting writer based on `SYS_WRITE0`. Unlike `SYS_WRITE`, it
+//! doesn't require opening a file handle first, so the debug host only has to
+//! implement a single operation to receive the output.
+//!
+//! This module is not covered by the semantic versioning guarantees.
+use core::fmt;
+
+/// The operation number of `SYS_WRITE0`
+const SYS_WRITE0: usize = 0x04;
+
+/// The size of the buffer used to build a NUL-terminated string
+const BUF_LEN: usize = 64;
+
+/// Output a NUL-terminated string to the debug host's console through
+/// `SYS_WRITE0`.
+///
+/// # Safety
+///
+/// `s` must point to a NUL-terminated string. A debugger supporting
+/// semihosting must be attached; otherwise, the breakpoint instruction causes
+/// a HardFault.
+#[inline]
+pub unsafe fn write0(s: *const u8) {
+    unsafe {
+        asm!(
+            "bkpt 0xab",
+            inout("r0") SYS_WRITE0 => _,
+            in("r1") s,
+            options(nostack, readonly, preserves_flags),
+        )
+    };
+}
+
+/// Implements [`fmt::Write`] by `SYS_WRITE0`. The string is split into
+/// chunks, each of which is copied to a stack buffer and terminated by NUL.
+/// NUL characters in the string can't be represented and are discarded.
+pub struct Write0;
+
+impl fmt::Write for Write0 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut buf = [0u8; BUF_LEN];
+        let mut len = 0;
+
+        for &byte in s.as_bytes() {
+            if byte == 0 {
+                continue;
+            }
+
+            buf[len] = byte;
+            len += 1;
+
+            // Leave room for the terminator
+            if len == BUF_LEN - 1 {
+                buf[len] = 0;
+                // Safety: `buf` is NUL-terminated
+                unsafe { write0(buf.as_ptr()) };
+                len = 0;
+            }
+        }
+
+        if len > 0 {
+            buf[len] = 0;
+            // Safety: `buf` is NUL-terminated
+            unsafe { write0(buf.as_ptr()) };
+        }
+
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use r3::kernel::InterruptPriority;
 
-/// The configuration for the implementation of `PortTimer` based on SysTick
-/// ([tickful]).
+/// The configuration for the implementations of `PortTimer` based on SysTick
+/// ([tickful], [tickless]).
 ///
 /// [tickful]: crate::use_systick_tickful
+/// [tickless]: crate::use_systick_tickless
 pub trait SysTickOptions {
     /// The numerator of the input clock frequency of SysTick.
     const FREQUENCY: u64;
@@ -17,7 +18,9 @@ pub trait SysTickOptions {
     const INTERRUPT_PRIORITY: InterruptPriority = 0xc0;
 
     /// The period of ticks, measured in SysTick cycles. Must be in range
-    /// `0..=0x1000000`.
+    /// `0..=0x1000000`. Only used by [the tickful implementation].
+    ///
+    /// [the tickful implementation]: crate::use_systick_tickful
     ///
     /// Defaults to
     /// `(FREQUENCY / FREQUENCY_DENOMINATOR / 100).max(1).min(0x1000000)` (100Hz).
@@ -32,6 +35,29 @@ pub trait SysTickOptions {
             x as u32
         }
     };
+
+    /// The maximum permissible timer interrupt latency, measured in SysTick
+    /// cycles. Only used by [the tickless implementation].
+    ///
+    /// SysTick's counter is only 24-bit wide, so the tickless implementation
+    /// can't tolerate an interrupt latency longer than `0x1000000` cycles
+    /// regardless of this value.
+    ///
+    /// Defaults to
+    /// `(FREQUENCY / FREQUENCY_DENOMINATOR / 100).max(1).min(0x800000)` (10ms).
+    ///
+    /// [the tickless implementation]: crate::use_systick_tickless
+    const HEADROOM: u32 = {
+        // FIXME: Work-around for `Ord::max` not being `const fn`
+        let x = Self::FREQUENCY / Self::FREQUENCY_DENOMINATOR / 100;
+        if x == 0 {
+            1
+        } else if x > 0x800000 {
+            0x800000
+        } else {
+            x as u32
+        }
+    };
 }
 
 /// Attach the tickful implementation of [`PortTimer`] that is based on SysTick
@@ -79,6 +105,9 @@ macro_rules! use_systick_tickful {
             impl PortTimer for $ty {
                 const MAX_TICK_COUNT: UTicks = u32::MAX;
                 const MAX_TIMEOUT: UTicks = u32::MAX;
+                const TICKLESS: bool = false;
+                const RESOLUTION: UTicks =
+                    <$ty as imp::SysTickTickfulInstance>::TICKFUL_CFG.max_tick_period_micros();
 
                 unsafe fn tick_count() -> UTicks {
                     // Safety: CPU Lock active
@@ -0,0 +1,151 @@
+/// Attach the tickless implementation of [`PortTimer`] that is based on
+/// SysTick to a given system type.
+///
+/// [`PortTimer`]: r3::kernel::PortTimer
+/// [a tickless scheme]: crate#tickless-systick
+///
+/// You should also do the following:
+///
+///  - Implement [`SysTickOptions`] manually.
+///  - Call `$ty::configure_systick()` in your configuration function.
+///    See the following example.
+///
+/// ```rust,ignore
+/// r3_port_arm_m::use_systick_tickless!(unsafe impl PortTimer for System);
+///
+/// impl r3_port_arm_m::SysTickOptions for System {
+///    // SysTick = AHB/8, AHB = HSI (internal 16-MHz RC oscillator)
+///     const FREQUENCY: u64 = 2_000_000;
+/// }
+///
+/// const fn configure_app(b: &mut CfgBuilder<System>) -> Objects {
+///     System::configure_systick(b);
+///     /* ... */
+/// }
+/// ```
+///
+/// [`SysTickOptions`]: crate::SysTickOptions
+///
+/// # Safety
+///
+///  - The target must really be a bare-metal Arm-M environment.
+///
+#[macro_export]
+macro_rules! use_systick_tickless {
+    (unsafe impl PortTimer for $ty:ty) => {
+        $crate::use_systick_tickless!(@inner $ty, use_dwt = false);
+    };
+
+    (@inner $ty:ty, use_dwt = $use_dwt:expr) => {
+        const _: () = {
+            use $crate::r3::{
+                kernel::{cfg::CfgBuilder, PortTimer, UTicks},
+                utils::Init,
+            };
+            use $crate::r3_portkit::tickless;
+            use $crate::{systick_tickless::imp, SysTickOptions};
+
+            const TICKLESS_CFG: tickless::TicklessCfg =
+                match tickless::TicklessCfg::new(tickless::TicklessOptions {
+                    hw_freq_num: <$ty as SysTickOptions>::FREQUENCY,
+                    hw_freq_denom: <$ty as SysTickOptions>::FREQUENCY_DENOMINATOR,
+                    hw_headroom_ticks: <$ty as SysTickOptions>::HEADROOM,
+                    // `imp` extends SysTick's 24-bit counter to 32 bits
+                    force_full_hw_period: true,
+                    resettable: false,
+                }) {
+                    Ok(x) => x,
+                    Err(e) => e.panic(),
+                };
+
+            static TIMER_STATE: imp::State<tickless::TicklessState<TICKLESS_CFG>> = Init::INIT;
+
+            impl PortTimer for $ty {
+                const MAX_TICK_COUNT: UTicks = TICKLESS_CFG.max_tick_count();
+                const MAX_TIMEOUT: UTicks = TICKLESS_CFG.max_timeout();
+
+                unsafe fn tick_count() -> UTicks {
+                    // Safety: CPU Lock active
+                    unsafe { TIMER_STATE.tick_count::<Self>() }
+                }
+
+                unsafe fn pend_tick() {
+                    // Safety: CPU Lock active
+                    unsafe { TIMER_STATE.pend_tick::<Self>() }
+                }
+
+                unsafe fn pend_tick_after(tick_count_delta: UTicks) {
+                    // Safety: CPU Lock active
+                    unsafe { TIMER_STATE.pend_tick_after::<Self>(tick_count_delta) }
+                }
+            }
+
+            // Safety: Only `use_systick_tickless!` is allowed to `impl` this
+            unsafe impl imp::SysTickTicklessInstance for $ty {
+                const TICKLESS_CFG: tickless::TicklessCfg = TICKLESS_CFG;
+
+                unsafe fn init() {
+                    // Safety: CPU Lock active, boot phase
+                    unsafe { TIMER_STATE.init($use_dwt) };
+                }
+
+                unsafe fn handle_tick() {
+                    // Safety: Interrupt context, CPU Lock inactive
+                    unsafe { TIMER_STATE.handle_tick::<Self>() };
+                }
+            }
+
+            impl $ty {
+                pub const fn configure_systick(b: &mut CfgBuilder<Self>) {
+                    imp::configure(b);
+                }
+            }
+        };
+    };
+}
+
+/// Attach the tickless implementation of [`PortTimer`] that uses the DWT cycle
+/// counter (`CYCCNT`) as a time source and SysTick as a timer interrupt
+/// source to a given system type.
+///
+/// [`PortTimer`]: r3::kernel::PortTimer
+/// [a tickless scheme]: crate#dwt-cycle-counter
+///
+/// The usage is identical to that of [`use_systick_tickless!`] except that
+/// [`SysTickOptions::FREQUENCY`] (and [`SysTickOptions::FREQUENCY_DENOMINATOR`])
+/// must specify the processor clock frequency. SysTick is configured to use
+/// the processor clock.
+///
+/// If the DWT cycle counter is unavailable (e.g., on Armv6-M), this
+/// implementation falls back to the same behavior as
+/// [`use_systick_tickless!`].
+///
+/// ```rust,ignore
+/// r3_port_arm_m::use_dwt_tickless!(unsafe impl PortTimer for System);
+///
+/// impl r3_port_arm_m::SysTickOptions for System {
+///     // The processor clock frequency
+///     const FREQUENCY: u64 = 16_000_000;
+/// }
+///
+/// const fn configure_app(b: &mut CfgBuilder<System>) -> Objects {
+///     System::configure_systick(b);
+///     /* ... */
+/// }
+/// ```
+///
+/// [`SysTickOptions::FREQUENCY`]: crate::SysTickOptions::FREQUENCY
+/// [`SysTickOptions::FREQUENCY_DENOMINATOR`]: crate::SysTickOptions::FREQUENCY_DENOMINATOR
+///
+/// # Safety
+///
+///  - The target must really be a bare-metal Arm-M environment.
+///  - DWT must not be used (or reconfigured) by anything else, such as a
+///    debugger.
+///
+#[macro_export]
+macro_rules! use_dwt_tickless {
+    (unsafe impl PortTimer for $ty:ty) => {
+        $crate::use_systick_tickless!(@inner $ty, use_dwt = true);
+    };
+}
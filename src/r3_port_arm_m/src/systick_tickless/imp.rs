@@ -0,0 +1,341 @@
+//! The tickless `PortTimer` implementation based on SysTick.
+use core::cell::UnsafeCell;
+use cortex_m::peripheral::SYST;
+use r3::{
+    kernel::{
+        cfg::CfgBuilder, InterruptHandler, InterruptLine, Kernel, PortToKernel, StartupHook, UTicks,
+    },
+    utils::Init,
+};
+use r3_portkit::tickless::{TicklessCfg, TicklessStateTrait};
+
+use crate::{SysTickOptions, INTERRUPT_SYSTICK};
+
+/// The number of cycles in SysTick's longest possible period.
+const FULL_PERIOD: u32 = 0x100_0000;
+
+/// The shortest period (measured in SysTick cycles) that we program SysTick
+/// with. [`StateInner::reload`] needs some time to replace `SYST_RVR` before
+/// the first period ends.
+const MIN_PERIOD: u32 = 0x100;
+
+/// Implemented on a system type by [`use_systick_tickless!`] or
+/// [`use_dwt_tickless!`].
+///
+/// # Safety
+///
+/// Only meant to be implemented by [`use_systick_tickless!`] or
+/// [`use_dwt_tickless!`].
+pub unsafe trait SysTickTicklessInstance: Kernel + SysTickOptions {
+    // FIXME: Specifying `TicklessCfg::new(...)` here causes a "cycle
+    //        detected" error
+    const TICKLESS_CFG: TicklessCfg;
+
+    /// Configure SysTick (and DWT).
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock active, boot phase
+    unsafe fn init();
+
+    /// Handle a SysTick interrupt.
+    ///
+    /// # Safety
+    ///
+    /// Interrupt context, CPU Lock inactive
+    unsafe fn handle_tick();
+}
+
+/// The configuration function.
+pub const fn configure<System: SysTickTicklessInstance>(b: &mut CfgBuilder<System>) -> () {
+    InterruptLine::build()
+        .line(INTERRUPT_SYSTICK)
+        .priority(System::INTERRUPT_PRIORITY)
+        .finish(b);
+    InterruptHandler::build()
+        .line(INTERRUPT_SYSTICK)
+        .start(
+            #[inline]
+            |_| unsafe { System::handle_tick() },
+        )
+        .finish(b);
+
+    StartupHook::build()
+        .start(
+            #[inline]
+            |_| unsafe { System::init() },
+        )
+        .finish(b);
+}
+
+pub struct State<TicklessState> {
+    inner: UnsafeCell<StateInner<TicklessState>>,
+}
+
+// Safety: `inner` is protected from concurrent access by CPU Lock
+unsafe impl<TicklessState> Sync for State<TicklessState> {}
+
+impl<TicklessState: Init> Init for State<TicklessState> {
+    const INIT: Self = Self { inner: Init::INIT };
+}
+
+/// SysTick's counter is only 24-bit wide, and reprogramming SysTick resets the
+/// counter. `StateInner` extends it to a 32-bit counter (the *hardware tick
+/// count* supplied to [`TicklessStateTrait`]) by accumulating the lengths of
+/// elapsed periods in software. If the DWT cycle counter is in use, it's used
+/// as the hardware tick count instead.
+struct StateInner<TicklessState> {
+    tickless: TicklessState,
+    /// The hardware tick count at the beginning of the current SysTick period.
+    period_start: u32,
+    /// The length of the current SysTick period, measured in SysTick cycles.
+    /// Only the first period after [`Self::reload`] can be shorter than
+    /// [`FULL_PERIOD`].
+    period_len: u32,
+    /// The hardware tick count at which `pend_tick_after` was last called.
+    timeout_start: u32,
+    /// The number of hardware ticks, counted from `timeout_start`, after which
+    /// `timer_tick` should be called.
+    timeout_len: u32,
+    /// `pend_tick` was called, and `timer_tick` should be called on the next
+    /// SysTick interrupt regardless of `timeout_len`.
+    tick_pending: bool,
+    /// The DWT cycle counter is used as the hardware tick count instead of
+    /// the accumulated SysTick periods.
+    dwt_active: bool,
+}
+
+impl<TicklessState: Init> Init for StateInner<TicklessState> {
+    const INIT: Self = Self {
+        tickless: Init::INIT,
+        period_start: 0,
+        period_len: FULL_PERIOD,
+        timeout_start: 0,
+        timeout_len: 0,
+        tick_pending: false,
+        dwt_active: false,
+    };
+}
+
+impl<TicklessState: TicklessStateTrait> State<TicklessState> {
+    /// Configure SysTick. If `use_dwt` is `true` and the DWT cycle counter is
+    /// available, configure and use the DWT cycle counter as well.
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock active, boot phase
+    pub unsafe fn init(&self, use_dwt: bool) {
+        // Safety: We have the control of SysTick (and DWT if `use_dwt`)
+        let mut peripherals = unsafe { cortex_m::Peripherals::steal() };
+
+        if use_dwt {
+            // `SysTickOptions::FREQUENCY` specifies the processor clock
+            // frequency in this mode. Make sure SysTick counts processor clock
+            // cycles, the same thing as the DWT cycle counter does.
+            peripherals
+                .SYST
+                .set_clock_source(cortex_m::peripheral::syst::SystClkSource::Core);
+
+            // Armv6-M doesn't have a cycle counter. If the cycle counter is
+            // unavailable, fall back to the SysTick-only operation.
+            #[cfg(not(armv6m))]
+            {
+                /// `DWT_CTRL.NOCYCCNT`
+                const DWT_CTRL_NOCYCCNT: u32 = 1 << 25;
+
+                // Set `DEMCR.TRCENA` to enable DWT
+                peripherals.DCB.enable_trace();
+
+                if peripherals.DWT.ctrl.read() & DWT_CTRL_NOCYCCNT == 0 {
+                    peripherals.DWT.enable_cycle_counter();
+
+                    // Safety: CPU Lock protects it from concurrent access
+                    let inner = unsafe { &mut *self.inner.get() };
+                    inner.dwt_active = true;
+                }
+            }
+        }
+
+        peripherals.SYST.set_reload(FULL_PERIOD - 1);
+        peripherals.SYST.clear_current();
+        peripherals.SYST.enable_interrupt();
+        peripherals.SYST.enable_counter();
+
+        // The kernel will call `pend_tick_after` before releasing CPU Lock, so
+        // we don't have to program the first timeout here
+    }
+
+    /// Handle a SysTick interrupt.
+    ///
+    /// # Safety
+    ///
+    /// Interrupt context, CPU Lock inactive
+    #[inline]
+    pub unsafe fn handle_tick<System: SysTickTicklessInstance>(&self) {
+        System::acquire_cpu_lock().unwrap();
+
+        // Safety: CPU Lock protects it from concurrent access
+        let inner = unsafe { &mut *self.inner.get() };
+
+        let hw_tick_count = inner.hw_tick_count();
+        let elapsed = hw_tick_count.wrapping_sub(inner.timeout_start);
+
+        let call_timer_tick = if inner.tick_pending || elapsed >= inner.timeout_len {
+            inner.tick_pending = false;
+            inner
+                .tickless
+                .mark_reference(&System::TICKLESS_CFG, hw_tick_count);
+            true
+        } else {
+            // The timeout is longer than what SysTick can represent. It's
+            // being measured by a chain of SysTick periods. If the remaining
+            // time fits in one period, reprogram SysTick so that the next
+            // interrupt occurs exactly at the end of the timeout. Otherwise,
+            // leave SysTick running with its full period.
+            let remaining = inner.timeout_len - elapsed;
+            if remaining < FULL_PERIOD {
+                inner.reload(remaining);
+            }
+            false
+        };
+
+        // Safety: We own the CPU Lock, we are not in a boot context
+        unsafe { System::release_cpu_lock().unwrap() };
+
+        if call_timer_tick {
+            // `timer_tick` will call `pend_tick[_after]`, so it's unnecessary
+            // to reprogram SysTick here
+
+            // Safety: CPU Lock inactive, an interrupt context
+            unsafe { System::timer_tick() };
+        }
+    }
+
+    /// Implements `PortTimer::tick_count`.
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock active
+    pub unsafe fn tick_count<System: SysTickTicklessInstance>(&self) -> UTicks {
+        // Safety: CPU Lock protects it from concurrent access
+        let inner = unsafe { &mut *self.inner.get() };
+
+        let hw_tick_count = inner.hw_tick_count();
+        inner
+            .tickless
+            .tick_count(&System::TICKLESS_CFG, hw_tick_count)
+    }
+
+    /// Implements `PortTimer::pend_tick`.
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock active
+    pub unsafe fn pend_tick<System: SysTickTicklessInstance>(&self) {
+        // Safety: CPU Lock protects it from concurrent access
+        let inner = unsafe { &mut *self.inner.get() };
+
+        inner.tick_pending = true;
+
+        InterruptLine::<System>::from_num(INTERRUPT_SYSTICK)
+            .pend()
+            .unwrap();
+    }
+
+    /// Implements `PortTimer::pend_tick_after`.
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock active
+    pub unsafe fn pend_tick_after<System: SysTickTicklessInstance>(
+        &self,
+        tick_count_delta: UTicks,
+    ) {
+        // Safety: CPU Lock protects it from concurrent access
+        let inner = unsafe { &mut *self.inner.get() };
+
+        let hw_tick_count = inner.hw_tick_count();
+        let hw_ticks = inner
+            .tickless
+            .mark_reference_and_measure(&System::TICKLESS_CFG, hw_tick_count, tick_count_delta)
+            .hw_ticks;
+
+        inner.timeout_start = hw_tick_count;
+        inner.timeout_len = hw_ticks;
+
+        // If the timeout doesn't fit in one period, `handle_tick` will chain
+        // multiple periods to measure it
+        inner.reload(hw_ticks.min(FULL_PERIOD));
+    }
+}
+
+impl<TicklessState> StateInner<TicklessState> {
+    /// Get the current hardware tick count.
+    ///
+    /// This method must be called at least once per SysTick period to keep
+    /// track of wrap-arounds. `handle_tick` ensures this is the case.
+    #[inline]
+    fn hw_tick_count(&mut self) -> u32 {
+        // Safety: We have the control of SysTick
+        let mut syst = unsafe { cortex_m::Peripherals::steal() }.SYST;
+
+        let mut cvr = SYST::get_current();
+
+        // `COUNTFLAG` is set when the counter transitions from `1` to `0`, in
+        // which case the current period has ended. (Reading `SYST_CSR` clears
+        // `COUNTFLAG`.)
+        if syst.has_wrapped() {
+            self.period_start = self.period_start.wrapping_add(self.period_len);
+            self.period_len = FULL_PERIOD;
+
+            // The counter might have wrapped around after we read it. Read it
+            // again to make sure we observe a value in the new period.
+            cvr = SYST::get_current();
+        }
+
+        #[cfg(not(armv6m))]
+        if self.dwt_active {
+            // The DWT cycle counter is 32-bit wide and keeps running while we
+            // reprogram SysTick, so it's more accurate
+            return cortex_m::peripheral::DWT::get_cycle_count();
+        }
+
+        // The counter counts down from `period_len - 1` to `0`, after which it
+        // is reloaded. A value of `0` is also observed right after the counter
+        // is cleared by `reload`.
+        let elapsed = if cvr == 0 { 0 } else { self.period_len - cvr };
+
+        self.period_start.wrapping_add(elapsed)
+    }
+
+    /// Restart SysTick with a new period of `period_len` cycles. The period
+    /// is clamped to `MIN_PERIOD..=FULL_PERIOD`. The subsequent periods will
+    /// be `FULL_PERIOD` cycles long.
+    ///
+    /// Reloading SysTick adds a slight error (a few cycles) to the time
+    /// measurement because SysTick keeps counting while we are reprogramming
+    /// it.
+    fn reload(&mut self, period_len: u32) {
+        let period_len = period_len.max(MIN_PERIOD).min(FULL_PERIOD);
+
+        // Update `period_start` to account for the time elapsed so far
+        let hw_tick_count = self.hw_tick_count();
+
+        // Safety: We have the control of SysTick
+        let mut syst = unsafe { cortex_m::Peripherals::steal() }.SYST;
+
+        // Clearing the counter also clears `COUNTFLAG`. The counter is reloaded
+        // with `SYST_RVR` in the next cycle.
+        syst.set_reload(period_len - 1);
+        syst.clear_current();
+
+        self.period_start = hw_tick_count;
+        self.period_len = period_len;
+
+        // Wait until the counter is reloaded and then restore `SYST_RVR` for
+        // the subsequent periods. `MIN_PERIOD` gives us enough time to do this
+        // before the first period ends.
+        while SYST::get_current() == 0 {}
+        syst.set_reload(FULL_PERIOD - 1);
+    }
+}
@@ -26,10 +26,56 @@ pub trait ThreadingOptions {
     /// `BASEPRI`.
     const CPU_LOCK_PRIORITY_MASK: u8 = 0;
 
+    /// The priority value assigned to PendSV, which the port uses to run the
+    /// dispatcher. Defaults to `0xff` (the lowest possible priority) when
+    /// unspecified.
+    ///
+    /// The upper bound of [`MANAGED_INTERRUPT_PRIORITY_RANGE`] is bound to this
+    /// value. That is, PendSV never preempts a managed interrupt handler, and
+    /// the dispatcher always runs after all managed interrupt handlers return.
+    /// Lowering this value allows the dispatcher to run promptly relative to
+    /// interrupt handlers that are assigned lower priorities, in exchange for
+    /// making such handlers unmanaged.
+    ///
+    /// Must be in range `CPU_LOCK_PRIORITY_MASK..256` so that CPU Lock can
+    /// mask PendSV.
+    ///
+    /// [`MANAGED_INTERRUPT_PRIORITY_RANGE`]: r3::kernel::PortInterrupts::MANAGED_INTERRUPT_PRIORITY_RANGE
+    const PEND_SV_PRIORITY: u8 = 0xff;
+
     /// Enables the use of the `wfi` instruction in the idle task to save power.
     /// Defaults to `true`.
     const USE_WFI: bool = true;
 
+    /// Makes every task start with a floating-point context. Defaults to
+    /// `false`. Has no effect on a target without a hardware FPU.
+    ///
+    /// By default, a task doesn't have a floating-point context until it
+    /// executes a floating-point instruction, and the port relies on the
+    /// processor's reset configuration of `FPCCR` to detect this. When this
+    /// option is `true`, the port also sets `FPCCR.ASPEN` and `FPCCR.LSPEN` on
+    /// boot, so the floating-point context is preserved even if a bootloader
+    /// has cleared them. The dispatcher saves and restores `s16`–`s31` for
+    /// every task, and [`STACK_MINIMUM_SIZE`] is raised to accommodate the
+    /// larger initial context.
+    ///
+    /// [`STACK_MINIMUM_SIZE`]: r3::kernel::PortThreading::STACK_MINIMUM_SIZE
+    const FORCE_FP_CONTEXT: bool = false;
+
+    /// The external interrupt lines that are not connected to any peripherals
+    /// and can be used as software interrupts. Defaults to `&[]` when
+    /// unspecified.
+    ///
+    /// This is used as the value of
+    /// [`PortInterrupts::SOFTWARE_INTERRUPT_LINES`], from which
+    /// [`SoftwareInterrupt`] allocates interrupt lines. Each element must be
+    /// greater than or equal to [`INTERRUPT_EXTERNAL0`] and must not be used
+    /// for other purposes.
+    ///
+    /// [`PortInterrupts::SOFTWARE_INTERRUPT_LINES`]: r3::kernel::PortInterrupts::SOFTWARE_INTERRUPT_LINES
+    /// [`SoftwareInterrupt`]: r3::kernel::SoftwareInterrupt
+    const SOFTWARE_INTERRUPT_LINES: &'static [InterruptNum] = &[];
+
     /// Get the top of the interrupt stack. Defaults to
     /// `*(SCB.VTOR as *const u32)`.
     ///
@@ -107,9 +153,10 @@ macro_rules! use_port {
         mod port_arm_m_impl {
             use super::$sys;
             use $crate::r3::kernel::{
-                ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-                PendInterruptLineError, Port, QueryInterruptLineError, SetInterruptLinePriorityError,
-                TaskCb, PortToKernel, PortInterrupts, PortThreading, UTicks, PortTimer,
+                ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError,
+                InterruptNum, InterruptPriority, PendInterruptLineError, Port, QueryInterruptLineError,
+                SetInterruptLinePriorityError, TaskCb, PortToKernel, PortInterrupts, PortThreading,
+                UTicks, PortTimer,
             };
             use $crate::core::ops::Range;
             use $crate::threading::{
@@ -134,6 +181,15 @@ macro_rules! use_port {
                 // formatting to be particularly memory-hungry.
                 const STACK_DEFAULT_SIZE: usize = 2048;
 
+                // The initial context created by `initialize_task_state` must
+                // fit in the stack
+                const STACK_MINIMUM_SIZE: usize =
+                    if <$sys as ThreadingOptions>::FORCE_FP_CONTEXT {
+                        $crate::threading::imp::INITIAL_FP_CONTEXT_SIZE
+                    } else {
+                        $crate::threading::imp::INITIAL_CONTEXT_SIZE
+                    };
+
                 unsafe fn dispatch_first_task() -> ! {
                     port_state().dispatch_first_task::<Self>()
                 }
@@ -142,6 +198,11 @@ macro_rules! use_port {
                     port_state().yield_cpu::<Self>()
                 }
 
+                #[inline(always)]
+                unsafe fn idle() {
+                    port_state().idle::<Self>()
+                }
+
                 unsafe fn exit_and_dispatch(task: &'static TaskCb<Self>) -> ! {
                     port_state().exit_and_dispatch::<Self>(task);
                 }
@@ -171,7 +232,11 @@ macro_rules! use_port {
 
             unsafe impl PortInterrupts for $sys {
                 const MANAGED_INTERRUPT_PRIORITY_RANGE: Range<InterruptPriority> =
-                    (<$sys as ThreadingOptions>::CPU_LOCK_PRIORITY_MASK as _)..256;
+                    (<$sys as ThreadingOptions>::CPU_LOCK_PRIORITY_MASK as _)
+                        ..(<$sys as ThreadingOptions>::PEND_SV_PRIORITY as InterruptPriority + 1);
+
+                const SOFTWARE_INTERRUPT_LINES: &'static [InterruptNum] =
+                    <$sys as ThreadingOptions>::SOFTWARE_INTERRUPT_LINES;
 
                 unsafe fn set_interrupt_line_priority(
                     line: InterruptNum,
@@ -180,6 +245,12 @@ macro_rules! use_port {
                     port_state().set_interrupt_line_priority::<Self>(line, priority)
                 }
 
+                unsafe fn get_interrupt_line_priority(
+                    line: InterruptNum,
+                ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+                    port_state().get_interrupt_line_priority::<Self>(line)
+                }
+
                 unsafe fn enable_interrupt_line(line: InterruptNum) -> Result<(), EnableInterruptLineError> {
                     port_state().enable_interrupt_line::<Self>(line)
                 }
@@ -201,6 +272,12 @@ macro_rules! use_port {
                 ) -> Result<bool, QueryInterruptLineError> {
                     port_state().is_interrupt_line_pending::<Self>(line)
                 }
+
+                unsafe fn is_interrupt_line_enabled(
+                    line: InterruptNum,
+                ) -> Result<bool, QueryInterruptLineError> {
+                    port_state().is_interrupt_line_enabled::<Self>(line)
+                }
             }
 
             unsafe impl EntryPoint for $sys {
@@ -2,9 +2,9 @@ use core::{cell::UnsafeCell, mem::MaybeUninit, slice};
 use memoffset::offset_of;
 use r3::{
     kernel::{
-        ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-        PendInterruptLineError, Port, PortToKernel, QueryInterruptLineError,
-        SetInterruptLinePriorityError, TaskCb,
+        ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError,
+        InterruptNum, InterruptPriority, PendInterruptLineError, Port, PortThreading, PortToKernel,
+        QueryInterruptLineError, SetInterruptLinePriorityError, TaskCb,
     },
     prelude::*,
     utils::{Init, ZeroInit},
@@ -19,6 +19,23 @@ use crate::{
     INTERRUPT_SYSTICK,
 };
 
+/// The size of the initial context created on a task stack by
+/// `initialize_task_state`, measured in bytes. It consists of an exception
+/// frame (8 words) and the extra context saved by our context switching code
+/// (10 words).
+pub const INITIAL_CONTEXT_SIZE: usize = (8 + 10) * 4;
+
+/// The size of the initial context created on a task stack by
+/// `initialize_task_state` when [`ThreadingOptions::FORCE_FP_CONTEXT`] is
+/// `true`, measured in bytes. The exception frame is extended with `s0`–`s15`,
+/// `FPSCR`, and a reserved word (18 words), and the extra context with
+/// `s16`–`s31` (16 words).
+pub const INITIAL_FP_CONTEXT_SIZE: usize = if cfg!(has_fpu) {
+    INITIAL_CONTEXT_SIZE + (18 + 16) * 4
+} else {
+    INITIAL_CONTEXT_SIZE
+};
+
 /// Implemented on a system type by [`use_port!`].
 ///
 /// # Safety
@@ -48,6 +65,9 @@ pub struct State {
     /// be accessed in naked functions. This field is actually of type
     /// `*mut Option<&'static TaskCb<System>>`.
     running_task_ptr: UnsafeCell<*mut ()>,
+    /// A function called by the dispatcher after choosing the next task, with
+    /// CPU Lock active. Used by [`use_mpu!`] to reprogram the MPU.
+    dispatch_hook: UnsafeCell<Option<unsafe fn()>>,
 }
 
 impl State {
@@ -61,6 +81,11 @@ unsafe impl ZeroInit for State {}
 #[repr(C)]
 pub struct TaskState {
     sp: UnsafeCell<u32>,
+    /// The value loaded to `PSPLIM` when the task is dispatched. This is the
+    /// lower bound of the task's stack. `handle_pend_sv` assumes this field
+    /// is located at offset `4`.
+    #[cfg(armv8m)]
+    sp_limit: UnsafeCell<u32>,
 }
 
 unsafe impl Sync for TaskState {}
@@ -68,6 +93,8 @@ unsafe impl Sync for TaskState {}
 impl Init for TaskState {
     const INIT: Self = Self {
         sp: UnsafeCell::new(0),
+        #[cfg(armv8m)]
+        sp_limit: UnsafeCell::new(0),
     };
 }
 
@@ -86,9 +113,30 @@ impl State {
             peripherals
                 .SCB
                 .set_priority(cortex_m::peripheral::scb::SystemHandler::SVCall, 0xff);
-            peripherals
-                .SCB
-                .set_priority(cortex_m::peripheral::scb::SystemHandler::PendSV, 0xff);
+            peripherals.SCB.set_priority(
+                cortex_m::peripheral::scb::SystemHandler::PendSV,
+                System::PEND_SV_PRIORITY,
+            );
+        }
+
+        // Enable automatic and lazy floating-point context stacking. The
+        // dispatcher relies on `EXC_RETURN.FType` to determine whether the
+        // current task has a floating-point context. These bits are set on
+        // reset, but a bootloader might have cleared them. Only done when
+        // requested by `FORCE_FP_CONTEXT` so that the port doesn't override
+        // the application's configuration otherwise.
+        #[cfg(has_fpu)]
+        if System::FORCE_FP_CONTEXT {
+            const FPCCR_ASPEN: u32 = 1 << 31;
+            const FPCCR_LSPEN: u32 = 1 << 30;
+            // Safety: The floating-point context of the boot context is
+            // discarded soon
+            unsafe {
+                peripherals
+                    .FPU
+                    .fpccr
+                    .modify(|x| x | FPCCR_ASPEN | FPCCR_LSPEN)
+            };
         }
 
         // Safety: We are a port, so it's okay to call this
@@ -97,6 +145,16 @@ impl State {
         }
     }
 
+    /// Set a function to be called by the dispatcher after choosing the next
+    /// task.
+    ///
+    /// # Safety
+    ///
+    /// Boot phase
+    pub(crate) unsafe fn set_dispatch_hook(&self, hook: unsafe fn()) {
+        unsafe { *self.dispatch_hook.get() = Some(hook) };
+    }
+
     pub unsafe fn dispatch_first_task<System: PortInstance>(&'static self) -> ! {
         // Pend PendSV
         cortex_m::peripheral::SCB::set_pendsv();
@@ -130,7 +188,9 @@ impl State {
             # The idle task uses MSP as its stack.
             mov sp, {msp_top}
 
-            # TODO: Set MSPLIM on Armv8-M
+            # `MSPLIM` is left untouched on Armv8-M because the lower bound of
+            # the interrupt stack is unknown to the port. `PSPLIM` is set by
+            # the dispatcher.
 
             # Release CPU Lock
             # TODO: Choose the appropriate method based on `CPU_LOCK_PRIORITY_MASK` "
@@ -143,24 +203,35 @@ impl State {
             msp_top = in(reg) msp_top,
         );
 
-        if System::USE_WFI {
-            pp_asm!(
-                "
-            0:
-                wfi
-                b 0b
-            ",
-                options(noreturn),
-            );
-        } else {
-            pp_asm!(
-                "
-            0:
-                b 0b
-            ",
-                options(noreturn),
-            );
-        }
+        // The idle loop. The idle task doesn't check for a runnable task by
+        // itself; the dispatcher (PendSV handler) does that and switches away
+        // from the idle task when it finds one. Therefore, there's no window
+        // between "checking for a runnable task" and "going to sleep" in which
+        // a wake-up event could be lost: An interrupt that makes a task
+        // runnable pends PendSV, and a pending exception with a sufficient
+        // priority to preempt the current execution causes `wfi` to return
+        // (or not to sleep in the first place). Interrupts are enabled at this
+        // point, so PendSV is taken right after that and the idle loop is left
+        // behind.
+        pp_asm!(
+            "
+        0:
+            bl {idle}
+            b 0b
+        ",
+            idle = sym Self::idle_loop_body::<System>,
+            options(noreturn),
+        );
+    }
+
+    /// The body of the idle loop.
+    ///
+    /// # Safety
+    ///
+    /// CPU Lock inactive, the idle task
+    unsafe extern "C" fn idle_loop_body<System: PortInstance>() {
+        // Safety: Upheld by the caller
+        unsafe { <System as PortThreading>::idle() };
     }
 
     pub unsafe fn yield_cpu<System: PortInstance>(&'static self) {
@@ -168,6 +239,13 @@ impl State {
         cortex_m::peripheral::SCB::set_pendsv();
     }
 
+    #[inline(always)]
+    pub unsafe fn idle<System: PortInstance>(&'static self) {
+        if System::USE_WFI {
+            cortex_m::asm::wfi();
+        }
+    }
+
     pub unsafe fn exit_and_dispatch<System: PortInstance>(
         &'static self,
         _task: &'static TaskCb<System>,
@@ -213,9 +291,14 @@ impl State {
     ///
     #[naked]
     pub unsafe extern "C" fn handle_pend_sv<System: PortInstance>() {
-        // Precondition:
-        //  - `EXC_RETURN.Mode == 1` - Exception was taken in Thread mode. This
-        //    is true because PendSV is configured with the lowest priority.
+        // Precondition (after the `EXC_RETURN.Mode` check):
+        //  - `EXC_RETURN.Mode == 1` - Exception was taken in Thread mode.
+        //    PendSV is configured with a priority not higher than any managed
+        //    interrupt handlers (`PEND_SV_PRIORITY` bounds
+        //    `MANAGED_INTERRUPT_PRIORITY_RANGE`), but it can preempt an
+        //    unmanaged interrupt handler with a lower priority if
+        //    `PEND_SV_PRIORITY` is raised. In this case, the dispatch is
+        //    deferred by `defer_dispatch`.
         //  - `SPSEL.Mode == 1 && running_task.is_some()` - If the interrupted
         //    context is not the idle task, the exception frame should have been
         //    stacked to PSP.
@@ -223,17 +306,62 @@ impl State {
         //    context is the idle task, the exception frame should have been
         //    stacked to MSP.
 
+        extern "C" fn defer_dispatch<System: PortInstance>() {
+            // Lower PendSV's priority and pend it again, so that it's taken
+            // when all interrupt handlers return to Thread mode
+            // Safety: We don't make "priority-based critical sections"
+            unsafe {
+                cortex_m::Peripherals::steal()
+                    .SCB
+                    .set_priority(cortex_m::peripheral::scb::SystemHandler::PendSV, 0xff)
+            };
+            cortex_m::peripheral::SCB::set_pendsv();
+        }
+
         extern "C" fn choose_next_task<System: PortInstance>() {
             // Choose the next task to run
             unsafe { State::enter_cpu_lock_inner::<System>() };
 
+            // Restore PendSV's priority, which might have been lowered by
+            // `defer_dispatch`
+            if System::PEND_SV_PRIORITY != 0xff {
+                // Safety: We don't make "priority-based critical sections"
+                unsafe {
+                    cortex_m::Peripherals::steal().SCB.set_priority(
+                        cortex_m::peripheral::scb::SystemHandler::PendSV,
+                        System::PEND_SV_PRIORITY,
+                    )
+                };
+            }
+
             // Safety: CPU Lock active
             unsafe { System::choose_running_task() };
 
+            // Safety: CPU Lock active
+            if let Some(hook) = unsafe { *System::port_state().dispatch_hook.get() } {
+                unsafe { hook() };
+            }
+
             unsafe { State::leave_cpu_lock_inner::<System>() };
         }
 
         pp_asm!("
+            # Defer the dispatch if PendSV preempted an interrupt handler
+            #
+            #    <lr = EXC_RETURN>
+            #
+            #    if (lr & Mode) == 0:
+            #        defer_dispatch();
+            #        return;
+
+            mov r0, lr
+            lsls r0, r0, #28
+            bmi 1f
+            push {{r0, lr}}
+            bl {defer_dispatch}
+            pop {{r0, pc}}
+        1:
+
             # Save the context of the previous task
             #
             #    <r4-r11 = context,
@@ -305,6 +433,8 @@ impl State {
             #    r1 = running_task
             #    if r1.is_some():
             #        r2 = r1.port_task_state.sp
+            #        if cfg!(armv8m):
+            #            psplim = r1.port_task_state.sp_limit
             #
             #        lr = r2[0]
             #        control = r2[1]
@@ -337,6 +467,13 @@ impl State {
                 cbz r1, 0f
         "   }                                                               "
             ldr r2, [r1]                                                    "
+            if cfg!(armv8m) {                                               "
+                # Set the stack limit before switching to the task's stack so
+                # that an overflow traps instead of corrupting memory
+                ldr r3, [r1, #4]
+                msr psplim, r3
+        "   }                                                               "
+                                                                            "
             if cfg!(any(armv6m, armv8m_base)) {                             "
                 ldmia r2!, {{r0, r3}}
                 mov lr, r0
@@ -373,6 +510,7 @@ impl State {
             msr control, r0
             bx lr
         ",
+            defer_dispatch = sym defer_dispatch::<System>,
             choose_next_task = sym choose_next_task::<System>,
             PORT_STATE = sym System::PORT_STATE,
             OFFSET_RUNNING_TASK_PTR = const Self::OFFSET_RUNNING_TASK_PTR,
@@ -422,9 +560,42 @@ impl State {
     ) {
         let stack = task.attr.stack.as_ptr();
         let mut sp = (stack as *mut u8).wrapping_add(stack.len()) as *mut MaybeUninit<u32>;
-        // TODO: Enforce minimum stack size
 
         let preload_all = cfg!(feature = "preload-registers");
+        let fp_context = cfg!(has_fpu) && System::FORCE_FP_CONTEXT;
+
+        // Writing the initial context to an undersized stack would silently
+        // corrupt the memory below the stack region. The configurator rejects
+        // such a stack by `STACK_MINIMUM_SIZE`, so this is just a sanity check.
+        let initial_context_size = if fp_context {
+            INITIAL_FP_CONTEXT_SIZE
+        } else {
+            INITIAL_CONTEXT_SIZE
+        };
+        debug_assert!(
+            stack.len() >= initial_context_size,
+            "the stack of task {:p} is too small ({} bytes) to hold the initial \
+             context ({} bytes)",
+            task,
+            stack.len(),
+            initial_context_size,
+        );
+
+        // Floating-point part of the exception frame, which is present if
+        // `EXC_RETURN.FType == 0`
+        #[cfg(has_fpu)]
+        if fp_context {
+            let exc_frame_fp = unsafe {
+                sp = sp.wrapping_sub(18);
+                slice::from_raw_parts_mut(sp, 18)
+            };
+
+            // S0-S15, Reserved: Uninitialized
+            // FPSCR: The default value that the processor would use for a new
+            // floating-point context
+            exc_frame_fp[16] =
+                MaybeUninit::new(unsafe { (*cortex_m::peripheral::FPU::ptr()).fpdscr.read() });
+        }
 
         // Exception frame (automatically saved and restored as part of
         // the architectually-defined exception entry/return sequence)
@@ -457,36 +628,51 @@ impl State {
 
         // Extra context (saved and restored by our code as part of context
         // switching)
+        let extra_ctx_len = if fp_context { 10 + 16 } else { 10 };
         let extra_ctx = unsafe {
-            sp = sp.wrapping_sub(10);
-            slice::from_raw_parts_mut(sp, 10)
+            sp = sp.wrapping_sub(extra_ctx_len);
+            slice::from_raw_parts_mut(sp, extra_ctx_len)
         };
 
         // EXC_RETURN: 0xfffffffd (“Return to Thread Mode; Exception return gets
         //             state from the Process stack; On return execution uses
         //             the Process Stack.”)
+        //             `EXC_RETURN.FType == 1`, so the task starts without a
+        //             floating-point context. The processor creates one when
+        //             the task executes a floating-point instruction.
+        //             If `FORCE_FP_CONTEXT` is `true`, `EXC_RETURN.FType` is
+        //             cleared, so the task starts with one.
         // TODO: This differs for Armv8-M
         // TODO: Plus, we shouldn't hard-code this here
-        extra_ctx[0] = MaybeUninit::new(0xfffffffd);
+        extra_ctx[0] = MaybeUninit::new(if fp_context { 0xffffffed } else { 0xfffffffd });
         // CONTROL: SPSEL = 1 (Use PSP)
         extra_ctx[1] = MaybeUninit::new(0x00000002);
         // TODO: Secure context (Armv8-M)
-        // TODO: PSPLIM
+
+        // S16-S31: Uninitialized (present only if `fp_context`)
 
         // R4-R11: Uninitialized
+        let gprs = &mut extra_ctx[extra_ctx_len - 8..];
         if preload_all {
-            extra_ctx[2] = MaybeUninit::new(0x04040404);
-            extra_ctx[3] = MaybeUninit::new(0x05050505);
-            extra_ctx[4] = MaybeUninit::new(0x06060606);
-            extra_ctx[5] = MaybeUninit::new(0x07070707);
-            extra_ctx[6] = MaybeUninit::new(0x08080808);
-            extra_ctx[7] = MaybeUninit::new(0x09090909);
-            extra_ctx[8] = MaybeUninit::new(0x10101010);
-            extra_ctx[9] = MaybeUninit::new(0x11111111);
+            gprs[0] = MaybeUninit::new(0x04040404);
+            gprs[1] = MaybeUninit::new(0x05050505);
+            gprs[2] = MaybeUninit::new(0x06060606);
+            gprs[3] = MaybeUninit::new(0x07070707);
+            gprs[4] = MaybeUninit::new(0x08080808);
+            gprs[5] = MaybeUninit::new(0x09090909);
+            gprs[6] = MaybeUninit::new(0x10101010);
+            gprs[7] = MaybeUninit::new(0x11111111);
         }
 
         let task_state = &task.port_task_state;
         unsafe { *task_state.sp.get() = sp as _ };
+
+        // PSPLIM: The lower bound of the stack. `PSPLIM[2:0]` is RES0, so
+        // round it up to a multiple of 8 bytes.
+        #[cfg(armv8m)]
+        unsafe {
+            *task_state.sp_limit.get() = ((stack as *mut u8 as u32) + 7) & !7
+        };
     }
 
     #[inline(always)]
@@ -533,6 +719,25 @@ impl State {
         }
     }
 
+    pub fn get_interrupt_line_priority<System: PortInstance>(
+        &'static self,
+        num: InterruptNum,
+    ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+        // Note: The unimplemented low-order bits of `NVIC_IPRn` and `SHPRn`
+        //       read as zero
+        if !INTERRUPT_NUM_RANGE.contains(&num) {
+            Err(GetInterruptLinePriorityError::BadParam)
+        } else if num >= INTERRUPT_EXTERNAL0 {
+            Ok(cortex_m::peripheral::NVIC::get_priority(Int(num)) as _)
+        } else if num == INTERRUPT_SYSTICK {
+            Ok(cortex_m::peripheral::SCB::get_priority(
+                cortex_m::peripheral::scb::SystemHandler::SysTick,
+            ) as _)
+        } else {
+            Err(GetInterruptLinePriorityError::BadParam)
+        }
+    }
+
     #[inline]
     pub fn enable_interrupt_line<System: PortInstance>(
         &'static self,
@@ -615,6 +820,20 @@ impl State {
             Err(QueryInterruptLineError::BadParam)
         }
     }
+
+    #[inline]
+    pub fn is_interrupt_line_enabled<System: PortInstance>(
+        &self,
+        num: InterruptNum,
+    ) -> Result<bool, QueryInterruptLineError> {
+        if !INTERRUPT_NUM_RANGE.contains(&num) {
+            Err(QueryInterruptLineError::BadParam)
+        } else if num >= INTERRUPT_EXTERNAL0 {
+            Ok(cortex_m::peripheral::NVIC::is_enabled(Int(num)))
+        } else {
+            Err(QueryInterruptLineError::BadParam)
+        }
+    }
 }
 
 /// Used by `use_port!`
@@ -625,4 +844,10 @@ pub const fn validate<System: PortInstance>() {
         "`CPU_LOCK_PRIORITY_MASK` must be zero because the target architecture \
          does not have a BASEPRI register"
     );
+
+    assert!(
+        System::PEND_SV_PRIORITY >= System::CPU_LOCK_PRIORITY_MASK,
+        "`PEND_SV_PRIORITY` must not be higher than `CPU_LOCK_PRIORITY_MASK` \
+         because PendSV must be masked by CPU Lock"
+    );
 }
@@ -0,0 +1,134 @@
+//! Assigns a read-only MPU region to a task and checks that the task can read
+//! from the region but a write to the region triggers a MemManage fault
+//! (HardFault on Armv6-M). Another task, which doesn't have the region, checks
+//! that the region is writable when the task isn't running. Requires the `mpu`
+//! feature.
+use core::sync::atomic::{AtomicU32, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use crate::fault::{set_fault_handler, Fault};
+
+/// The binary logarithm of the region size in bytes
+const REGION_SIZE_LOG2: u8 = 5;
+
+/// Configurable Fault Status Register
+#[cfg(not(armv6m))]
+const CFSR: *const u32 = 0xe000ed28 as _;
+/// `CFSR.DACCVIOL`: A data access violation caused the MemManage fault
+#[cfg(not(armv6m))]
+const CFSR_DACCVIOL: u32 = 1 << 1;
+/// `CFSR.MMARVALID`: `MMFAR` holds a valid fault address
+#[cfg(not(armv6m))]
+const CFSR_MMARVALID: u32 = 1 << 7;
+/// MemManage Fault Address Register
+#[cfg(not(armv6m))]
+const MMFAR: *const u32 = 0xe000ed34 as _;
+
+pub struct App<System> {
+    #[cfg_attr(not(feature = "mpu"), allow(dead_code))]
+    task1: Task<System>,
+    /// The memory covered by the read-only region
+    data: Hunk<System, [AtomicU32]>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let data = Hunk::<_, [AtomicU32]>::build()
+            .len((1 << REGION_SIZE_LOG2) / 4)
+            .align(1 << REGION_SIZE_LOG2)
+            .finish(b);
+
+        #[cfg(feature = "mpu")]
+        r3::kernel::StartupHook::build()
+            .start(startup_hook::<System, D>)
+            .finish(b);
+
+        App { task1, data }
+    }
+}
+
+#[cfg(feature = "mpu")]
+fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    use r3_port_arm_m::{MpuRegion, MpuRegionAttrs};
+
+    let App { task1, data } = D::app();
+    let region = MpuRegion {
+        base: data.as_ptr() as u32,
+        size_log2: REGION_SIZE_LOG2,
+        attrs: MpuRegionAttrs::RO
+            .union(MpuRegionAttrs::NORMAL)
+            .union(MpuRegionAttrs::EXECUTE_NEVER),
+    };
+
+    // Safety: We are in the boot phase
+    unsafe { crate::mpu::set_task_region(task1.id(), region) };
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    if cfg!(not(feature = "mpu")) {
+        log::warn!("The `mpu` feature is disabled, skipping the test");
+        D::success();
+        return;
+    }
+
+    // The region isn't applied to this task
+    D::app().data[0].store(42, Ordering::Relaxed);
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let data = &D::app().data[0];
+
+    // The region is readable
+    assert_eq!(data.load(Ordering::Relaxed), 42);
+
+    set_fault_handler(fault_handler::<System, D>);
+
+    log::debug!("writing to the read-only region");
+    data.store(1, Ordering::Relaxed);
+
+    panic!("the write to the read-only region succeeded");
+}
+
+fn fault_handler<System: Kernel, D: Driver<App<System>>>(fault: Fault) {
+    log::debug!("caught {:?}", fault);
+
+    let data = &D::app().data[0];
+
+    #[cfg(not(armv6m))]
+    {
+        assert_eq!(fault, Fault::MemManage);
+
+        let cfsr = unsafe { CFSR.read_volatile() };
+        log::debug!("CFSR = {:#010x}", cfsr);
+        assert_ne!(cfsr & CFSR_DACCVIOL, 0, "CFSR.DACCVIOL is not set");
+        if cfsr & CFSR_MMARVALID != 0 {
+            let mmfar = unsafe { MMFAR.read_volatile() };
+            assert_eq!(mmfar, data as *const AtomicU32 as u32);
+        }
+    }
+
+    // Armv6-M doesn't have configurable fault exceptions
+    #[cfg(armv6m)]
+    assert_eq!(fault, Fault::HardFault);
+
+    // The write didn't take effect
+    assert_eq!(data.load(Ordering::Relaxed), 42);
+
+    D::success();
+}
@@ -0,0 +1,127 @@
+//! Pends PendSV while an interrupt handler with a lower priority than PendSV is
+//! running and checks that the context switch is deferred until the handler
+//! returns. This test is meaningful only when PendSV's priority is raised by
+//! the `pend-sv-priority-raised` feature.
+use core::sync::atomic::{AtomicBool, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, InterruptNum, Task},
+    prelude::*,
+    utils::Init,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+/// The high-priority interrupt line, which is managed.
+const INT_LINE_HIGH: InterruptNum = r3_port_arm_m::INTERRUPT_EXTERNAL0;
+const HIGH_PRIORITY: u8 = 0x20;
+
+/// The low-priority interrupt line. Its priority is lower than
+/// `PEND_SV_PRIORITY` when the `pend-sv-priority-raised` feature is enabled.
+const INT_LINE_LOW: InterruptNum = r3_port_arm_m::INTERRUPT_EXTERNAL0 + 2;
+const LOW_PRIORITY: u8 = 0x80;
+
+pub struct App<System> {
+    task2: Task<System>,
+    int_low: InterruptLine<System>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// Set when the low-priority interrupt handler is about to return.
+    low_handler_done: AtomicBool,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        low_handler_done: Init::INIT,
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        InterruptHandler::build()
+            .line(INT_LINE_HIGH)
+            .start(isr_high::<System, D>)
+            .finish(b);
+        InterruptLine::build()
+            .line(INT_LINE_HIGH)
+            .priority(HIGH_PRIORITY as _)
+            .enabled(true)
+            .finish(b);
+
+        // Safety: `isr_low` doesn't make system calls
+        unsafe {
+            InterruptHandler::build()
+                .line(INT_LINE_LOW)
+                .unmanaged()
+                .start(isr_low::<System, D>)
+                .finish(b);
+        }
+        let int_low = InterruptLine::build()
+            .line(INT_LINE_LOW)
+            .priority(LOW_PRIORITY as _)
+            .enabled(true)
+            .finish(b);
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App {
+            task2,
+            int_low,
+            state,
+        }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    log::debug!("pending the low-priority interrupt line");
+
+    // Switches to `task2` after `isr_low` returns
+    D::app().int_low.pend().unwrap();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // The context switch must not have happened inside `isr_low`
+    assert!(D::app().state.low_handler_done.load(Ordering::Relaxed));
+
+    D::success();
+}
+
+fn isr_low<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // Pend the high-priority interrupt line without making a system call.
+    // `isr_high` preempts this handler and activates `task2`, which pends
+    // PendSV. If `PEND_SV_PRIORITY` is higher than `LOW_PRIORITY`, PendSV
+    // also preempts this handler, and the dispatcher has to defer the context
+    // switch.
+    // Safety: `ISPR` is a set-only register
+    unsafe {
+        let nvic = &*cortex_m::peripheral::NVIC::ptr();
+        let irq = INT_LINE_HIGH - r3_port_arm_m::INTERRUPT_EXTERNAL0;
+        nvic.ispr[irq / 32].write(1 << (irq % 32));
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+
+    D::app()
+        .state
+        .low_handler_done
+        .store(true, Ordering::Relaxed);
+}
+
+fn isr_high<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // The low-priority handler is still running
+    assert!(!D::app().state.low_handler_done.load(Ordering::Relaxed));
+
+    D::app().task2.activate().unwrap();
+}
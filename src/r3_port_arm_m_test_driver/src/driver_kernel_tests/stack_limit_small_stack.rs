@@ -0,0 +1,86 @@
+//! Runs a function with a large stack frame in a task with a small stack and
+//! checks that the resulting stack overflow is reported as a stack limit
+//! violation. Requires Armv8-M, on which the port sets `PSPLIM` for each task.
+use core::ptr;
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use crate::fault::{set_fault_handler, Fault};
+
+/// Configurable Fault Status Register
+#[cfg(armv8m_main)]
+const CFSR: *const u32 = 0xe000ed28 as _;
+/// `CFSR.STKOF`: A stack limit violation caused the UsageFault
+#[cfg(armv8m_main)]
+const CFSR_STKOF: u32 = 1 << 20;
+
+pub struct App<System> {
+    _phantom: core::marker::PhantomData<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .stack_size(256)
+            .finish(b);
+
+        App {
+            _phantom: core::marker::PhantomData,
+        }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    if !cfg!(armv8m) {
+        log::warn!("The target doesn't support stack limit checking, skipping the test");
+        D::success();
+        return;
+    }
+
+    set_fault_handler(fault_handler::<System, D>);
+
+    use_large_frame();
+
+    panic!("the stack overflow wasn't detected");
+}
+
+/// Allocate a stack frame much larger than the task's stack.
+#[inline(never)]
+fn use_large_frame() {
+    // The volatile accesses prevent the compiler from eliminating the array
+    let mut buf = [0u8; 1024];
+    for x in buf.iter_mut() {
+        unsafe { ptr::write_volatile(x, 1) };
+    }
+}
+
+fn fault_handler<System: Kernel, D: Driver<App<System>>>(fault: Fault) {
+    log::debug!("caught {:?}", fault);
+
+    // Armv8-M Mainline: UsageFault, which is escalated to HardFault if it's
+    // disabled. The cause is recorded in `CFSR` in either case.
+    #[cfg(armv8m_main)]
+    {
+        assert!(
+            fault == Fault::UsageFault || fault == Fault::HardFault,
+            "unexpected {:?}",
+            fault
+        );
+
+        let cfsr = unsafe { CFSR.read_volatile() };
+        log::debug!("CFSR = {:#010x}", cfsr);
+        assert_ne!(cfsr & CFSR_STKOF, 0, "CFSR.STKOF is not set");
+    }
+
+    // Armv8-M Baseline doesn't have configurable fault exceptions
+    #[cfg(armv8m_base)]
+    assert_eq!(fault, Fault::HardFault);
+
+    D::success();
+}
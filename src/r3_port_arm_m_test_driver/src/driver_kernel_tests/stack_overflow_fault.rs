@@ -0,0 +1,111 @@
+//! Makes a task overflow its stack by unbounded recursion and checks that the
+//! overflow is trapped by a fault before it clobbers the memory below the
+//! stack. Requires Armv8-M, on which the port sets `PSPLIM` for each task.
+use core::ptr;
+use r3::{
+    kernel::{self, cfg::CfgBuilder, StackHunk, Task},
+    prelude::*,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use crate::fault::{set_fault_handler, Fault};
+
+const STACK_LEN: usize = 1024;
+
+/// The length of the guard region placed immediately below the task's stack
+const GUARD_LEN: usize = 256;
+const GUARD_PATTERN: u8 = 0xa5;
+
+pub struct App<System> {
+    /// The guard region, immediately followed by the task's stack
+    guard: kernel::Hunk<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let guard = kernel::Hunk::build()
+            .len(GUARD_LEN + STACK_LEN)
+            .align(8)
+            .finish(b);
+
+        // Safety: The region following the guard region is solely used for
+        // the task's stack
+        let stack = unsafe {
+            StackHunk::from_hunk(
+                kernel::Hunk::from_offset(guard.offset() + GUARD_LEN),
+                STACK_LEN,
+            )
+        };
+
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .stack_hunk(stack)
+            .finish(b);
+
+        App { guard }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    if !cfg!(armv8m) {
+        log::warn!("The target doesn't support stack limit checking, skipping the test");
+        D::success();
+        return;
+    }
+
+    let guard = D::app().guard.as_ptr();
+    for i in 0..GUARD_LEN {
+        unsafe { guard.add(i).write_volatile(GUARD_PATTERN) };
+    }
+
+    set_fault_handler(fault_handler::<System, D>);
+
+    log::debug!("overflowing the stack");
+    let depth = recurse(guard as usize + GUARD_LEN, 0);
+
+    panic!(
+        "the stack overflow wasn't detected (recursion depth = {})",
+        depth
+    );
+}
+
+/// Recurse until a stack overflow is detected. Returns when the stack pointer
+/// enters the guard region, which means the overflow went undetected.
+#[inline(never)]
+fn recurse(stack_bottom: usize, depth: usize) -> usize {
+    // Consume the stack space. The volatile accesses prevent the compiler
+    // from eliminating the array.
+    let mut buf = [0u8; 32];
+    if (buf.as_ptr() as usize) < stack_bottom {
+        return depth;
+    }
+    unsafe { ptr::write_volatile(&mut buf[0], depth as u8) };
+
+    recurse(stack_bottom, depth + 1) + unsafe { ptr::read_volatile(&buf[0]) } as usize
+}
+
+fn fault_handler<System: Kernel, D: Driver<App<System>>>(fault: Fault) {
+    log::debug!("caught {:?}", fault);
+
+    // UsageFault is escalated to HardFault if it's disabled
+    assert!(
+        fault == Fault::UsageFault || fault == Fault::HardFault,
+        "unexpected {:?}",
+        fault
+    );
+
+    // The memory below the stack must be intact
+    let guard = D::app().guard.as_ptr();
+    for i in 0..GUARD_LEN {
+        assert_eq!(
+            unsafe { guard.add(i).read_volatile() },
+            GUARD_PATTERN,
+            "the guard region was clobbered at offset {}",
+            i
+        );
+    }
+
+    D::success();
+}
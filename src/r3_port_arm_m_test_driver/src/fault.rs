@@ -0,0 +1,50 @@
+//! Fault exception handlers. Driver-defined tests can register a function to be
+//! called when a fault is taken. Unexpected faults cause a panic, which is
+//! reported as a test failure.
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The kind of a fault exception.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    HardFault,
+    MemManage,
+    BusFault,
+    UsageFault,
+}
+
+/// The registered fault handler (`fn(Fault)`), or `0` if there is none.
+static FAULT_HANDLER: AtomicUsize = AtomicUsize::new(0);
+
+/// Register a function to be called when a fault exception is taken. The
+/// function is expected to report the test result and not return.
+pub fn set_fault_handler(handler: fn(Fault)) {
+    FAULT_HANDLER.store(handler as usize, Ordering::Relaxed);
+}
+
+fn handle_fault(fault: Fault) -> ! {
+    let handler = FAULT_HANDLER.load(Ordering::Relaxed);
+    if handler != 0 {
+        // Safety: `FAULT_HANDLER` only contains `0` or `fn(Fault)`
+        let handler: fn(Fault) = unsafe { core::mem::transmute(handler) };
+        handler(fault);
+        panic!("the fault handler returned after handling {:?}", fault);
+    }
+
+    panic!("unexpected {:?}", fault);
+}
+
+#[cortex_m_rt::exception]
+fn HardFault(_: &cortex_m_rt::ExceptionFrame) -> ! {
+    handle_fault(Fault::HardFault)
+}
+
+#[cortex_m_rt::exception]
+fn DefaultHandler(irqn: i16) {
+    let fault = match irqn {
+        -12 => Fault::MemManage,
+        -11 => Fault::BusFault,
+        -10 => Fault::UsageFault,
+        _ => panic!("unexpected exception (IRQn = {})", irqn),
+    };
+    handle_fault(fault)
+}
@@ -9,10 +9,16 @@
 
 #[cfg(feature = "board-rp_pico")]
 mod board_rp2040;
+#[cfg(feature = "kernel_tests")]
+mod fault;
 #[cfg(feature = "output-rtt")]
 mod logger_rtt;
 #[cfg(feature = "output-semihosting")]
 mod logger_semihosting;
+#[cfg(feature = "mpu")]
+mod mpu;
+#[cfg(feature = "output-semihosting-write0")]
+mod output_write0;
 
 #[allow(unused_macros)]
 macro_rules! instantiate_test {
@@ -35,6 +41,7 @@ macro_rules! instantiate_test {
         #[cfg(feature = "output-semihosting")]
         use panic_semihosting as _;
         // `board-rp_pico`: provided by `crate::board_rp2040`
+        // `output-semihosting-write0`: provided by `crate::output_write0`
 
         fn report_success() {
             // The test runner will catch this
@@ -44,6 +51,9 @@ macro_rules! instantiate_test {
             #[cfg(feature = "output-semihosting")]
             cortex_m_semihosting::hprintln!("!- TEST WAS SUCCESSFUL -!").unwrap();
 
+            #[cfg(feature = "output-semihosting-write0")]
+            output_write0::stdout_write_fmt(format_args!("!- TEST WAS SUCCESSFUL -!\n"));
+
             #[cfg(feature = "board-rp_pico")]
             r3_support_rp2040::sprintln!(
                 "{}!- TEST WAS SUCCESSFUL -!",
@@ -62,7 +72,10 @@ macro_rules! instantiate_test {
 
         port::use_port!(unsafe struct System);
         port::use_rt!(unsafe System);
+        #[cfg(not(feature = "systick-tickless"))]
         port::use_systick_tickful!(unsafe impl PortTimer for System);
+        #[cfg(feature = "systick-tickless")]
+        port::use_systick_tickless!(unsafe impl PortTimer for System);
 
         impl port::ThreadingOptions for System {
             // On some chips, RTT stops working when the processor is suspended
@@ -72,6 +85,27 @@ macro_rules! instantiate_test {
 
             #[cfg(feature = "cpu-lock-by-basepri")]
             const CPU_LOCK_PRIORITY_MASK: u8 = 0x20;
+
+            // Must be lower than or equal to the lowest priority in
+            // `INTERRUPT_PRIORITIES`
+            #[cfg(feature = "pend-sv-priority-raised")]
+            const PEND_SV_PRIORITY: u8 = 0x60;
+
+            #[cfg(feature = "force-fp-context")]
+            const FORCE_FP_CONTEXT: bool = true;
+
+            // Must not overlap with `INTERRUPT_LINES`
+            const SOFTWARE_INTERRUPT_LINES: &'static [InterruptNum] = &[20, 21];
+        }
+
+        #[cfg(feature = "mpu")]
+        port::use_mpu!(unsafe System);
+
+        #[cfg(feature = "mpu")]
+        impl port::MpuOptions for System {
+            fn task_regions(task: r3::kernel::Id) -> &'static [port::MpuRegion] {
+                crate::mpu::task_regions(task)
+            }
         }
 
         impl port::SysTickOptions for System {
@@ -178,6 +212,12 @@ macro_rules! instantiate_test {
                 logger_semihosting::init();
             }).finish(b);
 
+            // Redirect the log output to the multiplexed `SYS_WRITE0` stream
+            #[cfg(feature = "output-semihosting-write0")]
+            StartupHook::build().start(|_| {
+                output_write0::init();
+            }).finish(b);
+
             // Create a USB serial device and redirect the log output and the
             // main message to it
             #[cfg(feature = "board-rp_pico")]
@@ -185,10 +225,18 @@ macro_rules! instantiate_test {
 
             System::configure_systick(b);
 
+            #[cfg(feature = "mpu")]
+            System::configure_mpu(b);
+
             test_case::App::new::<Driver>(b)
         }
     };
 
+    // A driver-defined test is specified (see `gen.rs`)
+    (driver_test: { $($test:tt)* }) => {
+        instantiate_test!({ $($test)* },);
+    };
+
     () => {
         compile_error!("no test is specified");
     }
@@ -205,8 +253,19 @@ macro_rules! reject_excess {
 // Get the selected test case and instantiate
 #[cfg(feature = "kernel_benchmarks")]
 r3_test_suite::get_selected_kernel_benchmarks!(instantiate_test!());
-#[cfg(feature = "kernel_tests")]
+#[cfg(all(feature = "kernel_tests", not(driver_test)))]
 r3_test_suite::get_selected_kernel_tests!(instantiate_test!());
+// Generated by `build.rs`. Invokes `instantiate_test!` when a driver-defined
+// test is requested.
+include!(concat!(env!("OUT_DIR"), "/gen.rs"));
+
+#[cfg(feature = "kernel_tests")]
+mod driver_kernel_tests {
+    pub mod mpu_mem_manage_fault;
+    pub mod pend_sv_defer;
+    pub mod stack_limit_small_stack;
+    pub mod stack_overflow_fault;
+}
 
 #[cfg(not(feature = "run"))]
 fn main() {
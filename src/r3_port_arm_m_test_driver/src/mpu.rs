@@ -0,0 +1,26 @@
+//! Implements `MpuOptions::task_regions`. Driver-defined tests can assign an
+//! MPU region to a task.
+use core::slice;
+use r3::kernel::Id;
+use r3_port_arm_m::MpuRegion;
+
+static mut TASK_REGION: Option<(Id, MpuRegion)> = None;
+
+/// Assign an MPU region to the task identified by `task`.
+///
+/// # Safety
+///
+/// Must be called in the boot phase (e.g., in a startup hook), when the
+/// dispatcher isn't running yet.
+pub unsafe fn set_task_region(task: Id, region: MpuRegion) {
+    unsafe { TASK_REGION = Some((task, region)) };
+}
+
+/// Implements `MpuOptions::task_regions`.
+pub fn task_regions(task: Id) -> &'static [MpuRegion] {
+    // Safety: `TASK_REGION` is only modified in the boot phase
+    match unsafe { &TASK_REGION } {
+        Some((region_task, region)) if *region_task == task => slice::from_ref(region),
+        _ => &[],
+    }
+}
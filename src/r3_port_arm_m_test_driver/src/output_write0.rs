@@ -0,0 +1,64 @@
+//! The output mechanism based on semihosting `SYS_WRITE0`. The operation has
+//! only one output stream, so the primary message and the log output are
+//! multiplexed into one stream.
+use core::{fmt, panic::PanicInfo};
+use r3_port_arm_m::rt::semihosting::Write0;
+
+pub mod mux {
+    pub const BEGIN_MAIN: &str = "\x171";
+    pub const BEGIN_LOG: &str = "\x172";
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    // Disable IRQ
+    cortex_m::interrupt::disable();
+
+    stdout_write_fmt(format_args!("{}\n", info));
+
+    loop {
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+pub fn stdout_write_fmt(args: fmt::Arguments<'_>) {
+    cortex_m::interrupt::free(|_| {
+        let _ = fmt::Write::write_str(&mut Write0, mux::BEGIN_MAIN);
+        let _ = fmt::Write::write_fmt(&mut Write0, args);
+    });
+}
+
+pub fn stderr_write_fmt(args: fmt::Arguments<'_>) {
+    cortex_m::interrupt::free(|_| {
+        let _ = fmt::Write::write_str(&mut Write0, mux::BEGIN_LOG);
+        let _ = fmt::Write::write_fmt(&mut Write0, args);
+    });
+}
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        stderr_write_fmt(format_args!(
+            "[{:5} {}] {}\n",
+            record.level(),
+            record.target(),
+            record.args()
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn init() {
+    // Note: Some targets don't support CAS atomics. This is why we need to use
+    //       `set_logger_racy` here.
+    // Safety: There are no other threads calling `set_logger_racy` at the
+    //         same time.
+    unsafe { log::set_logger_racy(&Logger).unwrap() };
+    log::set_max_level(log::LevelFilter::Trace);
+}
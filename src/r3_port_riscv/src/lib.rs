@@ -16,8 +16,9 @@
 #![no_std]
 use core::ops::Range;
 use r3::kernel::{
-    ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-    PendInterruptLineError, QueryInterruptLineError, SetInterruptLinePriorityError,
+    ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError, InterruptNum,
+    InterruptPriority, PendInterruptLineError, QueryInterruptLineError,
+    SetInterruptLinePriorityError,
 };
 
 /// Used by macros
@@ -150,6 +151,23 @@ pub trait InterruptController {
         Err(SetInterruptLinePriorityError::BadParam)
     }
 
+    /// Handle the call to [`PortInterrupts::get_interrupt_line_priority`] for a
+    /// platform interrupt line.
+    ///
+    /// The provided interrupt number must be greater than or equal to
+    /// [`INTERRUPT_PLATFORM_START`].
+    ///
+    /// [`PortInterrupts::get_interrupt_line_priority`]: r3::kernel::PortInterrupts::get_interrupt_line_priority
+    ///
+    /// # Safety
+    ///
+    /// See this trait's documentation.
+    unsafe fn get_interrupt_line_priority(
+        _line: InterruptNum,
+    ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+        Err(GetInterruptLinePriorityError::NotSupported)
+    }
+
     /// Handle the call to [`PortInterrupts::enable_interrupt_line`] for a
     /// platform interrupt line.
     ///
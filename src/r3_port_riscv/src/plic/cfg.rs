@@ -44,8 +44,9 @@ macro_rules! use_plic {
                 plic::{imp, plic_regs},
                 r3::kernel::{
                     cfg::CfgBuilder, ClearInterruptLineError, EnableInterruptLineError,
-                    InterruptNum, InterruptPriority, PendInterruptLineError, PortInterrupts,
-                    QueryInterruptLineError, SetInterruptLinePriorityError,
+                    GetInterruptLinePriorityError, InterruptNum, InterruptPriority,
+                    PendInterruptLineError, PortInterrupts, QueryInterruptLineError,
+                    SetInterruptLinePriorityError,
                 },
                 InterruptController, Plic, PlicOptions,
             };
@@ -79,6 +80,13 @@ macro_rules! use_plic {
                     imp::set_interrupt_line_priority::<Self>(line, priority)
                 }
 
+                #[inline]
+                unsafe fn get_interrupt_line_priority(
+                    line: InterruptNum,
+                ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+                    imp::get_interrupt_line_priority::<Self>(line)
+                }
+
                 #[inline]
                 unsafe fn enable_interrupt_line(
                     line: InterruptNum,
@@ -131,14 +139,30 @@ pub trait PlicOptions {
     /// in range `0..=1023`.
     const MAX_NUM: InterruptNum;
 
-    /// The PLIC context for the hart on which the kernel runs.
+    /// The PLIC context for the hart on which the kernel runs. If the `s-mode`
+    /// feature is enabled, this must be the hart's context for S-mode.
     const CONTEXT: usize = 0;
 
+    /// Additional PLIC contexts in which the driver disables all interrupt
+    /// sources on boot. Defaults to `&[]`.
+    ///
+    /// Some PLIC implementations provide more than one context per hart, e.g.,
+    /// one for M-mode and another for S-mode. This option can be used to
+    /// ensure the hart's other contexts don't receive any interrupts that are
+    /// meant to be handled by the kernel. The driver doesn't touch these
+    /// contexts after boot.
+    ///
+    /// The interrupt enable registers of these contexts must be accessible
+    /// from the privilege mode in which the kernel runs.
+    const DISABLED_CONTEXTS: &'static [usize] = &[];
+
     /// Enables the trick for nested interrupt processing.
     ///
     /// PLIC is not designed to allow nested interrupt processing. When this
     /// flag is enabled, the driver will signal completion earlier to start
-    /// accepting higher-priority interrupts.
+    /// accepting higher-priority interrupts. The priority threshold is
+    /// raised to the claimed interrupt's priority while its handler is
+    /// running, and it's restored when the handler returns.
     ///
     /// The following advices should be taken into consideration when enabling
     /// this option:
@@ -1,7 +1,8 @@
 /// The implementation of the Platform-Level Interrupt Controller driver.
 use r3::kernel::{
-    cfg::CfgBuilder, EnableInterruptLineError, InterruptHandler, InterruptNum, InterruptPriority,
-    Kernel, QueryInterruptLineError, SetInterruptLinePriorityError,
+    cfg::CfgBuilder, EnableInterruptLineError, GetInterruptLinePriorityError, InterruptHandler,
+    InterruptNum, InterruptPriority, Kernel, QueryInterruptLineError,
+    SetInterruptLinePriorityError,
 };
 
 use crate::{Plic, INTERRUPT_EXTERNAL, INTERRUPT_PLATFORM_START};
@@ -25,6 +26,13 @@ pub fn init<System: Plic>() {
         plic_regs.interrupt_enable[ctx][i].set(0);
     }
 
+    // Disable all interrupts in the other contexts as well
+    for &other_ctx in System::DISABLED_CONTEXTS.iter() {
+        for i in 0..(num_ints + 31) / 32 {
+            plic_regs.interrupt_enable[other_ctx][i].set(0);
+        }
+    }
+
     // Change the priority thread of the current context
     // to accept all interrupts
     plic_regs.ctxs[System::CONTEXT].priority_threshold.set(0);
@@ -32,10 +40,15 @@ pub fn init<System: Plic>() {
 
 #[inline]
 fn interrupt_handler<System: Plic + Kernel>(_: usize) {
-    if let Some((token, num)) = claim_interrupt::<System>() {
+    // Drain all pending interrupts in one go. This reduces the number of trap
+    // entries when multiple interrupts are raised at once.
+    while let Some((token, num)) = claim_interrupt::<System>() {
         if let Some(handler) = System::INTERRUPT_HANDLERS.get(num) {
             if System::USE_NESTING {
-                unsafe { riscv::register::mie::set_mext() };
+                // Allow preemption by higher-priority interrupts. The priority
+                // threshold was raised by `claim_interrupt`, so the PLIC only
+                // signals the interrupts that can preempt this one.
+                set_external_interrupt_enable(true);
             }
 
             // Safety: The interrupt controller driver is responsible for
@@ -44,14 +57,42 @@ fn interrupt_handler<System: Plic + Kernel>(_: usize) {
             unsafe { handler() };
 
             if System::USE_NESTING {
-                unsafe { riscv::register::mie::clear_mext() };
+                set_external_interrupt_enable(false);
             }
         }
 
+        // If `USE_NESTING` is enabled, this restores the priority threshold
+        // to the value observed by `claim_interrupt`. The nested invocations
+        // of this function (if any) have already completed and restored the
+        // threshold they observed (which is the one set by our
+        // `claim_interrupt`), so the threshold is restored in a LIFO order
+        // regardless of the nesting depth.
         end_interrupt::<System>(token);
     }
 }
 
+/// Set or clear `mie.MEIE` (`sie.SEIE` in S-mode).
+#[inline]
+fn set_external_interrupt_enable(enable: bool) {
+    #[cfg(not(feature = "s-mode"))]
+    unsafe {
+        if enable {
+            riscv::register::mie::set_mext();
+        } else {
+            riscv::register::mie::clear_mext();
+        }
+    }
+
+    #[cfg(feature = "s-mode")]
+    unsafe {
+        if enable {
+            riscv::register::sie::set_sext();
+        } else {
+            riscv::register::sie::clear_sext();
+        }
+    }
+}
+
 type Token = u32;
 
 #[inline]
@@ -63,7 +104,10 @@ fn claim_interrupt<System: Plic>() -> Option<(Token, InterruptNum)> {
         return None;
     }
     if System::USE_NESTING {
-        // Raise the priority threshold to mask the claimed interrupt
+        // Raise the priority threshold to mask the claimed interrupt and
+        // the ones with the same or lower priority. The new threshold is
+        // always higher than `old_threshold` because the PLIC only presents
+        // the interrupts whose priorities exceed the current threshold.
         let old_threshold = plic_regs.ctxs[System::CONTEXT].priority_threshold.get();
         let priority = plic_regs.interrupt_priority[num as usize].get();
 
@@ -112,6 +156,20 @@ pub fn set_interrupt_line_priority<System: Plic>(
     Ok(())
 }
 
+/// Implements [`crate::InterruptController::get_interrupt_line_priority`].
+pub fn get_interrupt_line_priority<System: Plic>(
+    line: InterruptNum,
+) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+    let plic_regs = System::plic_regs();
+    let line = line - INTERRUPT_PLATFORM_START;
+
+    if line > System::MAX_NUM {
+        return Err(GetInterruptLinePriorityError::BadParam);
+    }
+
+    Ok(plic_regs.interrupt_priority[line].get() as InterruptPriority)
+}
+
 /// Implements [`crate::InterruptController::enable_interrupt_line`].
 pub fn enable_interrupt_line<System: Plic>(
     line: InterruptNum,
@@ -1,7 +1,11 @@
-use crate::EntryPoint;
+use crate::{threading::imp::xtvec, EntryPoint};
 
 pub unsafe fn setup_interrupt_handler<System: EntryPoint>() {
     unsafe {
-        asm!("csrw mtvec, {}", in(reg) System::TRAP_HANDLER);
+        asm!(
+            "csrw {xtvec}, {handler}",
+            xtvec = const xtvec::CSR,
+            handler = in(reg) System::TRAP_HANDLER,
+        );
     }
 }
@@ -32,8 +32,8 @@ macro_rules! use_port {
         mod port_riscv_impl {
             use super::$sys;
             use $crate::r3::kernel::{
-                ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-                PendInterruptLineError, Port, QueryInterruptLineError, SetInterruptLinePriorityError,
+                ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError,
+                InterruptNum, InterruptPriority, PendInterruptLineError, Port, QueryInterruptLineError, SetInterruptLinePriorityError,
                 TaskCb, PortToKernel, PortInterrupts, PortThreading, UTicks, PortTimer, KernelCfg2,
                 cfg::InterruptHandlerFn,
             };
@@ -133,6 +133,13 @@ macro_rules! use_port {
                     PORT_STATE.set_interrupt_line_priority::<Self>(line, priority)
                 }
 
+                #[inline]
+                unsafe fn get_interrupt_line_priority(
+                    line: InterruptNum,
+                ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+                    PORT_STATE.get_interrupt_line_priority::<Self>(line)
+                }
+
                 #[inline]
                 unsafe fn enable_interrupt_line(
                     line: InterruptNum,
@@ -1,9 +1,9 @@
 use core::{cell::UnsafeCell, hint::unreachable_unchecked, mem::MaybeUninit, slice};
 use r3::{
     kernel::{
-        cfg::InterruptHandlerFn, ClearInterruptLineError, EnableInterruptLineError, InterruptNum,
-        InterruptPriority, PendInterruptLineError, Port, PortToKernel, QueryInterruptLineError,
-        SetInterruptLinePriorityError, TaskCb,
+        cfg::InterruptHandlerFn, ClearInterruptLineError, EnableInterruptLineError,
+        GetInterruptLinePriorityError, InterruptNum, InterruptPriority, PendInterruptLineError,
+        Port, PortToKernel, QueryInterruptLineError, SetInterruptLinePriorityError, TaskCb,
     },
     prelude::*,
     utils::Init,
@@ -12,109 +12,163 @@ use r3_portkit::pptext::pp_asm;
 
 use crate::{InterruptController, ThreadingOptions, INTERRUPT_PLATFORM_START, INTERRUPT_SOFTWARE};
 
-/// `mstatus` (Machine Status Register)
-mod mstatus {
-    pub const MIE: usize = 1 << 3;
-    pub const MPIE: usize = 1 << 7;
-    pub const MPP_M: usize = 0b11 << 11;
+/// The privilege level at which the kernel runs. `1` (S-mode) if the `s-mode`
+/// feature is enabled; `3` (M-mode) otherwise.
+///
+/// The CSR numbers and bit positions of the registers we use are derived from
+/// this value. For example, `xstatus::CSR` refers to `mstatus` (`0x300`) in
+/// M-mode and `sstatus` (`0x100`) in S-mode.
+const PRIV: usize = if cfg!(feature = "s-mode") { 1 } else { 3 };
+
+/// `xstatus` (Machine/Supervisor Status Register)
+mod xstatus {
+    use super::PRIV;
+
+    pub const CSR: usize = PRIV << 8;
+
+    pub const XIE: usize = 1 << PRIV;
+    pub const XPIE: usize = 1 << (4 + PRIV);
+    /// `xPP = x` (the previous privilege level is the current one)
+    pub const XPP_X: usize = if PRIV == 3 { 0b11 << 11 } else { 1 << 8 };
     pub const FS_0: usize = 1 << 13;
     pub const FS_1: usize = 1 << 14;
 
     #[inline(always)]
     pub fn clear_i<const VALUE: usize>() {
-        unsafe { asm!("csrci mstatus, {}", const VALUE) };
+        unsafe { asm!("csrci {csr}, {value}", csr = const CSR, value = const VALUE) };
     }
 
     #[inline(always)]
     pub fn set_i<const VALUE: usize>() {
-        unsafe { asm!("csrsi mstatus, {}", const VALUE) };
+        unsafe { asm!("csrsi {csr}, {value}", csr = const CSR, value = const VALUE) };
     }
 
     #[inline(always)]
     pub fn set(value: usize) {
-        unsafe { asm!("csrs mstatus, {}", in(reg) value) };
+        unsafe { asm!("csrs {csr}, {value}", csr = const CSR, value = in(reg) value) };
     }
 
     #[inline(always)]
     pub fn fetch_clear_i<const VALUE: usize>() -> usize {
         let read: usize;
-        unsafe { asm!("csrrci {}, mstatus, {}", lateout(reg) read, const VALUE) };
+        unsafe {
+            asm!(
+                "csrrci {read}, {csr}, {value}",
+                read = lateout(reg) read,
+                csr = const CSR,
+                value = const VALUE,
+            )
+        };
         read
     }
 
     #[inline(always)]
     pub fn read() -> usize {
         let read: usize;
-        unsafe { asm!("csrr {}, mstatus", lateout(reg) read) };
+        unsafe { asm!("csrr {read}, {csr}", read = lateout(reg) read, csr = const CSR) };
         read
     }
 }
-/// `mcause` (Machine Cause Register)
+
+/// `xcause` (Machine/Supervisor Cause Register)
 #[allow(non_upper_case_globals)]
 #[allow(dead_code)]
-mod mcause {
+mod xcause {
+    use super::PRIV;
+
+    pub const CSR: usize = 0x042 | (PRIV << 8);
+
     pub const Interrupt: usize = usize::MAX - usize::MAX / 2;
     pub const ExceptionCode_MASK: usize = usize::MAX / 2;
 
     #[inline(always)]
     pub fn read() -> usize {
         let read: usize;
-        unsafe { asm!("csrr {}, mcause", lateout(reg) read) };
+        unsafe { asm!("csrr {read}, {csr}", read = lateout(reg) read, csr = const CSR) };
         read
     }
 }
 
-/// `mip` (Machine Interrupt Pending)
-mod mip {
-    /// Machine Software Interrupt Pending
-    pub const MSIP: usize = 1 << 3;
-    /// Machine Timer Interrupt Pending
-    pub const MTIP: usize = 1 << 7;
-    /// Machine External Interrupt Pending
-    pub const MEIP: usize = 1 << 11;
+/// `xepc` (Machine/Supervisor Exception Program Counter)
+mod xepc {
+    use super::PRIV;
+
+    pub const CSR: usize = 0x041 | (PRIV << 8);
+}
+
+/// `xtvec` (Machine/Supervisor Trap-Vector Base-Address Register)
+pub(crate) mod xtvec {
+    use super::PRIV;
+
+    pub const CSR: usize = 0x005 | (PRIV << 8);
+}
+
+/// `xip` (Machine/Supervisor Interrupt Pending)
+mod xip {
+    use super::PRIV;
+
+    pub const CSR: usize = 0x044 | (PRIV << 8);
+
+    /// Software Interrupt Pending
+    pub const XSIP: usize = 1 << PRIV;
+    /// Timer Interrupt Pending
+    pub const XTIP: usize = 1 << (4 + PRIV);
+    /// External Interrupt Pending
+    pub const XEIP: usize = 1 << (8 + PRIV);
 
     #[inline(always)]
     pub fn read() -> usize {
         let read: usize;
-        unsafe { asm!("csrr {}, mip", lateout(reg) read) };
+        unsafe { asm!("csrr {read}, {csr}", read = lateout(reg) read, csr = const CSR) };
         read
     }
 
     #[inline(always)]
     pub fn clear(value: usize) {
-        unsafe { asm!("csrc mip, {}", in(reg) value) };
+        unsafe { asm!("csrc {csr}, {value}", csr = const CSR, value = in(reg) value) };
     }
 
     #[inline(always)]
     pub fn set(value: usize) {
-        unsafe { asm!("csrs mip, {}", in(reg) value) };
+        unsafe { asm!("csrs {csr}, {value}", csr = const CSR, value = in(reg) value) };
     }
 }
 
-/// `mip` (Machine Interrupt Enable)
-mod mie {
-    /// Machine Software Interrupt Enable
-    pub const MSIE: usize = 1 << 3;
-    /// Machine Timer Interrupt Enable
-    pub const MTIE: usize = 1 << 7;
-    /// Machine External Interrupt Enable
-    pub const MEIE: usize = 1 << 11;
+/// `xie` (Machine/Supervisor Interrupt Enable)
+mod xie {
+    use super::PRIV;
+
+    pub const CSR: usize = 0x004 | (PRIV << 8);
+
+    /// Software Interrupt Enable
+    pub const XSIE: usize = 1 << PRIV;
+    /// Timer Interrupt Enable
+    pub const XTIE: usize = 1 << (4 + PRIV);
+    /// External Interrupt Enable
+    pub const XEIE: usize = 1 << (8 + PRIV);
 
     #[inline(always)]
     pub fn fetch_clear(value: usize) -> usize {
         let read: usize;
-        unsafe { asm!("csrrc {}, mie, {}", lateout(reg) read, in(reg) value) };
+        unsafe {
+            asm!(
+                "csrrc {read}, {csr}, {value}",
+                read = lateout(reg) read,
+                csr = const CSR,
+                value = in(reg) value,
+            )
+        };
         read
     }
 
     #[inline(always)]
     pub fn clear(value: usize) {
-        unsafe { asm!("csrc mie, {}", in(reg) value) };
+        unsafe { asm!("csrc {csr}, {value}", csr = const CSR, value = in(reg) value) };
     }
 
     #[inline(always)]
     pub fn set(value: usize) {
-        unsafe { asm!("csrs mie, {}", in(reg) value) };
+        unsafe { asm!("csrs {csr}, {value}", csr = const CSR, value = in(reg) value) };
     }
 }
 
@@ -248,12 +302,12 @@ pub mod asm_inc {
 // macro" error not to occur
 mod instemu;
 
-/// The part of `mstatus` which is specific to each thread.
+/// The part of `xstatus` which is specific to each thread.
 ///
-/// `mstatus_part` is only used if `cfg!(target_feature = "f")`. `mstatus_part`
+/// `xstatus_part` is only used if `cfg!(target_feature = "f")`. `xstatus_part`
 /// is undefined otherwise.
 #[allow(dead_code)]
-const MSTATUS_PART_MASK: usize = mstatus::FS_1;
+const XSTATUS_PART_MASK: usize = xstatus::FS_1;
 
 /// Implemented on a system type by [`use_port!`].
 ///
@@ -321,7 +375,7 @@ impl State {
         // Enable FPU
         if cfg!(target_feature = "f") {
             // FS = 0b?1 (Initial or Dirty)
-            mstatus::set(mstatus::FS_0);
+            xstatus::set(xstatus::FS_0);
         }
 
         // Safety: We are the port, so it's okay to call this
@@ -330,14 +384,14 @@ impl State {
         // Enable local interrupts
         {
             let mut clear_set = [0usize; 2];
-            clear_set[System::USE_INTERRUPT_SOFTWARE as usize] |= mie::MSIE;
-            clear_set[System::USE_INTERRUPT_TIMER as usize] |= mie::MTIE;
-            clear_set[System::USE_INTERRUPT_EXTERNAL as usize] |= mie::MEIE;
+            clear_set[System::USE_INTERRUPT_SOFTWARE as usize] |= xie::XSIE;
+            clear_set[System::USE_INTERRUPT_TIMER as usize] |= xie::XTIE;
+            clear_set[System::USE_INTERRUPT_EXTERNAL as usize] |= xie::XEIE;
             if clear_set[0] != 0 {
-                mie::clear(clear_set[0]);
+                xie::clear(clear_set[0]);
             }
             if clear_set[1] != 0 {
-                mie::set(clear_set[1]);
+                xie::set(clear_set[1]);
             }
         }
 
@@ -359,17 +413,18 @@ impl State {
                 # Save the stack pointer for later use
                 STORE sp, ({MAIN_STACK}), a0
 
-                # `mstatus.MPIE` will be `1` all the time except in a software
+                # `xstatus.XPIE` will be `1` all the time except in a software
                 # exception handler
-                li a0, {MPIE}
-                csrs mstatus, a0
+                li a0, {XPIE}
+                csrs {XSTATUS}, a0
 
                 tail {push_second_level_state_and_dispatch}.dispatch
                 ",
                 MAIN_STACK = sym MAIN_STACK,
                 push_second_level_state_and_dispatch =
                     sym Self::push_second_level_state_and_dispatch::<System>,
-                MPIE = const mstatus::MPIE,
+                XPIE = const xstatus::XPIE,
+                XSTATUS = const xstatus::CSR,
                 options(noreturn),
             );
         }
@@ -427,21 +482,21 @@ impl State {
                 STORE t6, ({X_SIZE} * 15)(sp)
                 STORE ra, ({X_SIZE} * 16)(sp)
 
-                # MIE := 0
-                csrrci a0, mstatus, {MIE}
+                # xstatus.XIE := 0
+                csrrci a0, {XSTATUS}, {XIE}
 
             "   if cfg!(target_feature = "f") {                                     "
                     # If FP registers are in use, push FLS.F
                     #
-                    #   <a2 = mstatus_part>
-                    #   if mstatus_part.FS[1] != 0:
+                    #   <a2 = xstatus_part>
+                    #   if xstatus_part.FS[1] != 0:
                     #       sp: *mut FlsF;
                     #       sp -= 1;
                     #       sp['ft0'-'ft7'] = [ft0-ft7];
                     #       sp['fa0'-'fa7'] = [fa0-fa7];
                     #       sp['ft8'-'ft11'] = [ft8-ft11];
                     #       sp.fcsr = fcsr;
-                    #   <a0 = mstatus_part>
+                    #   <a0 = xstatus_part>
                     #
                     li a1, {FS_1}
                     and a1, a1, a0
@@ -480,11 +535,12 @@ impl State {
                 ",
                 push_second_level_state_and_dispatch =
                     sym Self::push_second_level_state_and_dispatch::<System>,
-                MIE = const mstatus::MIE,
-                FS_1 = const mstatus::FS_1,
+                XIE = const xstatus::XIE,
+                FS_1 = const xstatus::FS_1,
                 X_SIZE = const X_SIZE,
                 F_SIZE = const F_SIZE,
                 FLSF_SIZE = const FLSF_SIZE,
+                XSTATUS = const xstatus::CSR,
                 options(noreturn),
             );
         }
@@ -519,15 +575,15 @@ impl State {
     ///
     /// All entry points:
     ///
-    ///  - `mstatus.MIE` must be equal to `1`.
+    ///  - `xstatus.XIE` must be equal to `1`.
     ///
     /// All entry points but `dispatch`:
     ///
     ///  - If the current task is a task, SP should point to the
     ///    first-level state on the current task's stack. Otherwise, SP must be
     ///    zero.
-    ///  - In a configuration that uses `mstatus_part`, `a0` must include the
-    ///    `mstatus_part` of the current task.
+    ///  - In a configuration that uses `xstatus_part`, `a0` must include the
+    ///    `xstatus_part` of the current task.
     ///
     /// `dispatch`:
     ///
@@ -566,7 +622,7 @@ impl State {
             "   crate::threading::imp::asm_inc::define_load_store!()              "
             "   crate::threading::imp::asm_inc::define_fload_fstore!()              "
 
-                # <a0 = mstatus_part>
+                # <a0 = xstatus_part>
                 # Take a shortcut only if `DISPATCH_PENDING == 0`
                 lb a1, ({DISPATCH_PENDING})
                 bnez a1, 0f
@@ -589,7 +645,7 @@ impl State {
 
             .global {push_second_level_state_and_dispatch}.not_shortcutting
             {push_second_level_state_and_dispatch}.not_shortcutting:
-                # <a0 = mstatus_part>
+                # <a0 = xstatus_part>
 
                 # Skip saving the second-level state if the current context
                 # is an idle task. Also, in this case, we don't have a stack,
@@ -628,17 +684,17 @@ impl State {
                 # are coincidentally identical, at the same time
                 #
                 #  - Is it possible for FP registers to be in use?
-                #  - Do we use `mstatus_part`?
+                #  - Do we use `xstatus_part`?
                 #
             "   if cfg!(target_feature = "f") {                                     "
                     # If FP registers are in use, push SLS.F.
                     #
-                    #   <a0 = mstatus_part>
-                    #   if mstatus_part.FS[1] != 0:
+                    #   <a0 = xstatus_part>
+                    #   if xstatus_part.FS[1] != 0:
                     #       sp: *mut FReg;
                     #       sp -= 12;
                     #       sp[0..12] = [fs0-fs11];
-                    #   <a0 = mstatus_part>
+                    #   <a0 = xstatus_part>
                     #
                     li a2, {FS_1}
                     and a2, a2, a0
@@ -659,7 +715,7 @@ impl State {
                     FSTORE fs11, ({F_SIZE} * 11)(sp)
                 0:      # PushSLSFEnd
 
-                    # Push `mstatus_part`
+                    # Push `xstatus_part`
                     addi sp, sp, -{X_SIZE}
                     STORE a0, (sp)
             "   } else {                                                            "
@@ -700,21 +756,21 @@ impl State {
                 # are coincidentally identical, at the same time
                 #
                 #  - Is it possible for FP registers to be in use?
-                #  - Do we use `mstatus_part`?
+                #  - Do we use `xstatus_part`?
                 #
             "   if cfg!(target_feature = "f") {                                     "
-                    # Pop `mstatus_part`
+                    # Pop `xstatus_part`
                     LOAD a0, (sp)
                     addi sp, sp, {X_SIZE}
 
                     # If FP registers are in use, pop SLS.F.
                     #
-                    #   <a0 = mstatus_part>
-                    #   if mstatus_part.FS[1] != 0:
+                    #   <a0 = xstatus_part>
+                    #   if xstatus_part.FS[1] != 0:
                     #       sp: *mut FReg;
                     #       [fs0-fs11] = sp[0..12];
                     #       sp += 12;
-                    #   <a0 = mstatus_part>
+                    #   <a0 = xstatus_part>
                     #
                     li a2, {FS_1}
                     and a2, a2, a0
@@ -753,14 +809,14 @@ impl State {
 
             .global {push_second_level_state_and_dispatch}.pop_first_level_state
             {push_second_level_state_and_dispatch}.pop_first_level_state:
-                # <a0 = mstatus_part>
+                # <a0 = xstatus_part>
 
             "   if cfg!(target_feature = "f") {                                     "
                     # If FP registers were in use, pop FLS.F. Loading FP regs
-                    # will implicitly set `mstatus.FS[1]`.
+                    # will implicitly set `xstatus.FS[1]`.
                     #
-                    #   <a0 = mstatus_part>
-                    #   if mstatus_part.FS[1] != 0:
+                    #   <a0 = xstatus_part>
+                    #   if xstatus_part.FS[1] != 0:
                     #       sp: *mut FlsF;
                     #       [ft0-ft7] = sp['ft0'-'ft7'];
                     #       [fa0-fa7] = sp['fa0'-'fa7'];
@@ -768,7 +824,7 @@ impl State {
                     #       fcsr = sp.fcsr;
                     #       sp += 1;
                     #   else:
-                    #       mstatus.FS[1] = 0
+                    #       xstatus.FS[1] = 0
                     #
                     li a1, {FS_1}
                     and a0, a0, a1
@@ -801,33 +857,33 @@ impl State {
 
                     j 0f    # → PopFLSFEnd
                 1:      # NoPopFLSF
-                    csrc mstatus, a1
+                    csrc {XSTATUS}, a1
                 0:      # PopFLSFEnd
             "   } else {                                                            "
                     # unused: {F_SIZE} {FLSF_SIZE}
             "   }                                                                   "
 
-                # mstatus.MPP := M
-                # mstatus.MPIE := 1 (if `maintain-mpie` is enabled)
+                # xstatus.XPP := X
+                # xstatus.XPIE := 1 (if `maintain-pie` is enabled)
             "   if cfg!(feature = "maintain-pie") {                                "
-                    li a0, {MPP_M} | {MPIE}
+                    li a0, {XPP_X} | {XPIE}
             "   } else {                                                            "
-                    li a0, {MPP_M}
-                    # unused: {MPIE}
+                    li a0, {XPP_X}
+                    # unused: {XPIE}
             "   }                                                                   "
-                csrs mstatus, a0
+                csrs {XSTATUS}, a0
 
                 # Resume the next task by restoring FLS.X
                 #
                 #   <[s0-s11, sp] = resumed context>
                 #
-                #   mepc = sp[16];
+                #   xepc = sp[16];
                 #   [ra, t0-t2, a0-a5] = sp[0..10];
                 #   [a6-a7, t3-t6] = sp[10..16];
                 #   sp += 17;
                 #
-                #   pc = mepc;
-                #   mode = mstatus.MPP;
+                #   pc = xepc;
+                #   mode = xstatus.XPP;
                 #
                 #   <end of procedure>
                 #
@@ -836,7 +892,7 @@ impl State {
                 LOAD t0, ({X_SIZE} * 1)(sp)
                 LOAD t1, ({X_SIZE} * 2)(sp)
                 LOAD t2, ({X_SIZE} * 3)(sp)
-                csrw mepc, a7
+                csrw {XEPC}, a7
                 LOAD a0, ({X_SIZE} * 4)(sp)
                 LOAD a1, ({X_SIZE} * 5)(sp)
                 LOAD a2, ({X_SIZE} * 6)(sp)
@@ -850,7 +906,11 @@ impl State {
                 LOAD t5, ({X_SIZE} * 14)(sp)
                 LOAD t6, ({X_SIZE} * 15)(sp)
                 addi sp, sp, ({X_SIZE} * 17)
-                mret
+            "   if cfg!(feature = "s-mode") {                                       "
+                    sret
+            "   } else {                                                            "
+                    mret
+            "   }                                                                   "
 
             .global {push_second_level_state_and_dispatch}.idle_task
             {push_second_level_state_and_dispatch}.idle_task:
@@ -858,12 +918,12 @@ impl State {
                 # debugging.
                 #
                 #   sp = 0;
-                #   mstatus.MIE = 1;
+                #   xstatus.XIE = 1;
                 #   loop:
                 #       wfi();
                 #
                 mv sp, zero
-                csrsi mstatus, {MIE}
+                csrsi {XSTATUS}, {XIE}
             3:
                 wfi
                 j 3b
@@ -874,13 +934,15 @@ impl State {
                 get_running_task = sym get_running_task::<System>,
                 MAIN_STACK = sym MAIN_STACK,
                 DISPATCH_PENDING = sym DISPATCH_PENDING,
-                MPP_M = const mstatus::MPP_M,
-                MIE = const mstatus::MIE,
-                FS_1 = const mstatus::FS_1,
-                MPIE = const mstatus::MPIE,
+                XPP_X = const xstatus::XPP_X,
+                XIE = const xstatus::XIE,
+                FS_1 = const xstatus::FS_1,
+                XPIE = const xstatus::XPIE,
                 X_SIZE = const X_SIZE,
                 F_SIZE = const F_SIZE,
                 FLSF_SIZE = const FLSF_SIZE,
+                XSTATUS = const xstatus::CSR,
+                XEPC = const xepc::CSR,
                 options(noreturn)
             );
         }
@@ -892,14 +954,15 @@ impl State {
     ) -> ! {
         unsafe {
             asm!("
-                # MIE := 0
-                csrci mstatus, {MIE}
+                # xstatus.XIE := 0
+                csrci {XSTATUS}, {XIE}
 
                 j {push_second_level_state_and_dispatch}.dispatch
                 ",
-                MIE = const mstatus::MIE,
+                XIE = const xstatus::XIE,
                 push_second_level_state_and_dispatch =
                     sym Self::push_second_level_state_and_dispatch::<System>,
+                XSTATUS = const xstatus::CSR,
                 options(noreturn, nostack),
             );
         }
@@ -907,17 +970,17 @@ impl State {
 
     #[inline(always)]
     pub unsafe fn enter_cpu_lock<System: PortInstance>(&self) {
-        mstatus::clear_i::<{ mstatus::MIE }>();
+        xstatus::clear_i::<{ xstatus::XIE }>();
     }
 
     #[inline(always)]
     pub unsafe fn try_enter_cpu_lock<System: PortInstance>(&self) -> bool {
-        (mstatus::fetch_clear_i::<{ mstatus::MIE }>() & mstatus::MIE) != 0
+        (xstatus::fetch_clear_i::<{ xstatus::XIE }>() & xstatus::XIE) != 0
     }
 
     #[inline(always)]
     pub unsafe fn leave_cpu_lock<System: PortInstance>(&'static self) {
-        mstatus::set_i::<{ mstatus::MIE }>();
+        xstatus::set_i::<{ xstatus::XIE }>();
     }
 
     pub unsafe fn initialize_task_state<System: PortInstance>(
@@ -1003,11 +1066,11 @@ impl State {
             extra_ctx[11] = preload_val(0x27);
         }
 
-        // SLS.F is non-existent when `mstatus.FS[1] == 0`
+        // SLS.F is non-existent when `xstatus.FS[1] == 0`
 
         // SLS.HDR
         if cfg!(target_feature = "f") {
-            // mstatus
+            // xstatus
             //  - FS[1] = 0
             sp = sp.wrapping_sub(1);
             unsafe { *sp = MaybeUninit::new(0) };
@@ -1019,7 +1082,7 @@ impl State {
 
     #[inline(always)]
     pub fn is_cpu_lock_active<System: PortInstance>(&self) -> bool {
-        (mstatus::read() & mstatus::MIE) == 0
+        (xstatus::read() & xstatus::XIE) == 0
     }
 
     pub fn is_task_context<System: PortInstance>(&self) -> bool {
@@ -1039,6 +1102,19 @@ impl State {
         }
     }
 
+    pub fn get_interrupt_line_priority<System: PortInstance>(
+        &'static self,
+        num: InterruptNum,
+    ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+        if num < INTERRUPT_PLATFORM_START {
+            // Local interrupt lines don't have priorities
+            Err(GetInterruptLinePriorityError::BadParam)
+        } else {
+            // Safety: We are delegating the call in the intended way
+            unsafe { <System as InterruptController>::get_interrupt_line_priority(num) }
+        }
+    }
+
     #[inline]
     pub fn enable_interrupt_line<System: PortInstance>(
         &'static self,
@@ -1073,7 +1149,7 @@ impl State {
         num: InterruptNum,
     ) -> Result<(), PendInterruptLineError> {
         if num == INTERRUPT_SOFTWARE {
-            mip::set(mip::MSIP);
+            xip::set(xip::XSIP);
             Ok(())
         } else if num < INTERRUPT_PLATFORM_START {
             Err(PendInterruptLineError::BadParam)
@@ -1089,7 +1165,7 @@ impl State {
         num: InterruptNum,
     ) -> Result<(), ClearInterruptLineError> {
         if num == INTERRUPT_SOFTWARE {
-            mip::clear(mip::MSIP);
+            xip::clear(xip::XSIP);
             Ok(())
         } else if num < INTERRUPT_PLATFORM_START {
             Err(ClearInterruptLineError::BadParam)
@@ -1105,7 +1181,7 @@ impl State {
         num: InterruptNum,
     ) -> Result<bool, QueryInterruptLineError> {
         if num < INTERRUPT_PLATFORM_START {
-            Ok((mip::read() & (mip::MSIP << (num * 4))) != 0)
+            Ok((xip::read() & (xip::XSIP << (num * 4))) != 0)
         } else {
             // Safety: We are delegating the call in the intended way
             unsafe { <System as InterruptController>::is_interrupt_line_pending(num) }
@@ -1116,7 +1192,7 @@ impl State {
     #[naked]
     pub unsafe extern "C" fn exception_handler<System: PortInstance>() -> ! {
         const FRAME_SIZE: usize = if cfg!(target_feature = "f") {
-            // [background_sp, mstatus]
+            // [background_sp, xstatus]
             X_SIZE * 2
         } else {
             // [background_sp]
@@ -1142,8 +1218,8 @@ impl State {
                 #   <[a0-a7, t0-t6, s0-s11, sp] = background context state,
                 #    background context ∈ [task, idle task, interrupt]>
                 #   if sp == 0:
-                #       mstatus_part = 0;
-                #       <background context ∈ [idle task], a2 == mstatus_part>
+                #       xstatus_part = 0;
+                #       <background context ∈ [idle task], a2 == xstatus_part>
                 #       INTERRUPT_NESTING += 1;
                 #       goto SwitchToMainStack;
                 #
@@ -1157,7 +1233,7 @@ impl State {
                 #   sp -= 17;
                 #   sp[0..10] = [ra, t0-t2, a0-a5];
                 #   sp[10..16] = [a6-a7, t3-t6];
-                #   sp[16] = mepc
+                #   sp[16] = xepc
                 #
                 #   let background_sp = sp;
                 #   let background_flsx = sp;
@@ -1179,7 +1255,7 @@ impl State {
                                                 la a1, {INTERRUPT_NESTING}
                                                 lw a0, (a1)
                 STORE a2, ({X_SIZE} * 6)(sp)
-                csrr a2, mepc
+                csrr a2, {XEPC}
                 STORE a3, ({X_SIZE} * 7)(sp)
                 STORE a4, ({X_SIZE} * 8)(sp)
                 STORE a5, ({X_SIZE} * 9)(sp)
@@ -1191,35 +1267,35 @@ impl State {
                 STORE t6, ({X_SIZE} * 15)(sp)
                 STORE a2, ({X_SIZE} * 16)(sp)
             "   if cfg!(target_feature = "f") {                                     "
-                    csrr a2, mstatus
+                    csrr a2, {XSTATUS}
             "   }                                                                   "
                                                 addi a0, a0, 1
                                                 sw a0, (a1)
 
             "   if cfg!(target_feature = "f") {                                     "
                     # If FP registers are in use, push FLS.F to the background
-                    # context's stack. Clear `mstatus.FS[1]` to indicate that
+                    # context's stack. Clear `xstatus.FS[1]` to indicate that
                     # FP registers are not in use in the current invocation of
                     # the trap handler (it'll be set again on first use).
                     #
-                    #   <a2 = mstatus_part>
-                    #   if mstatus_part.FS[1] != 0:
+                    #   <a2 = xstatus_part>
+                    #   if xstatus_part.FS[1] != 0:
                     #       sp: *mut FlsF;
                     #       sp -= 1;
                     #       sp['ft0'-'ft7'] = [ft0-ft7];
                     #       sp['fa0'-'fa7'] = [fa0-fa7];
                     #       sp['ft8'-'ft11'] = [ft8-ft11];
                     #       sp.fcsr = fcsr;
-                    #       mstatus.FS[1] = 0;
+                    #       xstatus.FS[1] = 0;
                     #
                     #   let background_sp = sp;
-                    #   <a2 = mstatus_part>
+                    #   <a2 = xstatus_part>
                     #
                     li a1, {FS_1}
                     and a1, a1, a2
                     beqz a1, 0f      # → PushFLSFEnd
 
-                    csrc mstatus, a1
+                    csrc {XSTATUS}, a1
                     csrr a1, fcsr
 
                     addi sp, sp, -{FLSF_SIZE}
@@ -1271,11 +1347,11 @@ impl State {
                 # `MAIN_STACK`.
                 #
                 #   <INTERRUPT_NESTING == 0, background context ∈ [task, idle task],
-                #    a2 == mstatus_part>
+                #    a2 == xstatus_part>
                 #   *(MAIN_STACK - ceil(FRAME_SIZE, 16)) = sp;
                 #   sp = MAIN_STACK - ceil(FRAME_SIZE, 16);
                 #   <sp[0] == background_sp, sp & 15 == 0, sp != 0,
-                #    a0 == background_sp, a2 == mstatus_part>
+                #    a0 == background_sp, a2 == xstatus_part>
                 #
                 mv a0, sp
                 LOAD sp, ({MAIN_STACK})
@@ -1297,11 +1373,11 @@ impl State {
                 # aligned to a word boundary.
                 #
                 #   <INTERRUPT_NESTING > 0, background context ∈ [interrupt],
-                #    a2 == mstatus_part>
+                #    a2 == xstatus_part>
                 #   *((sp - FRAME_SIZE) & !15) = sp
                 #   sp = (sp - FRAME_SIZE) & !15
                 #   <sp[0] == background_sp, sp & 15 == 0, sp != 0,
-                #    a0 == background_sp, a2 == mstatus_part>
+                #    a0 == background_sp, a2 == xstatus_part>
                 #
                 mv a0, sp
                 addi sp, sp, -{FRAME_SIZE}
@@ -1310,12 +1386,12 @@ impl State {
 
             1:      # RealignStackEnd
             "   if cfg!(target_feature = "f") {                                     "
-                    # Save `mstatus_part`.
+                    # Save `xstatus_part`.
                     STORE a2, {X_SIZE}(sp)
             "   }                                                                   "
 
-                # Check `mcause.Interrurpt`.
-                csrr a1, mcause
+                # Check `xcause.Interrurpt`.
+                csrr a1, {XCAUSE}
                 srli a3, a1, 31
                 beqz a3, 1f
 
@@ -1346,8 +1422,8 @@ impl State {
                 # If the cause is a software trap, call `handle_exception`
             "   if cfg!(target_feature = "f") {                                     "
                     #
-                    #   <a0 == background_sp, a1 == mcause, a2 = mstatus_part>
-                    #   if mstatus_part.FS[1]:
+                    #   <a0 == background_sp, a1 == xcause, a2 = xstatus_part>
+                    #   if xstatus_part.FS[1]:
                     #       a0 += FLSF_SIZE;
                     #
                     slli a2, a2, {X_SIZE} * 8 - 1 - {FS_1_SHIFT}
@@ -1358,7 +1434,7 @@ impl State {
                     # unused: {FS_1_SHIFT}
             "   }                                                                   "
                 #
-                #   <a0 == background_flsx, a1 == mcause>
+                #   <a0 == background_flsx, a1 == xcause>
                 #   handle_exception(a0, a1);
                 #
                 call {handle_exception}
@@ -1374,7 +1450,7 @@ impl State {
                                             lw a1, (a2)
 
             "   if cfg!(target_feature = "f") {                                     "
-                    # Restore `mstatus_part`
+                    # Restore `xstatus_part`
                     LOAD a0, {X_SIZE}(sp)
             "   }                                                                   "
 
@@ -1425,21 +1501,24 @@ impl State {
                 F_SIZE = const F_SIZE,
                 FLSF_SIZE = const FLSF_SIZE,
                 FRAME_SIZE = const FRAME_SIZE,
-                FS_1 = const mstatus::FS_1,
-                FS_1_SHIFT = const mstatus::FS_1.trailing_zeros(),
+                FS_1 = const xstatus::FS_1,
+                FS_1_SHIFT = const xstatus::FS_1.trailing_zeros(),
+                XSTATUS = const xstatus::CSR,
+                XEPC = const xepc::CSR,
+                XCAUSE = const xcause::CSR,
                 options(noreturn)
             );
         }
     }
 
     unsafe fn handle_interrupt<System: PortInstance>() {
-        let all_local_interrupts = [0, mie::MSIE][System::USE_INTERRUPT_SOFTWARE as usize]
-            | [0, mie::MTIE][System::USE_INTERRUPT_TIMER as usize]
-            | [0, mie::MEIE][System::USE_INTERRUPT_EXTERNAL as usize];
+        let all_local_interrupts = [0, xie::XSIE][System::USE_INTERRUPT_SOFTWARE as usize]
+            | [0, xie::XTIE][System::USE_INTERRUPT_TIMER as usize]
+            | [0, xie::XEIE][System::USE_INTERRUPT_EXTERNAL as usize];
 
-        // `M[EST]IE` is used to simulate execution priority levels.
+        // `xie.X[EST]IE` is used to simulate execution priority levels.
         //
-        //  | MEIE | MSIE | MTIE | Priority |
+        //  | XEIE | XSIE | XTIE | Priority |
         //  | ---- | ---- | ---- | -------- |
         //  |    0 |    0 |    0 |        3 |
         //  |    1 |    0 |    0 |        2 |
@@ -1447,7 +1526,7 @@ impl State {
         //  |    1 |    1 |    1 | 0 (Task) |
         //
         // First, we raise the execution priority to maximum by clearing all of
-        // `M[EST]IE`. Then we lower the execution priority one by one as we
+        // `xie.X[EST]IE`. Then we lower the execution priority one by one as we
         // skim through the pending flags.
         //
         // We must not lower the execution priority to a background execution
@@ -1469,7 +1548,7 @@ impl State {
         // The actual implementaion is closer to the following:
         //
         //  let bg_exc_pri = get_exc_pri();  // This value is implicit
-        //  let mut found_bg_exc_pri;        // Represented by `mie_pending`
+        //  let mut found_bg_exc_pri;        // Represented by `xie_pending`
         //  set_exc_pri(3);
         //  enable_interrupts_globally();
         //  for exc_pri in (1 ..= 3).rev() {
@@ -1483,86 +1562,86 @@ impl State {
         //  set_exc_pri(found_bg_exc_pri);
         //
         //
-        let old_mie = mie::fetch_clear(all_local_interrupts);
-        let mut mie_pending = 0;
+        let old_xie = xie::fetch_clear(all_local_interrupts);
+        let mut xie_pending = 0;
 
         // Re-enable interrupts globally.
-        mstatus::set_i::<{ mstatus::MIE }>();
+        xstatus::set_i::<{ xstatus::XIE }>();
 
-        let mut mip = mip::read();
+        let mut pending = xip::read();
 
         // Check the pending flags and call the respective handlers in the
         // descending order of priority.
-        if System::USE_INTERRUPT_EXTERNAL && (old_mie & mie::MEIE) != 0 {
+        if System::USE_INTERRUPT_EXTERNAL && (old_xie & xie::XEIE) != 0 {
             // Safety: `USE_INTERRUPT_EXTERNAL == true`
             let handler = System::INTERRUPT_EXTERNAL_HANDLER
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
-            while (mip & mip::MEIP) != 0 {
+            while (pending & xip::XEIP) != 0 {
                 // Safety: The first-level interrupt handler is allowed to call
                 //         a second-level interrupt handler
                 unsafe { handler() };
 
-                mip = mip::read();
+                pending = xip::read();
             }
 
-            mie_pending = mie::MEIE;
+            xie_pending = xie::XEIE;
         }
 
-        if System::USE_INTERRUPT_SOFTWARE && (old_mie & mie::MSIE) != 0 {
+        if System::USE_INTERRUPT_SOFTWARE && (old_xie & xie::XSIE) != 0 {
             // Safety: `USE_INTERRUPT_SOFTWARE == true`
             let handler = System::INTERRUPT_SOFTWARE_HANDLER
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
             if System::USE_INTERRUPT_EXTERNAL {
-                debug_assert_eq!(mie_pending, mie::MEIE);
-                mie::set(mie::MEIE);
+                debug_assert_eq!(xie_pending, xie::XEIE);
+                xie::set(xie::XEIE);
             } else {
-                debug_assert_eq!(mie_pending, 0);
+                debug_assert_eq!(xie_pending, 0);
             }
 
-            while (mip & mip::MSIP) != 0 {
+            while (pending & xip::XSIP) != 0 {
                 // Safety: The first-level interrupt handler is allowed to call
                 //         a second-level interrupt handler
                 unsafe { handler() };
 
-                mip = mip::read();
+                pending = xip::read();
             }
 
-            mie_pending = mie::MSIE;
+            xie_pending = xie::XSIE;
         }
 
-        if System::USE_INTERRUPT_TIMER && (old_mie & mie::MTIE) != 0 {
+        if System::USE_INTERRUPT_TIMER && (old_xie & xie::XTIE) != 0 {
             // Safety: `USE_INTERRUPT_TIMER == true`
             let handler = System::INTERRUPT_TIMER_HANDLER
                 .unwrap_or_else(|| unsafe { unreachable_unchecked() });
 
             if System::USE_INTERRUPT_SOFTWARE {
-                debug_assert_eq!(mie_pending, mie::MSIE);
-                mie::set(mie::MSIE);
+                debug_assert_eq!(xie_pending, xie::XSIE);
+                xie::set(xie::XSIE);
             } else if System::USE_INTERRUPT_EXTERNAL {
-                debug_assert_eq!(mie_pending, mie::MEIE);
-                mie::set(mie::MEIE);
+                debug_assert_eq!(xie_pending, xie::XEIE);
+                xie::set(xie::XEIE);
             } else {
-                debug_assert_eq!(mie_pending, 0);
+                debug_assert_eq!(xie_pending, 0);
             }
 
-            while (mip & mip::MTIP) != 0 {
+            while (pending & xip::XTIP) != 0 {
                 // Safety: The first-level interrupt handler is allowed to call
                 //         a second-level interrupt handler
                 unsafe { handler() };
 
-                mip = mip::read();
+                pending = xip::read();
             }
 
-            mie_pending = mie::MTIE;
+            xie_pending = xie::XTIE;
         }
 
         // Disable interrupts globally before returning.
-        mstatus::clear_i::<{ mstatus::MIE }>();
+        xstatus::clear_i::<{ xstatus::XIE }>();
 
-        debug_assert_ne!(mie_pending, 0);
-        mie::set(mie_pending);
+        debug_assert_ne!(xie_pending, 0);
+        xie::set(xie_pending);
     }
 }
 
@@ -19,11 +19,11 @@ pub(super) static mut RESERVATION_ADDR_VALUE: [usize; 2] = [0; 2];
 /// # Inputs
 ///
 ///  - `a0`: pointer to FLS.X (a portion of the background context state)
-///  - `a1`: `mcause`
+///  - `a1`: `xcause`
 ///  - `s0-s11`: `s0-s11` from the background context state
 ///
 #[naked]
-pub(super) unsafe extern "C" fn handle_exception(_fl_state: *mut usize, _mcause: usize) {
+pub(super) unsafe extern "C" fn handle_exception(_fl_state: *mut usize, _xcause: usize) {
     // TODO: catch double fault
     // FIXME: We can't put CFI directives yet because the compiler wraps the
     //     function with `.cfi_startproc` and `.cfi_endproc` conditionally,
@@ -33,19 +33,19 @@ pub(super) unsafe extern "C" fn handle_exception(_fl_state: *mut usize, _mcause:
     unsafe {
         pp_asm!("
         "   crate::threading::imp::asm_inc::define_load_store!()                "
-            # <a0 == fl_state, a1 == mcause>
+            # <a0 == fl_state, a1 == xcause>
             addi sp, sp, -16
             STORE ra, (sp)
 
         "   if cfg!(feature = "emulate-lr-sc")  {                               "
-                # If `mcause` ∈ [5, 7], LR/SC emulation might resolve the
+                # If `xcause` ∈ [5, 7], LR/SC emulation might resolve the
                 # exception.
                 andi a3, a1, -3
                 li a2, 5
                 beq a3, a2, 9f      # TryLRSCEmulation
         "   }                                                                   "
         8:      # LRSCEmulationUnapplicable
-            # <a0 == fl_state, a1 = mcause>
+            # <a0 == fl_state, a1 = xcause>
 
             # Panic.
             tail {panic_on_unhandled_exception}
@@ -164,11 +164,11 @@ pub(super) unsafe extern "C" fn handle_exception(_fl_state: *mut usize, _mcause:
     }
 }
 
-unsafe fn panic_on_unhandled_exception(fl_state: *mut usize, mcause: usize) -> ! {
+unsafe fn panic_on_unhandled_exception(fl_state: *mut usize, xcause: usize) -> ! {
     // Read the original PC from the first-level state
     let pc = unsafe { *fl_state.offset(16) };
 
-    panic!("unhandled exception {} at 0x{:08x}", mcause, pc);
+    panic!("unhandled exception {} at 0x{:08x}", xcause, pc);
 }
 
 #[cfg(not(feature = "emulate-lr-sc"))]
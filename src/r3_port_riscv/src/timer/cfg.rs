@@ -2,10 +2,18 @@
 use r3::kernel::InterruptNum;
 
 /// Attach the implementation of [`PortTimer`] that is based on the RISC-V timer
-/// (`mtime`/`mtimecfg`) to a given system type. This macro also implements
+/// (`mtime`/`mtimecmp`) to a given system type. This macro also implements
 /// [`Timer`] on the system type.
 /// **Requires [`TimerOptions`].**
 ///
+/// This driver is suitable for the timer provided by a SiFive CLINT (Core
+/// Local Interruptor) and compatible devices, such as ACLINT MTIMER. The
+/// driver reads `mtime` in [`tick_count`] and writes `mtime + delta` to
+/// `mtimecmp` in [`pend_tick_after`].
+///
+/// [`tick_count`]: r3::kernel::PortTimer::tick_count
+/// [`pend_tick_after`]: r3::kernel::PortTimer::pend_tick_after
+///
 /// [`PortTimer`]: r3::kernel::PortTimer
 /// [`Timer`]: crate::Timer
 ///
@@ -19,9 +27,10 @@ use r3::kernel::InterruptNum;
 /// r3_port_riscv::use_timer!(unsafe impl PortTimer for System);
 ///
 /// impl r3_port_riscv::TimerOptions for System {
-///     const MTIME_PTR: usize = 0x1001_1000;
-///     const MTIMECMP_PTR: usize = 0x1001_1000;
-///     const FREQUENCY: u64 = 1_000_000;
+///     // CLINT on QEMU `virt`, hart 0
+///     const MTIME_PTR: usize = 0x0200_bff8;
+///     const MTIMECMP_PTR: usize = 0x0200_4000;
+///     const FREQUENCY: u64 = 10_000_000;
 /// }
 ///
 /// const fn configure_app(b: &mut CfgBuilder<System>) -> Objects {
@@ -113,13 +122,24 @@ macro_rules! use_timer {
 /// The options for [`use_timer!`].
 pub trait TimerOptions {
     /// The memory address of the `mtime` register.
+    ///
+    /// For a SiFive CLINT, this is `CLINT_BASE + 0xbff8`.
+    ///
+    /// This is not used if the `s-mode` feature is enabled. The driver reads
+    /// the `time` CSR instead.
     const MTIME_PTR: usize;
 
     /// The memory address of the `mtimecmp` register.
+    ///
+    /// For a SiFive CLINT, this is `CLINT_BASE + 0x4000 + 8 * hart_id`.
+    ///
+    /// This is not used if the `s-mode` feature is enabled. The driver
+    /// programs the timer through the SBI Timer Extension instead.
     const MTIMECMP_PTR: usize;
 
     /// When set to `true`, the driver clears the lower 32 bits of the `mtime`
-    /// register on boot.
+    /// register on boot. Defaults to `true` unless the `s-mode` feature is
+    /// enabled. Must be `false` if the `s-mode` feature is enabled.
     ///
     /// Disabling this might increase the runtime overhead of the driver.
     /// Nevertheless, the need to disable this might arise for numerous reasons
@@ -131,7 +151,7 @@ pub trait TimerOptions {
     ///    could confuse the code running in the other harts.
     ///
     /// [is not supported by QEMU]: https://github.com/qemu/qemu/blob/672b2f2695891b6d818bddc3ce0df964c7627969/hw/riscv/sifive_clint.c#L165-L173
-    const RESET_MTIME: bool = true;
+    const RESET_MTIME: bool = !cfg!(feature = "s-mode");
 
     /// The numerator of the effective timer clock rate of the dual timer.
     const FREQUENCY: u64;
@@ -44,11 +44,34 @@ trait TimerInstanceExt: TimerInstance {
 
     #[cfg(target_arch = "riscv64")]
     #[inline(always)]
+    fn mtimecmp_reg64() -> &'static ReadWrite<u64> {
+        // Safety: Verified by the user of `use_timer!`
+        unsafe { &*(Self::MTIMECMP_PTR as *const _) }
+    }
+
+    /// Read the lower 32 bits of `mtime`.
+    #[cfg(not(feature = "s-mode"))]
+    #[inline(always)]
+    fn mtime_lo() -> u32 {
+        Self::mtime_reg32()[0].get()
+    }
+
+    /// Read the lower 32 bits of `mtime` through the `time` CSR.
+    #[cfg(feature = "s-mode")]
+    #[inline(always)]
+    fn mtime_lo() -> u32 {
+        let read: usize;
+        unsafe { asm!("csrr {}, time", lateout(reg) read) };
+        read as u32
+    }
+
+    #[cfg(all(not(feature = "s-mode"), target_arch = "riscv64"))]
+    #[inline(always)]
     fn mtime() -> u64 {
         Self::mtime_reg64().get()
     }
 
-    #[cfg(not(target_arch = "riscv64"))]
+    #[cfg(all(not(feature = "s-mode"), not(target_arch = "riscv64")))]
     #[inline(always)]
     fn mtime() -> u64 {
         loop {
@@ -60,11 +83,98 @@ trait TimerInstanceExt: TimerInstance {
             }
         }
     }
+
+    #[cfg(all(feature = "s-mode", target_arch = "riscv64"))]
+    #[inline(always)]
+    fn mtime() -> u64 {
+        let read: u64;
+        unsafe { asm!("csrr {}, time", lateout(reg) read) };
+        read
+    }
+
+    #[cfg(all(feature = "s-mode", not(target_arch = "riscv64")))]
+    #[inline(always)]
+    fn mtime() -> u64 {
+        loop {
+            let (hi1, lo, hi2): (u32, u32, u32);
+            unsafe {
+                asm!(
+                    "
+                        csrr {hi1}, timeh
+                        csrr {lo}, time
+                        csrr {hi2}, timeh
+                    ",
+                    hi1 = lateout(reg) hi1,
+                    lo = lateout(reg) lo,
+                    hi2 = lateout(reg) hi2,
+                )
+            };
+            if hi1 == hi2 {
+                return lo as u64 | ((hi2 as u64) << 32);
+            }
+        }
+    }
+
+    #[cfg(all(not(feature = "s-mode"), target_arch = "riscv64"))]
+    #[inline(always)]
+    fn set_mtimecmp(value: u64) {
+        Self::mtimecmp_reg64().set(value);
+    }
+
+    #[cfg(all(not(feature = "s-mode"), not(target_arch = "riscv64")))]
+    #[inline(always)]
+    fn set_mtimecmp(value: u64) {
+        // Updating `mtimecmp` non-atomically could make it temporarily hold a
+        // value smaller than both of the old and new values, raising a
+        // spurious timer interrupt. Avoid this by using the sequence suggested
+        // by the RISC-V privileged specification: no intermediate value is
+        // smaller than the new value.
+        Self::mtimecmp_reg32()[1].set(u32::MAX);
+        Self::mtimecmp_reg32()[0].set(value as u32);
+        Self::mtimecmp_reg32()[1].set((value >> 32) as u32);
+    }
+
+    /// Program the timer through the SBI Timer Extension because `mtimecmp`
+    /// isn't accessible from S-mode. This also clears `sip.STIP`.
+    #[cfg(feature = "s-mode")]
+    #[inline(always)]
+    fn set_mtimecmp(value: u64) {
+        /// The extension ID of the SBI Timer Extension (`"TIME"`)
+        const EID_TIME: usize = 0x54494D45;
+        /// The function ID of `sbi_set_timer`
+        const FID_SET_TIMER: usize = 0;
+
+        #[cfg(target_arch = "riscv64")]
+        unsafe {
+            asm!(
+                "ecall",
+                inlateout("a0") value as usize => _,
+                lateout("a1") _,
+                in("a6") FID_SET_TIMER,
+                in("a7") EID_TIME,
+            )
+        };
+
+        #[cfg(not(target_arch = "riscv64"))]
+        unsafe {
+            asm!(
+                "ecall",
+                inlateout("a0") value as usize => _,
+                inlateout("a1") (value >> 32) as usize => _,
+                in("a6") FID_SET_TIMER,
+                in("a7") EID_TIME,
+            )
+        };
+    }
 }
 impl<T: TimerInstance> TimerInstanceExt for T {}
 
 /// The configuration function.
 pub const fn configure<System: TimerInstance>(b: &mut CfgBuilder<System>) {
+    if cfg!(feature = "s-mode") && System::RESET_MTIME {
+        panic!("`RESET_MTIME` is not supported in S-mode");
+    }
+
     InterruptHandler::build()
         .line(System::INTERRUPT_NUM)
         .start(handle_tick::<System>)
@@ -82,7 +192,7 @@ pub fn init<System: TimerInstance>() {
     if System::RESET_MTIME {
         System::mtime_reg32()[0].set(0);
     } else {
-        tstate.reset(tcfg, System::mtime_reg32()[0].get());
+        tstate.reset(tcfg, System::mtime_lo());
     }
 }
 
@@ -94,7 +204,7 @@ pub fn init<System: TimerInstance>() {
 pub unsafe fn tick_count<System: TimerInstance>() -> UTicks {
     let tcfg = &System::TICKLESS_CFG;
 
-    let hw_tick_count = System::mtime_reg32()[0].get();
+    let hw_tick_count = System::mtime_lo();
 
     // Safety: CPU Lock protects it from concurrent access
     let tstate = unsafe { &mut *System::tickless_state() };
@@ -107,8 +217,7 @@ pub unsafe fn tick_count<System: TimerInstance>() -> UTicks {
 ///
 /// Only meant to be referenced by `use_timer!`.
 pub unsafe fn pend_tick<System: TimerInstance>() {
-    System::mtimecmp_reg32()[0].set(0);
-    System::mtimecmp_reg32()[1].set(0);
+    System::set_mtimecmp(0);
 }
 
 /// Implements [`r3::kernel::PortTimer::pend_tick_after`]
@@ -128,10 +237,7 @@ pub unsafe fn pend_tick_after<System: TimerInstance>(tick_count_delta: UTicks) {
 
     let next_hw_tick_count = cur_hw_tick_count + hw_ticks as u64;
 
-    // Since we have CPU Lock, spurious timer interrupts while non-atomically
-    // updating `mtimecmp` are acceptable
-    System::mtimecmp_reg32()[0].set(next_hw_tick_count as u32);
-    System::mtimecmp_reg32()[1].set((next_hw_tick_count >> 32) as u32);
+    System::set_mtimecmp(next_hw_tick_count);
 }
 
 #[inline]
@@ -141,7 +247,7 @@ fn handle_tick<System: TimerInstance>(_: usize) {
     // Safety: CPU Lock protects it from concurrent access
     let tstate = unsafe { &mut *System::tickless_state() };
 
-    let cur_hw_tick_count = System::mtime_reg32()[0].get();
+    let cur_hw_tick_count = System::mtime_lo();
     tstate.mark_reference(tcfg, cur_hw_tick_count);
 
     // Safety: CPU Lock inactive, an interrupt context
@@ -164,6 +164,7 @@ pub(crate) const fn configure<System: Kernel>(b: &mut CfgBuilder<System>) -> ()
             .line(INTERRUPT_GPIO0)
             .start(|_| clear_interrupt_line(INTERRUPT_GPIO0).unwrap())
             .priority(i32::MIN)
+            .chain(true)
             .unmanaged()
             .finish(b);
 
@@ -171,6 +172,7 @@ pub(crate) const fn configure<System: Kernel>(b: &mut CfgBuilder<System>) -> ()
             .line(INTERRUPT_GPIO1)
             .start(|_| clear_interrupt_line(INTERRUPT_GPIO1).unwrap())
             .priority(i32::MIN)
+            .chain(true)
             .unmanaged()
             .finish(b);
     }
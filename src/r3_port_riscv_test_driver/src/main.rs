@@ -30,6 +30,9 @@ mod uart;
 #[cfg(feature = "output-k210-uart")]
 #[path = "uart_k210.rs"]
 mod uart;
+#[cfg(feature = "output-ns16550-uart")]
+#[path = "uart_ns16550.rs"]
+mod uart;
 
 #[cfg(feature = "interrupt-e310x")]
 mod interrupt_e310x;
@@ -40,6 +43,8 @@ mod e310x;
 mod k210;
 #[cfg(feature = "board-u540-qemu")]
 mod u540;
+#[cfg(feature = "board-virt-qemu")]
+mod virt;
 
 #[allow(unused_macros)]
 macro_rules! instantiate_test {
@@ -92,6 +97,20 @@ macro_rules! instantiate_test {
             const MAX_PRIORITY: InterruptPriority = 7;
             const MAX_NUM: InterruptNum = 53;
             const PLIC_BASE: usize = 0x0c00_0000;
+            // hart 1, M-mode
+            const CONTEXT: usize = 1;
+            // hart 1, S-mode
+            const DISABLED_CONTEXTS: &'static [usize] = &[2];
+        }
+
+        #[cfg(feature = "interrupt-virt-qemu")]
+        port::use_plic!(unsafe impl InterruptController for System);
+        #[cfg(feature = "interrupt-virt-qemu")]
+        impl port::PlicOptions for System {
+            const MAX_PRIORITY: InterruptPriority = 7;
+            const MAX_NUM: InterruptNum = 53;
+            const PLIC_BASE: usize = 0x0c00_0000;
+            // hart 0, S-mode
             const CONTEXT: usize = 1;
         }
 
@@ -111,7 +130,8 @@ macro_rules! instantiate_test {
             #[cfg(any(
                 feature = "board-e310x-red-v",
                 feature = "board-e310x-qemu",
-                feature = "board-maix"
+                feature = "board-maix",
+                feature = "board-virt-qemu"
             ))]
             const MTIMECMP_PTR: usize = 0x0200_4000;
             #[cfg(feature = "board-u540-qemu")]
@@ -123,6 +143,8 @@ macro_rules! instantiate_test {
             const FREQUENCY: u64 = u540::MTIME_FREQUENCY;
             #[cfg(feature = "board-maix")]
             const FREQUENCY: u64 = k210::MTIME_FREQUENCY;
+            #[cfg(feature = "board-virt-qemu")]
+            const FREQUENCY: u64 = virt::MTIME_FREQUENCY;
 
             // Updating `mtime` is not supported by QEMU.
             const RESET_MTIME: bool = false;
@@ -138,6 +160,7 @@ macro_rules! instantiate_test {
             fn success() {
                 report_success();
             }
+            #[cfg(not(feature = "s-mode"))]
             fn performance_time() -> u32 {
                 unsafe {
                     let mcycle;
@@ -146,6 +169,15 @@ macro_rules! instantiate_test {
                 }
             }
 
+            #[cfg(feature = "s-mode")]
+            fn performance_time() -> u32 {
+                unsafe {
+                    let cycle;
+                    asm!("csrr {}, cycle", out(reg)cycle);
+                    cycle
+                }
+            }
+
             const PERFORMANCE_TIME_UNIT: &'static str = "cycle(s)";
 
             #[cfg(feature = "interrupt-e310x")]
@@ -3,7 +3,14 @@ use core::panic::PanicInfo;
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
     // Disable interrupts
-    unsafe { riscv::register::mstatus::clear_mie() };
+    #[cfg(not(feature = "s-mode"))]
+    unsafe {
+        riscv::register::mstatus::clear_mie()
+    };
+    #[cfg(feature = "s-mode")]
+    unsafe {
+        riscv::register::sstatus::clear_sie()
+    };
 
     crate::uart::stdout_write_fmt(format_args!("{}\n", info));
 
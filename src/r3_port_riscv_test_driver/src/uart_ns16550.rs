@@ -0,0 +1,72 @@
+//! The UART driver for the NS16550A-compatible UART of QEMU `virt` machine.
+//! The machine has only one UART, so the primary message and the log output
+//! are multiplexed into one stream.
+use core::fmt;
+
+const UART_BASE: *mut u8 = 0x1000_0000 as _;
+
+/// Call `f` with the machine-mode or supervisor-mode interrupts disabled.
+#[inline]
+fn interrupt_free(f: impl FnOnce()) {
+    let xstatus: usize;
+    // `xstatus.xIE` is bit 3 in M-mode and bit 1 in S-mode
+    #[cfg(not(feature = "s-mode"))]
+    unsafe {
+        asm!("csrrci {}, mstatus, 8", lateout(reg) xstatus)
+    };
+    #[cfg(feature = "s-mode")]
+    unsafe {
+        asm!("csrrci {}, sstatus, 2", lateout(reg) xstatus)
+    };
+
+    f();
+
+    #[cfg(not(feature = "s-mode"))]
+    unsafe {
+        asm!("csrs mstatus, {}", in(reg) xstatus & 8)
+    };
+    #[cfg(feature = "s-mode")]
+    unsafe {
+        asm!("csrs sstatus, {}", in(reg) xstatus & 2)
+    };
+}
+
+fn write_u8(x: u8) {
+    // On QEMU, this will instantly send the character to the console
+    unsafe { UART_BASE.write_volatile(x) };
+}
+
+pub fn stdout_write_fmt(args: fmt::Arguments<'_>) {
+    interrupt_free(|| {
+        // Switch to the primary message channel using our multiplexing protocol
+        write_u8(0x17);
+        write_u8(b'1');
+
+        let _ = fmt::Write::write_fmt(&mut SerialWrapper, args);
+    });
+}
+
+pub fn stderr_write_fmt(args: fmt::Arguments<'_>) {
+    interrupt_free(|| {
+        // Switch to the log channel using our multiplexing protocol
+        write_u8(0x17);
+        write_u8(b'2');
+
+        let _ = fmt::Write::write_fmt(&mut SerialWrapper, args);
+    });
+}
+
+struct SerialWrapper;
+
+impl fmt::Write for SerialWrapper {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.as_bytes() {
+            if *byte == '\n' as u8 {
+                write_u8('\r' as u8);
+            }
+
+            write_u8(*byte);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! QEMU `virt` machine, running in S-mode on top of OpenSBI
+pub const MTIME_FREQUENCY: u64 = 10_000_000;
+
+/// The entry point in S-mode. OpenSBI jumps to this function (the ELF entry
+/// point specified by `r3_test_runner`) in S-mode. `riscv-rt`'s `_start`
+/// can't be used because it accesses M-mode CSRs.
+///
+/// This does what `_start` and `_start_rust` would do for a single-hart
+/// system and calls the entry function defined by `use_rt!`.
+#[naked]
+#[export_name = "_s_mode_start"]
+unsafe extern "C" fn s_mode_start() -> ! {
+    unsafe {
+        asm!(
+            "
+                # Disable interrupts
+                csrw sie, zero
+
+                .option push
+                .option norelax
+                la gp, __global_pointer$
+                .option pop
+
+                la sp, _stack_start
+                mv s0, sp
+
+                j {start_rust}
+            ",
+            start_rust = sym s_mode_start_rust,
+            options(noreturn),
+        );
+    }
+}
+
+unsafe extern "C" fn s_mode_start_rust() -> ! {
+    extern "C" {
+        static mut _sbss: u32;
+        static mut _ebss: u32;
+        static mut _sdata: u32;
+        static mut _edata: u32;
+        static _sidata: u32;
+    }
+
+    extern "Rust" {
+        fn main() -> !;
+    }
+
+    unsafe {
+        // Zero-initialize `.bss`
+        let mut p: *mut u32 = &mut _sbss;
+        while p < &mut _ebss as *mut u32 {
+            p.write_volatile(0);
+            p = p.add(1);
+        }
+
+        // Initialize `.data`. This is a no-op if the image is loaded to RAM.
+        let mut p: *mut u32 = &mut _sdata;
+        let mut src: *const u32 = &_sidata;
+        if p as *const u32 != src {
+            while p < &mut _edata as *mut u32 {
+                p.write_volatile(src.read());
+                p = p.add(1);
+                src = src.add(1);
+            }
+        }
+
+        main()
+    }
+}
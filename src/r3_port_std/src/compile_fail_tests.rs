@@ -0,0 +1,147 @@
+//! Checks that the kernel configurator rejects invalid configurations at
+//! compile time.
+//!
+//! A task with a stack of `STACK_MINIMUM_SIZE` bytes is accepted:
+//!
+//! ```
+//! #![feature(const_fn)]
+//! #![feature(const_mut_refs)]
+//! #![feature(const_fn_fn_ptr_basics)]
+//! use r3::kernel::{cfg::CfgBuilder, PortThreading, Task};
+//!
+//! r3_port_std::use_port!(unsafe struct System);
+//!
+//! const COTTAGE: () = r3::build!(System, configure_app => ());
+//!
+//! const fn configure_app(b: &mut CfgBuilder<System>) -> () {
+//!     Task::build()
+//!         .start(task_body)
+//!         .priority(0)
+//!         .stack_size(<System as PortThreading>::STACK_MINIMUM_SIZE)
+//!         .finish(b);
+//! }
+//!
+//! fn task_body(_: usize) {}
+//!
+//! # #[cfg(any())]
+//! # fn main() {}
+//! ```
+//!
+//! A task with a smaller stack is rejected:
+//!
+//! ```compile_fail
+//! #![feature(const_fn)]
+//! #![feature(const_mut_refs)]
+//! #![feature(const_fn_fn_ptr_basics)]
+//! use r3::kernel::{cfg::CfgBuilder, PortThreading, Task};
+//!
+//! r3_port_std::use_port!(unsafe struct System);
+//!
+//! const COTTAGE: () = r3::build!(System, configure_app => ());
+//!
+//! const fn configure_app(b: &mut CfgBuilder<System>) -> () {
+//!     Task::build()
+//!         .start(task_body)
+//!         .priority(0)
+//!         .stack_size(<System as PortThreading>::STACK_MINIMUM_SIZE - 1)
+//!         .finish(b);
+//! }
+//!
+//! fn task_body(_: usize) {}
+//!
+//! # #[cfg(any())]
+//! # fn main() {}
+//! ```
+//!
+//! Multiple interrupt handlers can be attached to an interrupt line if all but
+//! one of them are marked with `chain`:
+//!
+//! ```
+//! #![feature(const_fn)]
+//! #![feature(const_mut_refs)]
+//! #![feature(const_fn_fn_ptr_basics)]
+//! use r3::kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine};
+//!
+//! r3_port_std::use_port!(unsafe struct System);
+//!
+//! const COTTAGE: () = r3::build!(System, configure_app => ());
+//!
+//! const fn configure_app(b: &mut CfgBuilder<System>) -> () {
+//!     InterruptLine::build().line(1).priority(0).finish(b);
+//!     InterruptHandler::build().line(1).start(isr).finish(b);
+//!     InterruptHandler::build()
+//!         .line(1)
+//!         .start(isr)
+//!         .chain(true)
+//!         .finish(b);
+//! }
+//!
+//! fn isr(_: usize) {}
+//!
+//! # #[cfg(any())]
+//! # fn main() {}
+//! ```
+//!
+//! Otherwise, the configuration is rejected:
+//!
+//! ```compile_fail
+//! #![feature(const_fn)]
+//! #![feature(const_mut_refs)]
+//! #![feature(const_fn_fn_ptr_basics)]
+//! use r3::kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine};
+//!
+//! r3_port_std::use_port!(unsafe struct System);
+//!
+//! const COTTAGE: () = r3::build!(System, configure_app => ());
+//!
+//! const fn configure_app(b: &mut CfgBuilder<System>) -> () {
+//!     InterruptLine::build().line(1).priority(0).finish(b);
+//!     InterruptHandler::build().line(1).start(isr).finish(b);
+//!     InterruptHandler::build().line(1).start(isr).finish(b);
+//! }
+//!
+//! fn isr(_: usize) {}
+//!
+//! # #[cfg(any())]
+//! # fn main() {}
+//! ```
+//!
+//! A mutex's priority ceiling must be a valid task priority:
+//!
+//! ```
+//! #![feature(const_fn)]
+//! #![feature(const_mut_refs)]
+//! #![feature(const_fn_fn_ptr_basics)]
+//! use r3::kernel::{cfg::CfgBuilder, Mutex, MutexProtocol};
+//!
+//! r3_port_std::use_port!(unsafe struct System);
+//!
+//! const COTTAGE: () = r3::build!(System, configure_app => ());
+//!
+//! const fn configure_app(b: &mut CfgBuilder<System>) -> () {
+//!     b.num_task_priority_levels(4);
+//!     Mutex::build().protocol(MutexProtocol::Ceiling(3)).finish(b);
+//! }
+//!
+//! # #[cfg(any())]
+//! # fn main() {}
+//! ```
+//!
+//! ```compile_fail
+//! #![feature(const_fn)]
+//! #![feature(const_mut_refs)]
+//! #![feature(const_fn_fn_ptr_basics)]
+//! use r3::kernel::{cfg::CfgBuilder, Mutex, MutexProtocol};
+//!
+//! r3_port_std::use_port!(unsafe struct System);
+//!
+//! const COTTAGE: () = r3::build!(System, configure_app => ());
+//!
+//! const fn configure_app(b: &mut CfgBuilder<System>) -> () {
+//!     b.num_task_priority_levels(4);
+//!     Mutex::build().protocol(MutexProtocol::Ceiling(4)).finish(b);
+//! }
+//!
+//! # #[cfg(any())]
+//! # fn main() {}
+//! ```
@@ -0,0 +1,111 @@
+//! Initiates graceful shutdown on Ctrl-C ([`Options::shutdown_on_ctrl_c`]).
+//!
+//! [`Options::shutdown_on_ctrl_c`]: crate::Options::shutdown_on_ctrl_c
+use std::sync::atomic::Ordering;
+
+use crate::PortInstance;
+
+#[cfg(unix)]
+use crate::threading::ok_or_errno;
+
+/// The signals handled by [`CtrlCHandler`].
+#[cfg(unix)]
+const SIGNALS: [libc::c_int; 2] = [libc::SIGINT, libc::SIGTERM];
+
+/// A handle to an installed Ctrl-C handler. The original handler is restored
+/// when this is dropped.
+pub(crate) struct CtrlCHandler {
+    #[cfg(unix)]
+    old_actions: [libc::sigaction; SIGNALS.len()],
+    #[cfg(windows)]
+    handler: winapi::um::wincon::PHANDLER_ROUTINE,
+}
+
+impl CtrlCHandler {
+    /// Install a handler that initiates graceful shutdown of `System` when the
+    /// process receives SIGINT or SIGTERM (Unix) or a console control event
+    /// (Windows). If the process receives another one, it's handled by the
+    /// default handler, which usually terminates the process.
+    #[cfg(unix)]
+    pub(crate) fn install<System: PortInstance>() -> Self {
+        extern "C" fn handle_signal<System: PortInstance>(_: libc::c_int) {
+            // `SA_RESETHAND` makes the next signal terminate the process
+            let state = System::port_state();
+            state.ctrl_c_received.store(true, Ordering::Release);
+            state.signal_relay.wake();
+        }
+
+        log::trace!("installing the Ctrl-C handler");
+
+        // Safety: `sigaction` is a POD type
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = handle_signal::<System> as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESETHAND | libc::SA_RESTART;
+        ok_or_errno(unsafe { libc::sigemptyset(&mut action.sa_mask) }).unwrap();
+
+        // Safety: `sigaction` is a POD type
+        let mut old_actions: [libc::sigaction; SIGNALS.len()] = unsafe { std::mem::zeroed() };
+        for (&signal, old_action) in SIGNALS.iter().zip(old_actions.iter_mut()) {
+            ok_or_errno(unsafe { libc::sigaction(signal, &action, old_action) }).unwrap();
+        }
+
+        Self { old_actions }
+    }
+
+    /// Install a handler that initiates graceful shutdown of `System` when the
+    /// process receives SIGINT or SIGTERM (Unix) or a console control event
+    /// (Windows). If the process receives another one, it's handled by the
+    /// default handler, which usually terminates the process.
+    #[cfg(windows)]
+    pub(crate) fn install<System: PortInstance>() -> Self {
+        use winapi::{
+            shared::minwindef::{BOOL, DWORD, FALSE, TRUE},
+            um::{consoleapi::SetConsoleCtrlHandler, wincon::PHANDLER_ROUTINE},
+        };
+
+        // This is called in a new thread created by the system
+        unsafe extern "system" fn handle_console_ctrl<System: PortInstance>(_: DWORD) -> BOOL {
+            if System::port_state()
+                .ctrl_c_received
+                .swap(true, Ordering::AcqRel)
+            {
+                // Let the default handler terminate the process
+                FALSE
+            } else {
+                crate::shutdown::<System>();
+                TRUE
+            }
+        }
+
+        log::trace!("installing the Ctrl-C handler");
+
+        let handler: PHANDLER_ROUTINE = Some(handle_console_ctrl::<System>);
+        let result = unsafe { SetConsoleCtrlHandler(handler, TRUE) };
+        assert_ne!(result, 0, "{}", std::io::Error::last_os_error());
+
+        Self { handler }
+    }
+}
+
+impl Drop for CtrlCHandler {
+    fn drop(&mut self) {
+        log::trace!("uninstalling the Ctrl-C handler");
+
+        #[cfg(unix)]
+        for (&signal, old_action) in SIGNALS.iter().zip(self.old_actions.iter()) {
+            ok_or_errno(unsafe { libc::sigaction(signal, old_action, std::ptr::null_mut()) })
+                .unwrap();
+        }
+
+        #[cfg(windows)]
+        {
+            let result = unsafe {
+                winapi::um::consoleapi::SetConsoleCtrlHandler(
+                    self.handler,
+                    winapi::shared::minwindef::FALSE,
+                )
+            };
+            assert_ne!(result, 0, "{}", std::io::Error::last_os_error());
+        }
+    }
+}
@@ -11,15 +11,17 @@ use atomic_ref::AtomicRef;
 use once_cell::sync::OnceCell;
 use r3::{
     kernel::{
-        ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-        PendInterruptLineError, Port, PortToKernel, QueryInterruptLineError,
-        SetInterruptLinePriorityError, TaskCb, UTicks,
+        ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError,
+        InterruptLineTriggerMode, InterruptNum, InterruptPriority, PendInterruptLineError, Port,
+        PortThreading, PortToKernel, QueryInterruptLineError, SetInterruptLinePriorityError,
+        SetInterruptLineTriggerModeError, TaskCb, UTicks,
     },
     prelude::*,
 };
 use std::{
     cell::Cell,
-    sync::mpsc,
+    collections::VecDeque,
+    sync::{atomic::AtomicUsize, mpsc},
     time::{Duration, Instant},
 };
 use try_mutex::TryMutex;
@@ -35,6 +37,14 @@ mod threading;
 #[cfg(test)]
 mod threading_test;
 
+#[cfg(doctest)]
+mod compile_fail_tests;
+
+#[cfg(unix)]
+mod signal_relay;
+
+mod ctrl_c;
+
 mod sched;
 mod ums;
 mod utils;
@@ -67,6 +77,117 @@ pub const INTERRUPT_LINE_TIMER: InterruptNum = 1022;
 /// The default interrupt priority for [`INTERRUPT_LINE_TIMER`].
 pub const INTERRUPT_PRIORITY_TIMER: InterruptPriority = 16383;
 
+/// The (software) interrupt lines reserved for [`SoftwareInterrupt`]s.
+///
+/// [`SoftwareInterrupt`]: r3::kernel::SoftwareInterrupt
+pub const INTERRUPT_LINES_SOFTWARE: &[InterruptNum] = &[
+    1006, 1007, 1008, 1009, 1010, 1011, 1012, 1013, 1014, 1015, 1016, 1017, 1018, 1019, 1020, 1021,
+];
+
+/// The options for [`State::new_with_options`]. They are usually specified
+/// through [`use_port!`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Enables the deterministic mode. Defaults to `false`.
+    ///
+    /// In the deterministic mode, the port doesn't let the host system's
+    /// thread scheduling or timing affect the simulated system's behavior:
+    ///
+    ///  - The tick count is driven by a logical clock, which advances only
+    ///    when the simulated processor is idle (i.e., there are no runnable
+    ///    tasks or interrupt handlers) and there's an outstanding timeout
+    ///    requested by [`PortTimer::pend_tick_after`]. The logical clock jumps
+    ///    straight to the timeout. Consequently, **a task that busy-waits for
+    ///    the time to pass will never complete.**
+    ///
+    ///  - Ties between runnable threads are broken by their thread IDs.
+    ///
+    ///  - Interrupt requests from external threads ([`pend_interrupt_line`]
+    ///    and [`pend_interrupt_line_from_signal`]) are queued and delivered
+    ///    one at a time when the simulated processor becomes idle, before the
+    ///    logical clock is advanced. They never preempt a running thread.
+    ///    Consequently, **a task that busy-waits for an external interrupt
+    ///    will never complete.**
+    ///
+    ///  - The port records the events that affect the control flow of the
+    ///    simulated system. They can be retrieved by [`State::event_log`].
+    ///
+    /// Given the same external inputs in the same order, two runs
+    /// produce the same sequence of events, and each event happens at the same
+    /// point of the execution. Comparing the event logs of two runs reveals
+    /// where they diverged.
+    ///
+    /// [`PortTimer::pend_tick_after`]: r3::kernel::PortTimer::pend_tick_after
+    pub deterministic: bool,
+
+    /// The seed for any randomness in the port. Defaults to `0x00c0ffee`.
+    ///
+    /// Currently, this is added to the tick count so that the kernel doesn't
+    /// depend on the tick count starting at zero.
+    pub seed: UTicks,
+
+    /// Installs a handler for Ctrl-C (SIGINT and SIGTERM on Unix, console
+    /// control events on Windows) during [`State::port_boot`]. Defaults to
+    /// `false`.
+    ///
+    /// The handler initiates graceful shutdown as [`shutdown`] does, letting
+    /// the port's threads exit cleanly and `port_boot` return. If the process
+    /// receives another one before that, the default handler runs, which
+    /// usually terminates the process. The original handlers are restored when
+    /// `port_boot` returns.
+    ///
+    /// This is disabled by default so that a library embedding the port
+    /// doesn't interfere with the host application's signal handling.
+    pub shutdown_on_ctrl_c: bool,
+}
+
+impl Options {
+    /// The default options.
+    pub const DEFAULT: Self = Self {
+        deterministic: false,
+        seed: 0x00c0ffee,
+        shutdown_on_ctrl_c: false,
+    };
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// An event recorded by the port in [the deterministic mode].
+///
+/// [the deterministic mode]: Options::deterministic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KernelEvent {
+    /// The logical clock advanced to `time` (measured in microseconds) to
+    /// fire the timer interrupt.
+    ClockAdvanced { time: u64 },
+    /// An interrupt handler for the interrupt line `line` was activated at
+    /// the logical time `time`.
+    InterruptActivated { line: InterruptNum, time: u64 },
+    /// An interrupt request from an external thread for the interrupt line
+    /// `line` was delivered at the logical time `time`.
+    ExternalInterruptPended { line: InterruptNum, time: u64 },
+}
+
+/// A function called by the dispatcher. See
+/// [`State::set_context_switch_hook`].
+///
+/// The parameters are the task that was running before the dispatch, the task
+/// that is chosen to run next, and the time when the dispatch happened,
+/// measured since an unspecified origin (or the logical time in [the
+/// deterministic mode]). A task is `None` if the processor is idle (or the task
+/// has just exited).
+///
+/// [the deterministic mode]: Options::deterministic
+pub type ContextSwitchHook<System> = fn(
+    prev_task: Option<&'static TaskCb<System>>,
+    next_task: Option<&'static TaskCb<System>>,
+    time: Duration,
+);
+
 /// Implemented on a system type by [`use_port!`].
 ///
 /// # Safety
@@ -74,6 +195,9 @@ pub const INTERRUPT_PRIORITY_TIMER: InterruptPriority = 16383;
 /// Only meant to be implemented by [`use_port!`].
 #[doc(hidden)]
 pub unsafe trait PortInstance: Kernel + Port<PortTaskState = TaskState> {
+    /// The resolution of the tick count, measured in microseconds.
+    const TICK_RESOLUTION_MICROS: UTicks;
+
     fn port_state() -> &'static State;
 }
 
@@ -88,6 +212,19 @@ pub struct State {
     thread_group: OnceCell<ums::ThreadGroup<sched::SchedState>>,
     timer_cmd_send: TryMutex<Option<mpsc::Sender<TimerCmd>>>,
     origin: AtomicRef<'static, Instant>,
+    options: Options,
+    /// The number of times the timer interrupt handler has been called.
+    num_timer_ticks: AtomicUsize,
+    /// The number of times [`PortThreading::idle`] has been called.
+    num_idle_calls: AtomicUsize,
+    /// `Option<ContextSwitchHook<System>>` cast to `usize` (`0` = `None`).
+    context_switch_hook: AtomicUsize,
+    /// Used by [`pend_interrupt_line_from_signal`].
+    #[cfg(unix)]
+    signal_relay: signal_relay::SignalRelay,
+    /// Set by the Ctrl-C handler installed if
+    /// [`Options::shutdown_on_ctrl_c`] is enabled.
+    ctrl_c_received: AtomicBool,
 }
 
 #[derive(Debug)]
@@ -121,7 +258,21 @@ enum Tsm {
 }
 
 enum TimerCmd {
-    SetTimeout { at: Instant },
+    SetTimeout {
+        at: Instant,
+    },
+    /// Set a timeout based on the logical clock (measured in microseconds).
+    /// Used in the deterministic mode.
+    SetLogicalTimeout {
+        at: u64,
+    },
+    /// Pend the interrupt line `line` on behalf of an external thread when
+    /// the simulated processor becomes idle. Used in the deterministic mode.
+    PendExternal {
+        line: InterruptNum,
+    },
+    /// Cancel the outstanding timeout (if any).
+    Cancel,
 }
 
 /// The role of a thread.
@@ -193,26 +344,57 @@ impl TaskState {
 #[allow(clippy::missing_safety_doc)]
 impl State {
     pub const fn new() -> Self {
+        Self::new_with_options(Options::DEFAULT)
+    }
+
+    pub const fn new_with_options(options: Options) -> Self {
         Self {
             thread_group: OnceCell::new(),
             timer_cmd_send: TryMutex::new(None),
             origin: AtomicRef::new(None),
+            options,
+            num_timer_ticks: AtomicUsize::new(0),
+            num_idle_calls: AtomicUsize::new(0),
+            context_switch_hook: AtomicUsize::new(0),
+            #[cfg(unix)]
+            signal_relay: signal_relay::SignalRelay::new(),
+            ctrl_c_received: AtomicBool::new(false),
         }
     }
 
     /// Initialize the user-mode scheduling system and boot the kernel.
     ///
     /// Returns when the shutdown initiated by [`shutdown`] completes.
+    /// [Shutdown hooks] are called just before returning.
+    ///
+    /// [Shutdown hooks]: r3::kernel::ShutdownHook
     pub fn port_boot<System: PortInstance>(&self) {
+        assert!(
+            System::MAX_TIMEOUT > 0 && System::MAX_TIMEOUT <= System::MAX_TICK_COUNT,
+            "`max_timeout` must be in range `1..=max_tick_count`"
+        );
+        assert!(
+            System::TICK_RESOLUTION_MICROS > 0
+                && System::TICK_RESOLUTION_MICROS <= System::MAX_TIMEOUT,
+            "`tick_resolution_micros` must be in range `1..=max_timeout`"
+        );
+
         // Create a UMS thread group.
-        let (thread_group, join_handle) = ums::ThreadGroup::new(sched::SchedState::new::<System>());
+        let (thread_group, join_handle) =
+            ums::ThreadGroup::new(sched::SchedState::new::<System>(self.options.deterministic));
 
         self.thread_group.set(thread_group).ok().unwrap();
 
         // Start a timer thread
         let (timer_cmd_send, timer_cmd_recv) = mpsc::channel();
         log::trace!("starting the timer thread");
+        let deterministic = self.options.deterministic;
         let timer_join_handle = std::thread::spawn(move || {
+            if deterministic {
+                logical_timer_thread::<System>(timer_cmd_recv);
+                return;
+            }
+
             let mut next_deadline = None;
             loop {
                 let recv_result = if let Some(next_deadline) = next_deadline {
@@ -233,11 +415,27 @@ impl State {
                     Ok(TimerCmd::SetTimeout { at }) => {
                         next_deadline = Some(at);
                     }
+                    Ok(TimerCmd::SetLogicalTimeout { .. }) => unreachable!(),
+                    Ok(TimerCmd::PendExternal { .. }) => unreachable!(),
+                    Ok(TimerCmd::Cancel) => {
+                        next_deadline = None;
+                    }
                 }
             }
         });
         *self.timer_cmd_send.lock() = Some(timer_cmd_send);
 
+        // Start a signal relay thread
+        #[cfg(unix)]
+        let signal_relay_thread = System::port_state().signal_relay.start::<System>();
+
+        // Install a Ctrl-C handler
+        let ctrl_c_handler = if self.options.shutdown_on_ctrl_c {
+            Some(ctrl_c::CtrlCHandler::install::<System>())
+        } else {
+            None
+        };
+
         // Create the initial UMS worker thread, where the boot phase of the
         // kernel runs
         let mut lock = self.thread_group.get().unwrap().lock();
@@ -269,6 +467,9 @@ impl State {
         // Wait until the thread group shuts down
         let result = join_handle.join();
 
+        // Restore the original Ctrl-C handler
+        drop(ctrl_c_handler);
+
         // Stop the timer thread.
         // `timer_cmd_recv.recv` will return `Err(_)` when we drop the
         // corresponding sender (`timer_cmd_send`).
@@ -277,10 +478,18 @@ impl State {
         timer_join_handle.join().unwrap();
         log::trace!("stopped the timer thread");
 
+        // Stop the signal relay thread
+        #[cfg(unix)]
+        signal_relay_thread.stop();
+
         // Propagate any panic that occured in a worker thread
         if let Err(e) = result {
             std::panic::resume_unwind(e);
         }
+
+        // Call shutdown hooks.
+        // Safety: We are a port, and all worker threads have exited
+        unsafe { <System as PortToKernel>::call_shutdown_hooks() };
     }
 
     pub unsafe fn dispatch_first_task<System: PortInstance>(&'static self) -> ! {
@@ -325,7 +534,10 @@ impl State {
         assert_eq!(expect_worker_thread::<System>(), ThreadRole::Interrupt);
 
         unsafe { self.enter_cpu_lock::<System>() };
+        // Safety: CPU Lock active
+        let prev_task = unsafe { *System::state().running_task_ptr() };
         unsafe { System::choose_running_task() };
+        self.call_context_switch_hook::<System>(prev_task);
         unsafe { self.leave_cpu_lock::<System>() };
 
         let mut lock = self.thread_group.get().unwrap().lock();
@@ -373,6 +585,54 @@ impl State {
         } else {
             None
         };
+        drop(lock);
+
+        if running_task.is_none() {
+            // Safety: CPU Lock inactive, not a task context
+            unsafe { <System as PortThreading>::idle() };
+        }
+    }
+
+    /// Register a function to be called by the dispatcher every time it
+    /// chooses a task to run, which is useful for recording a task execution
+    /// timeline. Pass `None` to unregister the function.
+    ///
+    /// The function is called with CPU Lock active, so it can't make most
+    /// system calls. Note that the previous and next tasks can be identical.
+    ///
+    /// `System` must be the system type associated with `self`.
+    pub fn set_context_switch_hook<System: PortInstance>(
+        &self,
+        hook: Option<ContextSwitchHook<System>>,
+    ) {
+        assert!(
+            std::ptr::eq(self, System::port_state()),
+            "`System` is not associated with this `State`"
+        );
+        self.context_switch_hook
+            .store(hook.map_or(0, |hook| hook as usize), Ordering::Relaxed);
+    }
+
+    /// Call the function registered by [`Self::set_context_switch_hook`] (if
+    /// any).
+    fn call_context_switch_hook<System: PortInstance>(
+        &self,
+        prev_task: Option<&'static TaskCb<System>>,
+    ) {
+        let hook = self.context_switch_hook.load(Ordering::Relaxed);
+        if hook == 0 {
+            return;
+        }
+
+        // Safety: `hook` was created from `ContextSwitchHook<System>` by
+        //         `set_context_switch_hook`
+        let hook: ContextSwitchHook<System> = unsafe { std::mem::transmute(hook) };
+
+        // Safety: CPU Lock active
+        let next_task = unsafe { *System::state().running_task_ptr() };
+        let time = Duration::from_micros(self.now_micros() as u64);
+
+        hook(prev_task, next_task, time);
     }
 
     pub unsafe fn yield_cpu<System: PortInstance>(&'static self) {
@@ -384,6 +644,19 @@ impl State {
             .unwrap();
     }
 
+    pub unsafe fn idle<System: PortInstance>(&'static self) {
+        log::trace!("idle");
+        expect_worker_thread::<System>();
+        assert!(!self.is_cpu_lock_active::<System>());
+
+        self.num_idle_calls.fetch_add(1, Ordering::Relaxed);
+
+        // Give other worker threads a chance to run. The dispatcher returns
+        // after this, and the simulated processor stays idle until an
+        // interrupt is pended.
+        ums::yield_now();
+    }
+
     pub unsafe fn exit_and_dispatch<System: PortInstance>(
         &'static self,
         task: &'static TaskCb<System>,
@@ -432,8 +705,33 @@ impl State {
         let mut tsm = pts.tsm.lock();
         match &*tsm {
             Tsm::Dormant => {}
-            Tsm::Running(_) => {
-                todo!("terminating a thread is not implemented yet");
+            Tsm::Running(thread_id) => {
+                // The task is being reinitialized while the backing thread is
+                // still alive. Discard the thread.
+                let thread_id = *thread_id;
+                log::trace!(
+                    "terminating the thread {:?} of the task {:p}",
+                    thread_id,
+                    task
+                );
+
+                // The current thread can't be the one being terminated. If it
+                // were, the kernel would call `exit_and_dispatch` instead.
+                assert_ne!(ums::current_thread(), Some(thread_id));
+
+                let mut lock = self.thread_group.get().unwrap().lock();
+
+                // Make sure the scheduler won't choose the terminated thread.
+                // The dispatcher will choose a new one.
+                if lock.scheduler().task_thread == Some(thread_id) {
+                    lock.scheduler().task_thread = None;
+                }
+
+                // Safety: The kernel has discarded the task's context, so
+                // we're allowed to discard its stack contents
+                unsafe { lock.terminate(thread_id) };
+
+                *tsm = Tsm::Dormant;
             }
             Tsm::Uninit => {
                 *tsm = Tsm::Dormant;
@@ -483,6 +781,18 @@ impl State {
         Ok(())
     }
 
+    pub fn get_interrupt_line_priority<System: PortInstance>(
+        &self,
+        num: InterruptNum,
+    ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+        expect_worker_thread::<System>();
+
+        (self.thread_group.get().unwrap().lock())
+            .scheduler()
+            .line_priority(num)
+            .map_err(|sched::BadIntLineError| GetInterruptLinePriorityError::BadParam)
+    }
+
     pub fn enable_interrupt_line<System: PortInstance>(
         &'static self,
         num: InterruptNum,
@@ -525,7 +835,15 @@ impl State {
 
         let mut lock = self.thread_group.get().unwrap().lock();
         lock.scheduler()
-            .update_line(num, |line| line.pended = true)
+            .update_line(num, |line| {
+                line.pended = true;
+
+                // Pending a level-triggered interrupt line asserts the
+                // simulated interrupt source
+                if line.trigger_mode == InterruptLineTriggerMode::Level {
+                    line.asserted = true;
+                }
+            })
             .map_err(|sched::BadIntLineError| PendInterruptLineError::BadParam)?;
 
         if sched::check_preemption_by_interrupt(self.thread_group.get().unwrap(), &mut lock) {
@@ -543,9 +861,11 @@ impl State {
         log::trace!("clear_interrupt_line{:?}", (num,));
         expect_worker_thread::<System>();
 
+        // A level-triggered interrupt line is pended again immediately if its
+        // interrupt source is still asserted
         (self.thread_group.get().unwrap().lock())
             .scheduler()
-            .update_line(num, |line| line.pended = false)
+            .update_line(num, |line| line.pended = line.asserted)
             .map_err(|sched::BadIntLineError| ClearInterruptLineError::BadParam)
     }
 
@@ -561,13 +881,90 @@ impl State {
             .map_err(|sched::BadIntLineError| QueryInterruptLineError::BadParam)
     }
 
-    // TODO: Make these customizable to test the kernel under multiple conditions
+    pub fn is_interrupt_line_enabled<System: PortInstance>(
+        &self,
+        num: InterruptNum,
+    ) -> Result<bool, QueryInterruptLineError> {
+        expect_worker_thread::<System>();
+
+        (self.thread_group.get().unwrap().lock())
+            .scheduler()
+            .is_line_enabled(num)
+            .map_err(|sched::BadIntLineError| QueryInterruptLineError::BadParam)
+    }
+
+    pub fn set_interrupt_line_trigger_mode<System: PortInstance>(
+        &self,
+        num: InterruptNum,
+        mode: InterruptLineTriggerMode,
+    ) -> Result<(), SetInterruptLineTriggerModeError> {
+        log::trace!("set_interrupt_line_trigger_mode{:?}", (num, mode));
+        expect_worker_thread::<System>();
+
+        (self.thread_group.get().unwrap().lock())
+            .scheduler()
+            .update_line(num, |line| {
+                line.trigger_mode = mode;
+                if mode == InterruptLineTriggerMode::Edge {
+                    line.asserted = false;
+                }
+            })
+            .map_err(|sched::BadIntLineError| SetInterruptLineTriggerModeError::BadParam)
+    }
+
+    /// Set the interrupt priority mask, which simulates the BASEPRI register
+    /// of Arm-M. While it's set, interrupts with priority values greater than
+    /// or equal to `mask` are not taken. `None` disables masking.
+    ///
+    /// Unlike CPU Lock, this is invisible to the kernel, so masking the
+    /// interrupts used by the port (e.g., [`INTERRUPT_LINE_DISPATCH`] and
+    /// [`INTERRUPT_LINE_TIMER`]) delays dispatching and timer processing until
+    /// the mask is lifted.
+    pub fn set_interrupt_priority_mask<System: PortInstance>(
+        &'static self,
+        mask: Option<InterruptPriority>,
+    ) {
+        log::trace!("set_interrupt_priority_mask{:?}", (mask,));
+        expect_worker_thread::<System>();
+
+        let mut lock = self.thread_group.get().unwrap().lock();
+        lock.scheduler().priority_mask = mask;
+
+        if sched::check_preemption_by_interrupt(self.thread_group.get().unwrap(), &mut lock) {
+            drop(lock);
+            ums::yield_now();
+        }
+    }
+
+    /// Get the interrupt priority mask set by
+    /// [`Self::set_interrupt_priority_mask`].
+    pub fn interrupt_priority_mask<System: PortInstance>(&self) -> Option<InterruptPriority> {
+        expect_worker_thread::<System>();
+
+        (self.thread_group.get().unwrap().lock())
+            .scheduler()
+            .priority_mask
+    }
+
+    /// The default value of [`PortTimer::MAX_TICK_COUNT`]. This can be
+    /// overridden by [`use_port!`].
+    ///
+    /// [`PortTimer::MAX_TICK_COUNT`]: r3::kernel::PortTimer::MAX_TICK_COUNT
     pub const MAX_TICK_COUNT: UTicks = UTicks::MAX;
+
+    /// The default value of [`PortTimer::MAX_TIMEOUT`]. This can be
+    /// overridden by [`use_port!`].
+    ///
+    /// [`PortTimer::MAX_TIMEOUT`]: r3::kernel::PortTimer::MAX_TIMEOUT
     pub const MAX_TIMEOUT: UTicks = UTicks::MAX / 2;
 
-    pub fn tick_count<System: PortInstance>(&self) -> UTicks {
-        expect_worker_thread::<System>();
+    /// The default value of [`PortInstance::TICK_RESOLUTION_MICROS`]. This can
+    /// be overridden by [`use_port!`].
+    pub const TICK_RESOLUTION_MICROS: UTicks = 1;
 
+    /// Get the origin point of the tick count, establishing one if it hasn't
+    /// been established yet.
+    fn origin(&self) -> Instant {
         let origin = if let Some(x) = self.origin.load(Ordering::Acquire) {
             x
         } else {
@@ -601,25 +998,54 @@ impl State {
             }
         };
 
-        let micros = Instant::now().duration_since(*origin).as_micros();
+        *origin
+    }
 
-        /// Implementation of <https://xkcd.com/221/> with a different magic
-        /// number
-        fn get_random_number() -> UTicks {
-            0x00c0ffee
+    /// Get the current time, measured in microseconds since the origin. In
+    /// the deterministic mode, this returns the logical clock.
+    fn now_micros(&self) -> u128 {
+        if self.options.deterministic {
+            let mut lock = self.thread_group.get().unwrap().lock();
+            lock.scheduler().logical_time as u128
+        } else {
+            Instant::now().duration_since(self.origin()).as_micros()
         }
+    }
 
-        // Calculate `micros % MAX_TICK_COUNT + 1` by truncating upper bits. Add
-        // some random number so that the kernel doesn't depend on zero-start.
-        (micros as UTicks).wrapping_add(get_random_number())
+    pub fn tick_count<System: PortInstance>(&self) -> UTicks {
+        expect_worker_thread::<System>();
+
+        let micros = self.now_micros();
+
+        // Round down to the tick resolution
+        let resolution = System::TICK_RESOLUTION_MICROS as u128;
+        let micros = micros - micros % resolution;
+
+        // Calculate `micros % (MAX_TICK_COUNT + 1)`. Add the seed so that the
+        // kernel doesn't depend on zero-start.
+        ((micros + self.options.seed as u128) % (System::MAX_TICK_COUNT as u128 + 1)) as UTicks
     }
 
     pub fn pend_tick_after<System: PortInstance>(&self, tick_count_delta: UTicks) {
         expect_worker_thread::<System>();
         log::trace!("pend_tick_after({:?})", tick_count_delta);
 
-        // Calculate when `timer_tick` should be called
-        let now = Instant::now() + Duration::from_micros(tick_count_delta.into());
+        // Calculate when `timer_tick` should be called. The tick count
+        // observed by the kernel is rounded down to the tick resolution, so
+        // round up the deadline to make sure the tick count has advanced by
+        // `tick_count_delta` by then.
+        let resolution = System::TICK_RESOLUTION_MICROS as u128;
+        let micros = self.now_micros();
+        let micros = micros - micros % resolution + tick_count_delta as u128;
+        let micros = (micros + resolution - 1) / resolution * resolution;
+
+        let cmd = if self.options.deterministic {
+            TimerCmd::SetLogicalTimeout { at: micros as u64 }
+        } else {
+            TimerCmd::SetTimeout {
+                at: self.origin() + Duration::from_micros(micros as u64),
+            }
+        };
 
         // Lock the scheduler because we aren't sure what would happen if
         // `Sender::send` was interrupted
@@ -627,28 +1053,164 @@ impl State {
 
         let timer_cmd_send = self.timer_cmd_send.lock();
         let timer_cmd_send = timer_cmd_send.as_ref().unwrap();
-        timer_cmd_send
-            .send(TimerCmd::SetTimeout { at: now })
-            .unwrap();
+        timer_cmd_send.send(cmd).unwrap();
     }
 
     pub fn pend_tick<System: PortInstance>(&'static self) {
         expect_worker_thread::<System>();
         log::trace!("pend_tick");
 
+        {
+            // Lock the scheduler because we aren't sure what would happen if
+            // `Sender::send` was interrupted
+            let _sched_lock = lock_scheduler::<System>();
+
+            // `timer_tick` will call `pend_tick_after` again, so the
+            // outstanding timeout is no longer needed. Cancel it so that it
+            // doesn't cause a redundant call to `timer_tick`.
+            let timer_cmd_send = self.timer_cmd_send.lock();
+            let timer_cmd_send = timer_cmd_send.as_ref().unwrap();
+            timer_cmd_send.send(TimerCmd::Cancel).unwrap();
+        }
+
         self.pend_interrupt_line::<System>(INTERRUPT_LINE_TIMER)
             .unwrap();
     }
 
+    /// Get the number of times the timer interrupt handler has been called.
+    /// Used for testing.
+    pub fn num_timer_ticks(&self) -> usize {
+        self.num_timer_ticks.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of times [`PortThreading::idle`] has been called. Used
+    /// for testing.
+    pub fn num_idle_calls(&self) -> usize {
+        self.num_idle_calls.load(Ordering::Relaxed)
+    }
+
+    /// Get the events recorded so far. Always returns an empty `Vec` unless
+    /// [the deterministic mode] is enabled.
+    ///
+    /// [the deterministic mode]: Options::deterministic
+    pub fn event_log(&self) -> Vec<KernelEvent> {
+        (self.thread_group.get().unwrap().lock())
+            .scheduler()
+            .event_log()
+            .to_vec()
+    }
+
     extern "C" fn timer_handler<System: PortInstance>() {
         assert_eq!(expect_worker_thread::<System>(), ThreadRole::Interrupt);
         log::trace!("timer_handler");
 
+        System::port_state()
+            .num_timer_ticks
+            .fetch_add(1, Ordering::Relaxed);
+
         // Safety: CPU Lock inactive, an interrupt context
         unsafe { <System as PortToKernel>::timer_tick() };
     }
 }
 
+/// The interval at which the timer thread checks if the simulated processor
+/// has become idle in the deterministic mode.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The pending inputs to the simulated system in the deterministic mode.
+#[derive(Default)]
+struct LogicalInputs {
+    next_deadline: Option<u64>,
+    /// The interrupt requests from external threads, in the order of arrival.
+    external_pends: VecDeque<InterruptNum>,
+}
+
+impl LogicalInputs {
+    fn is_empty(&self) -> bool {
+        self.next_deadline.is_none() && self.external_pends.is_empty()
+    }
+
+    fn handle_cmd(&mut self, cmd: TimerCmd) {
+        match cmd {
+            TimerCmd::SetLogicalTimeout { at } => self.next_deadline = Some(at),
+            TimerCmd::PendExternal { line } => self.external_pends.push_back(line),
+            TimerCmd::Cancel => self.next_deadline = None,
+            TimerCmd::SetTimeout { .. } => unreachable!(),
+        }
+    }
+}
+
+/// The body of the timer thread in the deterministic mode. Besides firing the
+/// timer interrupt, this thread delivers the interrupt requests from external
+/// threads.
+fn logical_timer_thread<System: PortInstance>(timer_cmd_recv: mpsc::Receiver<TimerCmd>) {
+    let thread_group = System::port_state().thread_group.get().unwrap();
+    let mut inputs = LogicalInputs::default();
+
+    loop {
+        let recv_result = if !inputs.is_empty() {
+            timer_cmd_recv.recv_timeout(IDLE_POLL_INTERVAL)
+        } else {
+            timer_cmd_recv
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected)
+        };
+
+        match recv_result {
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Ok(cmd) => {
+                inputs.handle_cmd(cmd);
+                continue;
+            }
+        }
+
+        let mut lock = thread_group.lock();
+
+        // Deliver inputs only when nothing can run anymore. This makes the
+        // points where interrupts are taken independent of the host system's
+        // timing.
+        if !lock.scheduler().is_idle() {
+            continue;
+        }
+
+        // The commands are sent with the scheduler lock held, so they are all
+        // in the queue by now
+        while let Ok(cmd) = timer_cmd_recv.try_recv() {
+            inputs.handle_cmd(cmd);
+        }
+
+        let sched_state = lock.scheduler();
+
+        // External interrupt requests take precedence over advancing the
+        // clock. Deliver them one at a time so that each one is processed
+        // to completion before the next one.
+        let num = if let Some(line) = inputs.external_pends.pop_front() {
+            let time = sched_state.logical_time;
+            sched_state.log_event(KernelEvent::ExternalInterruptPended { line, time });
+            log::trace!("delivering an external interrupt request for {}", line);
+            line
+        } else if let Some(deadline) = inputs.next_deadline.take() {
+            sched_state.logical_time = sched_state.logical_time.max(deadline);
+            let time = sched_state.logical_time;
+            sched_state.log_event(KernelEvent::ClockAdvanced { time });
+            log::trace!("advancing the logical clock to {}", time);
+            INTERRUPT_LINE_TIMER
+        } else {
+            continue;
+        };
+
+        sched_state
+            .update_line(num, |line| line.pended = true)
+            .ok()
+            .unwrap();
+
+        if sched::check_preemption_by_interrupt(thread_group, &mut lock) {
+            lock.preempt();
+        }
+    }
+}
+
 /// Assert that the current thread is a worker thread of `System`.
 fn expect_worker_thread<System: PortInstance>() -> ThreadRole {
     // TODO: Check that the current worker thread belongs to
@@ -660,8 +1222,11 @@ fn expect_worker_thread<System: PortInstance>() -> ThreadRole {
 
 /// Initiate graceful shutdown.
 ///
-/// The shutdown completes when all threads complete execution. Usually, the
-/// process will exit after this.
+/// The shutdown completes when all threads complete execution. After that,
+/// [shutdown hooks] are called on the main thread. Usually, the process will
+/// exit after this.
+///
+/// [shutdown hooks]: r3::kernel::ShutdownHook
 ///
 /// Note: There is no safe way to restart the simulated system without
 /// restarting an entire process.
@@ -679,6 +1244,11 @@ pub fn shutdown<System: PortInstance>() {
 /// It's illegal to call this method from a thread managed by the port (i.e.,
 /// you can't call it from a task or an interrupt handler). Use
 /// [`r3::kernel::InterruptLine::pend`] instead in such cases.
+///
+/// In [the deterministic mode], the request is queued and takes effect when
+/// the simulated processor becomes idle.
+///
+/// [the deterministic mode]: Options::deterministic
 pub fn pend_interrupt_line<System: PortInstance>(
     num: InterruptNum,
 ) -> Result<(), PendInterruptLineError> {
@@ -692,6 +1262,21 @@ pub fn pend_interrupt_line<System: PortInstance>(
 
     let state = System::port_state();
     let mut lock = state.thread_group.get().unwrap().lock();
+
+    if state.options.deterministic {
+        if num >= NUM_INTERRUPT_LINES {
+            return Err(PendInterruptLineError::BadParam);
+        }
+
+        // Leave it to the timer thread. The request is silently discarded if
+        // the system has already shut down.
+        if let Some(timer_cmd_send) = &*state.timer_cmd_send.lock() {
+            let _ = timer_cmd_send.send(TimerCmd::PendExternal { line: num });
+        }
+
+        return Ok(());
+    }
+
     lock.scheduler()
         .update_line(num, |line| line.pended = true)
         .map_err(|sched::BadIntLineError| PendInterruptLineError::BadParam)?;
@@ -704,6 +1289,20 @@ pub fn pend_interrupt_line<System: PortInstance>(
     Ok(())
 }
 
+/// Pend an interrupt line from a signal handler.
+///
+/// Unlike [`pend_interrupt_line`], this function is async-signal-safe, i.e.,
+/// it can be called from a signal handler, which might have interrupted any
+/// thread, including the ones managed by the port. The request is forwarded
+/// to the simulated hardware scheduler by a dedicated thread, so the interrupt
+/// handler doesn't start before this function returns.
+#[cfg(unix)]
+pub fn pend_interrupt_line_from_signal<System: PortInstance>(
+    num: InterruptNum,
+) -> Result<(), PendInterruptLineError> {
+    System::port_state().signal_relay.pend(num)
+}
+
 /// Temporarily lock the scheduler, disabling preemption.
 ///
 /// *All* operating system and port functions will be unavailable until the lock
@@ -713,23 +1312,178 @@ pub fn lock_scheduler<System: PortInstance>() -> impl Sized {
     state.thread_group.get().unwrap().lock()
 }
 
+/// Define a system type and implement the port traits on it. Generates
+/// `fn main()`, which boots the kernel.
+///
+/// The following options can be optionally specified in any order:
+///
+///  - `max_tick_count` and `max_timeout` customize the tick count range. This
+///    is useful for testing the kernel's handling of timer wrap-around, which
+///    rarely happens with the default (huge) range. `max_timeout` defaults to
+///    `max_tick_count / 2`.
+///
+///  - `tick_resolution_micros` specifies the resolution of the tick count,
+///    measured in microseconds. The default value is
+///    [`State::TICK_RESOLUTION_MICROS`] (1 microsecond).
+///
+///  - `deterministic`, `seed`, and `shutdown_on_ctrl_c` set the corresponding
+///    fields of [`Options`].
+///
+/// ```rust,ignore
+/// // `MAX_TICK_COUNT = State::MAX_TICK_COUNT`, `MAX_TIMEOUT = State::MAX_TIMEOUT`
+/// r3_port_std::use_port!(unsafe struct System);
+///
+/// // `MAX_TICK_COUNT = 0xffffff`, `MAX_TIMEOUT = 0xffffff / 2`
+/// r3_port_std::use_port!(unsafe struct System; max_tick_count = 0xffffff);
+///
+/// // `MAX_TICK_COUNT = 0xffffff`, `MAX_TIMEOUT = 0x7fff`
+/// r3_port_std::use_port!(
+///     unsafe struct System;
+///     max_tick_count = 0xffffff,
+///     max_timeout = 0x7fff,
+/// );
+///
+/// // The tick count advances in 1-millisecond steps
+/// r3_port_std::use_port!(unsafe struct System; tick_resolution_micros = 1000);
+///
+/// // Enable the deterministic mode
+/// r3_port_std::use_port!(unsafe struct System; deterministic = true);
+///
+/// // Shut down gracefully on Ctrl-C
+/// r3_port_std::use_port!(unsafe struct System; shutdown_on_ctrl_c = true);
+/// ```
+///
+/// # Choosing the Tick Resolution
+///
+/// The kernel always measures time in microseconds, so the tick count can't be
+/// finer than that. Choosing a coarser tick resolution makes the tick count
+/// advance in bigger steps, and timeouts are rounded up to the next step. This
+/// reduces the number of timer interrupts (and the wall-clock overhead caused
+/// by them) when there are many timeouts scheduled close to each other, at the
+/// cost of the timing precision. The resolution should be an order of
+/// magnitude smaller than the shortest timeout that has to be measured
+/// accurately.
 #[macro_export]
 macro_rules! use_port {
-    (unsafe $vis:vis struct $sys:ident) => {
+    (unsafe $vis:vis struct $sys:ident $(; $($opts:tt)*)?) => {
+        $crate::use_port!(
+            @parse_opts [unsafe $vis struct $sys]
+            [max_tick_count: $crate::State::MAX_TICK_COUNT]
+            [max_timeout:]
+            [tick_resolution_micros: $crate::State::TICK_RESOLUTION_MICROS]
+            [options:]
+            $($($opts)*)?
+        );
+    };
+
+    // Parse the options
+    (
+        @parse_opts $head:tt [max_tick_count: $old:expr] $max_timeout:tt $res:tt $options:tt
+        max_tick_count = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head [max_tick_count: $value] $max_timeout $res $options
+            $($($rest)*)?
+        );
+    };
+    (
+        @parse_opts $head:tt $max_tick_count:tt [max_timeout: $($old:expr)?] $res:tt $options:tt
+        max_timeout = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head $max_tick_count [max_timeout: $value] $res $options
+            $($($rest)*)?
+        );
+    };
+    (
+        @parse_opts $head:tt $max_tick_count:tt $max_timeout:tt [tick_resolution_micros: $old:expr]
+        $options:tt
+        tick_resolution_micros = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head $max_tick_count $max_timeout [tick_resolution_micros: $value]
+            $options $($($rest)*)?
+        );
+    };
+    (
+        @parse_opts $head:tt $max_tick_count:tt $max_timeout:tt $res:tt [options: $($opt:tt)*]
+        deterministic = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head $max_tick_count $max_timeout $res
+            [options: $($opt)* deterministic: $value,] $($($rest)*)?
+        );
+    };
+    (
+        @parse_opts $head:tt $max_tick_count:tt $max_timeout:tt $res:tt [options: $($opt:tt)*]
+        seed = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head $max_tick_count $max_timeout $res
+            [options: $($opt)* seed: $value,] $($($rest)*)?
+        );
+    };
+    (
+        @parse_opts $head:tt $max_tick_count:tt $max_timeout:tt $res:tt [options: $($opt:tt)*]
+        shutdown_on_ctrl_c = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head $max_tick_count $max_timeout $res
+            [options: $($opt)* shutdown_on_ctrl_c: $value,] $($($rest)*)?
+        );
+    };
+
+    // All options were parsed
+    (
+        @parse_opts $head:tt [max_tick_count: $max_tick_count:expr] [max_timeout:] $res:tt
+        $options:tt
+    ) => {
+        $crate::use_port!(
+            @parse_opts $head [max_tick_count: $max_tick_count]
+            [max_timeout: ($max_tick_count) / 2] $res $options
+        );
+    };
+    (
+        @parse_opts [unsafe $vis:vis struct $sys:ident]
+        [max_tick_count: $max_tick_count:expr]
+        [max_timeout: $max_timeout:expr]
+        [tick_resolution_micros: $tick_resolution_micros:expr]
+        [options: $($field:ident: $value:expr,)*]
+    ) => {
+        $crate::use_port!(
+            @inner unsafe $vis struct $sys,
+            $max_tick_count,
+            $max_timeout,
+            $tick_resolution_micros,
+            $crate::Options { $($field: $value,)* ..$crate::Options::DEFAULT }
+        );
+    };
+
+    (
+        @inner unsafe $vis:vis struct $sys:ident,
+        $max_tick_count:expr,
+        $max_timeout:expr,
+        $tick_resolution_micros:expr,
+        $options:expr
+    ) => {
         $vis struct $sys;
 
         mod port_std_impl {
             use super::$sys;
             use $crate::r3::kernel::{
-                ClearInterruptLineError, EnableInterruptLineError, InterruptNum, InterruptPriority,
-                PendInterruptLineError, Port, QueryInterruptLineError, SetInterruptLinePriorityError,
-                TaskCb, PortToKernel, PortInterrupts, PortThreading, UTicks, PortTimer,
+                ClearInterruptLineError, EnableInterruptLineError, GetInterruptLinePriorityError,
+                InterruptLineTriggerMode, InterruptNum, InterruptPriority, PendInterruptLineError,
+                Port, QueryInterruptLineError, SetInterruptLinePriorityError,
+                SetInterruptLineTriggerModeError, TaskCb, PortToKernel, PortInterrupts,
+                PortThreading, UTicks, PortTimer,
             };
             use $crate::{State, TaskState, PortInstance};
 
-            pub(super) static PORT_STATE: State = State::new();
+            pub(super) static PORT_STATE: State = State::new_with_options($options);
 
             unsafe impl PortInstance for $sys {
+                const TICK_RESOLUTION_MICROS: UTicks = $tick_resolution_micros;
+
                 #[inline]
                 fn port_state() -> &'static State {
                     &PORT_STATE
@@ -750,6 +1504,10 @@ macro_rules! use_port {
                     PORT_STATE.yield_cpu::<Self>()
                 }
 
+                unsafe fn idle() {
+                    PORT_STATE.idle::<Self>()
+                }
+
                 unsafe fn exit_and_dispatch(task: &'static TaskCb<Self>) -> ! {
                     PORT_STATE.exit_and_dispatch::<Self>(task);
                 }
@@ -779,6 +1537,9 @@ macro_rules! use_port {
                 const MANAGED_INTERRUPT_PRIORITY_RANGE:
                     ::std::ops::Range<InterruptPriority> = 0..InterruptPriority::MAX;
 
+                const SOFTWARE_INTERRUPT_LINES: &'static [InterruptNum] =
+                    $crate::INTERRUPT_LINES_SOFTWARE;
+
                 unsafe fn set_interrupt_line_priority(
                     line: InterruptNum,
                     priority: InterruptPriority,
@@ -786,6 +1547,12 @@ macro_rules! use_port {
                     PORT_STATE.set_interrupt_line_priority::<Self>(line, priority)
                 }
 
+                unsafe fn get_interrupt_line_priority(
+                    line: InterruptNum,
+                ) -> Result<InterruptPriority, GetInterruptLinePriorityError> {
+                    PORT_STATE.get_interrupt_line_priority::<Self>(line)
+                }
+
                 unsafe fn enable_interrupt_line(line: InterruptNum) -> Result<(), EnableInterruptLineError> {
                     PORT_STATE.enable_interrupt_line::<Self>(line)
                 }
@@ -807,11 +1574,25 @@ macro_rules! use_port {
                 ) -> Result<bool, QueryInterruptLineError> {
                     PORT_STATE.is_interrupt_line_pending::<Self>(line)
                 }
+
+                unsafe fn is_interrupt_line_enabled(
+                    line: InterruptNum,
+                ) -> Result<bool, QueryInterruptLineError> {
+                    PORT_STATE.is_interrupt_line_enabled::<Self>(line)
+                }
+
+                unsafe fn set_interrupt_line_trigger_mode(
+                    line: InterruptNum,
+                    mode: InterruptLineTriggerMode,
+                ) -> Result<(), SetInterruptLineTriggerModeError> {
+                    PORT_STATE.set_interrupt_line_trigger_mode::<Self>(line, mode)
+                }
             }
 
             impl PortTimer for $sys {
-                const MAX_TICK_COUNT: UTicks = State::MAX_TICK_COUNT;
-                const MAX_TIMEOUT: UTicks = State::MAX_TIMEOUT;
+                const MAX_TICK_COUNT: UTicks = $max_tick_count;
+                const MAX_TIMEOUT: UTicks = $max_timeout;
+                const RESOLUTION: UTicks = $tick_resolution_micros;
 
                 unsafe fn tick_count() -> UTicks {
                     PORT_STATE.tick_count::<Self>()
@@ -1,11 +1,13 @@
 //! Simulates a hardware scheduler.
 use r3::{
-    kernel::{cfg::InterruptHandlerFn, InterruptNum, InterruptPriority, Kernel},
+    kernel::{
+        cfg::InterruptHandlerFn, InterruptLineTriggerMode, InterruptNum, InterruptPriority, Kernel,
+    },
     utils::Init,
 };
 use std::collections::{BTreeSet, HashMap};
 
-use crate::{ums, ThreadRole, NUM_INTERRUPT_LINES, THREAD_ROLE};
+use crate::{ums, KernelEvent, ThreadRole, NUM_INTERRUPT_LINES, THREAD_ROLE};
 
 /// The state of the simulated hardware scheduler.
 pub struct SchedState {
@@ -16,12 +18,26 @@ pub struct SchedState {
     pended_lines: BTreeSet<(InterruptPriority, InterruptNum)>,
     active_int_handlers: Vec<(InterruptPriority, ums::ThreadId)>,
     pub cpu_lock: bool,
+    /// The interrupt priority mask, which simulates the BASEPRI register of
+    /// Arm-M. Interrupts with priority values greater than or equal to this
+    /// value are masked. `None` disables masking.
+    pub priority_mask: Option<InterruptPriority>,
 
     /// The currently-selected task thread.
     pub task_thread: Option<ums::ThreadId>,
 
     /// Garbage can
     zombies: Vec<ums::ThreadId>,
+
+    /// Indicates whether the deterministic mode is enabled.
+    deterministic: bool,
+
+    /// The logical clock, measured in microseconds. Only used in the
+    /// deterministic mode.
+    pub logical_time: u64,
+
+    /// The events recorded so far. Only used in the deterministic mode.
+    event_log: Vec<KernelEvent>,
 }
 
 /// The configuration of an interrupt line.
@@ -31,6 +47,12 @@ pub struct IntLine {
     pub start: Option<InterruptHandlerFn>,
     pub enable: bool,
     pub pended: bool,
+    pub trigger_mode: InterruptLineTriggerMode,
+    /// Indicates whether the simulated interrupt source of a level-triggered
+    /// interrupt line is asserted. The source is asserted by
+    /// `pend_interrupt_line` and deasserted when an interrupt handler for the
+    /// line completes.
+    pub asserted: bool,
 }
 
 impl Init for IntLine {
@@ -39,20 +61,26 @@ impl Init for IntLine {
         start: None,
         enable: false,
         pended: false,
+        trigger_mode: InterruptLineTriggerMode::Edge,
+        asserted: false,
     };
 }
 
 pub struct BadIntLineError;
 
 impl SchedState {
-    pub fn new<System: Kernel>() -> Self {
+    pub fn new<System: Kernel>(deterministic: bool) -> Self {
         let mut this = Self {
             int_lines: HashMap::new(),
             pended_lines: BTreeSet::new(),
             active_int_handlers: Vec::new(),
             cpu_lock: true,
+            priority_mask: None,
             task_thread: None,
             zombies: Vec::new(),
+            deterministic,
+            logical_time: 0,
+            event_log: Vec::new(),
         };
 
         for i in 0..NUM_INTERRUPT_LINES {
@@ -99,17 +127,65 @@ impl SchedState {
         }
     }
 
+    pub fn is_line_enabled(&self, i: InterruptNum) -> Result<bool, BadIntLineError> {
+        if i >= NUM_INTERRUPT_LINES {
+            return Err(BadIntLineError);
+        }
+
+        if let Some(line) = self.int_lines.get(&i) {
+            Ok(line.enable)
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub fn line_priority(&self, i: InterruptNum) -> Result<InterruptPriority, BadIntLineError> {
+        if i >= NUM_INTERRUPT_LINES {
+            return Err(BadIntLineError);
+        }
+
+        Ok(self
+            .int_lines
+            .get(&i)
+            .map_or(IntLine::INIT.priority, |line| line.priority))
+    }
+
     /// Schedule the specified thread until it naturally exits.
     pub fn recycle_thread(&mut self, thread_id: ums::ThreadId) {
         self.zombies.push(thread_id);
     }
+
+    /// Get a flag indicating whether no threads are runnable, i.e., the
+    /// simulated processor is idle and nothing will happen until an interrupt
+    /// is pended.
+    pub fn is_idle(&self) -> bool {
+        self.zombies.is_empty() && self.active_int_handlers.is_empty() && self.task_thread.is_none()
+    }
+
+    /// Record an event if the deterministic mode is enabled.
+    pub fn log_event(&mut self, event: KernelEvent) {
+        if self.deterministic {
+            self.event_log.push(event);
+        }
+    }
+
+    /// Get the events recorded so far.
+    pub fn event_log(&self) -> &[KernelEvent] {
+        &self.event_log
+    }
 }
 
 impl ums::Scheduler for SchedState {
     fn choose_next_thread(&mut self) -> Option<ums::ThreadId> {
         if let Some(&thread_id) = self.zombies.first() {
-            // Clean up zombie threads as soon as possible
-            Some(thread_id)
+            // Clean up zombie threads as soon as possible. In the
+            // deterministic mode, don't let the order of `zombies` (which
+            // is disturbed by `swap_remove`) decide which one runs first.
+            if self.deterministic {
+                self.zombies.iter().min().copied()
+            } else {
+                Some(thread_id)
+            }
         } else if let Some(&(_, thread_id)) = self.active_int_handlers.last() {
             Some(thread_id)
         } else if self.cpu_lock {
@@ -139,7 +215,7 @@ impl ums::Scheduler for SchedState {
 ///
 /// This should be called after changing some properties of `SchedState` in a
 /// way that might cause interrupt handlers to activate, such as disabling
-/// `cpu_lock`.
+/// `cpu_lock` or raising `priority_mask`.
 #[must_use]
 pub fn check_preemption_by_interrupt(
     thread_group: &'static ums::ThreadGroup<SchedState>,
@@ -168,6 +244,19 @@ pub fn check_preemption_by_interrupt(
             break;
         }
 
+        // Masking by the priority mask
+        if let Some(mask) = sched_state.priority_mask {
+            if pri >= mask {
+                log::trace!(
+                    "not handling an interrupt with priority {} because of \
+                        the priority mask {}",
+                    pri,
+                    mask,
+                );
+                break;
+            }
+        }
+
         // Masking by an already active interrupt
         if let Some(&(existing_pri, _)) = sched_state.active_int_handlers.last() {
             if existing_pri < pri {
@@ -183,6 +272,11 @@ pub fn check_preemption_by_interrupt(
 
         // Take the interrupt
         sched_state.pended_lines.remove(&(pri, num));
+        if let Some(line) = sched_state.int_lines.get_mut(&num) {
+            line.pended = false;
+        }
+        let time = sched_state.logical_time;
+        sched_state.log_event(KernelEvent::InterruptActivated { line: num, time });
 
         // Find the interrupt handler for `num`. Return
         // `default_interrupt_handler` if there's none.
@@ -200,6 +294,12 @@ pub fn check_preemption_by_interrupt(
 
             let mut lock = thread_group.lock();
 
+            // The interrupt handler has serviced the simulated interrupt
+            // source
+            if let Some(line) = lock.scheduler().int_lines.get_mut(&num) {
+                line.asserted = false;
+            }
+
             // Make this interrupt handler inactive
             let (_, popped_thread_id) = lock.scheduler().active_int_handlers.pop().unwrap();
             assert_eq!(thread_id, popped_thread_id);
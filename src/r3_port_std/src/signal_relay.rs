@@ -0,0 +1,138 @@
+//! Relays interrupt requests made by signal handlers to the simulated hardware
+//! scheduler.
+use r3::{
+    kernel::{InterruptNum, PendInterruptLineError},
+    utils::Init,
+};
+use std::{
+    sync::atomic::{AtomicI32, AtomicU64, Ordering},
+    thread::JoinHandle,
+};
+
+use crate::{
+    threading::{isize_ok_or_errno, ok_or_errno},
+    PortInstance, NUM_INTERRUPT_LINES,
+};
+
+const WORD_LEN: usize = 64;
+
+/// The state of the signal relay.
+pub(crate) struct SignalRelay {
+    /// The set of interrupt lines pended by [`Self::pend`] and not transferred
+    /// to the scheduler yet.
+    ///
+    /// This is a plain bit array of atomic words (as opposed to something
+    /// protected by a lock) so that it can be updated from a signal handler.
+    pended_lines: [AtomicU64; NUM_INTERRUPT_LINES / WORD_LEN],
+    /// The write end of the pipe used to wake up the relay thread, or `-1` if
+    /// the relay thread isn't running.
+    wake_fd: AtomicI32,
+}
+
+/// The handle to a running relay thread. Returned by [`SignalRelay::start`].
+pub(crate) struct RelayThread {
+    relay: &'static SignalRelay,
+    join_handle: JoinHandle<()>,
+}
+
+impl SignalRelay {
+    pub(crate) const fn new() -> Self {
+        Self {
+            pended_lines: Init::INIT,
+            wake_fd: AtomicI32::new(-1),
+        }
+    }
+
+    /// Start a relay thread, which transfers the interrupt requests made by
+    /// [`Self::pend`] to the scheduler of `System`.
+    pub(crate) fn start<System: PortInstance>(&'static self) -> RelayThread {
+        let mut fds = [0; 2];
+        ok_or_errno(unsafe { libc::pipe(fds.as_mut_ptr()) }).unwrap();
+        let [read_fd, write_fd] = fds;
+
+        // Writing to the pipe must not block the signal handler even if the
+        // pipe is full. It's okay to drop wake-up tokens in that case because
+        // there are unconsumed tokens in the pipe.
+        ok_or_errno(unsafe { libc::fcntl(write_fd, libc::F_SETFL, libc::O_NONBLOCK) }).unwrap();
+
+        self.wake_fd.store(write_fd, Ordering::Release);
+
+        log::trace!("starting the signal relay thread");
+        let join_handle = std::thread::spawn(move || {
+            let mut buf = [0u8; 64];
+            loop {
+                // Transfer the requests made so far
+                self.transfer::<System>();
+
+                // Initiate shutdown if requested by the Ctrl-C handler
+                if System::port_state().ctrl_c_received.load(Ordering::Acquire) {
+                    crate::shutdown::<System>();
+                }
+
+                // Wait for a wake-up token
+                match isize_ok_or_errno(unsafe {
+                    libc::read(read_fd, buf.as_mut_ptr() as _, buf.len())
+                }) {
+                    Ok(0) => break, // `stop` was called
+                    Ok(_) => {}
+                    Err(errno::Errno(libc::EINTR)) => {}
+                    Err(e) => panic!("failed to read the wake-up pipe: {}", e),
+                }
+            }
+
+            unsafe { libc::close(read_fd) };
+        });
+
+        RelayThread {
+            relay: self,
+            join_handle,
+        }
+    }
+
+    fn transfer<System: PortInstance>(&self) {
+        for (i, word) in self.pended_lines.iter().enumerate() {
+            let mut bits = word.swap(0, Ordering::Acquire);
+            while bits != 0 {
+                let num = i * WORD_LEN + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                crate::pend_interrupt_line::<System>(num as InterruptNum).unwrap();
+            }
+        }
+    }
+
+    /// Pend the specified interrupt line. This method is async-signal-safe.
+    pub(crate) fn pend(&self, num: InterruptNum) -> Result<(), PendInterruptLineError> {
+        if num >= NUM_INTERRUPT_LINES {
+            return Err(PendInterruptLineError::BadParam);
+        }
+
+        self.pended_lines[num / WORD_LEN].fetch_or(1 << (num % WORD_LEN), Ordering::Release);
+
+        self.wake();
+
+        Ok(())
+    }
+
+    /// Wake up the relay thread. If the relay thread isn't running yet, the
+    /// outstanding requests will be processed when it starts. This method is
+    /// async-signal-safe.
+    pub(crate) fn wake(&self) {
+        let fd = self.wake_fd.load(Ordering::Acquire);
+        if fd >= 0 {
+            // Errors are ignored. See the comment in `start`.
+            unsafe { libc::write(fd, &0u8 as *const u8 as _, 1) };
+        }
+    }
+}
+
+impl RelayThread {
+    /// Stop the relay thread.
+    pub(crate) fn stop(self) {
+        log::trace!("stopping the signal relay thread");
+        let fd = self.relay.wake_fd.swap(-1, Ordering::AcqRel);
+        // Closing the write end makes the relay thread's `read` return `0`
+        unsafe { libc::close(fd) };
+        self.join_handle.join().unwrap();
+        log::trace!("stopped the signal relay thread");
+    }
+}
@@ -7,7 +7,7 @@ use std::{
     os::raw::c_int,
     ptr::{null_mut, NonNull},
     sync::{
-        atomic::{AtomicPtr, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
         Arc, Once,
     },
     thread,
@@ -110,6 +110,8 @@ struct ThreadData {
     park_sock: [c_int; 2],
     park_count: AtomicUsize,
     pthread_id: Atomic<libc::pthread_t>,
+    /// Set by [`Thread::request_exit`].
+    exit_requested: AtomicBool,
 }
 
 impl ThreadData {
@@ -130,6 +132,7 @@ impl ThreadData {
             park_sock,
             park_count: AtomicUsize::new(0),
             pthread_id: Atomic::<libc::pthread_t>::new(0),
+            exit_requested: AtomicBool::new(false),
         }
     }
 
@@ -218,6 +221,12 @@ fn park_inner(data: &ThreadData) {
 
         break;
     }
+
+    if data.exit_requested.load(Ordering::Acquire) {
+        // Safety: The caller of `request_exit` is responsible for ensuring
+        //         this is safe
+        unsafe { exit_thread() };
+    }
 }
 
 impl Thread {
@@ -236,6 +245,20 @@ impl Thread {
         .unwrap();
     }
 
+    /// Make the thread exit as soon as it wakes up from `park` (including
+    /// remote park).
+    ///
+    /// # Safety
+    ///
+    /// The thread must have been started by [`spawn`]. It comes with all the
+    /// unsafety of [`exit_thread`]. The thread may be in the middle of
+    /// anything when it's parked, and anything it owns at that point will be
+    /// leaked.
+    pub unsafe fn request_exit(&self) {
+        self.data.exit_requested.store(true, Ordering::Release);
+        self.unpark();
+    }
+
     /// Force the thread to park.
     ///
     /// The effect is equivalent to calling `park` on the target thread.
@@ -301,7 +324,7 @@ fn register_remote_park_signal_handler() {
     }
 }
 
-fn isize_ok_or_errno(x: isize) -> Result<isize, errno::Errno> {
+pub(crate) fn isize_ok_or_errno(x: isize) -> Result<isize, errno::Errno> {
     if x >= 0 {
         Ok(x)
     } else {
@@ -309,7 +332,7 @@ fn isize_ok_or_errno(x: isize) -> Result<isize, errno::Errno> {
     }
 }
 
-fn ok_or_errno(x: c_int) -> Result<c_int, errno::Errno> {
+pub(crate) fn ok_or_errno(x: c_int) -> Result<c_int, errno::Errno> {
     if x >= 0 {
         Ok(x)
     } else {
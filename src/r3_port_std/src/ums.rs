@@ -42,7 +42,7 @@ pub struct ThreadGroupLockGuard<'a, Sched: ?Sized> {
 }
 
 /// Identifies a thread in [`ThreadGroup`].
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ThreadId(PoolPtr);
 
 /// Encapsulates the state of a client-supplied user-mode scheduler.
@@ -199,6 +199,43 @@ impl<'a, Sched: Scheduler> ThreadGroupLockGuard<'a, Sched> {
         guard.unpark_next_thread();
     }
 
+    /// Terminate the specified worker thread, which must not be the current
+    /// thread of the thread group.
+    ///
+    /// The thread is removed from the thread group immediately. Unlike when a
+    /// thread exits by itself, [`Scheduler::thread_exited`] is not called.
+    /// The client is responsible for making sure the scheduler will not choose
+    /// the terminated thread anymore.
+    ///
+    /// The underlying OS thread exits as soon as it wakes up from the parked
+    /// state, bypassing all destructors on its stack.
+    ///
+    /// # Safety
+    ///
+    /// It comes with all the unsafety of terminating a thread, such as that it
+    /// could unpin pinned local variables. The thread could be preempted while
+    /// holding a lock, in which case the lock will never be released.
+    pub unsafe fn terminate(&mut self, thread_id: ThreadId) {
+        let guard = &mut *self.guard;
+        assert_ne!(
+            guard.cur_thread_id,
+            Some(thread_id),
+            "cannot terminate the current thread"
+        );
+
+        log::trace!("terminating {:?}", thread_id);
+
+        let worker_thread = guard.threads.deallocate(thread_id.0).unwrap();
+        guard.num_threads -= 1;
+
+        // Safety: Inherited. The thread was started by `threading::spawn`.
+        unsafe { worker_thread.join_handle.unwrap().thread().request_exit() };
+
+        if guard.num_threads == 0 && guard.shutting_down {
+            guard.complete_shutdown();
+        }
+    }
+
     /// Initiate graceful shutdown for the thread group.
     ///
     /// The shutdown completes when all threads complete execution. After this
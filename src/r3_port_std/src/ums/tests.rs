@@ -374,3 +374,90 @@ fn exit_current_thread() {
 
     join_handle.join().unwrap();
 }
+
+/// Terminates a preempted thread.
+#[test]
+fn terminate() {
+    init_logger();
+
+    struct St {
+        counters: [AtomicUsize; 2],
+        done: AtomicBool,
+        cur_thread: AtomicUsize,
+        threads: OnceCell<[ThreadId; 2]>,
+    }
+    let st: &_ = Box::leak(Box::new(St {
+        counters: [AtomicUsize::new(0), AtomicUsize::new(0)],
+        done: AtomicBool::new(false),
+        cur_thread: AtomicUsize::new(0),
+        threads: OnceCell::new(),
+    }));
+
+    impl Scheduler for &'static St {
+        fn choose_next_thread(&mut self) -> Option<ThreadId> {
+            let threads = self.threads.get().unwrap();
+            threads
+                .get(self.cur_thread.load(Ordering::Relaxed))
+                .cloned()
+        }
+    }
+
+    let (tg, join_handle) = ThreadGroup::new(st);
+
+    {
+        let mut lock = tg.lock();
+
+        // This thread never exits by itself
+        let t0 = lock.spawn(move |_| loop {
+            st.counters[0].fetch_add(1, Ordering::Relaxed);
+        });
+
+        let t1 = lock.spawn(move |_| {
+            while !st.done.load(Ordering::Relaxed) {
+                st.counters[1].fetch_add(1, Ordering::Relaxed);
+            }
+
+            // Schedule no thread
+            st.cur_thread.store(usize::MAX, Ordering::Relaxed);
+        });
+
+        st.threads.set([t0, t1]).unwrap();
+    }
+
+    // Run thread 0 for a while
+    tg.lock().preempt();
+    sleep(Duration::from_millis(100));
+    assert_ne!(st.counters[0].load(Ordering::Relaxed), 0);
+
+    // Switch to thread 1 and terminate thread 0
+    st.cur_thread.store(1, Ordering::Relaxed);
+    {
+        let mut lock = tg.lock();
+        lock.preempt();
+        unsafe { lock.terminate(st.threads.get().unwrap()[0]) };
+    }
+
+    // Thread 0 shouldn't run anymore
+    let old_counters: Vec<_> = st
+        .counters
+        .iter()
+        .map(|c| c.load(Ordering::Relaxed))
+        .collect();
+
+    sleep(Duration::from_millis(100));
+
+    let new_counters: Vec<_> = st
+        .counters
+        .iter()
+        .map(|c| c.load(Ordering::Relaxed))
+        .collect();
+
+    assert_eq!(old_counters[0], new_counters[0]);
+    assert_ne!(old_counters[1], new_counters[1]);
+
+    // The shutdown should complete without waiting for thread 0
+    st.done.store(true, Ordering::Relaxed);
+    tg.lock().shutdown();
+
+    join_handle.join().unwrap();
+}
@@ -50,7 +50,7 @@ pub struct IterablePool<T> {
 ///
 /// `Pool` uses zero-based indices, but when stored in `PoolPtr`, they are
 /// one-based to meet the requirement of `NonZeroUsize`.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct PoolPtr(pub NonZeroUsize);
 
 #[derive(Debug, Clone)]
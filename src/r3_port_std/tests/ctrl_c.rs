@@ -0,0 +1,69 @@
+//! Raises SIGINT with `shutdown_on_ctrl_c` enabled and checks that it initiates
+//! graceful shutdown. This is a separate test binary because signal handlers
+//! are process-global.
+#![cfg(unix)]
+#![feature(const_fn)]
+#![feature(const_mut_refs)]
+#![feature(const_fn_fn_ptr_basics)]
+
+mod app {
+    use r3::{
+        kernel::{cfg::CfgBuilder, Task},
+        prelude::*,
+        time::Duration,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    r3_port_std::use_port!(unsafe struct System; shutdown_on_ctrl_c = true);
+
+    const COTTAGE: () = r3::build!(System, configure_app => ());
+
+    static IS_SIGNAL_RAISED: AtomicBool = AtomicBool::new(false);
+
+    const fn configure_app(b: &mut CfgBuilder<System>) {
+        Task::build()
+            .start(task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+    }
+
+    /// Get the current disposition of SIGINT.
+    fn sigint_handler() -> libc::sighandler_t {
+        // Safety: `sigaction` is a POD type
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        let result = unsafe { libc::sigaction(libc::SIGINT, std::ptr::null(), &mut action) };
+        assert_eq!(result, 0);
+        action.sa_sigaction
+    }
+
+    fn task_body(_: usize) {
+        // The default action of SIGINT would terminate the process
+        log::debug!("raising SIGINT");
+        assert_eq!(unsafe { libc::raise(libc::SIGINT) }, 0);
+        IS_SIGNAL_RAISED.store(true, Ordering::Relaxed);
+
+        // Give the signal relay thread time to initiate shutdown
+        System::sleep(Duration::from_millis(100)).unwrap();
+
+        // Exit without calling `shutdown`. `port_boot` returns only if the
+        // shutdown was initiated by the Ctrl-C handler.
+    }
+
+    pub fn run() {
+        let original_handler = sigint_handler();
+
+        port_std_impl::PORT_STATE.port_boot::<System>();
+
+        assert!(IS_SIGNAL_RAISED.load(Ordering::Relaxed));
+
+        // The original handler is restored
+        assert_eq!(sigint_handler(), original_handler);
+    }
+}
+
+#[test]
+fn shutdown_on_ctrl_c() {
+    let _ = env_logger::try_init();
+    app::run();
+}
@@ -0,0 +1,73 @@
+//! Creates a deadlock involving two tasks and two mutexes with deadlock
+//! detection enabled and checks that it's detected. This is a separate test
+//! binary because deadlock detection is a kernel-wide setting.
+#![feature(const_fn)]
+#![feature(const_mut_refs)]
+#![feature(const_fn_fn_ptr_basics)]
+
+mod app {
+    use r3::kernel::{cfg::CfgBuilder, Mutex, Task};
+
+    r3_port_std::use_port!(unsafe struct System);
+
+    struct Objects {
+        mutex1: Mutex<System>,
+        mutex2: Mutex<System>,
+        task2: Task<System>,
+    }
+
+    const COTTAGE: Objects = r3::build!(System, configure_app => Objects);
+
+    const fn configure_app(b: &mut CfgBuilder<System>) -> Objects {
+        b.deadlock_detection(true);
+
+        let mutex1 = Mutex::build().finish(b);
+        let mutex2 = Mutex::build().finish(b);
+
+        Task::build()
+            .start(task1_body)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build().start(task2_body).priority(1).finish(b);
+
+        Objects {
+            mutex1,
+            mutex2,
+            task2,
+        }
+    }
+
+    fn task1_body(_: usize) {
+        COTTAGE.mutex1.lock().unwrap();
+
+        // `task2` preempts `task1`, locks `mutex2`, and then blocks on `mutex1`
+        COTTAGE.task2.activate().unwrap();
+
+        // This completes a cycle, which should be detected
+        let _ = COTTAGE.mutex2.lock();
+        unreachable!();
+    }
+
+    fn task2_body(_: usize) {
+        COTTAGE.mutex2.lock().unwrap();
+
+        // Not a deadlock yet; `task1` is the owner of `mutex1` but isn't
+        // waiting for anything
+        let _ = COTTAGE.mutex1.lock();
+        unreachable!();
+    }
+
+    pub fn run() {
+        port_std_impl::PORT_STATE.port_boot::<System>();
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "deadlock detected (task -> mutex -> owner): task 1 -> mutex 2 -> task 2 -> mutex 1 -> task 1"
+)]
+fn deadlock_detection() {
+    let _ = env_logger::try_init();
+    app::run();
+}
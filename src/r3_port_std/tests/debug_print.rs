@@ -0,0 +1,150 @@
+//! Puts tasks in various wait states and checks that `Kernel::debug` describes
+//! what each task is waiting for and lists the outstanding timeouts.
+#![feature(const_fn)]
+#![feature(const_mut_refs)]
+#![feature(const_fn_fn_ptr_basics)]
+
+mod app {
+    use r3::{
+        kernel::{cfg::CfgBuilder, EventGroup, EventGroupWaitFlags, Mutex, Semaphore, Task, Timer},
+        prelude::*,
+        time::Duration,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    r3_port_std::use_port!(unsafe struct System);
+
+    struct Objects {
+        eg: EventGroup<System>,
+        mutex: Mutex<System>,
+        sem: Semaphore<System>,
+        mutex_task: Task<System>,
+    }
+
+    const COTTAGE: Objects = r3::build!(System, configure_app => Objects);
+
+    static IS_CHECKED: AtomicBool = AtomicBool::new(false);
+
+    const fn configure_app(b: &mut CfgBuilder<System>) -> Objects {
+        let eg = EventGroup::build().finish(b);
+        let mutex = Mutex::build().finish(b);
+        let sem = Semaphore::build().initial(0).maximum(1).finish(b);
+
+        // This timer's first tick comes after the timeouts of
+        // `sem_task_body` and `sleep_task_body`
+        Timer::build()
+            .start(|_| unreachable!())
+            .delay(Duration::from_secs(200))
+            .active(true)
+            .finish(b);
+
+        // The waiting tasks have higher priority than `main_task`, so they
+        // all have blocked by the time `main_task` starts running
+        Task::build()
+            .start(eg_task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(sem_task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(park_task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(sleep_task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        let mutex_task = Task::build().start(mutex_task_body).priority(1).finish(b);
+        Task::build()
+            .start(main_task_body)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        Objects {
+            eg,
+            mutex,
+            sem,
+            mutex_task,
+        }
+    }
+
+    fn eg_task_body(_: usize) {
+        COTTAGE.eg.wait(1, EventGroupWaitFlags::empty()).unwrap();
+    }
+
+    fn sem_task_body(_: usize) {
+        COTTAGE
+            .sem
+            .wait_one_timeout(Duration::from_secs(100))
+            .unwrap();
+    }
+
+    fn park_task_body(_: usize) {
+        System::park().unwrap();
+    }
+
+    fn sleep_task_body(_: usize) {
+        System::sleep(Duration::from_secs(100)).unwrap();
+    }
+
+    fn mutex_task_body(_: usize) {
+        COTTAGE.mutex.lock().unwrap();
+        COTTAGE.mutex.unlock().unwrap();
+    }
+
+    fn main_task_body(_: usize) {
+        // Make `mutex_task` wait for `mutex`
+        COTTAGE.mutex.lock().unwrap();
+        COTTAGE.mutex_task.activate().unwrap();
+
+        let dump = format!("{:?}", System::debug());
+        log::debug!("{}", dump);
+
+        assert!(dump.contains("Waiting { reason: EventGroupBits, timeout: None }"));
+        assert!(dump.contains("Waiting { reason: Semaphore, timeout: Some("));
+        assert!(dump.contains("Waiting { reason: Park, timeout: None }"));
+        assert!(dump.contains("Waiting { reason: Sleep, timeout: Some("));
+        assert!(dump.contains("Waiting { reason: Mutex, timeout: None }"));
+
+        // The running task isn't waiting for anything
+        assert!(dump.contains("wait_state: NotWaiting"));
+
+        // The outstanding timeouts are listed in the order of arrival
+        if cfg!(debug_assertions) {
+            let timeouts = &dump[dump.find("timeouts: [").unwrap()..];
+            let sem_pos = timeouts
+                .find("owner: Task { id: 2, reason: Semaphore }")
+                .unwrap();
+            let sleep_pos = timeouts
+                .find("owner: Task { id: 4, reason: Sleep }")
+                .unwrap();
+            let timer_pos = timeouts.find("owner: Timer { id: 1 }").unwrap();
+            assert!(sem_pos < timer_pos);
+            assert!(sleep_pos < timer_pos);
+        }
+
+        IS_CHECKED.store(true, Ordering::Relaxed);
+
+        COTTAGE.mutex.unlock().unwrap();
+        r3_port_std::shutdown::<System>();
+    }
+
+    pub fn run() {
+        port_std_impl::PORT_STATE.port_boot::<System>();
+
+        assert!(IS_CHECKED.load(Ordering::Relaxed));
+    }
+}
+
+#[test]
+fn debug_print_wait_state() {
+    let _ = env_logger::try_init();
+    app::run();
+}
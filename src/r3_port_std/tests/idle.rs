@@ -0,0 +1,76 @@
+//! Checks that the simulated processor doesn't consume CPU time while it's
+//! idle. This is a separate test binary so that the measurement of the
+//! process's CPU time isn't disturbed by other tests.
+#![cfg(unix)]
+#![feature(const_fn)]
+#![feature(const_mut_refs)]
+#![feature(const_fn_fn_ptr_basics)]
+
+mod app {
+    use r3::{
+        kernel::{cfg::CfgBuilder, Task},
+        prelude::*,
+        time::Duration,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    r3_port_std::use_port!(unsafe struct System);
+
+    const COTTAGE: () = r3::build!(System, configure_app => ());
+
+    static IS_SUCCESSFUL: AtomicBool = AtomicBool::new(false);
+
+    const fn configure_app(b: &mut CfgBuilder<System>) {
+        Task::build()
+            .start(task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+    }
+
+    /// Get the CPU time consumed by the current process.
+    fn process_cpu_time() -> std::time::Duration {
+        let mut ts = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // Safety: `ts` is a valid `timespec`
+        let result = unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut ts) };
+        assert_eq!(result, 0);
+        std::time::Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32)
+    }
+
+    fn task_body(_: usize) {
+        // There are no other tasks, so the processor is idle while this task
+        // is sleeping
+        let cpu_time0 = process_cpu_time();
+        System::sleep(Duration::from_millis(500)).unwrap();
+        let cpu_time1 = process_cpu_time();
+
+        let cpu_time = cpu_time1 - cpu_time0;
+        log::debug!("CPU time consumed during sleep(500ms): {:?}", cpu_time);
+        assert!(
+            cpu_time < std::time::Duration::from_millis(100),
+            "the idle system consumed {:?} of CPU time in 500ms",
+            cpu_time
+        );
+
+        IS_SUCCESSFUL.store(true, Ordering::Relaxed);
+        r3_port_std::shutdown::<System>();
+    }
+
+    pub fn run() {
+        port_std_impl::PORT_STATE.port_boot::<System>();
+
+        assert!(
+            IS_SUCCESSFUL.load(Ordering::Relaxed),
+            "The program deadlocked without calling `success`"
+        );
+    }
+}
+
+#[test]
+fn idle_cpu_usage() {
+    let _ = env_logger::try_init();
+    app::run();
+}
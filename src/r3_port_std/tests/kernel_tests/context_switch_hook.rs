@@ -0,0 +1,130 @@
+//! Registers a context switch hook and checks that it observes the dispatches
+//! between two tasks.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, TaskCb},
+    prelude::*,
+    time::Duration,
+    utils::Init,
+};
+use r3_test_suite::kernel_tests::Driver;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    task1: Task<System>,
+    task2: Task<System>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    num_calls: AtomicUsize,
+    /// The addresses of the last observed tasks' `TaskCb`s (`0` = `None`).
+    last_prev_task: AtomicUsize,
+    last_next_task: AtomicUsize,
+    /// The last observed timestamp, measured in microseconds.
+    last_time: AtomicUsize,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_calls: Init::INIT,
+        last_prev_task: Init::INIT,
+        last_next_task: Init::INIT,
+        last_time: Init::INIT,
+    };
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App {
+            task1,
+            task2,
+            state,
+        }
+    }
+}
+
+fn task_cb_addr<System: PortInstance>(task: Task<System>) -> usize {
+    System::get_task_cb(task.id().get() - 1).unwrap() as *const TaskCb<System> as usize
+}
+
+fn hook<System: PortInstance, D: Driver<App<System>>>(
+    prev_task: Option<&'static TaskCb<System>>,
+    next_task: Option<&'static TaskCb<System>>,
+    time: std::time::Duration,
+) {
+    let state = &*D::app().state;
+    let addr = |task: Option<&'static TaskCb<System>>| task.map_or(0, |cb| cb as *const _ as usize);
+
+    let time = time.as_micros() as usize;
+    assert!(time >= state.last_time.load(Ordering::Relaxed));
+    state.last_time.store(time, Ordering::Relaxed);
+
+    state
+        .last_prev_task
+        .store(addr(prev_task), Ordering::Relaxed);
+    state
+        .last_next_task
+        .store(addr(next_task), Ordering::Relaxed);
+    state.num_calls.fetch_add(1, Ordering::Relaxed);
+}
+
+fn task1_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task1,
+        task2,
+        state,
+    } = D::app();
+
+    System::port_state().set_context_switch_hook::<System>(Some(hook::<System, D>));
+
+    // `task2` preempts `task1`
+    task2.activate().unwrap();
+
+    // `task2` has exited, and `task1` was dispatched again
+    assert_eq!(
+        state.last_next_task.load(Ordering::Relaxed),
+        task_cb_addr(*task1)
+    );
+    let num_calls = state.num_calls.load(Ordering::Relaxed);
+    assert!(num_calls >= 2, "num_calls = {}", num_calls);
+
+    // The hook isn't called after it's unregistered
+    System::port_state().set_context_switch_hook::<System>(None);
+    System::sleep(Duration::from_millis(10)).unwrap();
+    assert_eq!(state.num_calls.load(Ordering::Relaxed), num_calls);
+
+    D::success();
+}
+
+fn task2_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task1,
+        task2,
+        state,
+    } = D::app();
+
+    assert_eq!(
+        state.last_prev_task.load(Ordering::Relaxed),
+        task_cb_addr(*task1)
+    );
+    assert_eq!(
+        state.last_next_task.load(Ordering::Relaxed),
+        task_cb_addr(*task2)
+    );
+}
@@ -0,0 +1,64 @@
+//! Checks the deterministic mode. The logical clock should advance straight to
+//! the next timeout, and the event log should record the timer interrupts.
+use core::marker::PhantomData;
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    time::Duration,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use r3_port_std::{KernelEvent, PortInstance, INTERRUPT_LINE_TIMER};
+
+pub struct App<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        App {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    // A long sleep completes without actually waiting
+    let start = std::time::Instant::now();
+    System::sleep(Duration::from_secs(100)).unwrap();
+    let elapsed = start.elapsed();
+    log::debug!("sleep(100s) took {:?} in the wall-clock time", elapsed);
+    assert!(elapsed < std::time::Duration::from_secs(30));
+
+    // Every advancement of the logical clock is followed by the timer
+    // interrupt
+    let event_log = System::port_state().event_log();
+    log::debug!("event_log = {:?}", event_log);
+
+    let mut num_clock_advances = 0;
+    let mut last_time = 0;
+    for (i, event) in event_log.iter().enumerate() {
+        if let KernelEvent::ClockAdvanced { time } = *event {
+            assert!(time >= last_time);
+            assert_eq!(
+                event_log.get(i + 1),
+                Some(&KernelEvent::InterruptActivated {
+                    line: INTERRUPT_LINE_TIMER,
+                    time
+                })
+            );
+            last_time = time;
+            num_clock_advances += 1;
+        }
+    }
+    assert_ne!(num_clock_advances, 0);
+    assert!(last_time >= 100_000_000);
+
+    D::success();
+}
@@ -0,0 +1,109 @@
+//! Pends an interrupt from an external thread in the deterministic mode. The
+//! interrupt should be held back until the simulated processor becomes idle.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, Task},
+    prelude::*,
+    time::Duration,
+};
+use r3_test_suite::kernel_tests::Driver;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use r3_port_std::{KernelEvent, PortInstance};
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    done: Hunk<System, AtomicBool>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .enabled(true)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let done = Hunk::<_, AtomicBool>::build().finish(b);
+
+        App { int, done }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let int = if let Some(int) = D::app().int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    // Pend the interrupt from a host thread and wait until the request is
+    // queued
+    log::debug!("pending {:?} from an external thread", int);
+    std::thread::spawn(move || {
+        r3_port_std::pend_interrupt_line::<System>(int.num()).unwrap();
+    })
+    .join()
+    .unwrap();
+
+    // The interrupt handler doesn't run while this task is running, no matter
+    // how long it takes
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(!D::app().done.load(Ordering::Relaxed));
+
+    // The interrupt handler runs as soon as the processor becomes idle
+    System::sleep(Duration::from_millis(1)).unwrap();
+    assert!(D::app().done.load(Ordering::Relaxed));
+
+    // The request is delivered before the logical clock advances
+    let event_log = System::port_state().event_log();
+    log::debug!("event_log = {:?}", event_log);
+
+    let i = event_log
+        .iter()
+        .position(|e| matches!(*e, KernelEvent::ExternalInterruptPended { .. }))
+        .expect("the external interrupt request was not recorded");
+    let time = if let KernelEvent::ExternalInterruptPended { line, time } = event_log[i] {
+        assert_eq!(line, int.num());
+        time
+    } else {
+        unreachable!()
+    };
+    assert_eq!(
+        event_log.get(i + 1),
+        Some(&KernelEvent::InterruptActivated {
+            line: int.num(),
+            time
+        })
+    );
+    assert!(event_log[i + 2..]
+        .iter()
+        .any(|e| matches!(*e, KernelEvent::ClockAdvanced { .. })));
+
+    D::success();
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().done.store(true, Ordering::Relaxed);
+}
@@ -0,0 +1,52 @@
+//! Checks that `PortThreading::idle` is called when all tasks are blocked on a
+//! semaphore.
+use r3::{
+    kernel::{cfg::CfgBuilder, Semaphore, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    sem: Semaphore<System>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        Timer::build()
+            .active(true)
+            .delay(Duration::from_millis(100))
+            .start(timer_body::<System, D>)
+            .finish(b);
+
+        let sem = Semaphore::build().initial(0).maximum(1).finish(b);
+
+        App { sem }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let num_idle_calls = || System::port_state().num_idle_calls();
+
+    // The only task blocks on the semaphore, so the processor becomes idle
+    // until the timer signals the semaphore
+    let count0 = num_idle_calls();
+    D::app().sem.wait_one().unwrap();
+    let count1 = num_idle_calls();
+    log::debug!("idle calls while blocked: {}", count1 - count0);
+    assert_ne!(count1, count0);
+
+    D::success();
+}
+
+fn timer_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    D::app().sem.signal_one().unwrap();
+}
@@ -0,0 +1,127 @@
+//! Sets the interrupt priority mask and checks that only the interrupts with
+//! priority values less than the mask are taken.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, Task},
+    prelude::*,
+    utils::Init,
+};
+use r3_test_suite::kernel_tests::Driver;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    ints: Option<[InterruptLine<System>; 2]>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of the calls to the interrupt handlers.
+    num_calls: [AtomicUsize; 2],
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_calls: Init::INIT,
+    };
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let ints = if let (&[int_line0, int_line1, ..], &[int_pri0, int_pri1, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line0)
+                .start(isr::<System, D>)
+                .param(0)
+                .finish(b);
+            InterruptHandler::build()
+                .line(int_line1)
+                .start(isr::<System, D>)
+                .param(1)
+                .finish(b);
+
+            Some([
+                InterruptLine::build()
+                    .line(int_line0)
+                    .priority(int_pri0)
+                    .enabled(true)
+                    .finish(b),
+                InterruptLine::build()
+                    .line(int_line1)
+                    .priority(int_pri1)
+                    .enabled(true)
+                    .finish(b),
+            ])
+        } else {
+            None
+        };
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App { ints, state }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let [int0, int1] = if let Some(ints) = D::app().ints {
+        ints
+    } else {
+        log::warn!("Not enough interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+    let state = D::app().state;
+    let num_calls = |i: usize| state.num_calls[i].load(Ordering::Relaxed);
+
+    let pri0 = int0.get_priority().unwrap();
+    let pri1 = int1.get_priority().unwrap();
+    assert!(pri0 < pri1);
+
+    let port_state = System::port_state();
+    assert_eq!(port_state.interrupt_priority_mask::<System>(), None);
+
+    // Mask `int1` but not `int0`
+    port_state.set_interrupt_priority_mask::<System>(Some(pri1));
+    assert_eq!(port_state.interrupt_priority_mask::<System>(), Some(pri1));
+
+    int1.pend().unwrap();
+    assert_eq!(num_calls(1), 0);
+    assert_eq!(int1.is_pending(), Ok(true));
+
+    int0.pend().unwrap();
+    assert_eq!(num_calls(0), 1);
+
+    // Lifting the mask lets the pending interrupt in
+    port_state.set_interrupt_priority_mask::<System>(None);
+    assert_eq!(num_calls(1), 1);
+    assert_eq!(int1.is_pending(), Ok(false));
+
+    // Mask both
+    port_state.set_interrupt_priority_mask::<System>(Some(pri0));
+    int0.pend().unwrap();
+    int1.pend().unwrap();
+    assert_eq!((num_calls(0), num_calls(1)), (1, 1));
+
+    // Raising the mask lets only `int0` in
+    port_state.set_interrupt_priority_mask::<System>(Some(pri1));
+    assert_eq!((num_calls(0), num_calls(1)), (2, 1));
+
+    port_state.set_interrupt_priority_mask::<System>(None);
+    assert_eq!((num_calls(0), num_calls(1)), (2, 2));
+
+    D::success();
+}
+
+fn isr<System: PortInstance, D: Driver<App<System>>>(i: usize) {
+    D::app().state.num_calls[i].fetch_add(1, Ordering::Relaxed);
+}
@@ -0,0 +1,120 @@
+//! Checks the exact behavior of the simulated trigger modes. Pending a
+//! level-triggered interrupt line asserts its interrupt source, which stays
+//! asserted until the interrupt handler completes, so the interrupt line
+//! re-pends if it's cleared before that. Taking an interrupt unpends the
+//! interrupt line regardless of its trigger mode.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, InterruptLineTriggerMode, Task},
+    prelude::*,
+    utils::Init,
+};
+use r3_test_suite::kernel_tests::Driver;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of the calls to the interrupt handler.
+    num_calls: AtomicUsize,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_calls: Init::INIT,
+    };
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App { int, state }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let int = if let Some(int) = D::app().int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+    let state = D::app().state;
+    let num_calls = || state.num_calls.load(Ordering::Relaxed);
+
+    // Edge-triggered
+    int.set_trigger_mode(InterruptLineTriggerMode::Edge)
+        .unwrap();
+    int.disable().unwrap();
+    int.pend().unwrap();
+    assert_eq!(int.is_pending(), Ok(true));
+    int.clear().unwrap();
+    assert_eq!(int.is_pending(), Ok(false));
+
+    int.pend().unwrap();
+    assert_eq!(num_calls(), 0);
+    int.enable().unwrap();
+    assert_eq!(num_calls(), 1);
+    assert_eq!(int.is_pending(), Ok(false));
+
+    // Level-triggered - the interrupt source stays asserted after clearing
+    int.set_trigger_mode(InterruptLineTriggerMode::Level)
+        .unwrap();
+    int.disable().unwrap();
+    int.pend().unwrap();
+    int.clear().unwrap();
+    assert_eq!(int.is_pending(), Ok(true));
+
+    // The interrupt handler deasserts the interrupt source
+    int.enable().unwrap();
+    assert_eq!(num_calls(), 2);
+    assert_eq!(int.is_pending(), Ok(false));
+
+    // The interrupt line doesn't re-pend once the interrupt source is
+    // deasserted
+    int.disable().unwrap();
+    int.clear().unwrap();
+    assert_eq!(int.is_pending(), Ok(false));
+
+    D::success();
+}
+
+fn isr<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let App { int, state } = D::app();
+    state.num_calls.fetch_add(1, Ordering::Relaxed);
+
+    // The interrupt line was unpended when the interrupt was taken
+    assert_eq!(int.unwrap().is_pending(), Ok(false));
+}
@@ -0,0 +1,119 @@
+//! Pends an interrupt from a signal handler.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, Task},
+    prelude::*,
+};
+use r3_test_suite::kernel_tests::Driver;
+use std::{
+    os::raw::c_int,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use r3_port_std::PortInstance;
+
+/// The signal used by this test. (`SIGUSR1` is used by the port.)
+const SIGNAL: c_int = libc::SIGUSR2;
+
+/// The interrupt line pended by the signal handler.
+static INT_LINE: AtomicUsize = AtomicUsize::new(0);
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    done: Hunk<System, AtomicBool>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body1::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .enabled(true)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let done = Hunk::<_, AtomicBool>::build().finish(b);
+
+        App { int, done }
+    }
+}
+
+fn task_body1<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let int = if let Some(int) = D::app().int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    INT_LINE.store(int.num(), Ordering::Relaxed);
+
+    // Register the signal handler
+    unsafe {
+        let mut sa: libc::sigaction = std::mem::zeroed();
+        sa.sa_sigaction = signal_handler::<System> as libc::sighandler_t;
+        libc::sigemptyset(&mut sa.sa_mask);
+        assert_eq!(libc::sigaction(SIGNAL, &sa, std::ptr::null_mut()), 0);
+    }
+
+    // Take the minimum of several measurements so that the test isn't
+    // affected by occasional hiccups of the host system
+    let mut min_latency = Duration::from_secs(u64::MAX);
+
+    for _ in 0..10 {
+        D::app().done.store(false, Ordering::Relaxed);
+
+        // Raise the signal. The signal handler runs in the current thread.
+        log::debug!("raising the signal");
+        let start = Instant::now();
+        assert_eq!(unsafe { libc::raise(SIGNAL) }, 0);
+
+        log::debug!("waiting for `done` to be set...");
+        while !D::app().done.load(Ordering::Relaxed) {
+            assert!(start.elapsed() < Duration::from_secs(5), "timeout");
+        }
+
+        // This includes the time taken to return from the interrupt handler
+        let latency = start.elapsed();
+        log::debug!("latency = {:?}", latency);
+        min_latency = min_latency.min(latency);
+    }
+
+    log::debug!("min_latency = {:?}", min_latency);
+    assert!(
+        min_latency < Duration::from_micros(1000),
+        "the interrupt took {:?} to be taken",
+        min_latency
+    );
+
+    D::success();
+}
+
+extern "C" fn signal_handler<System: PortInstance>(_: c_int) {
+    r3_port_std::pend_interrupt_line_from_signal::<System>(INT_LINE.load(Ordering::Relaxed))
+        .unwrap();
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().done.store(true, Ordering::Relaxed);
+}
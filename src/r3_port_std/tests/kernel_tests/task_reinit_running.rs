@@ -0,0 +1,75 @@
+//! Reinitializes the state of a task whose backing thread is still alive
+//! (i.e., the task was preempted in the middle of its execution) and checks
+//! that the task restarts from its entry point. The terminated thread must not
+//! prevent the system from shutting down.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, PortThreading, Task},
+    prelude::*,
+};
+use r3_test_suite::kernel_tests::Driver;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    task1: Task<System>,
+    task2: Task<System>,
+    num_starts: Hunk<System, AtomicUsize>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let num_starts = Hunk::<_, AtomicUsize>::build().finish(b);
+
+        App {
+            task1,
+            task2,
+            num_starts,
+        }
+    }
+}
+
+fn task1_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task2, num_starts, ..
+    } = D::app();
+
+    match num_starts.fetch_add(1, Ordering::Relaxed) {
+        0 => {
+            // `task2` preempts `task1` and reinitializes `task1`'s state
+            task2.activate().unwrap();
+
+            unreachable!("the backing thread should have been terminated");
+        }
+        1 => {
+            // `task1` restarted from the entry point on a new thread
+            D::success();
+        }
+        i => unreachable!("{}", i),
+    }
+}
+
+fn task2_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let App { task1, .. } = D::app();
+
+    let task1_cb = System::get_task_cb(task1.id().get() - 1).unwrap();
+
+    // `task1` is in the middle of `Task::activate`, so its backing thread is
+    // alive. Discard its context as if the kernel restarted the task.
+    System::acquire_cpu_lock().unwrap();
+    // Safety: CPU Lock active. `task1` is not the running task.
+    unsafe { System::initialize_task_state(task1_cb) };
+    // Safety: We acquired CPU Lock
+    unsafe { System::release_cpu_lock().unwrap() };
+}
@@ -0,0 +1,67 @@
+//! Checks that timeouts are measured correctly regardless of the tick
+//! resolution specified by `use_port!`.
+use core::marker::PhantomData;
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    time::Duration,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        App {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    log::debug!(
+        "TICK_RESOLUTION_MICROS = {}",
+        System::TICK_RESOLUTION_MICROS
+    );
+
+    // `time_resolution` reflects the tick resolution
+    assert_eq!(
+        System::time_resolution(),
+        Duration::from_micros(System::TICK_RESOLUTION_MICROS as i32)
+    );
+
+    // Take the minimum of several measurements so that the test isn't
+    // affected by occasional hiccups of the host system
+    let mut min_elapsed = std::time::Duration::from_secs(u64::MAX);
+
+    for _ in 0..5 {
+        let start = std::time::Instant::now();
+        System::sleep(Duration::from_millis(10)).unwrap();
+        let elapsed = start.elapsed();
+        log::debug!("sleep(10ms) took {:?}", elapsed);
+
+        // The sleep must not end early
+        assert!(elapsed >= std::time::Duration::from_millis(10));
+
+        min_elapsed = min_elapsed.min(elapsed);
+    }
+
+    // The sleep should not take much longer than the resolution permits
+    assert!(
+        min_elapsed < std::time::Duration::from_millis(20),
+        "sleep(10ms) took {:?}",
+        min_elapsed
+    );
+
+    D::success();
+}
@@ -0,0 +1,50 @@
+//! Checks that the timer interrupt handler is not called while there are no
+//! pending timeouts.
+use core::marker::PhantomData;
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    time::Duration,
+};
+use r3_test_suite::kernel_tests::Driver;
+
+use r3_port_std::PortInstance;
+
+pub struct App<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: PortInstance> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        App {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+fn task_body<System: PortInstance, D: Driver<App<System>>>(_: usize) {
+    let num_timer_ticks = || System::port_state().num_timer_ticks();
+
+    // There are no pending timeouts. The timer interrupt shouldn't fire while
+    // we are blocking the worker thread.
+    let count0 = num_timer_ticks();
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let count1 = num_timer_ticks();
+    log::debug!("timer ticks during idle: {}", count1 - count0);
+    assert_eq!(count1, count0);
+
+    // Register a timeout. The timer interrupt should fire at least once to
+    // wake us up.
+    System::sleep(Duration::from_millis(100)).unwrap();
+    let count2 = num_timer_ticks();
+    log::debug!("timer ticks during sleep: {}", count2 - count1);
+    assert_ne!(count2, count1);
+
+    D::success();
+}
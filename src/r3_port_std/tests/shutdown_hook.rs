@@ -0,0 +1,73 @@
+//! Checks that shutdown hooks are called in the correct order before
+//! `port_boot` returns.
+#![feature(const_fn)]
+#![feature(const_mut_refs)]
+#![feature(const_fn_fn_ptr_basics)]
+
+mod app {
+    use r3::kernel::{cfg::CfgBuilder, ShutdownHook, Task};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    r3_port_std::use_port!(unsafe struct System);
+
+    const COTTAGE: () = r3::build!(System, configure_app => ());
+
+    static IS_TASK_COMPLETE: AtomicBool = AtomicBool::new(false);
+    static IS_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+    /// The sequence of `param`s of the called hooks, encoded as decimal digits
+    static CALL_ORDER: AtomicUsize = AtomicUsize::new(0);
+
+    const fn configure_app(b: &mut CfgBuilder<System>) {
+        Task::build()
+            .start(task_body)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        ShutdownHook::build().start(hook).param(1).finish(b);
+        ShutdownHook::build()
+            .start(hook)
+            .param(2)
+            .priority(10)
+            .finish(b);
+        ShutdownHook::build().start(hook).param(3).finish(b);
+        ShutdownHook::build()
+            .start(set_flag)
+            .priority(-10)
+            .finish(b);
+    }
+
+    fn task_body(_: usize) {
+        IS_TASK_COMPLETE.store(true, Ordering::Relaxed);
+        r3_port_std::shutdown::<System>();
+    }
+
+    fn hook(param: usize) {
+        // The system has already stopped
+        assert!(IS_TASK_COMPLETE.load(Ordering::Relaxed));
+        assert!(!IS_HOOK_CALLED.load(Ordering::Relaxed));
+
+        let order = CALL_ORDER.load(Ordering::Relaxed);
+        CALL_ORDER.store(order * 10 + param, Ordering::Relaxed);
+    }
+
+    fn set_flag(_: usize) {
+        IS_HOOK_CALLED.store(true, Ordering::Relaxed);
+    }
+
+    pub fn run() {
+        port_std_impl::PORT_STATE.port_boot::<System>();
+
+        assert!(IS_HOOK_CALLED.load(Ordering::Relaxed));
+
+        // Descending order of priority, reverse registration order
+        assert_eq!(CALL_ORDER.load(Ordering::Relaxed), 231);
+    }
+}
+
+#[test]
+fn shutdown_hook() {
+    let _ = env_logger::try_init();
+    app::run();
+}
@@ -44,25 +44,43 @@ impl KernelTestUtil {
 }
 
 mod kernel_tests {
+    pub mod context_switch_hook;
+    pub mod deterministic;
+    pub mod deterministic_external_interrupt;
     pub mod external_interrupt;
+    pub mod idle;
+    pub mod interrupt_priority_mask;
     pub mod interrupt_table_sparsity;
+    pub mod interrupt_trigger_mode_strict;
+    #[cfg(unix)]
+    pub mod signal_interrupt;
     pub mod stack_align;
+    pub mod task_reinit_running;
+    pub mod tick_resolution;
+    pub mod timer_tick_suppression;
 }
 
 macro_rules! instantiate_kernel_tests {
-    ( $( { $($tt:tt)* }, )* ) => {
+    ( [$($port_opts:tt)*] $( { $($tt:tt)* }, )* ) => {
         instantiate_kernel_tests!(
-            @inner
+            @inner [$($port_opts)*]
 
             $( { $($tt)* }, )*
 
             // Port-specific tests
+            { path: crate::kernel_tests::context_switch_hook, name_ident: context_switch_hook, },
             { path: crate::kernel_tests::external_interrupt, name_ident: external_interrupt, },
+            { path: crate::kernel_tests::idle, name_ident: idle, },
+            { path: crate::kernel_tests::interrupt_priority_mask, name_ident: interrupt_priority_mask, },
             { path: crate::kernel_tests::interrupt_table_sparsity, name_ident: interrupt_table_sparsity, },
+            { path: crate::kernel_tests::interrupt_trigger_mode_strict, name_ident: interrupt_trigger_mode_strict, },
             { path: crate::kernel_tests::stack_align, name_ident: stack_align, },
+            { path: crate::kernel_tests::task_reinit_running, name_ident: task_reinit_running, },
+            { path: crate::kernel_tests::tick_resolution, name_ident: tick_resolution, },
+            { path: crate::kernel_tests::timer_tick_suppression, name_ident: timer_tick_suppression, },
         );
     };
-    ( @inner $(
+    ( @inner [$($port_opts:tt)*] $(
         { path: $path:path, name_ident: $name_ident:ident, $($rest:tt)* },
     )*) => {$(
         mod $name_ident {
@@ -70,10 +88,10 @@ macro_rules! instantiate_kernel_tests {
             use r3_test_suite::kernel_tests;
             use $path as test_case;
 
-            r3_port_std::use_port!(unsafe struct System);
+            r3_port_std::use_port!(unsafe struct System $($port_opts)*);
 
             struct Driver;
-            static TEST_UTIL: super::KernelTestUtil = super::KernelTestUtil::new();
+            static TEST_UTIL: crate::KernelTestUtil = crate::KernelTestUtil::new();
 
             impl kernel_tests::Driver<test_case::App<System>> for Driver {
                 fn app() -> &'static test_case::App<System> {
@@ -90,6 +108,12 @@ macro_rules! instantiate_kernel_tests {
 
                 const INTERRUPT_LINES: &'static [InterruptNum] = &[0, 1, 2, 3];
                 const INTERRUPT_PRIORITIES: &'static [InterruptPriority] = &[0, 4];
+
+                // Context switching involves the host OS's scheduler, which
+                // might be busy running other tests
+                const MAX_CONTEXT_SWITCH_LATENCY_US: u32 = 10_000;
+                // Interrupt handlers are executed by host threads, too
+                const MAX_INTERRUPT_LATENCY_US: u32 = 10_000;
             }
 
             static COTTAGE: test_case::App<System> =
@@ -105,7 +129,50 @@ macro_rules! instantiate_kernel_tests {
     )*};
 }
 
-r3_test_suite::get_kernel_tests!(instantiate_kernel_tests!());
+r3_test_suite::get_kernel_tests!(instantiate_kernel_tests!([]));
+
+/// Runs the same tests with a narrower tick count range. The tick count starts
+/// at `0xc0ffee` and wraps around shortly after boot. The range is not a power
+/// of two, so this also exercises the kernel's handling of
+/// `MAX_TICK_COUNT < UTicks::MAX`.
+mod small_tick_range {
+    r3_test_suite::get_kernel_tests!(instantiate_kernel_tests!(
+        [; max_tick_count = 0xc0ffff, max_timeout = 0x7fffff]
+    ));
+}
+
+/// Runs `tick_resolution` with a 1-millisecond tick resolution.
+mod coarse_tick_resolution {
+    instantiate_kernel_tests!(
+        @inner [; tick_resolution_micros = 1000]
+        { path: crate::kernel_tests::tick_resolution, name_ident: tick_resolution, },
+    );
+}
+
+/// Runs `deterministic` and `deterministic_external_interrupt` in the
+/// deterministic mode. The other tests aren't run in this mode because some of
+/// them busy-wait for the time to pass or an external interrupt.
+mod deterministic {
+    instantiate_kernel_tests!(
+        @inner [; deterministic = true]
+        { path: crate::kernel_tests::deterministic, name_ident: deterministic, },
+        {
+            path: crate::kernel_tests::deterministic_external_interrupt,
+            name_ident: deterministic_external_interrupt,
+        },
+    );
+}
+
+/// Runs `signal_interrupt`. This test is not included in the list of
+/// port-specific tests because its signal handler is process-global and can't
+/// be shared by multiple test instances running concurrently.
+#[cfg(unix)]
+mod signal_interrupt {
+    instantiate_kernel_tests!(
+        @inner []
+        { path: crate::kernel_tests::signal_interrupt, name_ident: signal_interrupt, },
+    );
+}
 
 // TODO: This would be a good place to add semi-whitebox tests for e.g.,
 //       2nd-level interrupt handler generation
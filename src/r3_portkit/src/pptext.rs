@@ -194,69 +194,6 @@ pub macro pp_text_macro {
     },
 }
 
-/// Preprocessed `llvm_asm!`.
-pub macro pp_llvm_asm {
-    // -------------------------------------------------------------------
-    // Munch the input until `:` or EOF is found
-    (
-        @internal,
-        unprocessed: [{ : $($unprocessed:tt)* }],
-        code: [{ $($code:tt)* }],
-    ) => {{
-        $crate::pptext::pp_llvm_asm!(
-            @done,
-            unprocessed: [{ : $($unprocessed)* }],
-            code: [{ $($code)* }],
-        );
-    }},
-    (
-        @internal,
-        unprocessed: [{}],
-        code: [{ $($code:tt)* }],
-    ) => {{
-        $crate::pptext::pp_llvm_asm!(
-            @done,
-            unprocessed: [{}],
-            code: [{ $($code)* }],
-        );
-    }},
-
-    (
-        @internal,
-        unprocessed: [{ $fragment:tt $($unprocessed:tt)* }],
-        code: [{ $($code:tt)* }],
-    ) => {{
-        $crate::pptext::pp_llvm_asm!(
-            @internal,
-            unprocessed: [{ $($unprocessed)* }],
-            code: [{ $($code)* $fragment }],
-        );
-    }},
-    // -------------------------------------------------------------------
-    (
-        @done,
-        unprocessed: [{ $($unprocessed:tt)* }],
-        code: [{ $($code:tt)* }],
-    ) => {{
-        $crate::pptext::pp_text_macro! {
-            macro pp_llvm_asm_code { $($code)* }
-        }
-        llvm_asm!(pp_llvm_asm_code!() $($unprocessed)*);
-    }},
-    // -------------------------------------------------------------------
-    // The entry point
-    (
-        // TODO: remove `$l:lit`
-        $l:literal $($rest:tt)*
-    ) => {
-        $crate::pptext::pp_llvm_asm!(
-            @internal,
-            unprocessed: [{ $l $($rest)* }],
-            code: [{}],
-        );
-    },
-}
-
 /// Preprocessed `asm!`.
 ///
 /// # Examples
@@ -159,6 +159,16 @@ impl TickfulCfg {
     pub const fn division(&self) -> u64 {
         self.division
     }
+
+    /// Get the maximum amount by which the tick count advances in a single
+    /// tick, i.e., the tick period rounded up to a whole microsecond.
+    pub const fn max_tick_period_micros(&self) -> u32 {
+        if self.tick_period_submicros == 0 {
+            self.tick_period_micros
+        } else {
+            self.tick_period_micros + 1
+        }
+    }
 }
 
 /// Instantiates the optimal version of [`TickfulStateCore`] using a
@@ -310,6 +320,26 @@ mod tests {
         );
     }
 
+    /// `max_tick_period_micros` rounds up the tick period.
+    #[test]
+    fn tickful_max_tick_period_micros() {
+        let cfg = |hw_freq_num, hw_tick_period| {
+            TickfulCfg::new(TickfulOptions {
+                hw_freq_num,
+                hw_freq_denom: 1,
+                hw_tick_period,
+            })
+            .unwrap()
+        };
+
+        // 125MHz clock, 125-cycle period = 1μs
+        assert_eq!(cfg(125_000_000, 125).max_tick_period_micros(), 1);
+        // 10MHz clock, 1-cycle period = (1/10)μs
+        assert_eq!(cfg(10_000_000, 1).max_tick_period_micros(), 1);
+        // 375MHz clock, 1250-cycle period = (10/3)μs
+        assert_eq!(cfg(375_000_000, 1250).max_tick_period_micros(), 4);
+    }
+
     /// The clock frequency given to `TickfulCfg` must not be zero.
     #[test]
     fn tickful_zero_freq() {
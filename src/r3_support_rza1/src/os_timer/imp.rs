@@ -1,5 +1,8 @@
 //! The implementation of the RZ/A1 OS Timer driver.
-use r3::kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, Kernel, PortToKernel, UTicks};
+use r3::kernel::{
+    cfg::CfgBuilder, InterruptHandler, InterruptLine, InterruptLineTriggerMode, Kernel,
+    PortToKernel, UTicks,
+};
 use r3_port_arm::Gic;
 use r3_portkit::tickless::{TicklessCfg, TicklessStateTrait};
 use rza1::ostm0 as ostm;
@@ -81,9 +84,9 @@ pub fn init<System: OsTimerInstance>() {
     debug_assert_eq!(tcfg.hw_max_tick_count(), u32::MAX);
 
     // Configure the interrupt line as edge-triggered
-    System::set_interrupt_line_trigger_mode(
+    <System as Gic>::set_interrupt_line_trigger_mode(
         System::INTERRUPT_OSTM,
-        r3_port_arm::InterruptLineTriggerMode::RisingEdge,
+        InterruptLineTriggerMode::Edge,
     )
     .unwrap();
 }
@@ -18,6 +18,8 @@ pub(crate) struct TestDriver {
     target_arch_opt: targets::BuildOpt,
     link_dir: tempdir::TempDir,
     meta: Meta,
+    /// Serializes builds, which share the same output directory.
+    build_lock: tokio::sync::Mutex<()>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -42,9 +44,11 @@ pub(crate) enum TestDriverNewError {
 
 /// The additional parameters used while building the executable image of a test
 /// driver.
-pub(crate) struct BuildOpt {
+pub(crate) struct BuildOpt<'a> {
     pub verbose: bool,
     pub log_level: LogLevel,
+    /// Additional Cargo features to enable when building the test driver
+    pub driver_features: &'a [String],
 }
 
 #[derive(Clone, Copy, arg_enum_proc_macro::ArgEnum)]
@@ -63,6 +67,8 @@ pub(crate) enum TestDriverRunError {
     ExeNotFound(PathBuf),
     #[error("The build command failed.")]
     BuildTest(#[source] subprocess::SubprocessError),
+    #[error("Could not copy the compiled executable.")]
+    CopyExe(#[source] std::io::Error),
     #[error("Could not run the test '{0}'.")]
     Run(String, #[source] anyhow::Error),
 }
@@ -222,6 +228,7 @@ impl TestDriver {
             link_dir,
             target,
             meta,
+            build_lock: tokio::sync::Mutex::new(()),
         })
     }
 
@@ -232,21 +239,57 @@ impl TestDriver {
 
     /// Compile an executable of the test driver and run it using the specified
     /// debug probe interface.
+    ///
+    /// If `timeout` is specified, the test run fails with
+    /// [`TestRunError::Timeout`] if programming the target and acquiring the
+    /// test result don't complete within the specified duration. Any child
+    /// processes spawned by the debug probe are killed when this happens.
+    ///
+    /// This method can be called concurrently. The builds are serialized, but
+    /// the execution of the built images may overlap.
     pub(crate) async fn run(
         &self,
         test_run: &selection::TestRun<'_>,
-        build_opt: BuildOpt,
+        build_opt: BuildOpt<'_>,
+        timeout: Option<Duration>,
         debug_probe: &mut (impl targets::DebugProbe + ?Sized),
     ) -> Result<Result<(), TestRunError>, TestDriverRunError> {
-        self.compile(test_run, build_opt).await?;
+        let (_exe_dir, exe_path) = {
+            let _build_guard = self.build_lock.lock().await;
+            self.compile(test_run, build_opt).await?;
+
+            // Copy the executable so that it isn't overwritten by a subsequent
+            // build while it's being used
+            let exe_dir =
+                tempdir::TempDir::new("r3_test_runner").map_err(TestDriverRunError::CopyExe)?;
+            let exe_path = exe_dir.path().join(self.exe_path.file_name().unwrap());
+            log::debug!("Copying the executable to '{}'", exe_path.display());
+            tokio::fs::copy(&self.exe_path, &exe_path)
+                .await
+                .map_err(TestDriverRunError::CopyExe)?;
+
+            (exe_dir, exe_path)
+        };
 
         log::debug!("Running the test");
-        let acquisition_result = debug_probe_program_and_get_output_until(
+        let acquisition_fut = debug_probe_program_and_get_output_until(
             debug_probe,
-            &self.exe_path,
+            &exe_path,
             [b"!- TEST WAS SUCCESSFUL -!", &b"panicked at"[..]].iter(),
-        )
-        .await;
+        );
+        // Dropping `acquisition_fut` drops the output stream and any
+        // `tokio::process::Child`s held by it, which are spawned with
+        // `kill_on_drop(true)`
+        let acquisition_result = if let Some(timeout) = timeout {
+            tokio::time::timeout(timeout, acquisition_fut)
+                .await
+                .unwrap_or_else(|_| {
+                    log::debug!("The test run did not complete in {:?}", timeout);
+                    Err(RunError::Timeout)
+                })
+        } else {
+            acquisition_fut.await
+        };
 
         // Interpret the result
         let test_result = match acquisition_result {
@@ -277,7 +320,11 @@ impl TestDriver {
     async fn compile(
         &self,
         test_run: &selection::TestRun<'_>,
-        BuildOpt { verbose, log_level }: BuildOpt,
+        BuildOpt {
+            verbose,
+            log_level,
+            driver_features,
+        }: BuildOpt<'_>,
     ) -> Result<(), TestDriverRunError> {
         let Self {
             exe_path,
@@ -317,6 +364,7 @@ impl TestDriver {
                         .iter()
                         .map(|f| format!("--features={}", f)),
                 )
+                .args(driver_features.iter().map(|f| format!("--features={}", f)))
                 .args(if test_run.cpu_lock_by_basepri {
                     Some("--features=cpu-lock-by-basepri")
                 } else {
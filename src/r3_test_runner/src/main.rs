@@ -1,5 +1,5 @@
 #![feature(decl_macro)] // `macro`
-use std::{env, path::Path};
+use std::{cell::RefCell, env, future::Future, path::Path, pin::Pin};
 use structopt::StructOpt;
 use thiserror::Error;
 
@@ -32,6 +32,10 @@ enum MainError {
     TestFail,
     #[error("The target architecture '{0}' is invalid or unsupported.")]
     BadTarget(targets::Arch),
+    #[error("The target requires a chip name to be specified by `--chip`.")]
+    ChipRequired,
+    #[error("Could not configure the target for the chip '{0}'.")]
+    BadChip(String, #[source] anyhow::Error),
 }
 
 /// Test runner for the Arm-M port of R3
@@ -41,6 +45,12 @@ struct Opt {
     #[structopt(short = "t", long = "target", parse(try_from_str = try_parse_target),
         possible_values(&TARGET_POSSIBLE_VALUES))]
     target: &'static dyn targets::Target,
+    /// Target chip name (e.g., `fe310-g002`), only used by targets that aren't
+    /// tied to a particular chip, such as `probe_rs_riscv`
+    ///
+    /// For `probe_rs_riscv`, this is the chip name recognized by `probe-rs`.
+    #[structopt(long = "chip")]
+    chip: Option<String>,
     /// Override target architecture
     ///
     /// See the documentation of `Arch::from_str` for full syntax.
@@ -65,6 +75,20 @@ struct Opt {
     /// Display build progress and warnings
     #[structopt(short = "v")]
     verbose: bool,
+    /// Enable the specified Cargo feature of the test driver (e.g.,
+    /// `systick-tickless`). Can be specified multiple times.
+    #[structopt(short = "f", long = "driver-feature", number_of_values = 1)]
+    driver_features: Vec<String>,
+    /// Fail a test run if programming the target and running the test don't
+    /// complete in N seconds (0 means infinity)
+    #[structopt(long = "timeout", default_value = "300")]
+    timeout: u64,
+    /// Run up to N test runs concurrently
+    ///
+    /// This is only effective for targets supporting concurrent runs, such as
+    /// emulators. The builds are still performed one at a time.
+    #[structopt(short = "j", long = "jobs", default_value = "1")]
+    jobs: usize,
     /// Keep going until N tests fail (0 means infinity)
     #[structopt(short = "k", long = "keep-going", default_value = "5")]
     keep_going: usize,
@@ -107,7 +131,21 @@ async fn main_inner() -> anyhow::Result<()> {
             .expect("Couldn't get the parent of `CARGO_MANIFEST_DIR`")
     };
 
-    let target_arch = opt.target_arch.unwrap_or_else(|| opt.target.target_arch());
+    // Configure the target for the specified chip
+    let target: &'static dyn targets::Target = if let Some(chip) = &opt.chip {
+        let target = opt
+            .target
+            .with_chip(chip)
+            .map_err(|e| MainError::BadChip(chip.clone(), e))?;
+        // This lives until the end of the program anyway
+        Box::leak(target)
+    } else if opt.target.requires_chip() {
+        return Err(MainError::ChipRequired.into());
+    } else {
+        opt.target
+    };
+
+    let target_arch = opt.target_arch.unwrap_or_else(|| target.target_arch());
     log::debug!("target_arch = {}", target_arch);
 
     let target_arch_opt = target_arch
@@ -116,14 +154,10 @@ async fn main_inner() -> anyhow::Result<()> {
     log::debug!("target_arch_opt = {:?}", target_arch_opt);
 
     // Initialize the test driver interface
-    let test_driver = driverinterface::TestDriver::new(
-        &driver_base_path,
-        opt.target,
-        &target_arch,
-        target_arch_opt,
-    )
-    .await
-    .map_err(MainError::TestDriver)?;
+    let test_driver =
+        driverinterface::TestDriver::new(&driver_base_path, target, &target_arch, target_arch_opt)
+            .await
+            .map_err(MainError::TestDriver)?;
 
     // Select tests
     let test_source = selection::TestSource {
@@ -154,52 +188,92 @@ async fn main_inner() -> anyhow::Result<()> {
 
     log::info!("Performing {} test run(s)", test_runs.len());
 
-    // Connect to the target
-    log::debug!("Connecting to the target");
-    let mut debug_probe = opt
-        .target
-        .connect()
-        .await
-        .map_err(MainError::ConnectTarget)?;
-
-    let mut failed_tests = Vec::new();
-    let mut tests_skipped_to_fail_fast = Vec::new();
-
-    for test_run in test_runs.iter() {
-        if opt.keep_going != 0 && failed_tests.len() >= opt.keep_going {
-            // Skip all remaining tests if a certain number of tests have failed
-            tests_skipped_to_fail_fast.push(test_run.to_string());
-            continue;
-        }
+    let num_jobs = if opt.jobs > 1 && !target.supports_concurrent_runs() {
+        log::warn!("The target doesn't support concurrent test runs; ignoring `--jobs`");
+        1
+    } else {
+        opt.jobs.max(1)
+    };
+    log::debug!("num_jobs = {}", num_jobs);
 
-        let full_test_name = test_run.case.to_string();
-        log::info!(" - {}", test_run);
-
-        // Build and run the test driver
-        let test_result = test_driver
-            .run(
-                test_run,
-                driverinterface::BuildOpt {
-                    verbose: opt.verbose,
-                    log_level: opt.log_level,
-                },
-                &mut *debug_probe,
-            )
-            .await
-            .map_err(|e| MainError::RunTest(full_test_name, e))?;
+    let test_run_queue = RefCell::new(test_runs.iter());
+    let failed_tests = RefCell::new(Vec::new());
+    let tests_skipped_to_fail_fast = RefCell::new(Vec::new());
 
-        match test_result {
-            Ok(()) => {
-                log::info!("Test run '{}' was successful", test_run);
-            }
-            Err(msg) => {
-                // Test did run, but the result was failure.
-                log::error!("Test run '{}' failed: {}", test_run, msg);
-                failed_tests.push(test_run.to_string());
-                continue;
+    // Spawn workers, each of which takes test runs from `test_run_queue` and
+    // performs them one by one
+    let workers = (0..num_jobs).map(|worker_i| {
+        let (opt, test_driver) = (&opt, &test_driver);
+        let (test_run_queue, failed_tests, tests_skipped_to_fail_fast) =
+            (&test_run_queue, &failed_tests, &tests_skipped_to_fail_fast);
+
+        Box::pin(async move {
+            let mut debug_probe = None;
+
+            loop {
+                let test_run = match test_run_queue.borrow_mut().next() {
+                    Some(test_run) => test_run,
+                    None => break,
+                };
+
+                if opt.keep_going != 0 && failed_tests.borrow().len() >= opt.keep_going {
+                    // Skip all remaining tests if a certain number of tests
+                    // have failed
+                    tests_skipped_to_fail_fast
+                        .borrow_mut()
+                        .push(test_run.to_string());
+                    continue;
+                }
+
+                let full_test_name = test_run.case.to_string();
+                log::info!(" - {}", test_run);
+
+                // Connect to the target
+                if debug_probe.is_none() {
+                    log::debug!("[{}] Connecting to the target", worker_i);
+                    debug_probe = Some(target.connect().await.map_err(MainError::ConnectTarget)?);
+                }
+                let debug_probe: &mut dyn targets::DebugProbe =
+                    &mut **debug_probe.as_mut().unwrap();
+
+                // Build and run the test driver
+                let test_result = test_driver
+                    .run(
+                        test_run,
+                        driverinterface::BuildOpt {
+                            verbose: opt.verbose,
+                            log_level: opt.log_level,
+                            driver_features: &opt.driver_features,
+                        },
+                        Some(opt.timeout)
+                            .filter(|&secs| secs != 0)
+                            .map(std::time::Duration::from_secs),
+                        debug_probe,
+                    )
+                    .await
+                    .map_err(|e| MainError::RunTest(full_test_name, e))?;
+
+                match test_result {
+                    Ok(()) => {
+                        log::info!("Test run '{}' was successful", test_run);
+                    }
+                    Err(msg) => {
+                        // Test did run, but the result was failure.
+                        log::error!("Test run '{}' failed: {}", test_run, msg);
+                        failed_tests.borrow_mut().push(test_run.to_string());
+                        continue;
+                    }
+                }
             }
-        }
-    }
+
+            Ok(())
+        }) as Pin<Box<dyn Future<Output = Result<(), MainError>> + '_>>
+    });
+
+    utils::try_join_all(workers).await?;
+
+    let failed_tests = failed_tests.into_inner();
+    let tests_skipped_to_fail_fast = tests_skipped_to_fail_fast.into_inner();
 
     log::info!(
         "Summary: {} success, {} fail, {} skipped",
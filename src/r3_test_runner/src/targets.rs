@@ -18,6 +18,13 @@ pub trait Target: Send + Sync {
 
     /// Get the additional Cargo features to enable when building
     /// `r3_port_*_test_driver`.
+    ///
+    /// This is also where a target chooses how the test driver sends its
+    /// output. For example, `output-rtt` selects RTT, `output-semihosting`
+    /// selects Arm semihosting handled by QEMU, and
+    /// `output-semihosting-write0` selects Arm semihosting `SYS_WRITE0` calls
+    /// serviced by the runner through a debug probe. Either way, the output is
+    /// scanned for the same test-completion markers.
     fn cargo_features(&self) -> &[&str];
 
     /// Generate the `memory.x` file to be included by the linker script of
@@ -27,6 +34,27 @@ pub trait Target: Send + Sync {
 
     /// Connect to the target.
     fn connect(&self) -> Pin<Box<dyn Future<Output = Result<Box<dyn DebugProbe>>>>>;
+
+    /// Indicates whether [`Self::connect`] can be called more than once to
+    /// run tests concurrently (e.g., on multiple emulator instances). Defaults
+    /// to `false`, which is the right choice for a physical target.
+    fn supports_concurrent_runs(&self) -> bool {
+        false
+    }
+
+    /// Indicates whether the target requires a chip name to be specified by
+    /// `--chip`. Defaults to `false`.
+    fn requires_chip(&self) -> bool {
+        false
+    }
+
+    /// Get a variant of this target for the chip specified by `--chip`.
+    ///
+    /// This is implemented by a target that isn't tied to a particular chip.
+    /// The default implementation returns an error.
+    fn with_chip(&self, chip: &str) -> Result<Box<dyn Target>> {
+        anyhow::bail!("The target doesn't support specifying a chip ('{}').", chip)
+    }
 }
 
 pub trait DebugProbe: Send {
@@ -41,7 +69,11 @@ pub trait DebugProbe: Send {
 type DynAsyncRead<'a> = Pin<Box<dyn AsyncRead + 'a>>;
 
 pub static TARGETS: &[(&str, &dyn Target)] = &[
-    ("nucleo_f401re", &probe_rs::NucleoF401re),
+    ("nucleo_f401re", &probe_rs::NucleoF401re::RTT),
+    (
+        "nucleo_f401re_semihosting",
+        &probe_rs::NucleoF401re::SEMIHOSTING,
+    ),
     ("qemu_mps2_an385", &qemu::arm::QemuMps2An385),
     ("qemu_mps2_an505", &qemu::arm::QemuMps2An505),
     ("qemu_realview_pbx_a9", &qemu::arm::QemuRealviewPbxA9),
@@ -50,7 +82,11 @@ pub static TARGETS: &[(&str, &dyn Target)] = &[
     ("qemu_sifive_e_rv64", &qemu::riscv::QemuSiFiveE(Xlen::_64)),
     ("qemu_sifive_u_rv32", &qemu::riscv::QemuSiFiveU(Xlen::_32)),
     ("qemu_sifive_u_rv64", &qemu::riscv::QemuSiFiveU(Xlen::_64)),
+    ("qemu_virt_sbi_rv32", &qemu::riscv::QemuVirtSbi(Xlen::_32)),
+    ("qemu_virt_sbi_rv64", &qemu::riscv::QemuVirtSbi(Xlen::_64)),
     ("red_v", &jlink::RedV),
+    ("red_v_openocd", &openocd::RedVOpenOcd),
+    ("probe_rs_riscv", &probe_rs::ProbeRsRiscv::GENERIC),
     ("maix", &kflash::Maix),
     ("rp_pico", &rp_pico::RaspberryPiPico),
 ];
@@ -73,6 +109,18 @@ impl<T: Target> Target for OverrideTargetArch<T> {
     fn connect(&self) -> Pin<Box<dyn Future<Output = Result<Box<dyn DebugProbe>>>>> {
         self.1.connect()
     }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        self.1.supports_concurrent_runs()
+    }
+
+    fn requires_chip(&self) -> bool {
+        self.1.requires_chip()
+    }
+
+    fn with_chip(&self, chip: &str) -> Result<Box<dyn Target>> {
+        self.1.with_chip(chip)
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -180,10 +180,16 @@ pub enum ProcessElfError {
     Read(#[source] std::io::Error),
     #[error("Couldn't parse the ELF file")]
     Parse(#[source] goblin::error::Error),
+    #[error("The loadable region at 0x{0:x} extends past the end of the address space")]
+    AddressOverflow(u64),
+    #[error("The loadable regions at 0x{0:x} and 0x{1:x} overlap with conflicting contents")]
+    ConflictingRegions(u64, u64),
 }
 
+#[derive(Debug)]
 pub struct LoadableCode {
-    /// The regions to be loaded onto the target.
+    /// The regions to be loaded onto the target, sorted by address. Adjacent
+    /// regions are merged into one.
     pub regions: Vec<(Vec<u8>, u64)>,
     /// The entry point.
     pub entry: u64,
@@ -193,31 +199,68 @@ pub struct LoadableCode {
 pub async fn read_elf(exe: &Path) -> Result<LoadableCode, ProcessElfError> {
     let elf_bytes = tokio::fs::read(&exe).await.map_err(ProcessElfError::Read)?;
 
-    spawn_blocking(move || {
-        let elf = goblin::elf::Elf::parse(&elf_bytes).map_err(ProcessElfError::Parse)?;
-
-        let regions = elf
-            .program_headers
-            .iter()
-            .filter_map(|ph| {
-                if ph.p_type == goblin::elf32::program_header::PT_LOAD && ph.p_filesz > 0 {
-                    Some((
-                        elf_bytes[ph.p_offset as usize..][..ph.p_filesz as usize].to_vec(),
-                        ph.p_paddr,
-                    ))
-                } else {
-                    None
-                }
-            })
-            .collect();
+    spawn_blocking(move || read_elf_bytes(&elf_bytes))
+        .await
+        .unwrap() // Ignore `JoinError`
+}
+
+/// The synchronous part of [`read_elf`].
+fn read_elf_bytes(elf_bytes: &[u8]) -> Result<LoadableCode, ProcessElfError> {
+    let elf = goblin::elf::Elf::parse(elf_bytes).map_err(ProcessElfError::Parse)?;
 
-        Ok(LoadableCode {
-            regions,
-            entry: elf.entry,
+    let regions = elf
+        .program_headers
+        .iter()
+        .filter_map(|ph| {
+            if ph.p_type == goblin::elf32::program_header::PT_LOAD && ph.p_filesz > 0 {
+                Some((
+                    elf_bytes[ph.p_offset as usize..][..ph.p_filesz as usize].to_vec(),
+                    ph.p_paddr,
+                ))
+            } else {
+                None
+            }
         })
+        .collect();
+
+    Ok(LoadableCode {
+        regions: merge_regions(regions)?,
+        entry: elf.entry,
     })
-    .await
-    .unwrap() // Ignore `JoinError`
+}
+
+/// Sort the specified regions by address and coalesce adjacent or overlapping
+/// ones so that they can be loaded by fewer operations. Overlapping regions
+/// must agree on the contents of the overlapping part.
+fn merge_regions(mut regions: Vec<(Vec<u8>, u64)>) -> Result<Vec<(Vec<u8>, u64)>, ProcessElfError> {
+    regions.sort_by_key(|&(_, addr)| addr);
+
+    let mut merged: Vec<(Vec<u8>, u64)> = Vec::with_capacity(regions.len());
+    for (data, addr) in regions {
+        let end = addr
+            .checked_add(data.len() as u64)
+            .ok_or(ProcessElfError::AddressOverflow(addr))?;
+
+        if let Some((last_data, last_addr)) = merged.last_mut() {
+            let last_end = *last_addr + last_data.len() as u64;
+            if addr <= last_end {
+                // `[addr, overlap_end)` is covered by both regions
+                let overlap_end = last_end.min(end);
+                let overlap_len = (overlap_end - addr) as usize;
+                let last_overlap = &last_data[(addr - *last_addr) as usize..][..overlap_len];
+                if last_overlap != &data[..overlap_len] {
+                    return Err(ProcessElfError::ConflictingRegions(*last_addr, addr));
+                }
+
+                last_data.extend_from_slice(&data[overlap_len..]);
+                continue;
+            }
+        }
+
+        merged.push((data, addr));
+    }
+
+    Ok(merged)
 }
 
 struct OutputReader {
@@ -233,3 +276,117 @@ impl AsyncRead for OutputReader {
         Pin::new(self.child.stdout.as_mut().unwrap()).poll_read(cx, buf)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Construct a little-endian ELF32 executable containing the specified
+    /// `PT_LOAD` segments.
+    fn make_elf32(entry: u32, segments: &[(&[u8], u32)]) -> Vec<u8> {
+        const EHSIZE: usize = 52;
+        const PHENTSIZE: usize = 32;
+
+        let mut elf = Vec::new();
+        let push16 = |elf: &mut Vec<u8>, x: u16| elf.extend_from_slice(&x.to_le_bytes());
+        let push32 = |elf: &mut Vec<u8>, x: u32| elf.extend_from_slice(&x.to_le_bytes());
+
+        // ELF header
+        elf.extend_from_slice(b"\x7fELF");
+        elf.extend_from_slice(&[1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // ELFCLASS32, ELFDATA2LSB
+        push16(&mut elf, 2); // e_type = ET_EXEC
+        push16(&mut elf, 243); // e_machine = EM_RISCV
+        push32(&mut elf, 1); // e_version
+        push32(&mut elf, entry); // e_entry
+        push32(&mut elf, EHSIZE as u32); // e_phoff
+        push32(&mut elf, 0); // e_shoff
+        push32(&mut elf, 0); // e_flags
+        push16(&mut elf, EHSIZE as u16); // e_ehsize
+        push16(&mut elf, PHENTSIZE as u16); // e_phentsize
+        push16(&mut elf, segments.len() as u16); // e_phnum
+        push16(&mut elf, 40); // e_shentsize
+        push16(&mut elf, 0); // e_shnum
+        push16(&mut elf, 0); // e_shstrndx
+        assert_eq!(elf.len(), EHSIZE);
+
+        // Program headers
+        let mut offset = EHSIZE + PHENTSIZE * segments.len();
+        for &(data, addr) in segments {
+            push32(&mut elf, goblin::elf32::program_header::PT_LOAD); // p_type
+            push32(&mut elf, offset as u32); // p_offset
+            push32(&mut elf, addr); // p_vaddr
+            push32(&mut elf, addr); // p_paddr
+            push32(&mut elf, data.len() as u32); // p_filesz
+            push32(&mut elf, data.len() as u32); // p_memsz
+            push32(&mut elf, 0b101); // p_flags = PF_R | PF_X
+            push32(&mut elf, 1); // p_align
+            offset += data.len();
+        }
+
+        // Segment contents
+        for &(data, _) in segments {
+            elf.extend_from_slice(data);
+        }
+
+        elf
+    }
+
+    #[test]
+    fn read_multi_segment_elf() {
+        let elf = make_elf32(
+            0x2001_0000,
+            &[
+                (b"ijkl", 0x8000_0000),
+                (b"efgh", 0x2001_0004),
+                (b"", 0x2001_0008),
+                (b"abcd", 0x2001_0000),
+                (b"mnop", 0x8000_0100),
+            ],
+        );
+
+        let code = read_elf_bytes(&elf).unwrap();
+        assert_eq!(code.entry, 0x2001_0000);
+        assert_eq!(
+            code.regions,
+            [
+                (b"abcdefgh".to_vec(), 0x2001_0000),
+                (b"ijkl".to_vec(), 0x8000_0000),
+                (b"mnop".to_vec(), 0x8000_0100),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_overlapping_regions() {
+        let regions = merge_regions(vec![
+            (b"cdef".to_vec(), 0x102),
+            (b"abcd".to_vec(), 0x100),
+            (b"d".to_vec(), 0x103),
+        ])
+        .unwrap();
+        assert_eq!(regions, [(b"abcdef".to_vec(), 0x100)]);
+    }
+
+    #[test]
+    fn reject_conflicting_regions() {
+        let result = merge_regions(vec![(b"abcd".to_vec(), 0x100), (b"xy".to_vec(), 0x102)]);
+        assert!(
+            matches!(
+                result,
+                Err(ProcessElfError::ConflictingRegions(0x100, 0x102))
+            ),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn reject_address_overflow() {
+        let result = merge_regions(vec![(b"abcd".to_vec(), u64::MAX - 1)]);
+        assert!(
+            matches!(result, Err(ProcessElfError::AddressOverflow(_))),
+            "{:?}",
+            result
+        );
+    }
+}
@@ -1,17 +1,26 @@
 use anyhow::Result;
 use std::{
+    convert::TryInto,
+    fmt::Write,
     future::Future,
     io,
     path::Path,
     pin::Pin,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::Duration,
 };
 use tempdir::TempDir;
 use tokio::{io::AsyncRead, process::Child};
 
-use super::{Arch, DebugProbe, DynAsyncRead, Target};
+use super::{jlink, Arch, DebugProbe, DynAsyncRead, Target};
 use crate::subprocess;
 
+/// The maximum duration for which an OpenOCD session for programming a target
+/// is allowed to run. A hung debug adapter or board causes the test to fail
+/// instead of stalling the test runner forever.
+const PROGRAM_TIMEOUT: Duration = Duration::from_secs(120);
+
 /// GR-PEACH
 pub struct GrPeach;
 
@@ -200,3 +209,157 @@ impl AsyncRead for OutputReader {
         Pin::new(self.child.stdout.as_mut().unwrap()).poll_read(cx, buf)
     }
 }
+
+/// SparkFun RED-V RedBoard or Things Plus, programmed through OpenOCD
+pub struct RedVOpenOcd;
+
+impl Target for RedVOpenOcd {
+    fn target_arch(&self) -> Arch {
+        jlink::RedV.target_arch()
+    }
+
+    fn cargo_features(&self) -> &[&str] {
+        jlink::RedV.cargo_features()
+    }
+
+    fn memory_layout_script(&self) -> String {
+        jlink::RedV.memory_layout_script()
+    }
+
+    fn connect(&self) -> Pin<Box<dyn Future<Output = Result<Box<dyn DebugProbe>>>>> {
+        Box::pin(std::future::ready(Ok(Box::new(OpenOcdDebugProbe {
+            // RED-V is a derivative of HiFive1 Rev B
+            board_cfg: "board/sifive-hifive1-revb.cfg",
+            write_to_flash: true,
+            probe_rs_probe: "1366:1061",
+            probe_rs_target: "riscv",
+            rtt_options: super::probe_rs::RttOptions {
+                // See `jlink::Fe310JLinkDebugProbe`
+                halt_on_access: true,
+            },
+        }) as _)))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+enum OpenOcdDebugProbeGetOutputError {
+    #[error("Error while analyzing the ELF file")]
+    ProcessElf(#[source] jlink::ProcessElfError),
+    #[error("Error while creating a temporary directory")]
+    CreateTempDir(#[source] std::io::Error),
+    #[error("Error while creating a temporary file")]
+    CreateTempFile(#[source] std::io::Error),
+    #[error("Error while programming the device")]
+    Program(#[source] subprocess::SubprocessError),
+    #[error("OpenOCD did not finish programming the device in {0:?}")]
+    Timeout(Duration),
+    #[error("Error while opening the probe")]
+    OpenProbe(#[source] probe_rs::DebugProbeError),
+    #[error("Error while attaching to the probe")]
+    Attach(#[source] probe_rs::Error),
+}
+
+/// A generic [`DebugProbe`] that programs the target by OpenOCD and then
+/// retrieves the output through RTT by `probe-rs`.
+struct OpenOcdDebugProbe {
+    /// The OpenOCD configuration file describing the debug adapter and the
+    /// target, e.g., `board/sifive-hifive1-revb.cfg`.
+    board_cfg: &'static str,
+    /// Write the loadable regions by `flash write_image` instead of
+    /// `load_image`.
+    write_to_flash: bool,
+    /// The `probe-rs` selector of the debug probe used to access RTT.
+    probe_rs_probe: &'static str,
+    /// The `probe-rs` selector of the target chip used to access RTT.
+    probe_rs_target: &'static str,
+    rtt_options: super::probe_rs::RttOptions,
+}
+
+impl DebugProbe for OpenOcdDebugProbe {
+    fn program_and_get_output(
+        &mut self,
+        exe: &Path,
+    ) -> Pin<Box<dyn Future<Output = Result<DynAsyncRead<'_>>> + '_>> {
+        let exe = exe.to_owned();
+        Box::pin(async move {
+            // Extract loadable sections
+            let jlink::LoadableCode { regions, entry } = jlink::read_elf(&exe)
+                .await
+                .map_err(OpenOcdDebugProbeGetOutputError::ProcessElf)?;
+
+            // Extract loadable regions to separate binary files
+            let tempdir = TempDir::new("r3_test_runner")
+                .map_err(OpenOcdDebugProbeGetOutputError::CreateTempDir)?;
+            let section_files: Vec<_> = (0..regions.len())
+                .map(|i| {
+                    let name = format!("{}.bin", i);
+                    tempdir.path().join(&name)
+                })
+                .collect();
+            for (path, (data, _)) in section_files.iter().zip(regions.iter()) {
+                log::debug!("Writing {} byte(s) to '{}'", data.len(), path.display());
+                tokio::fs::write(&path, data)
+                    .await
+                    .map_err(OpenOcdDebugProbeGetOutputError::CreateTempFile)?;
+            }
+
+            // Generate commands for OpenOCD
+            let mut cmd = String::new();
+            writeln!(cmd, "init").unwrap();
+            writeln!(cmd, "reset halt").unwrap();
+            for (path, (_, offset)) in section_files.iter().zip(regions.iter()) {
+                if self.write_to_flash {
+                    write!(cmd, "flash write_image erase").unwrap();
+                } else {
+                    write!(cmd, "load_image").unwrap();
+                }
+                writeln!(cmd, " \"{}\" 0x{:08x} bin", path.display(), offset).unwrap();
+            }
+            writeln!(cmd, "resume 0x{:x}", entry).unwrap();
+            writeln!(cmd, "shutdown").unwrap();
+
+            let cmd_file = tempdir.path().join("program.cfg");
+            tokio::fs::write(&cmd_file, &cmd)
+                .await
+                .map_err(OpenOcdDebugProbeGetOutputError::CreateTempFile)?;
+
+            // Program the device and start the program. The child process is
+            // killed if the timeout expires (`kill_on_drop`).
+            log::debug!("Launching OpenOCD and executing '{:?}'", cmd);
+            let program = subprocess::CmdBuilder::new("openocd")
+                .arg("-f")
+                .arg(self.board_cfg)
+                .arg("-f")
+                .arg(&cmd_file)
+                .spawn_expecting_success_quiet();
+            tokio::time::timeout(PROGRAM_TIMEOUT, program)
+                .await
+                .map_err(|_| OpenOcdDebugProbeGetOutputError::Timeout(PROGRAM_TIMEOUT))?
+                .map_err(OpenOcdDebugProbeGetOutputError::Program)?;
+
+            log::debug!("Waiting for 1 seconds");
+
+            // The stale RTT data from a previous run might still be there until
+            // the new startup code zero-fills the memory.
+            tokio::time::delay_for(Duration::from_secs(1)).await;
+            log::debug!("Opening the debug probe using `probe-rs`");
+
+            // Open the probe using `probe-rs`. OpenOCD has already released
+            // the probe by this point.
+            let selector: probe_rs::DebugProbeSelector = self.probe_rs_probe.try_into().unwrap();
+            let probe = probe_rs::Probe::open(selector)
+                .map_err(OpenOcdDebugProbeGetOutputError::OpenProbe)?;
+
+            let selector: probe_rs::config::TargetSelector =
+                self.probe_rs_target.try_into().unwrap();
+            let session = Arc::new(Mutex::new(
+                probe
+                    .attach(selector)
+                    .map_err(OpenOcdDebugProbeGetOutputError::Attach)?,
+            ));
+
+            // Open the RTT channels
+            Ok(super::probe_rs::attach_rtt(session, &exe, self.rtt_options).await?)
+        })
+    }
+}
@@ -5,6 +5,7 @@ use std::{
     future::Future,
     io::Write,
     mem::replace,
+    ops::Range,
     path::Path,
     pin::Pin,
     sync::{Arc, Mutex},
@@ -12,14 +13,32 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::{
-    io::{AsyncBufRead, AsyncRead},
+    io::{AsyncBufRead, AsyncRead, BufReader},
     task::{spawn_blocking, JoinHandle},
     time::{delay_for, Delay},
 };
 
-use super::{Arch, DebugProbe, DynAsyncRead, Target};
+use super::{demux::Demux, Arch, DebugProbe, DynAsyncRead, Target};
 
-pub struct NucleoF401re;
+mod semihosting;
+
+pub struct NucleoF401re {
+    output: Output,
+}
+
+impl NucleoF401re {
+    /// Receives the output through RTT.
+    pub const RTT: Self = Self {
+        output: Output::Rtt(RttOptions {
+            halt_on_access: false,
+        }),
+    };
+
+    /// Receives the output through semihosting `SYS_WRITE0`.
+    pub const SEMIHOSTING: Self = Self {
+        output: Output::Semihosting,
+    };
+}
 
 impl Target for NucleoF401re {
     fn target_arch(&self) -> Arch {
@@ -27,7 +46,7 @@ impl Target for NucleoF401re {
     }
 
     fn cargo_features(&self) -> &[&str] {
-        &["output-rtt"]
+        self.output.cargo_features()
     }
 
     fn memory_layout_script(&self) -> String {
@@ -48,23 +67,174 @@ impl Target for NucleoF401re {
     }
 
     fn connect(&self) -> Pin<Box<dyn Future<Output = Result<Box<dyn DebugProbe>>>>> {
+        let output = self.output;
+        Box::pin(async move {
+            spawn_blocking(move || {
+                ProbeRsDebugProbe::new(
+                    Some("0483:374b".try_into().unwrap()),
+                    "stm32f401re".into(),
+                    output,
+                )
+                .map(|x| Box::new(x) as _)
+            })
+            .await
+            .unwrap()
+        })
+    }
+}
+
+/// A RISC-V chip supported by `probe-rs`, which is specified by `--chip`.
+///
+/// The first debug probe found is used. The Cargo features of the test driver
+/// for the interrupt controller and the board (e.g., `interrupt-e310x`) must
+/// be supplied by `--driver-feature`.
+pub struct ProbeRsRiscv {
+    chip: Option<ProbeRsChip>,
+}
+
+struct ProbeRsChip {
+    /// The chip name recognized by `probe-rs`.
+    name: String,
+    /// The address range of the non-volatile memory to store the program in.
+    flash: Range<u32>,
+    /// The address range of RAM.
+    ram: Range<u32>,
+}
+
+impl ProbeRsRiscv {
+    /// The instance listed in [`super::TARGETS`], which doesn't have a chip
+    /// name yet.
+    pub const GENERIC: Self = Self { chip: None };
+
+    fn chip(&self) -> &ProbeRsChip {
+        // `main` ensures `with_chip` is called before using this target
+        self.chip.as_ref().expect("chip name hasn't been specified")
+    }
+}
+
+impl Target for ProbeRsRiscv {
+    fn target_arch(&self) -> Arch {
+        Arch::RV32IMAC
+    }
+
+    fn cargo_features(&self) -> &[&str] {
+        &["output-rtt"]
+    }
+
+    fn memory_layout_script(&self) -> String {
+        let ProbeRsChip { flash, ram, .. } = self.chip();
+        format!(
+            r#"
+                MEMORY
+                {{
+                    FLASH : ORIGIN = 0x{:08x}, LENGTH = {}
+                    RAM : ORIGIN = 0x{:08x}, LENGTH = {}
+                }}
+
+                REGION_ALIAS("REGION_TEXT", FLASH);
+                REGION_ALIAS("REGION_RODATA", FLASH);
+                REGION_ALIAS("REGION_DATA", RAM);
+                REGION_ALIAS("REGION_BSS", RAM);
+                REGION_ALIAS("REGION_HEAP", RAM);
+                REGION_ALIAS("REGION_STACK", RAM);
+
+                _hart_stack_size = 1K;
+            "#,
+            flash.start,
+            flash.end - flash.start,
+            ram.start,
+            ram.end - ram.start,
+        )
+    }
+
+    fn connect(&self) -> Pin<Box<dyn Future<Output = Result<Box<dyn DebugProbe>>>>> {
+        let chip_name = self.chip().name.clone();
         Box::pin(async {
-            spawn_blocking(|| {
-                ProbeRsDebugProbe::new("0483:374b".try_into().unwrap(), "stm32f401re".into())
-                    .map(|x| Box::new(x) as _)
+            spawn_blocking(move || {
+                ProbeRsDebugProbe::new(
+                    None,
+                    chip_name.as_str().into(),
+                    Output::Rtt(RttOptions {
+                        // Not all RISC-V debug modules support memory access
+                        // while the hart is running
+                        halt_on_access: true,
+                    }),
+                )
+                .map(|x| Box::new(x) as _)
             })
             .await
             .unwrap()
         })
     }
+
+    fn requires_chip(&self) -> bool {
+        self.chip.is_none()
+    }
+
+    fn with_chip(&self, chip: &str) -> Result<Box<dyn Target>> {
+        let target = probe_rs::config::get_target_by_name(chip)?;
+
+        // Choose the first memory regions of each type
+        let flash = target
+            .memory_map
+            .iter()
+            .find_map(|region| match region {
+                probe_rs::config::MemoryRegion::Nvm(region) => Some(region.range.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("The chip doesn't have a non-volatile memory."))?;
+        let ram = target
+            .memory_map
+            .iter()
+            .find_map(|region| match region {
+                probe_rs::config::MemoryRegion::Ram(region) => Some(region.range.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("The chip doesn't have RAM."))?;
+
+        log::debug!("flash = {:x?}, ram = {:x?}", flash, ram);
+
+        Ok(Box::new(Self {
+            chip: Some(ProbeRsChip {
+                name: chip.to_owned(),
+                flash,
+                ram,
+            }),
+        }))
+    }
+}
+
+/// Specifies how the output of the test driver is received.
+#[derive(Clone, Copy)]
+pub enum Output {
+    /// Read the RTT channels (`output-rtt`).
+    Rtt(RttOptions),
+    /// Service semihosting `SYS_WRITE0` calls (`output-semihosting-write0`).
+    /// The primary message and the log output are multiplexed into one
+    /// stream, which is demultiplexed by [`Demux`]. Only supported on Arm.
+    Semihosting,
+}
+
+impl Output {
+    /// Get the Cargo feature of the test driver that selects this output
+    /// mechanism.
+    fn cargo_features(&self) -> &'static [&'static str] {
+        match self {
+            Self::Rtt(_) => &["output-rtt"],
+            Self::Semihosting => &["output-semihosting-write0"],
+        }
+    }
 }
 
 struct ProbeRsDebugProbe {
     session: Arc<Mutex<probe_rs::Session>>,
+    output: Output,
 }
 
 #[derive(thiserror::Error, Debug)]
 enum OpenError {
+    #[error("No debug probes were found")]
+    NoProbe,
     #[error("Error while opening the probe")]
     OpenProbe(#[source] probe_rs::DebugProbeError),
     #[error("Error while attaching to the probe")]
@@ -80,17 +250,30 @@ enum RunError {
 }
 
 impl ProbeRsDebugProbe {
+    /// Open a debug probe and attach to the target. If `probe_sel` is `None`,
+    /// the first debug probe found will be used.
     fn new(
-        probe_sel: probe_rs::DebugProbeSelector,
+        probe_sel: Option<probe_rs::DebugProbeSelector>,
         target_sel: probe_rs::config::TargetSelector,
+        output: Output,
     ) -> anyhow::Result<Self> {
-        let probe = probe_rs::Probe::open(probe_sel).map_err(OpenError::OpenProbe)?;
+        let probe = if let Some(probe_sel) = probe_sel {
+            probe_rs::Probe::open(probe_sel)
+        } else {
+            let probe_info = probe_rs::Probe::list_all()
+                .into_iter()
+                .next()
+                .ok_or(OpenError::NoProbe)?;
+            log::debug!("Using the debug probe {:?}", probe_info);
+            probe_info.open()
+        }
+        .map_err(OpenError::OpenProbe)?;
 
         let session = Arc::new(Mutex::new(
             probe.attach(target_sel).map_err(OpenError::Attach)?,
         ));
 
-        Ok(Self { session })
+        Ok(Self { session, output })
     }
 }
 
@@ -101,6 +284,7 @@ impl DebugProbe for ProbeRsDebugProbe {
     ) -> Pin<Box<dyn Future<Output = Result<DynAsyncRead<'_>>> + '_>> {
         let exe = exe.to_owned();
         let session = Arc::clone(&self.session);
+        let output = self.output;
 
         Box::pin(async move {
             // Flash the executable
@@ -126,8 +310,16 @@ impl DebugProbe for ProbeRsDebugProbe {
                 .reset()
                 .map_err(RunError::Reset)?;
 
-            // Attach to RTT
-            Ok(attach_rtt(session, &exe, Default::default()).await?)
+            match output {
+                // Attach to RTT
+                Output::Rtt(rtt_options) => Ok(attach_rtt(session, &exe, rtt_options).await?),
+
+                // Service semihosting calls
+                Output::Semihosting => {
+                    let output = semihosting::ReadSemihosting::new(session);
+                    Ok(Box::pin(Demux::new(BufReader::new(output))) as DynAsyncRead<'static>)
+                }
+            }
         })
     }
 }
@@ -145,7 +337,7 @@ pub enum AttachRttError {
     Timeout,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct RttOptions {
     /// When set to `true`, the core is halted whenever accessing RTT.
     pub halt_on_access: bool,
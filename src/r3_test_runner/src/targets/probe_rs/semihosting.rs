@@ -0,0 +1,185 @@
+//! Receives the output of an Arm target through semihosting `SYS_WRITE0`
+//! (`output-semihosting-write0`).
+//!
+//! A semihosting call is a `BKPT 0xab` instruction, which halts the core while
+//! a debugger is attached. [`ReadSemihosting`] polls the core, and when it
+//! finds the core halted at a semihosting call, it services the call and
+//! resumes the core.
+use futures_core::ready;
+use probe_rs::{CoreRegisterAddress, MemoryInterface};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+use tokio::{io::AsyncRead, sync::mpsc, task::spawn_blocking};
+
+use super::POLL_INTERVAL;
+
+/// The encoding of `BKPT 0xab` (T1)
+const BKPT_SEMIHOSTING: u16 = 0xbeab;
+
+/// The operation number of `SYS_WRITE0`
+const SYS_WRITE0: u32 = 0x04;
+
+const REG_R0: CoreRegisterAddress = CoreRegisterAddress(0);
+const REG_R1: CoreRegisterAddress = CoreRegisterAddress(1);
+const REG_PC: CoreRegisterAddress = CoreRegisterAddress(15);
+
+/// The maximum length of a string accepted from `SYS_WRITE0`. This prevents
+/// a bogus pointer from making us read the whole address space.
+const MAX_STRING_LEN: usize = 4096;
+
+#[derive(thiserror::Error, Debug)]
+pub enum SemihostingError {
+    #[error("Error while accessing the core")]
+    Core(#[source] probe_rs::Error),
+    #[error("The core halted at 0x{0:08x} for a reason other than a semihosting call")]
+    UnexpectedHalt(u32),
+    #[error("The string passed to SYS_WRITE0 is not terminated")]
+    StringTooLong,
+}
+
+impl From<probe_rs::Error> for SemihostingError {
+    fn from(x: probe_rs::Error) -> Self {
+        Self::Core(x)
+    }
+}
+
+/// Streams the strings output by `SYS_WRITE0` calls.
+pub struct ReadSemihosting {
+    recv: mpsc::UnboundedReceiver<Result<Vec<u8>, SemihostingError>>,
+    /// Tells the polling thread to stop
+    stop: Arc<AtomicBool>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl ReadSemihosting {
+    /// Start servicing the semihosting calls made by the first core.
+    pub fn new(session: Arc<Mutex<probe_rs::Session>>) -> Self {
+        let (send, recv) = mpsc::unbounded_channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let stop2 = Arc::clone(&stop);
+        spawn_blocking(move || {
+            while !stop2.load(Ordering::Relaxed) {
+                match service_call(&session) {
+                    Ok(Some(bytes)) => {
+                        if send.send(Ok(bytes)).is_err() {
+                            break;
+                        }
+                    }
+                    // The core is running; wait for a while and try again
+                    Ok(None) => std::thread::sleep(POLL_INTERVAL),
+                    Err(e) => {
+                        let _ = send.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            recv,
+            stop,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Drop for ReadSemihosting {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl AsyncRead for ReadSemihosting {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<tokio::io::Result<usize>> {
+        let this = &mut *self;
+
+        while this.pos == this.buf.len() {
+            match ready!(this.recv.poll_recv(cx)) {
+                Some(Ok(bytes)) => {
+                    this.buf = bytes;
+                    this.pos = 0;
+                }
+                Some(Err(e)) => {
+                    return Poll::Ready(Err(tokio::io::Error::new(tokio::io::ErrorKind::Other, e)))
+                }
+                // The polling thread has stopped
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+
+        let num_bytes_read = (this.buf.len() - this.pos).min(buf.len());
+        buf[..num_bytes_read].copy_from_slice(&this.buf[this.pos..][..num_bytes_read]);
+        this.pos += num_bytes_read;
+        Poll::Ready(Ok(num_bytes_read))
+    }
+}
+
+/// Check if the core is halted at a semihosting call, and if so, service the
+/// call and resume the core. Returns `Ok(None)` if the core is running.
+fn service_call(session: &Mutex<probe_rs::Session>) -> Result<Option<Vec<u8>>, SemihostingError> {
+    let mut session = session.lock().unwrap();
+    let mut core = session.core(0)?;
+
+    if !core.core_halted()? {
+        return Ok(None);
+    }
+
+    let pc = core.read_core_reg(REG_PC)?;
+    let mut inst = [0u8; 2];
+    core.read_8(pc, &mut inst)?;
+    if u16::from_le_bytes(inst) != BKPT_SEMIHOSTING {
+        return Err(SemihostingError::UnexpectedHalt(pc));
+    }
+
+    let op = core.read_core_reg(REG_R0)?;
+    let bytes = if op == SYS_WRITE0 {
+        let addr = core.read_core_reg(REG_R1)?;
+        let bytes = read_c_str(&mut core, addr)?;
+        log::trace!("SYS_WRITE0 {:?}", String::from_utf8_lossy(&bytes));
+        bytes
+    } else {
+        log::warn!("Unsupported semihosting operation 0x{:x} (ignored)", op);
+        // Report the failure to the caller
+        core.write_core_reg(REG_R0, u32::MAX)?;
+        Vec::new()
+    };
+
+    // Skip the `BKPT` instruction and resume the core
+    core.write_core_reg(REG_PC, pc + 2)?;
+    core.run()?;
+
+    Ok(Some(bytes))
+}
+
+/// Read a NUL-terminated string from the target's memory.
+fn read_c_str(core: &mut probe_rs::Core<'_>, mut addr: u32) -> Result<Vec<u8>, SemihostingError> {
+    let mut out = Vec::new();
+    let mut chunk = [0u8; 32];
+
+    while out.len() < MAX_STRING_LEN {
+        core.read_8(addr, &mut chunk)?;
+
+        if let Some(len) = chunk.iter().position(|&b| b == 0) {
+            out.extend_from_slice(&chunk[..len]);
+            return Ok(out);
+        }
+
+        out.extend_from_slice(&chunk);
+        addr += chunk.len() as u32;
+    }
+
+    Err(SemihostingError::StringTooLong)
+}
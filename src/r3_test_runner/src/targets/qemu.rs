@@ -6,9 +6,12 @@ use std::{
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::{io::AsyncRead, process::Child};
+use tokio::{
+    io::{AsyncRead, BufReader},
+    process::Child,
+};
 
-use super::{DebugProbe, DynAsyncRead};
+use super::{demux::Demux, DebugProbe, DynAsyncRead};
 use crate::subprocess;
 
 pub mod arm;
@@ -17,6 +20,8 @@ pub mod riscv;
 struct QemuDebugProbe {
     qemu_cmd: &'static str,
     qemu_args: &'static [&'static str],
+    /// Demultiplex the output by [`Demux`].
+    demux: bool,
 }
 
 impl QemuDebugProbe {
@@ -24,6 +29,16 @@ impl QemuDebugProbe {
         Self {
             qemu_cmd,
             qemu_args,
+            demux: false,
+        }
+    }
+
+    /// Demultiplex the output by [`Demux`]. This is for targets which output
+    /// both of the primary message and the log output to one serial port.
+    fn with_demux(self) -> Self {
+        Self {
+            demux: true,
+            ..self
         }
     }
 }
@@ -33,6 +48,7 @@ impl DebugProbe for QemuDebugProbe {
         &mut self,
         exe: &Path,
     ) -> Pin<Box<dyn Future<Output = Result<DynAsyncRead<'_>>> + '_>> {
+        let demux = self.demux;
         let result = subprocess::CmdBuilder::new(self.qemu_cmd)
             .arg("-kernel")
             .arg(exe)
@@ -45,7 +61,14 @@ impl DebugProbe for QemuDebugProbe {
                 "id=none,driver=none",
             ])
             .spawn_and_get_child()
-            .map(|child| Box::pin(OutputReader { child }) as DynAsyncRead<'static>)
+            .map(|child| {
+                let output = OutputReader { child };
+                if demux {
+                    Box::pin(Demux::new(BufReader::new(output))) as DynAsyncRead<'static>
+                } else {
+                    Box::pin(output) as DynAsyncRead<'static>
+                }
+            })
             .map_err(|e| e.into());
 
         Box::pin(std::future::ready(result))
@@ -43,6 +43,10 @@ impl Target for QemuMps2An385 {
             )) as Box<dyn DebugProbe>)
         })
     }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        true
+    }
 }
 
 pub struct QemuMps2An505;
@@ -85,6 +89,10 @@ impl Target for QemuMps2An505 {
             )) as Box<dyn DebugProbe>)
         })
     }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        true
+    }
 }
 
 /// ARM RealView Platform Baseboard Explore for Cortex-A9 on QEMU
@@ -125,4 +133,8 @@ impl Target for QemuRealviewPbxA9 {
             )) as Box<dyn DebugProbe>)
         })
     }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        true
+    }
 }
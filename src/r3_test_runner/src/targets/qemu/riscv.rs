@@ -66,6 +66,10 @@ impl Target for QemuSiFiveE {
             )) as Box<dyn DebugProbe>)
         })
     }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        true
+    }
 }
 
 /// The RISC-V board compatible with SiFive U SDK on QEMU
@@ -129,4 +133,88 @@ impl Target for QemuSiFiveU {
             )) as Box<dyn DebugProbe>)
         })
     }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        true
+    }
+}
+
+/// QEMU `virt` machine, running the kernel in S-mode on top of OpenSBI
+pub struct QemuVirtSbi(pub Xlen);
+
+impl Target for QemuVirtSbi {
+    fn target_arch(&self) -> Arch {
+        match self.0 {
+            Xlen::_32 => Arch::RV32GC,
+            Xlen::_64 => Arch::RV64GC,
+        }
+    }
+
+    fn cargo_features(&self) -> &[&str] {
+        &[
+            "output-ns16550-uart",
+            "interrupt-virt-qemu",
+            "board-virt-qemu",
+        ]
+    }
+
+    fn memory_layout_script(&self) -> String {
+        r#"
+            MEMORY
+            {
+                /* Skip first 4M allocated for OpenSBI */
+                RAM : ORIGIN = 0x80400000, LENGTH = 16M
+            }
+
+            REGION_ALIAS("REGION_TEXT", RAM);
+            REGION_ALIAS("REGION_RODATA", RAM);
+            REGION_ALIAS("REGION_DATA", RAM);
+            REGION_ALIAS("REGION_BSS", RAM);
+            REGION_ALIAS("REGION_HEAP", RAM);
+            REGION_ALIAS("REGION_STACK", RAM);
+
+            /* OpenSBI jumps to the ELF entry point in S-mode. `riscv-rt`'s
+             * `_start` only works in M-mode, so replace it with the test
+             * driver's S-mode entry point. The first `ENTRY` command takes
+             * precedence, so this overrides the one in `link.x`. */
+            ENTRY(_s_mode_start);
+
+            _hart_stack_size = 1K;
+        "#
+        .to_owned()
+    }
+
+    fn connect(&self) -> Pin<Box<dyn Future<Output = Result<Box<dyn DebugProbe>>>>> {
+        let xlen = self.0;
+        Box::pin(async move {
+            Ok(Box::new(
+                QemuDebugProbe::new(
+                    match xlen {
+                        Xlen::_32 => "qemu-system-riscv32",
+                        Xlen::_64 => "qemu-system-riscv64",
+                    },
+                    &[
+                        "-machine",
+                        "virt",
+                        "-smp",
+                        "1",
+                        // Boot the kernel in S-mode using the bundled OpenSBI
+                        "-bios",
+                        "default",
+                        // UART0 → stdout (multiplexed)
+                        "-serial",
+                        "file:/dev/stdout",
+                        // Disable monitor
+                        "-monitor",
+                        "none",
+                    ],
+                )
+                .with_demux(),
+            ) as Box<dyn DebugProbe>)
+        })
+    }
+
+    fn supports_concurrent_runs(&self) -> bool {
+        true
+    }
 }
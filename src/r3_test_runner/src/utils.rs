@@ -1,4 +1,10 @@
-use std::{fmt, future::Future, time::Duration};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 use tokio::time::delay_for;
 
 pub struct CommaSeparatedNoSpace<T>(pub T);
@@ -114,3 +120,44 @@ where
         }
     }
 }
+
+/// Poll the given futures concurrently until all of them complete successfully
+/// or any of them fails. The remaining futures are dropped in the latter case.
+pub fn try_join_all<'a, E>(
+    futures: impl IntoIterator<Item = Pin<Box<dyn Future<Output = Result<(), E>> + 'a>>>,
+) -> impl Future<Output = Result<(), E>> + 'a
+where
+    E: 'a,
+{
+    TryJoinAll {
+        futures: futures.into_iter().map(Some).collect(),
+    }
+}
+
+struct TryJoinAll<'a, E> {
+    futures: Vec<Option<Pin<Box<dyn Future<Output = Result<(), E>> + 'a>>>>,
+}
+
+impl<E> Future for TryJoinAll<'_, E> {
+    type Output = Result<(), E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut is_pending = false;
+
+        for slot in self.futures.iter_mut() {
+            if let Some(fut) = slot {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(Ok(())) => *slot = None,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => is_pending = true,
+                }
+            }
+        }
+
+        if is_pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
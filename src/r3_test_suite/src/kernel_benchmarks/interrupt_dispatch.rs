@@ -0,0 +1,90 @@
+//! Measures the latency of dispatching a task from an interrupt handler.
+//!
+//! ```text
+//!                main        int0          task1
+//!                 │ │          ┊            │ │
+//!                 │ │          ┊      park  └┬┘
+//!                 │ │   pend   ┊             ┊
+//!                 └┬┘ ──────► ┌┴┐            ┊
+//!                  ┊          │ │  unpark    ┊     ┐
+//!                  ┊          └┬┘ ─────────► ┌┴┐   │ I_UNPARK_DISPATCHING
+//!                  ┊           ┊             │ │   ┘
+//!                 ┌┴┐ ◀─────────────────────  └┬┘
+//!                 │ │          ┊      park    ┊
+//! ```
+//!
+//! The result depends on the port's implementation of dispatching. For
+//! example, on Arm-M, it's affected by the priority assigned to PendSV.
+use r3::kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, Kernel, Task};
+
+use super::Bencher;
+use crate::utils::benchmark::Interval;
+
+use_benchmark_in_kernel_benchmark! {
+    pub unsafe struct App<System> {
+        inner: AppInner<System>,
+    }
+}
+
+struct AppInner<System> {
+    task1: Task<System>,
+    int: Option<InterruptLine<System>>,
+}
+
+const I_UNPARK_DISPATCHING: Interval = "unpark task from interrupt with dispatch";
+
+impl<System: Kernel> AppInner<System> {
+    /// Used by `use_benchmark_in_kernel_benchmark!`
+    const fn new<B: Bencher<System, Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, B>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[.., int_pri]) =
+            (B::INTERRUPT_LINES, B::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, B>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .enabled(true)
+                    .priority(int_pri)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        Self { task1, int }
+    }
+
+    /// Used by `use_benchmark_in_kernel_benchmark!`
+    fn iter<B: Bencher<System, Self>>() {
+        let int = if let Some(int) = B::app().int {
+            int
+        } else {
+            panic!("this benchmark requires at least one interrupt line");
+        };
+
+        // `isr` → `task1` → (return to) `main`
+        int.pend().unwrap();
+    }
+}
+
+fn isr<System: Kernel, B: Bencher<System, AppInner<System>>>(_: usize) {
+    B::mark_start(); // I_UNPARK_DISPATCHING
+    B::app().task1.unpark_exact().unwrap();
+}
+
+fn task1_body<System: Kernel, B: Bencher<System, AppInner<System>>>(_: usize) {
+    loop {
+        System::park().unwrap();
+        B::mark_end(I_UNPARK_DISPATCHING);
+    }
+}
@@ -0,0 +1,97 @@
+//! Checks the return value of [`r3::kernel::Kernel::current_context`] in
+//! various contexts.
+use r3::{
+    kernel::{cfg::CfgBuilder, Context, InterruptHandler, InterruptLine, StartupHook, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+
+pub struct App<System> {
+    timer: Timer<System>,
+    int: Option<InterruptLine<System>>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        StartupHook::build()
+            .start(startup_hook::<System, D>)
+            .finish(b);
+
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let timer = Timer::build()
+            .start(timer_body::<System, D>)
+            .delay(Duration::from_millis(10))
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .enabled(true)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        App { timer, int }
+    }
+}
+
+fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    assert_eq!(System::current_context(), Context::Boot);
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    assert_eq!(System::current_context(), Context::Task);
+
+    // The result doesn't depend on CPU Lock or Priority Boost
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(System::current_context(), Context::Task);
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    #[cfg(feature = "priority_boost")]
+    {
+        System::boost_priority().unwrap();
+        assert_eq!(System::current_context(), Context::Task);
+        unsafe { System::unboost_priority() }.unwrap();
+    }
+
+    D::app().timer.start().unwrap();
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    assert_eq!(System::current_context(), Context::Interrupt);
+
+    if let Some(int) = D::app().int {
+        int.pend().unwrap();
+    } else {
+        log::warn!("No interrupt lines defined, skipping a portion of the test");
+        D::success();
+    }
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    assert_eq!(System::current_context(), Context::Interrupt);
+
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(System::current_context(), Context::Interrupt);
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    D::success();
+}
@@ -50,5 +50,41 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     );
     assert!(!System::has_cpu_lock());
 
+    // Acquire CPU Lock using an RAII guard
+    let guard = System::lock_cpu().unwrap();
+    assert!(System::has_cpu_lock());
+
+    // Can't do it again because it's already acquired
+    assert_eq!(
+        System::lock_cpu().err(),
+        Some(r3::kernel::CpuLockError::BadContext),
+    );
+    assert_eq!(
+        System::acquire_cpu_lock(),
+        Err(r3::kernel::CpuLockError::BadContext),
+    );
+    assert!(System::has_cpu_lock());
+
+    // Dropping the guard releases CPU Lock
+    drop(guard);
+    assert!(!System::has_cpu_lock());
+
+    // The guard releases CPU Lock on every exit path
+    assert_eq!(critical_section::<System>(true), Err(()));
+    assert!(!System::has_cpu_lock());
+    assert_eq!(critical_section::<System>(false), Ok(42));
+    assert!(!System::has_cpu_lock());
+
     D::success();
 }
+
+fn critical_section<System: Kernel>(early_return: bool) -> Result<u32, ()> {
+    let _guard = System::lock_cpu().unwrap();
+    assert!(System::has_cpu_lock());
+
+    if early_return {
+        return Err(());
+    }
+
+    Ok(42)
+}
@@ -0,0 +1,188 @@
+//! Off-loads heavy computation from a periodic timer callback to a
+//! `DeferredWork` and checks that (1) the timer keeps firing steadily while the
+//! computation is in progress, (2) `schedule` doesn't extend the execution
+//! time of the timer callback, and (3) multiple requests made before the work
+//! starts executing are coalesced into one.
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, DeferredWork, Task, Timer},
+    prelude::*,
+    time::Duration,
+    utils::Init,
+};
+
+use super::Driver;
+use crate::utils::time::KernelTimeExt;
+
+/// The period of the timer, measured in milliseconds.
+const PERIOD_MS: u32 = 50;
+
+/// The execution time of the deferred work, measured in milliseconds. This is
+/// longer than the timer's period.
+const WORK_TIME_MS: u32 = 120;
+
+/// How long the timer runs, measured in milliseconds.
+const RUN_TIME_MS: u32 = 600;
+
+pub struct App<System> {
+    timer: Timer<System>,
+    work: DeferredWork<System>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of the calls to the timer callback.
+    num_ticks: AtomicUsize,
+    /// The number of the executions of the deferred work.
+    num_runs: AtomicUsize,
+    /// The time of the last call to the timer callback, measured in
+    /// microseconds. `u32::MAX` if there's none.
+    last_tick_us: AtomicU32,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_ticks: Init::INIT,
+        num_runs: Init::INIT,
+        last_tick_us: AtomicU32::new(u32::MAX),
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let timer = Timer::build()
+            .delay(Duration::from_millis(PERIOD_MS as _))
+            .period(Duration::from_millis(PERIOD_MS as _))
+            .start(timer_body::<System, D>)
+            .finish(b);
+
+        let work = DeferredWork::build()
+            .start(work_body::<System, D>)
+            .priority(2)
+            .finish(b);
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App { timer, work, state }
+    }
+}
+
+fn monotonic_time_us<System: Kernel>() -> u32 {
+    System::monotonic_time().as_micros() as u32
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        timer, work, state, ..
+    } = D::app();
+
+    // The deferred work doesn't run until it's scheduled
+    System::sleep_ms(PERIOD_MS);
+    assert_eq!(state.num_runs.load(Ordering::Relaxed), 0);
+
+    // Requests made before the work starts executing are coalesced. The
+    // backing task has a lower priority than this task, so it doesn't start
+    // executing until this task sleeps.
+    work.schedule().unwrap();
+    work.schedule().unwrap();
+    work.schedule().unwrap();
+    System::sleep_ms(WORK_TIME_MS * 2);
+    assert_eq!(state.num_runs.load(Ordering::Relaxed), 1);
+    state.num_runs.store(0, Ordering::Relaxed);
+
+    // Start the timer, which schedules the work in every tick
+    timer.start().unwrap();
+    System::sleep_ms(RUN_TIME_MS);
+    timer.stop().unwrap();
+
+    // Wait until the remaining work completes
+    System::sleep_ms(WORK_TIME_MS * 3);
+
+    let num_ticks = state.num_ticks.load(Ordering::Relaxed) as u32;
+    let num_runs = state.num_runs.load(Ordering::Relaxed) as u32;
+    log::debug!("num_ticks = {}, num_runs = {}", num_ticks, num_runs);
+
+    // The timer fired steadily even though the deferred work was hogging the
+    // processor
+    let expected_num_ticks = RUN_TIME_MS / PERIOD_MS;
+    assert!(
+        (expected_num_ticks - 1..=expected_num_ticks + 1).contains(&num_ticks),
+        "num_ticks = {} (expected = {} ± 1)",
+        num_ticks,
+        expected_num_ticks
+    );
+
+    // The requests made while the work was executing were coalesced
+    let max_num_runs = RUN_TIME_MS / WORK_TIME_MS + 2;
+    assert!(
+        (1..=max_num_runs).contains(&num_runs),
+        "num_runs = {} (expected = 1..={})",
+        num_runs,
+        max_num_runs
+    );
+
+    // Calling `schedule` in a task context is also allowed and can wake up
+    // the backing task
+    work.schedule().unwrap();
+    System::sleep_ms(WORK_TIME_MS * 2);
+    assert_eq!(state.num_runs.load(Ordering::Relaxed) as u32, num_runs + 1);
+
+    D::success();
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { work, state, .. } = D::app();
+
+    let now_us = monotonic_time_us::<System>();
+    log::trace!("tick at {}us", now_us);
+
+    // The interval between ticks stays close to the period
+    let last_tick_us = state.last_tick_us.load(Ordering::Relaxed);
+    if last_tick_us != u32::MAX {
+        let interval_ms = now_us.wrapping_sub(last_tick_us) / 1000;
+        assert!(
+            interval_ms < PERIOD_MS * 2,
+            "the timer was delayed by the deferred work (interval = {}ms)",
+            interval_ms
+        );
+    }
+    state.last_tick_us.store(now_us, Ordering::Relaxed);
+
+    // `schedule` returns quickly without running the work in place
+    work.schedule().unwrap();
+    work.schedule().unwrap();
+    let elapsed_us = monotonic_time_us::<System>().wrapping_sub(now_us);
+    assert!(
+        elapsed_us < WORK_TIME_MS * 1000 / 2,
+        "`schedule` took {}us",
+        elapsed_us
+    );
+
+    state.num_ticks.store(
+        state.num_ticks.load(Ordering::Relaxed) + 1,
+        Ordering::Relaxed,
+    );
+}
+
+fn work_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { state, .. } = D::app();
+
+    log::trace!("work started");
+
+    // Simulate heavy computation
+    let start_us = monotonic_time_us::<System>();
+    while monotonic_time_us::<System>().wrapping_sub(start_us) < WORK_TIME_MS * 1000 {}
+
+    state.num_runs.store(
+        state.num_runs.load(Ordering::Relaxed) + 1,
+        Ordering::Relaxed,
+    );
+}
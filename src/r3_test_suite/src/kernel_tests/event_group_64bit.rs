@@ -0,0 +1,70 @@
+//! Sets and waits for the upper 32 bits of an event group. Requires the
+//! `event_group_64bit` feature.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, EventGroup, EventGroupWaitFlags, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    eg: EventGroup<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let eg = EventGroup::build().initial(1 << 63).finish(b);
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { eg, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().seq.expect_and_replace(1, 2);
+
+    let eg = D::app().eg;
+    assert_eq!(eg.get().unwrap(), 1 << 63);
+
+    // Bits above bit 31 must not be truncated
+    assert_eq!(eg.clear(1 << 63).unwrap(), 1 << 63);
+    eg.set(1 << 32 | 1).unwrap();
+    assert_eq!(eg.get().unwrap(), 1 << 32 | 1);
+
+    eg.set(1 << 40).unwrap(); // unblocks `task2`
+
+    D::app().seq.expect_and_replace(3, 4);
+
+    assert_eq!(eg.get().unwrap(), 1);
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().seq.expect_and_replace(0, 1);
+
+    let eg = D::app().eg;
+
+    // start waiting, switching to `task1`
+    eg.wait(
+        1 << 40 | 1 << 32,
+        EventGroupWaitFlags::ALL | EventGroupWaitFlags::CLEAR,
+    )
+    .unwrap();
+
+    D::app().seq.expect_and_replace(2, 3);
+}
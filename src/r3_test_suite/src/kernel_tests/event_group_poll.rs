@@ -71,6 +71,10 @@ fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
     eg.poll(0b110, EventGroupWaitFlags::CLEAR).unwrap();
     assert_eq!(eg.get().unwrap(), 0b001);
 
+    eg.set(0b100).unwrap();
+    assert_eq!(eg.clear(0b101).unwrap(), 0b101);
+    assert_eq!(eg.get().unwrap(), 0b000);
+
     // `wait` is disallowed in a non-task context
     assert_eq!(
         eg.wait(0b1, EventGroupWaitFlags::CLEAR),
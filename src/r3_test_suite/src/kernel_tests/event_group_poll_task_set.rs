@@ -0,0 +1,98 @@
+//! Sets an event group in a task and checks the bits in an interrupt handler
+//! by `poll` and `get` without clearing them.
+use r3::{
+    hunk::Hunk,
+    kernel::{
+        cfg::CfgBuilder, EventGroup, EventGroupWaitFlags, InterruptHandler, InterruptLine,
+        PollEventGroupError, Task,
+    },
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    eg: EventGroup<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let eg = EventGroup::build().finish(b);
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .enabled(true)
+                    .priority(int_pri)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        App { int, eg, seq }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { int, eg, seq } = D::app();
+
+    let int = if let Some(int) = int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    seq.expect_and_replace(0, 1);
+    eg.set(0b101).unwrap();
+    int.pend().unwrap();
+
+    // The interrupt handler didn't clear any bits
+    seq.expect_and_replace(2, 3);
+    assert_eq!(eg.get().unwrap(), 0b101);
+
+    D::success();
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { eg, seq, .. } = D::app();
+    seq.expect_and_replace(1, 2);
+
+    assert_eq!(eg.get().unwrap(), 0b101);
+
+    // Without `CLEAR`, `poll` doesn't modify the bits
+    assert_eq!(eg.poll(0b100, EventGroupWaitFlags::empty()), Ok(0b101));
+    assert_eq!(eg.poll(0b101, EventGroupWaitFlags::ALL), Ok(0b101));
+    assert_eq!(
+        eg.poll(0b110, EventGroupWaitFlags::ALL),
+        Err(PollEventGroupError::Timeout)
+    );
+    assert_eq!(
+        eg.poll(0b010, EventGroupWaitFlags::empty()),
+        Err(PollEventGroupError::Timeout)
+    );
+
+    // The bits which are currently set among the specified ones
+    assert_eq!(eg.get().unwrap() & 0b110, 0b100);
+    assert_eq!(eg.get().unwrap(), 0b101);
+}
@@ -8,7 +8,8 @@
 //! 4. (`seq`: 3 → 4, 300ms) `task0` wakes up and sets the event bit.
 //! 5. (`seq`: 4 → 5, 300ms) `task1` wakes up and preempts `task0`, seeing that
 //!    the wait operation was successful. Another wait operation will not block
-//!    because the event bit is already set.
+//!    because the event bit is already set. A wait operation with a zero
+//!    timeout succeeds for the same reason.
 //! 6. (`seq`: 5 → 6, 300ms) `task1` exits.
 //! 7. (`seq`: 6 → 7, 300ms) `task0` starts running.
 //!
@@ -102,5 +103,15 @@ fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
         )
         .unwrap();
 
+    // A zero timeout doesn't prevent the wait operation from succeeding if
+    // the wait condition is already satisfied
+    assert_eq!(
+        D::app()
+            .eg
+            .wait_timeout(0b1, EventGroupWaitFlags::CLEAR, Duration::ZERO),
+        Ok(0b1),
+    );
+    assert_eq!(D::app().eg.get().unwrap(), 0);
+
     D::app().seq.expect_and_replace(5, 6);
 }
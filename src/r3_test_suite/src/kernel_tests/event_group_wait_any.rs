@@ -0,0 +1,82 @@
+//! Waits for bits in an event group by `EventGroup::wait_any` and
+//! `EventGroup::wait_all`.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, EventGroup, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    eg: EventGroup<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let eg = EventGroup::build().finish(b);
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { eg, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { eg, seq } = D::app();
+
+    seq.expect_and_replace(1, 2);
+    eg.set(0b0100).unwrap(); // unblocks `task2`
+
+    seq.expect_and_replace(3, 4);
+
+    // `task2` is waiting for both of `0b0011`, so it isn't woken up by only
+    // one of them
+    eg.set(0b0001).unwrap();
+    assert_eq!(eg.get().unwrap(), 0b0001);
+
+    seq.expect_and_replace(4, 5);
+    eg.set(0b1010).unwrap(); // unblocks `task2`
+
+    unreachable!();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { eg, seq } = D::app();
+
+    seq.expect_and_replace(0, 1);
+    let bits = eg.wait_any().unwrap(); // start waiting, switching to `task1`
+
+    // `wait_any` returns the first set bit
+    seq.expect_and_replace(2, 3);
+    assert_eq!(bits, 0b0100);
+
+    // `wait_any` doesn't clear bits
+    assert_eq!(eg.clear(0b0100).unwrap(), 0b0100);
+
+    let bits = eg.wait_all(0b0011).unwrap(); // start waiting, switching to `task1`
+
+    seq.expect_and_replace(5, 6);
+    assert_eq!(bits, 0b1011);
+
+    // `wait_all` doesn't clear bits either
+    assert_eq!(eg.get().unwrap(), 0b1011);
+
+    // The bits are already set, so these return immediately
+    assert_eq!(eg.wait_any().unwrap(), 0b1011);
+    assert_eq!(eg.wait_all(0b1010).unwrap(), 0b1011);
+
+    D::success();
+}
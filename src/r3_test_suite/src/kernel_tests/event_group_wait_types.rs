@@ -28,7 +28,7 @@ fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     let eg = D::app().eg;
 
     eg.set(0b100011).unwrap();
-    eg.clear(0b100000).unwrap();
+    assert_eq!(eg.clear(0b100000).unwrap(), 0b100011);
     eg.wait(0b11111, EventGroupWaitFlags::CLEAR).unwrap();
     assert_eq!(eg.get().unwrap(), 0b00);
 
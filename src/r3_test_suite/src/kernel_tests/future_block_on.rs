@@ -0,0 +1,97 @@
+//! Runs `async` blocks by `r3::future::block_on` and checks that they are
+//! resumed by `AsyncSemaphore` and `AsyncEventGroup` in the correct order.
+use assert_matches::assert_matches;
+use r3::{
+    future::{self, AsyncEventGroup, AsyncSemaphore},
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, EventGroupWaitFlags, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    sem: AsyncSemaphore<System>,
+    eg: AsyncEventGroup<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let sem = AsyncSemaphore::build().initial(0).maximum(1).finish(b);
+        let eg = AsyncEventGroup::build().initial(0).finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { sem, eg, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { sem, eg, seq } = D::app();
+
+    seq.expect_and_replace(1, 2);
+    sem.signal_one().unwrap(); // preempted by `task2`
+
+    seq.expect_and_replace(3, 4);
+    eg.set(0b01).unwrap(); // doesn't satisfy `task2`'s wait condition
+
+    seq.expect_and_replace(4, 5);
+    eg.set(0b10).unwrap(); // preempted by `task2`
+
+    seq.expect_and_replace(6, 7);
+
+    // A future that is immediately ready
+    assert_eq!(future::block_on::<System, _>(async { 42 }), Ok(42));
+
+    // `block_on` can't be called with CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_matches!(
+        future::block_on::<System, _>(async {}),
+        Err(future::BlockOnError::BadContext)
+    );
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { sem, eg, seq } = D::app();
+
+    let output = future::block_on::<System, _>(async {
+        seq.expect_and_replace(0, 1);
+
+        // Blocks until `task1` signals the semaphore
+        sem.wait().await;
+        seq.expect_and_replace(2, 3);
+        assert_eq!(sem.get(), Ok(0));
+
+        // Blocks until `task1` sets both bits
+        let bits = eg
+            .wait(0b11, EventGroupWaitFlags::ALL | EventGroupWaitFlags::CLEAR)
+            .await;
+        seq.expect_and_replace(5, 6);
+        assert_eq!(bits, 0b11);
+        assert_eq!(eg.get(), Ok(0));
+
+        // Ready immediately
+        sem.signal_one().unwrap();
+        sem.wait().await;
+
+        "done"
+    });
+
+    assert_eq!(output, Ok("done"));
+}
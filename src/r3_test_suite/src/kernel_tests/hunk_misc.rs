@@ -0,0 +1,111 @@
+//! Checks the alignment and initial values of typed hunks.
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, StartupHook, Task},
+    prelude::*,
+    utils::{Init, ZeroInit},
+};
+
+use super::Driver;
+
+#[repr(align(64))]
+struct Aligned(AtomicU32);
+
+impl Init for Aligned {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self(AtomicU32::new(0x1234_5678));
+}
+
+struct Zeroed(UnsafeCell<[u32; 4]>);
+
+// Safety: `Zeroed` is only accessed by one task
+unsafe impl Sync for Zeroed {}
+
+// Safety: `UnsafeCell<[u32; 4]>` is `ZeroInit`
+unsafe impl ZeroInit for Zeroed {}
+
+pub struct App<System> {
+    hunk_byte: Hunk<System, u8>,
+    hunk_aligned: Hunk<System, Aligned>,
+    hunk_bool: Hunk<System, AtomicBool>,
+    hunk_over_aligned: Hunk<System, u8>,
+    hunk_zeroed: Hunk<System, Zeroed>,
+    hunk_slice: Hunk<System, [u16]>,
+    hunk_init_with: Hunk<System, [u32; 4]>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+
+        StartupHook::build()
+            .start(startup_hook::<System, D>)
+            .finish(b);
+
+        // Interleave hunks with various alignments so that the allocator has
+        // to insert padding
+        let hunk_byte = Hunk::<_, u8>::build().finish(b);
+        let hunk_aligned = Hunk::<_, Aligned>::build().finish(b);
+        let hunk_bool = Hunk::<_, AtomicBool>::build().finish(b);
+        let hunk_over_aligned = Hunk::<_, u8>::build().align(32).finish(b);
+        let hunk_zeroed = Hunk::<_, Zeroed>::build().zeroed().finish(b);
+        let hunk_slice = Hunk::<_, [u16]>::build().len(3).zeroed().finish(b);
+        let hunk_init_with = Hunk::<_, [u32; 4]>::build()
+            .init_with(|| [1, 2, 3, 4])
+            .finish(b);
+
+        App {
+            hunk_byte,
+            hunk_aligned,
+            hunk_bool,
+            hunk_over_aligned,
+            hunk_zeroed,
+            hunk_slice,
+            hunk_init_with,
+        }
+    }
+}
+
+fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // `init_with` runs before startup hooks with non-negative priorities
+    assert_eq!(*D::app().hunk_init_with, [1, 2, 3, 4]);
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    // Alignment
+    assert_eq!(Hunk::as_ptr(app.hunk_aligned) as usize % 64, 0);
+    assert_eq!(Hunk::as_ptr(app.hunk_over_aligned) as usize % 32, 0);
+    assert_eq!(
+        Hunk::as_ptr(app.hunk_zeroed) as usize % core::mem::align_of::<Zeroed>(),
+        0
+    );
+    assert_eq!(
+        Hunk::as_ptr(app.hunk_slice) as *const u16 as usize % core::mem::align_of::<u16>(),
+        0
+    );
+
+    // Initial values
+    assert_eq!(*app.hunk_byte, 0);
+    assert_eq!(app.hunk_aligned.0.load(Ordering::Relaxed), 0x1234_5678);
+    assert!(!app.hunk_bool.load(Ordering::Relaxed));
+    assert_eq!(*app.hunk_over_aligned, 0);
+    assert_eq!(unsafe { *app.hunk_zeroed.0.get() }, [0; 4]);
+    assert_eq!(*app.hunk_slice, [0; 3]);
+    assert_eq!(*app.hunk_init_with, [1, 2, 3, 4]);
+
+    // Typed hunks provide shared access through `Deref`
+    app.hunk_bool.store(true, Ordering::Relaxed);
+    assert!(app.hunk_bool.load(Ordering::Relaxed));
+
+    D::success();
+}
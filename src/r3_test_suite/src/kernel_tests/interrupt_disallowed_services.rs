@@ -65,6 +65,8 @@ fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
         Err(kernel::SetInterruptLinePriorityError::BadContext),
     );
     #[cfg(feature = "priority_boost")]
+    assert!(!System::can_boost_priority());
+    #[cfg(feature = "priority_boost")]
     assert_eq!(
         System::boost_priority(),
         Err(kernel::BoostPriorityError::BadContext),
@@ -0,0 +1,91 @@
+//! Make sure interrupt handlers with the same priority are called in the
+//! registration order.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .param(1)
+                .finish(b);
+            InterruptHandler::build()
+                .line(int_line)
+                .chain(true)
+                .start(isr::<System, D>)
+                .param(2)
+                .finish(b);
+            InterruptHandler::build()
+                .line(int_line)
+                .chain(true)
+                .start(isr::<System, D>)
+                .param(4)
+                .priority(1)
+                .finish(b);
+            InterruptHandler::build()
+                .line(int_line)
+                .chain(true)
+                .start(isr::<System, D>)
+                .param(3)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .enabled(true)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { int, seq }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().seq.expect_and_replace(0, 1);
+
+    let int = if let Some(int) = D::app().int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    int.pend().unwrap();
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(i: usize) {
+    log::trace!("isr({})", i);
+    D::app().seq.expect_and_replace(i, i + 1);
+
+    if i == 4 {
+        D::success();
+    }
+}
@@ -32,54 +32,63 @@ impl<System: Kernel> App<System> {
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(1)
                 .priority(10)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(7)
                 .priority(70)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(5)
                 .priority(50)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(4)
                 .priority(40)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(10)
                 .priority(100)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(9)
                 .priority(90)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(8)
                 .priority(80)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(2)
                 .priority(20)
                 .finish(b);
             InterruptHandler::build()
                 .line(int_line)
+                .chain(true)
                 .start(isr::<System, D>)
                 .param(6)
                 .priority(60)
@@ -0,0 +1,145 @@
+//! Measures the interrupt latency by `InterruptLatencyProbe` and checks that
+//! the 99th percentile is below [`Driver::MAX_INTERRUPT_LATENCY_US`].
+use core::sync::atomic::{AtomicU32, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLatencyProbe, InterruptLine, Task},
+    prelude::*,
+    utils::Init,
+};
+
+use super::Driver;
+
+/// The number of interrupts to measure.
+const NUM_ITERATIONS: u32 = 1000;
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    probe: InterruptLatencyProbe<System>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of measured samples.
+    num_samples: AtomicU32,
+    /// The number of samples exceeding `MAX_INTERRUPT_LATENCY_US`.
+    num_slow_samples: AtomicU32,
+    /// The maximum measured latency.
+    max_latency: AtomicU32,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_samples: Init::INIT,
+        num_slow_samples: Init::INIT,
+        max_latency: Init::INIT,
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        let probe = InterruptLatencyProbe::build().finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .latency_probe(probe)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .enabled(true)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App { int, probe, state }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { int, probe, state } = D::app();
+
+    let int = if let Some(int) = int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    // The probe isn't armed yet
+    assert_eq!(probe.read_and_reset(), None);
+    assert_eq!(probe.worst_case(), 0);
+
+    // The interrupt handler isn't entered yet
+    probe.arm();
+    assert_eq!(probe.read_and_reset(), None);
+
+    for _ in 0..NUM_ITERATIONS {
+        probe.arm();
+        int.pend().unwrap(); // preempted by `isr`
+    }
+
+    let num_samples = state.num_samples.load(Ordering::Relaxed);
+    let num_slow_samples = state.num_slow_samples.load(Ordering::Relaxed);
+    let max_latency = state.max_latency.load(Ordering::Relaxed);
+    log::info!(
+        "{} samples, {} slow samples, max = {}us",
+        num_samples,
+        num_slow_samples,
+        max_latency
+    );
+    assert_eq!(num_samples, NUM_ITERATIONS);
+    assert_eq!(probe.worst_case(), max_latency);
+
+    // The probe was disarmed by `read_and_reset`
+    assert_eq!(probe.read_and_reset(), None);
+
+    // Check the 99th percentile
+    assert!(
+        num_slow_samples <= NUM_ITERATIONS / 100,
+        "{} out of {} interrupts took longer than {}us to be handled",
+        num_slow_samples,
+        NUM_ITERATIONS,
+        D::MAX_INTERRUPT_LATENCY_US,
+    );
+
+    D::success();
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { probe, state, .. } = D::app();
+
+    let latency = probe.read_and_reset().unwrap();
+
+    state.num_samples.store(
+        state.num_samples.load(Ordering::Relaxed) + 1,
+        Ordering::Relaxed,
+    );
+    if latency > D::MAX_INTERRUPT_LATENCY_US {
+        state.num_slow_samples.store(
+            state.num_slow_samples.load(Ordering::Relaxed) + 1,
+            Ordering::Relaxed,
+        );
+    }
+    if latency > state.max_latency.load(Ordering::Relaxed) {
+        state.max_latency.store(latency, Ordering::Relaxed);
+    }
+}
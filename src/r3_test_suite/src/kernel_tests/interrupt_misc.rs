@@ -57,8 +57,20 @@ fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
     );
 
     // Other methods are allowed in a boot context
+    match int.is_enabled() {
+        Ok(false) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
+        value => panic!("{:?}", value),
+    }
     int.enable().unwrap();
+    match int.is_enabled() {
+        Ok(true) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
+        value => panic!("{:?}", value),
+    }
     int.disable().unwrap();
+    match int.is_enabled() {
+        Ok(false) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
+        value => panic!("{:?}", value),
+    }
     match int.is_pending() {
         Ok(false) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
         value => panic!("{:?}", value),
@@ -97,6 +109,46 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
             unsafe { int.set_priority_unchecked(pri) }.unwrap();
         }
 
+        // `get_priority` returns the value set by `set_priority`. (Some
+        // hardware ignores the low-order bits of priority values, so only
+        // test a value that is likely to survive the round trip.)
+        int.set_priority(managed_range.start).unwrap();
+        match int.get_priority() {
+            Err(kernel::GetInterruptLinePriorityError::NotSupported) => {}
+            value => assert_eq!(value, Ok(managed_range.start)),
+        }
+
+        // `with_raised_priority` changes the priority only while the closure
+        // is running. Start with a different priority, and use the value read
+        // back (which might have lost its low-order bits) as the original one.
+        int.set_priority(managed_range.end - 1).unwrap();
+        let orig_pri = int.get_priority();
+        let mut called = false;
+        match int.with_raised_priority(managed_range.start, || {
+            called = true;
+            assert_eq!(int.get_priority(), Ok(managed_range.start));
+        }) {
+            Err(kernel::SetInterruptLinePriorityError::NotSupported) => {
+                assert!(!called);
+            }
+            value => {
+                assert_eq!(value, Ok(()));
+                assert!(called);
+                assert_eq!(int.get_priority(), orig_pri);
+
+                // The priority is restored even if the closure leaves CPU Lock
+                // active
+                int.with_raised_priority(managed_range.start, || {
+                    assert_eq!(int.get_priority(), Ok(managed_range.start));
+                    System::acquire_cpu_lock().unwrap();
+                })
+                .unwrap();
+                assert!(System::has_cpu_lock());
+                unsafe { System::release_cpu_lock() }.unwrap();
+                assert_eq!(int.get_priority(), orig_pri);
+            }
+        }
+
         // `set_priority` is disallowed when CPU Lock is active
         System::acquire_cpu_lock().unwrap();
         assert_eq!(
@@ -123,6 +175,10 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     );
 
     int.enable().unwrap();
+    match int.is_enabled() {
+        Ok(true) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
+        value => panic!("{:?}", value),
+    }
 
     // Before doing the next test, make sure `clear` is supported
     if int.clear().is_ok() {
@@ -140,6 +196,10 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
         // Pending the interrupt should succeed. The interrupt line is disabled,
         // so the interrupt handler will not actually get called.
         int.disable().unwrap();
+        match int.is_enabled() {
+            Ok(false) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
+            value => panic!("{:?}", value),
+        }
         int.pend().unwrap();
         match int.is_pending() {
             Ok(true) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
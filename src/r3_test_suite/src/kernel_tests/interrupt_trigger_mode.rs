@@ -0,0 +1,161 @@
+//! Changes the trigger mode of an interrupt line and checks that the pending
+//! flag reported after clearing it is consistent with whether the interrupt
+//! handler is called when the interrupt line is enabled.
+//!
+//! Clearing the pending flag of an edge-triggered interrupt line always
+//! succeeds. For a level-triggered interrupt line, it depends on whether the
+//! interrupt source is still asserted, which is port-specific. Either way, an
+//! interrupt line doesn't stay pending after its interrupt handler is called.
+use r3::{
+    hunk::Hunk,
+    kernel::{
+        self, cfg::CfgBuilder, InterruptHandler, InterruptLine, InterruptLineTriggerMode, Task,
+    },
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    int: Option<InterruptLine<System>>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { int, seq }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let int = if let Some(int) = D::app().int {
+        int
+    } else {
+        log::warn!("No interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    for &mode in &[
+        InterruptLineTriggerMode::Edge,
+        InterruptLineTriggerMode::Level,
+        InterruptLineTriggerMode::Edge,
+    ] {
+        match int.set_trigger_mode(mode) {
+            Ok(()) => {}
+            Err(kernel::SetInterruptLineTriggerModeError::NotSupported)
+            | Err(kernel::SetInterruptLineTriggerModeError::BadParam) => {
+                log::warn!("{:?} is not supported, skipping", mode);
+                continue;
+            }
+        }
+
+        log::debug!("checking {:?}", mode);
+        check_pending_flag::<System, D>(int, mode);
+    }
+
+    D::success();
+}
+
+fn check_pending_flag<System: Kernel, D: Driver<App<System>>>(
+    int: InterruptLine<System>,
+    mode: InterruptLineTriggerMode,
+) {
+    let seq = &D::app().seq;
+
+    int.disable().unwrap();
+
+    match int.pend() {
+        Ok(()) => {}
+        Err(kernel::PendInterruptLineError::NotSupported)
+        | Err(kernel::PendInterruptLineError::BadObjectState) => {
+            log::warn!("Can't pend the interrupt line, skipping");
+            return;
+        }
+        Err(e) => panic!("{:?}", e),
+    }
+
+    match int.clear() {
+        Ok(()) => {}
+        Err(kernel::ClearInterruptLineError::NotSupported)
+        | Err(kernel::ClearInterruptLineError::BadObjectState) => {
+            log::warn!("Can't clear the interrupt line, skipping");
+            return;
+        }
+        Err(e) => panic!("{:?}", e),
+    }
+
+    let pending = match int.is_pending() {
+        Ok(pending) => pending,
+        Err(kernel::QueryInterruptLineError::NotSupported) => {
+            log::warn!("Can't read the pending flag, skipping");
+            return;
+        }
+        Err(e) => panic!("{:?}", e),
+    };
+    log::debug!("pending = {:?}", pending);
+
+    // An edge-triggered interrupt line stays cleared
+    if mode == InterruptLineTriggerMode::Edge {
+        assert!(!pending);
+    }
+
+    // A level-triggered interrupt line that is still pending has its interrupt
+    // source asserted, so it re-pends every time it's cleared
+    if mode == InterruptLineTriggerMode::Level && pending {
+        int.clear().unwrap();
+        assert_eq!(int.is_pending(), Ok(true));
+    }
+
+    // The interrupt handler is called iff the interrupt line is still pending
+    if pending {
+        seq.expect_and_replace(0, 1);
+        int.enable().unwrap();
+        seq.expect_and_replace(2, 0);
+    } else {
+        int.enable().unwrap();
+    }
+
+    // A taken edge-triggered interrupt doesn't stay pended
+    if mode == InterruptLineTriggerMode::Edge {
+        seq.expect_and_replace(0, 1);
+        int.pend().unwrap();
+        seq.expect_and_replace(2, 0);
+    }
+
+    match int.is_pending() {
+        Ok(false) | Err(kernel::QueryInterruptLineError::NotSupported) => {}
+        value => panic!("{:?}", value),
+    }
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().seq.expect_and_replace(1, 2);
+}
@@ -0,0 +1,132 @@
+//! Measures the dispatch latency of a task by `LatencyProbe` and checks that
+//! the 99th percentile is below [`Driver::MAX_CONTEXT_SWITCH_LATENCY_US`].
+//!
+//! In each iteration, `task1` arms the probe and unparks `task2`, which has a
+//! higher priority and reads the probe upon being woken up.
+use core::sync::atomic::{AtomicU32, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, LatencyProbe, Task},
+    prelude::*,
+    utils::Init,
+};
+
+use super::Driver;
+
+/// The number of context switches to measure.
+const NUM_ITERATIONS: u32 = 10_000;
+
+pub struct App<System> {
+    task2: Task<System>,
+    probe: LatencyProbe<System>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of measured samples.
+    num_samples: AtomicU32,
+    /// The number of samples exceeding `MAX_CONTEXT_SWITCH_LATENCY_US`.
+    num_slow_samples: AtomicU32,
+    /// The maximum measured latency.
+    max_latency: AtomicU32,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_samples: Init::INIT,
+        num_slow_samples: Init::INIT,
+        max_latency: Init::INIT,
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let probe = LatencyProbe::build().finish(b);
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App {
+            task2,
+            probe,
+            state,
+        }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task2,
+        probe,
+        state,
+    } = D::app();
+
+    // The probe isn't armed yet
+    assert_eq!(probe.read_and_reset(), None);
+    assert_eq!(probe.worst_case(), 0);
+
+    for _ in 0..NUM_ITERATIONS {
+        probe.arm();
+        task2.unpark_exact().unwrap(); // preempted by `task2`
+    }
+
+    let num_samples = state.num_samples.load(Ordering::Relaxed);
+    let num_slow_samples = state.num_slow_samples.load(Ordering::Relaxed);
+    let max_latency = state.max_latency.load(Ordering::Relaxed);
+    log::info!(
+        "{} samples, {} slow samples, max = {}us",
+        num_samples,
+        num_slow_samples,
+        max_latency
+    );
+    assert_eq!(num_samples, NUM_ITERATIONS);
+    assert_eq!(probe.worst_case(), max_latency);
+
+    // The probe was disarmed by `read_and_reset`
+    assert_eq!(probe.read_and_reset(), None);
+
+    // Check the 99th percentile
+    assert!(
+        num_slow_samples <= NUM_ITERATIONS / 100,
+        "{} out of {} context switches took longer than {}us",
+        num_slow_samples,
+        NUM_ITERATIONS,
+        D::MAX_CONTEXT_SWITCH_LATENCY_US,
+    );
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { probe, state, .. } = D::app();
+
+    loop {
+        System::park().unwrap();
+
+        let latency = probe.read_and_reset().unwrap();
+
+        state.num_samples.store(
+            state.num_samples.load(Ordering::Relaxed) + 1,
+            Ordering::Relaxed,
+        );
+        if latency > D::MAX_CONTEXT_SWITCH_LATENCY_US {
+            state.num_slow_samples.store(
+                state.num_slow_samples.load(Ordering::Relaxed) + 1,
+                Ordering::Relaxed,
+            );
+        }
+        if latency > state.max_latency.load(Ordering::Relaxed) {
+            state.max_latency.store(latency, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Checks miscellaneous properties of [`r3::kernel::Notification`].
+use r3::{
+    hunk::Hunk,
+    kernel::{
+        cfg::CfgBuilder, Notification, NotifyNotificationError, PollNotificationError, Task,
+        WaitNotificationTimeoutError,
+    },
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task2: Task<System>,
+    notification: Notification<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(false)
+            .finish(b);
+
+        let notification = Notification::build().finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App {
+            task2,
+            notification,
+            seq,
+        }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task2,
+        notification,
+        seq,
+    } = D::app();
+
+    seq.expect_and_replace(0, 1);
+
+    // The notification is initially not pending
+    assert_eq!(notification.poll(), Err(PollNotificationError::Timeout));
+    assert_eq!(
+        notification.wait_timeout(Duration::from_millis(10)),
+        Err(WaitNotificationTimeoutError::Timeout)
+    );
+
+    // A single notification
+    notification.notify().unwrap();
+    assert_eq!(notification.poll(), Ok(false));
+    assert_eq!(notification.poll(), Err(PollNotificationError::Timeout));
+
+    // Multiple notifications coalesce, and the overflow is reported
+    notification.notify().unwrap();
+    notification.notify().unwrap();
+    notification.notify().unwrap();
+    assert_eq!(notification.wait(), Ok(true));
+    assert_eq!(notification.poll(), Err(PollNotificationError::Timeout));
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(
+        notification.notify(),
+        Err(NotifyNotificationError::BadContext)
+    );
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    task2.activate().unwrap(); // giving the control to `task2`
+
+    // `task2` is waiting on the notification
+    seq.expect_and_replace(2, 3);
+    notification.notify().unwrap(); // wakes up `task2`
+
+    // `task2` consumed the notification
+    seq.expect_and_replace(4, 5);
+    assert_eq!(notification.poll(), Err(PollNotificationError::Timeout));
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        notification, seq, ..
+    } = D::app();
+
+    seq.expect_and_replace(1, 2);
+    assert_eq!(notification.wait(), Ok(false));
+    seq.expect_and_replace(3, 4);
+}
@@ -28,12 +28,14 @@ impl<System: Kernel> App<System> {
 #[cfg(feature = "priority_boost")]
 fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     assert!(!System::is_priority_boost_active());
+    assert!(System::can_boost_priority());
 
     // Activate Priority Boost
     System::boost_priority().unwrap();
 
     // Can't do it again because it's already acquired
     assert!(System::is_priority_boost_active());
+    assert!(!System::can_boost_priority());
     assert_eq!(
         System::boost_priority(),
         Err(r3::kernel::BoostPriorityError::BadContext),
@@ -67,6 +69,7 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
 
     // Acquire CPU Lock, and see that Priority Boost doesn't activate in it
     System::acquire_cpu_lock().unwrap();
+    assert!(!System::can_boost_priority());
     assert_eq!(
         System::boost_priority(),
         Err(r3::kernel::BoostPriorityError::BadContext),
@@ -74,6 +77,7 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     unsafe { System::release_cpu_lock() }.unwrap();
 
     assert!(!System::is_priority_boost_active());
+    assert!(System::can_boost_priority());
 
     D::success();
 }
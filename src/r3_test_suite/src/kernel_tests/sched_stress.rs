@@ -0,0 +1,176 @@
+//! Launches multiple tasks, each of which performs a random sequence of
+//! scheduling operations (sleeping, waiting for a semaphore, signaling a
+//! semaphore, yielding, and changing its own priority). The sequence is
+//! generated by a seeded PRNG, so a failure can be reproduced by re-running the
+//! test with the same seed.
+//!
+//! The following properties are checked throughout the test:
+//!
+//!  - `Task::current` returns the calling task.
+//!  - A task's base priority and effective priority reflect the last value
+//!    set by the task.
+//!  - A blocking operation with timeout returns within a bounded time.
+//!  - All tasks run to completion (i.e., no tasks are stuck).
+//!
+use r3::{
+    kernel::{
+        cfg::CfgBuilder, EventGroup, EventGroupWaitFlags, Semaphore, SignalSemaphoreError, Task,
+        WaitSemaphoreTimeoutError,
+    },
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+
+/// The number of worker tasks.
+const NUM_TASKS: usize = 5;
+
+/// The number of operations performed by each worker task.
+const NUM_ITERATIONS: usize = 100;
+
+/// The seed for the PRNG. Each worker task derives its own seed from this
+/// value.
+const SEED: u32 = 0x2f6b_d1a3;
+
+/// The number of task priority levels that worker tasks can choose from.
+const NUM_WORKER_PRIORITIES: u32 = 3;
+
+pub struct App<System> {
+    sem: Semaphore<System>,
+    done: EventGroup<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let mut i = 0;
+        // FIXME: Work-around for `for` being unsupported in `const fn`
+        while i < NUM_TASKS {
+            Task::build()
+                .start(task_body::<System, D>)
+                .param(i)
+                .priority(i % NUM_WORKER_PRIORITIES as usize)
+                .active(true)
+                .finish(b);
+            i += 1;
+        }
+
+        Task::build()
+            .start(completion_task_body::<System, D>)
+            .priority(NUM_WORKER_PRIORITIES as usize)
+            .active(true)
+            .finish(b);
+
+        let sem = Semaphore::build().initial(0).maximum(NUM_TASKS).finish(b);
+        let done = EventGroup::build().finish(b);
+
+        App { sem, done }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(i: usize) {
+    let App { sem, done } = D::app();
+    let mut rng = Xorshift32(SEED ^ (i as u32).wrapping_mul(0x9e37_79b9) | 1);
+    let this_task = Task::<System>::current().unwrap().unwrap();
+    let mut priority = this_task.priority().unwrap();
+
+    for k in 0..NUM_ITERATIONS {
+        let op = rng.next() % 5;
+        log::trace!("[{}] #{}: op = {}", i, k, op);
+
+        match op {
+            0 => {
+                // Sleep for a short period of time
+                let delay = Duration::from_micros((rng.next() % 3000) as _);
+                #[cfg(feature = "system_time")]
+                let start = System::time().unwrap();
+
+                System::sleep(delay).unwrap();
+
+                #[cfg(feature = "system_time")]
+                check_elapsed::<System>(start, delay);
+            }
+            1 => {
+                // Wait for the semaphore with timeout
+                let delay = Duration::from_micros((rng.next() % 3000) as _);
+                #[cfg(feature = "system_time")]
+                let start = System::time().unwrap();
+
+                match sem.wait_one_timeout(delay) {
+                    Ok(()) | Err(WaitSemaphoreTimeoutError::Timeout) => {}
+                    Err(e) => panic!("unexpected error: {:?}", e),
+                }
+
+                #[cfg(feature = "system_time")]
+                check_elapsed::<System>(start, delay);
+            }
+            2 => {
+                // Signal the semaphore, possibly waking up another task
+                match sem.signal_one() {
+                    Ok(()) | Err(SignalSemaphoreError::QueueOverflow) => {}
+                    Err(e) => panic!("unexpected error: {:?}", e),
+                }
+            }
+            3 => {
+                // Yield the processor to other tasks of the same priority by
+                // re-entering the ready queue
+                this_task
+                    .set_priority(NUM_WORKER_PRIORITIES as usize)
+                    .unwrap();
+                this_task.set_priority(priority).unwrap();
+            }
+            4 => {
+                // Change the priority
+                priority = (rng.next() % NUM_WORKER_PRIORITIES) as usize;
+                this_task.set_priority(priority).unwrap();
+            }
+            _ => unreachable!(),
+        }
+
+        assert_eq!(Task::current().unwrap(), Some(this_task));
+        assert_eq!(this_task.priority().unwrap(), priority);
+        assert_eq!(this_task.effective_priority().unwrap(), priority);
+    }
+
+    done.set(1 << i).unwrap();
+}
+
+/// Check that a blocking operation with timeout `delay` that started at
+/// `start` has returned within a bounded time.
+#[cfg(feature = "system_time")]
+fn check_elapsed<System: Kernel>(start: r3::time::Time, delay: Duration) {
+    let elapsed = System::time().unwrap().duration_since(start).unwrap();
+    assert!(!elapsed.is_negative());
+    assert!(
+        elapsed.as_micros() < delay.as_micros() + 100_000,
+        "elapsed = {:?}, delay = {:?}",
+        elapsed,
+        delay
+    );
+}
+
+fn completion_task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // Wait until all tasks run to completion. Time out if some of the tasks
+    // got stuck.
+    D::app()
+        .done
+        .wait_timeout(
+            (1 << NUM_TASKS) - 1,
+            EventGroupWaitFlags::ALL,
+            Duration::from_secs(10),
+        )
+        .unwrap();
+
+    D::success();
+}
+
+struct Xorshift32(u32);
+
+impl Xorshift32 {
+    fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 17;
+        self.0 ^= self.0 << 5;
+        self.0
+    }
+}
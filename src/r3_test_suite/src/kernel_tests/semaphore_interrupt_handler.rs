@@ -91,4 +91,10 @@ fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
     );
 
     sem.signal(1).unwrap(); // wakes up `task2`
+
+    // Nobody is waiting, so the permit is deposited to the semaphore
+    sem.signal(1).unwrap();
+    assert_eq!(sem.get().unwrap(), 1);
+    sem.poll_one().unwrap();
+    assert_eq!(sem.get().unwrap(), 0);
 }
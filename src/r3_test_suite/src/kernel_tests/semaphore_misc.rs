@@ -48,6 +48,10 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     // Invalid semaphore ID
     let bad_eg: Semaphore<System> = unsafe { Semaphore::from_id(NonZeroUsize::new(42).unwrap()) };
     assert_eq!(bad_eg.get(), Err(r3::kernel::GetSemaphoreError::BadId));
+    assert_eq!(
+        bad_eg.wait_n(1),
+        Err(r3::kernel::WaitSemaphoreNError::BadId)
+    );
 
     // CPU Lock active
     System::acquire_cpu_lock().unwrap();
@@ -67,6 +71,10 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
         app.eg1.wait_one(),
         Err(r3::kernel::WaitSemaphoreError::BadContext)
     );
+    assert_eq!(
+        app.eg1.wait_n(1),
+        Err(r3::kernel::WaitSemaphoreNError::BadContext)
+    );
     assert_eq!(
         app.eg1.poll_one(),
         Err(r3::kernel::PollSemaphoreError::BadContext)
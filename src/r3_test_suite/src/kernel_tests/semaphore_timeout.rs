@@ -77,6 +77,12 @@ fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
 
     seq.expect_and_replace(2, 3);
 
+    // The timed-out wait must not leave `task1` in the wait queue. If it did,
+    // this permit would be given to `task1`'s stale wait object.
+    eg.signal(1).unwrap();
+    assert_eq!(eg.get().unwrap(), 1);
+    eg.poll_one().unwrap();
+
     // start waiting. wakes up when `task0` signals the semaphore
     eg.wait_one_timeout(Duration::from_millis(200)).unwrap();
 
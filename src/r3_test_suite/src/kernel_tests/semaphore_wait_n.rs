@@ -0,0 +1,123 @@
+//! Checks that `Semaphore::wait_n` doesn't complete until all requested
+//! permits are available, and that a task waiting for many permits blocks the
+//! tasks behind it in the wait queue.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Semaphore, Task, WaitSemaphoreNError},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    sem: Semaphore<System>,
+    task1: Task<System>,
+    task3: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(3)
+            .active(true)
+            .finish(b);
+        let task3 = Task::build()
+            .start(task3_body::<System, D>)
+            .priority(2)
+            .finish(b);
+
+        let sem = Semaphore::build().initial(0).maximum(4).finish(b);
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App {
+            sem,
+            task1,
+            task3,
+            seq,
+        }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { sem, seq, .. } = D::app();
+
+    seq.expect_and_replace(0, 1);
+
+    // Zero permits can be acquired at any time
+    sem.wait_n(0).unwrap();
+
+    // The number of permits exceeds the maximum value
+    assert_eq!(sem.wait_n(5), Err(WaitSemaphoreNError::BadParam));
+
+    sem.wait_n(3).unwrap(); // start waiting, switching to `task2`
+
+    // All three permits were given to this task at once
+    seq.expect_and_replace(3, 4);
+    assert_eq!(sem.get().unwrap(), 0);
+
+    // Start waiting again, switching to `task2`
+    assert_eq!(sem.wait_n(3), Err(WaitSemaphoreNError::Interrupted));
+
+    // `task2` interrupted this task. None of the two permits signaled while
+    // this task was waiting was given to this task, and one of them was given
+    // to `task3`.
+    seq.expect_and_replace(8, 9);
+    assert_eq!(sem.get().unwrap(), 1);
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        sem,
+        task1,
+        task3,
+        seq,
+    } = D::app();
+
+    seq.expect_and_replace(1, 2);
+
+    // `task1` is waiting for three permits, so it isn't woken up by the first
+    // two permits
+    sem.signal_one().unwrap();
+    assert_eq!(sem.get().unwrap(), 1);
+    sem.signal_one().unwrap();
+    assert_eq!(sem.get().unwrap(), 2);
+
+    seq.expect_and_replace(2, 3);
+    sem.signal_one().unwrap(); // wake up `task1`
+
+    // `task1` is waiting for three permits again
+    seq.expect_and_replace(4, 5);
+    task3.activate().unwrap(); // start `task3`
+
+    // `task3` is waiting for one permit behind `task1`
+    seq.expect_and_replace(6, 7);
+
+    // `task3` can't take a permit because `task1` is blocking it
+    sem.signal(2).unwrap();
+    assert_eq!(sem.get().unwrap(), 2);
+
+    // Interrupting `task1` unblocks `task3`, which takes one permit
+    seq.expect_and_replace(7, 8);
+    task1.interrupt().unwrap();
+    assert_eq!(sem.get().unwrap(), 1);
+
+    seq.expect_and_replace(10, 11);
+    D::success();
+}
+
+fn task3_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { sem, seq, .. } = D::app();
+
+    seq.expect_and_replace(5, 6);
+    sem.wait_one().unwrap(); // start waiting, switching to `task2`
+
+    seq.expect_and_replace(9, 10);
+}
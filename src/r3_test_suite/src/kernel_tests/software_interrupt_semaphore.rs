@@ -0,0 +1,171 @@
+//! Implements a counting semaphore by a software interrupt and uses it from
+//! two tasks. Tasks submit requests by updating counters and pending the
+//! software interrupt, and the software interrupt handler, which can't be
+//! preempted by the tasks, processes the requests and wakes up the waiting
+//! task.
+use core::sync::atomic::{AtomicUsize, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, SoftwareInterrupt, Task},
+    prelude::*,
+    utils::Init,
+};
+
+use super::Driver;
+
+/// The number of times the semaphore is signaled and waited for.
+const COUNT: usize = 8;
+
+pub struct App<System> {
+    task2: Task<System>,
+    swi: Option<SoftwareInterrupt<System>>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of signal requests. Written by `task1`.
+    signal_requests: AtomicUsize,
+    /// The number of wait requests. Written by `task2`.
+    wait_requests: AtomicUsize,
+    /// The number of processed signal requests. Written by `swi_handler`.
+    signals_processed: AtomicUsize,
+    /// The number of granted wait requests. Written by `swi_handler`.
+    waits_granted: AtomicUsize,
+    /// The semaphore value. Written by `swi_handler`.
+    value: AtomicUsize,
+    /// The number of the calls to `swi_handler`.
+    num_calls: AtomicUsize,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        signal_requests: Init::INIT,
+        wait_requests: Init::INIT,
+        signals_processed: Init::INIT,
+        waits_granted: Init::INIT,
+        value: Init::INIT,
+        num_calls: Init::INIT,
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let swi = if let (&[_, ..], &[int_pri, ..]) =
+            (System::SOFTWARE_INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            Some(
+                SoftwareInterrupt::build()
+                    .start(swi_handler::<System, D>)
+                    .priority(int_pri)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App { task2, swi, state }
+    }
+}
+
+/// Signals the semaphore.
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { swi, state, .. } = D::app();
+    let swi = if let Some(swi) = swi {
+        swi
+    } else {
+        return;
+    };
+
+    for i in 1..=COUNT {
+        state.signal_requests.store(i, Ordering::Relaxed);
+        swi.pend().unwrap();
+
+        // The software interrupt handler preempts this task
+        assert_eq!(state.signals_processed.load(Ordering::Relaxed), i);
+    }
+}
+
+/// Waits for the semaphore.
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { swi, state, .. } = D::app();
+    let swi = if let Some(swi) = swi {
+        swi
+    } else {
+        log::warn!("No software interrupt lines defined, skipping the test");
+        D::success();
+        return;
+    };
+
+    // A cleared software interrupt doesn't execute
+    System::acquire_cpu_lock().unwrap();
+    swi.pend().unwrap();
+    swi.clear().unwrap();
+    unsafe { System::release_cpu_lock() }.unwrap();
+    assert_eq!(state.num_calls.load(Ordering::Relaxed), 0);
+
+    for i in 1..=COUNT {
+        state.wait_requests.store(i, Ordering::Relaxed);
+        swi.pend().unwrap();
+
+        // Wait until the request is granted. This task has a higher priority
+        // than `task1`, so the semaphore is always empty at this point.
+        while state.waits_granted.load(Ordering::Relaxed) < i {
+            System::park().unwrap();
+        }
+
+        log::trace!("acquired the semaphore ({}/{})", i, COUNT);
+        assert_eq!(state.value.load(Ordering::Relaxed), 0);
+    }
+
+    assert_eq!(state.signals_processed.load(Ordering::Relaxed), COUNT);
+    assert_eq!(state.waits_granted.load(Ordering::Relaxed), COUNT);
+
+    D::success();
+}
+
+fn swi_handler<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { task2, state, .. } = D::app();
+
+    state.num_calls.store(
+        state.num_calls.load(Ordering::Relaxed) + 1,
+        Ordering::Relaxed,
+    );
+
+    // Process signal requests
+    let mut value = state.value.load(Ordering::Relaxed);
+    let signal_requests = state.signal_requests.load(Ordering::Relaxed);
+    value += signal_requests - state.signals_processed.load(Ordering::Relaxed);
+    state
+        .signals_processed
+        .store(signal_requests, Ordering::Relaxed);
+
+    // Process wait requests
+    let wait_requests = state.wait_requests.load(Ordering::Relaxed);
+    let old_waits_granted = state.waits_granted.load(Ordering::Relaxed);
+    let mut waits_granted = old_waits_granted;
+    while waits_granted < wait_requests && value > 0 {
+        value -= 1;
+        waits_granted += 1;
+    }
+    state.waits_granted.store(waits_granted, Ordering::Relaxed);
+    state.value.store(value, Ordering::Relaxed);
+
+    // Wake up the waiting task
+    if waits_granted != old_waits_granted {
+        task2.unpark().unwrap();
+    }
+}
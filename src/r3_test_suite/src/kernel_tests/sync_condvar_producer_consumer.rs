@@ -0,0 +1,125 @@
+//! Passes values from a producer task to a consumer task through a one-slot
+//! buffer protected by [`r3::sync::Mutex`], using [`r3::sync::Condvar`] to
+//! wait for the buffer to become empty or full.
+use assert_matches::assert_matches;
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    sync::{
+        condvar::{self, Condvar},
+        Mutex,
+    },
+};
+
+use super::Driver;
+
+/// The number of values to pass.
+const COUNT: u32 = 64;
+
+pub struct App<System> {
+    task2: Task<System>,
+    slot: Mutex<System, Option<u32>>,
+    not_empty: Condvar<System>,
+    not_full: Condvar<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(producer_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(consumer_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let slot = Mutex::build().finish(b);
+        let not_empty = Condvar::build().finish(b);
+        let not_full = Condvar::build().finish(b);
+
+        App {
+            task2,
+            slot,
+            not_empty,
+            not_full,
+        }
+    }
+}
+
+fn producer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    // Notifying a condition variable with no waiters is a no-op
+    app.not_full.notify_one().unwrap();
+    app.not_full.notify_all().unwrap();
+
+    // `notify_*` can't be called with CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_matches!(
+        app.not_empty.notify_one(),
+        Err(condvar::NotifyError::BadContext)
+    );
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    for i in 0..COUNT {
+        let mut slot = app.slot.lock().unwrap();
+        while slot.is_some() {
+            slot = app.not_full.wait(slot).unwrap();
+        }
+        *slot = Some(i);
+        drop(slot);
+        app.not_empty.notify_one().unwrap();
+    }
+
+    // Wait until the consumer takes the last value
+    let mut slot = app.slot.lock().unwrap();
+    while slot.is_some() {
+        slot = app.not_full.wait(slot).unwrap();
+    }
+    drop(slot);
+
+    // The consumer is now waiting on `not_empty`. Interrupting it makes `wait`
+    // return `Interrupted` with the mutex re-acquired.
+    app.task2.interrupt().unwrap();
+}
+
+fn consumer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    // `wait` can't be called with CPU Lock active. The mutex is returned
+    // without being released.
+    let slot = app.slot.lock().unwrap();
+    System::acquire_cpu_lock().unwrap();
+    let slot = match app.not_empty.wait(slot) {
+        Err(condvar::WaitError::BadContext(slot)) => slot,
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    };
+    unsafe { System::release_cpu_lock() }.unwrap();
+    drop(slot);
+
+    for i in 0..COUNT {
+        let mut slot = app.slot.lock().unwrap();
+        let value = loop {
+            if let Some(value) = slot.take() {
+                break value;
+            }
+            slot = app.not_empty.wait(slot).unwrap();
+        };
+        drop(slot);
+        app.not_full.notify_one().unwrap();
+
+        log::trace!("received {}", value);
+        assert_eq!(value, i);
+    }
+
+    let slot = app.slot.lock().unwrap();
+    match app.not_empty.wait(slot) {
+        Err(condvar::WaitError::Interrupted(slot)) => assert_eq!(*slot, None),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    D::success();
+}
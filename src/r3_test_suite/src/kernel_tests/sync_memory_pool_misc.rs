@@ -0,0 +1,114 @@
+//! Checks miscellaneous properties of [`r3::sync::MemoryPool`].
+use assert_matches::assert_matches;
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    sync::memory_pool::{self, MemoryPool},
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task1: Task<System>,
+    pool: MemoryPool<System, u32, 2>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(false)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        let pool = MemoryPool::build().finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { task1, pool, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(1, 2);
+
+    // The pool is exhausted
+    assert_matches!(
+        app.pool.try_allocate(),
+        Err(memory_pool::TryAllocError::WouldBlock)
+    );
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_matches!(
+        app.pool.try_allocate(),
+        Err(memory_pool::TryAllocError::BadContext)
+    );
+    assert_matches!(
+        app.pool.allocate(),
+        Err(memory_pool::AllocError::BadContext)
+    );
+    assert_matches!(
+        app.pool.allocate_timeout(Duration::from_millis(10)),
+        Err(memory_pool::AllocTimeoutError::BadContext)
+    );
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    // Block until `task2` releases a block
+    let mut block = app.pool.allocate().unwrap();
+    app.seq.expect_and_replace(3, 4);
+
+    // This block was recycled
+    assert_eq!(*block, 1);
+    *block = 3;
+    drop(block);
+
+    // The block we just released is the only free block
+    let block = app.pool.try_allocate().unwrap();
+    assert_eq!(*block, 3);
+    assert_matches!(
+        app.pool.try_allocate(),
+        Err(memory_pool::TryAllocError::WouldBlock)
+    );
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(0, 1);
+
+    // Blocks are initialized with `Init::INIT`
+    let mut block1 = app.pool.try_allocate().unwrap();
+    let mut block2 = app.pool.allocate().unwrap();
+    assert_eq!(*block1, 0);
+    assert_eq!(*block2, 0);
+    *block1 = 1;
+    *block2 = 2;
+
+    app.task1.activate().unwrap(); // giving the control to `task1`
+
+    // `task1` is waiting for a free block
+    app.seq.expect_and_replace(2, 3);
+    drop(block1); // wakes up `task1`
+
+    // `task1` has returned the block
+    app.seq.expect_and_replace(4, 5);
+    assert_eq!(*block2, 2);
+    drop(block2);
+
+    let block = app.pool.try_allocate().unwrap();
+    assert_eq!(*block, 3);
+
+    D::success();
+}
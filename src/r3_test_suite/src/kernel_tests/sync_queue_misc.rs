@@ -0,0 +1,107 @@
+//! Checks miscellaneous properties of [`r3::sync::Queue`].
+use assert_matches::assert_matches;
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    sync::queue::{self, Queue},
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task2: Task<System>,
+    queue: Queue<System, u32>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(2)
+            .active(false)
+            .finish(b);
+
+        let queue = Queue::build().capacity(2).finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { task2, queue, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(0, 1);
+
+    // The queue is empty
+    assert_matches!(app.queue.try_recv(), Err(queue::TryRecvError::Empty));
+    assert_matches!(
+        app.queue.recv_timeout(Duration::from_millis(10)),
+        Err(queue::RecvTimeoutError::Timeout)
+    );
+
+    // Fill the queue
+    app.queue.try_send(1).unwrap();
+    app.queue.send(2).unwrap();
+
+    // The queue is full
+    assert_matches!(app.queue.try_send(3), Err(queue::TrySendError::Full(3)));
+    assert_matches!(
+        app.queue.send_timeout(3, Duration::from_millis(10)),
+        Err(queue::SendTimeoutError::Timeout(3))
+    );
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_matches!(
+        app.queue.try_send(3),
+        Err(queue::TrySendError::BadContext(3))
+    );
+    assert_matches!(app.queue.try_recv(), Err(queue::TryRecvError::BadContext));
+    assert_matches!(app.queue.recv(), Err(queue::RecvError::BadContext));
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    // Values are received in the order they were sent
+    assert_eq!(app.queue.try_recv(), Ok(1));
+    assert_eq!(app.queue.recv_timeout(Duration::from_millis(10)), Ok(2));
+
+    app.task2.activate().unwrap();
+
+    // Block until `task2` sends a value
+    app.seq.expect_and_replace(1, 2);
+    assert_eq!(app.queue.recv(), Ok(10));
+    app.seq.expect_and_replace(3, 4);
+
+    // Fill the queue and block until `task2` receives a value
+    app.queue.send(20).unwrap();
+    app.queue.send(21).unwrap();
+    app.queue.send(22).unwrap();
+    app.seq.expect_and_replace(5, 6);
+
+    assert_eq!(app.queue.recv(), Ok(21));
+    assert_eq!(app.queue.recv(), Ok(22));
+    app.seq.expect_and_replace(6, 7);
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(2, 3);
+    app.queue.send(10).unwrap(); // wakes up `task1`
+
+    app.seq.expect_and_replace(4, 5);
+    assert_eq!(app.queue.recv(), Ok(20)); // wakes up `task1`
+
+    app.seq.expect_and_replace(7, 8);
+    D::success();
+}
@@ -0,0 +1,206 @@
+//! Runs several reader tasks and one writer task on [`r3::sync::RwLock`] and
+//! checks that readers can hold the lock concurrently but never at the same
+//! time as the writer.
+use assert_matches::assert_matches;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    sync::rwlock::{self, RwLock},
+    utils::Init,
+};
+
+use super::Driver;
+use crate::utils::time::KernelTimeExt;
+
+/// The number of reader tasks.
+const NUM_READERS: usize = 3;
+
+/// The number of times each task acquires the lock.
+const NUM_ITERATIONS: usize = 5;
+
+/// How long each task holds the lock, measured in milliseconds.
+const HOLD_TIME_MS: u32 = 20;
+
+pub struct App<System> {
+    readers: [Task<System>; NUM_READERS],
+    writer: Task<System>,
+    /// The writer increments both elements while holding the lock, sleeping
+    /// in between.
+    lock: RwLock<System, [usize; 2]>,
+    state: Hunk<System, State>,
+}
+
+struct State {
+    /// The number of readers currently holding the lock.
+    num_active_readers: AtomicUsize,
+    /// The maximum value of `num_active_readers`.
+    max_active_readers: AtomicUsize,
+    /// The number of tasks that completed all iterations.
+    num_done: AtomicUsize,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        num_active_readers: Init::INIT,
+        max_active_readers: Init::INIT,
+        num_done: Init::INIT,
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(main_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let mut readers = [None; NUM_READERS];
+
+        // FIXME: Work-around for `for` being unsupported in `const fn`
+        let mut i = 0;
+        while i < NUM_READERS {
+            readers[i] = Some(
+                Task::build()
+                    .start(reader_body::<System, D>)
+                    .priority(2)
+                    .finish(b),
+            );
+            i += 1;
+        }
+
+        // FIXME: Rewrite this with `<[_; 3]>::map` when it's compatible with `const fn`
+        let readers = [
+            readers[0].unwrap(),
+            readers[1].unwrap(),
+            readers[2].unwrap(),
+        ];
+
+        let writer = Task::build()
+            .start(writer_body::<System, D>)
+            .priority(2)
+            .finish(b);
+
+        let lock = RwLock::build().finish(b);
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App {
+            readers,
+            writer,
+            lock,
+            state,
+        }
+    }
+}
+
+fn main_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        readers,
+        writer,
+        lock,
+        state,
+    } = D::app();
+
+    // Multiple readers
+    let guard1 = lock.read().unwrap();
+    let guard2 = lock.try_read().unwrap();
+    assert_matches!(lock.try_write(), Err(rwlock::TryLockError::WouldBlock));
+    drop((guard1, guard2));
+
+    // A single writer
+    let mut guard = lock.write().unwrap();
+    assert_matches!(lock.try_read(), Err(rwlock::TryLockError::WouldBlock));
+    assert_matches!(lock.try_write(), Err(rwlock::TryLockError::WouldBlock));
+    *guard = [0, 0];
+    drop(guard);
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_matches!(lock.try_read(), Err(rwlock::TryLockError::BadContext));
+    assert_matches!(lock.read(), Err(rwlock::LockError::BadContext));
+    assert_matches!(lock.write(), Err(rwlock::LockError::BadContext));
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    for reader in readers.iter() {
+        reader.activate().unwrap();
+    }
+    writer.activate().unwrap();
+
+    // Wait until all tasks complete
+    while state.num_done.load(Ordering::Relaxed) < NUM_READERS + 1 {
+        System::sleep_ms(HOLD_TIME_MS);
+    }
+
+    let max_active_readers = state.max_active_readers.load(Ordering::Relaxed);
+    log::debug!("max_active_readers = {}", max_active_readers);
+    assert!(max_active_readers >= 2);
+
+    assert_eq!(*lock.read().unwrap(), [NUM_ITERATIONS; 2]);
+
+    D::success();
+}
+
+fn reader_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { lock, state, .. } = D::app();
+
+    for _ in 0..NUM_ITERATIONS {
+        let guard = lock.read().unwrap();
+
+        let num_active_readers = state.num_active_readers.load(Ordering::Relaxed) + 1;
+        state
+            .num_active_readers
+            .store(num_active_readers, Ordering::Relaxed);
+        if num_active_readers > state.max_active_readers.load(Ordering::Relaxed) {
+            state
+                .max_active_readers
+                .store(num_active_readers, Ordering::Relaxed);
+        }
+
+        // The writer doesn't modify the data while we are holding the lock
+        let value = *guard;
+        assert_eq!(value[0], value[1]);
+        System::sleep_ms(HOLD_TIME_MS);
+        assert_eq!(*guard, value);
+
+        state.num_active_readers.store(
+            state.num_active_readers.load(Ordering::Relaxed) - 1,
+            Ordering::Relaxed,
+        );
+        drop(guard);
+
+        System::sleep_ms(HOLD_TIME_MS / 4);
+    }
+
+    state.num_done.store(
+        state.num_done.load(Ordering::Relaxed) + 1,
+        Ordering::Relaxed,
+    );
+}
+
+fn writer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { lock, state, .. } = D::app();
+
+    for _ in 0..NUM_ITERATIONS {
+        let mut guard = lock.write().unwrap();
+
+        // No readers are holding the lock
+        assert_eq!(state.num_active_readers.load(Ordering::Relaxed), 0);
+
+        guard[0] += 1;
+        System::sleep_ms(HOLD_TIME_MS);
+        guard[1] += 1;
+
+        assert_eq!(state.num_active_readers.load(Ordering::Relaxed), 0);
+        drop(guard);
+
+        System::sleep_ms(HOLD_TIME_MS / 4);
+    }
+
+    state.num_done.store(
+        state.num_done.load(Ordering::Relaxed) + 1,
+        Ordering::Relaxed,
+    );
+}
@@ -0,0 +1,109 @@
+//! Checks miscellaneous properties of [`r3::sync::Semaphore`].
+use assert_matches::assert_matches;
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    sync::semaphore::{self, Semaphore},
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task2: Task<System>,
+    sem: Semaphore<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .active(false)
+            .finish(b);
+
+        let sem = Semaphore::build().initial(2).maximum(2).finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { task2, sem, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(0, 1);
+
+    let guard1 = app.sem.acquire().unwrap();
+    let guard2 = app.sem.try_acquire().unwrap();
+    assert_eq!(app.sem.available_permits(), Ok(0));
+
+    // No permits are available
+    assert_matches!(
+        app.sem.try_acquire(),
+        Err(semaphore::TryAcquireError::WouldBlock)
+    );
+    assert_matches!(
+        app.sem.acquire_timeout(Duration::from_millis(10)),
+        Err(semaphore::AcquireTimeoutError::Timeout)
+    );
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_matches!(
+        app.sem.try_acquire(),
+        Err(semaphore::TryAcquireError::BadContext)
+    );
+    assert_matches!(
+        app.sem.available_permits(),
+        Err(semaphore::AvailablePermitsError::BadContext)
+    );
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    // Dropping a guard releases the permit
+    drop(guard2);
+    assert_eq!(app.sem.available_permits(), Ok(1));
+
+    app.task2.activate().unwrap(); // giving the control to `task2`
+
+    // back from `task2`, which is being blocked...
+    app.seq.expect_and_replace(2, 3);
+
+    // Release the permit and let `task2` continue. The control will return to
+    // here when `task2` completes.
+    drop(guard1);
+
+    app.seq.expect_and_replace(4, 5);
+
+    // All permits have been released
+    assert_eq!(app.sem.available_permits(), Ok(2));
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(1, 2);
+
+    let guard1 = app.sem.try_acquire().unwrap();
+
+    // blocks because `task1` has the other permit
+    let guard2 = app.sem.acquire().unwrap();
+
+    // preempts `task1` when it releases the permit
+    app.seq.expect_and_replace(3, 4);
+    assert_eq!(app.sem.available_permits(), Ok(0));
+
+    drop((guard1, guard2));
+    assert_eq!(app.sem.available_permits(), Ok(2));
+}
@@ -0,0 +1,101 @@
+//! Runs two busy tasks with different priorities and checks the execution
+//! times measured by the kernel. The higher-priority task (`task1`)
+//! periodically sleeps to let the lower-priority task (`task2`) run, so the
+//! execution times should be roughly proportional to the busy and sleep
+//! durations of `task1`.
+use core::sync::atomic::{AtomicBool, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::time::KernelTimeExt;
+
+/// The number of iterations of `task1`'s main loop.
+const NUM_ITERATIONS: u32 = 4;
+
+/// How long `task1` spins in each iteration, measured in milliseconds.
+const BUSY_MS: u32 = 60;
+
+/// How long `task1` sleeps in each iteration, measured in milliseconds.
+const SLEEP_MS: u32 = 20;
+
+pub struct App<System> {
+    task1: Task<System>,
+    task2: Task<System>,
+    done: Hunk<System, AtomicBool>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        let done = Hunk::<_, AtomicBool>::build().finish(b);
+
+        App { task1, task2, done }
+    }
+}
+
+fn spin_ms<System: Kernel>(duration_ms: u32) {
+    let start = System::monotonic_time();
+    while (System::monotonic_time() - start).as_millis() < duration_ms as u128 {}
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { task1, task2, done } = D::app();
+
+    for _ in 0..NUM_ITERATIONS {
+        spin_ms::<System>(BUSY_MS);
+        System::sleep_ms(SLEEP_MS);
+    }
+    done.store(true, Ordering::Relaxed);
+
+    let time1 = task1.cpu_time().unwrap().as_millis() as u32;
+    let time2 = task2.cpu_time().unwrap().as_millis() as u32;
+    log::debug!("cpu_time: task1 = {}ms, task2 = {}ms", time1, time2);
+
+    // `task1` spent most of the time spinning
+    let expected_time1 = BUSY_MS * NUM_ITERATIONS;
+    assert!(
+        (expected_time1..expected_time1 * 2).contains(&time1),
+        "task1 = {}ms (expected = {}ms)",
+        time1,
+        expected_time1
+    );
+
+    // `task2` only ran while `task1` was sleeping
+    let expected_time2 = SLEEP_MS * NUM_ITERATIONS;
+    assert!(
+        time2 >= expected_time2 / 2 && time2 < expected_time1,
+        "task2 = {}ms (expected = {}ms)",
+        time2,
+        expected_time2
+    );
+    assert!(time1 > time2 * 2);
+
+    // The total execution time includes both tasks' execution times and
+    // never exceeds the elapsed time
+    let total = System::total_cpu_time().unwrap();
+    let now = System::monotonic_time();
+    log::debug!("total_cpu_time = {:?}, monotonic_time = {:?}", total, now);
+    assert!(total.as_millis() as u32 >= time1 + time2);
+    assert!(total <= now);
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { done, .. } = D::app();
+    while !done.load(Ordering::Relaxed) {}
+}
@@ -0,0 +1,124 @@
+//! Checks the return value of `Task::current` in various contexts.
+//!
+//!  - In a boot context, it returns `None`.
+//!  - In an interrupt handler that interrupted a task, it returns the
+//!    interrupted task.
+//!  - In a timer callback that runs while no task is ready to run, it returns
+//!    `None`.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, InterruptHandler, InterruptLine, StartupHook, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task: Task<System>,
+    timer: Timer<System>,
+    int: Option<InterruptLine<System>>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        StartupHook::build()
+            .start(startup_hook::<System, D>)
+            .finish(b);
+
+        let task = Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let timer = Timer::build()
+            .start(timer_body::<System, D>)
+            .delay(Duration::from_millis(100))
+            .finish(b);
+
+        let int = if let (&[int_line, ..], &[int_pri, ..]) =
+            (D::INTERRUPT_LINES, D::INTERRUPT_PRIORITIES)
+        {
+            InterruptHandler::build()
+                .line(int_line)
+                .start(isr::<System, D>)
+                .finish(b);
+
+            Some(
+                InterruptLine::build()
+                    .line(int_line)
+                    .priority(int_pri)
+                    .enabled(true)
+                    .finish(b),
+            )
+        } else {
+            None
+        };
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App {
+            task,
+            timer,
+            int,
+            seq,
+        }
+    }
+}
+
+fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // No task has been dispatched yet
+    assert_eq!(Task::<System>::current(), Ok(None));
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task,
+        timer,
+        int,
+        seq,
+    } = D::app();
+
+    seq.expect_and_replace(0, 1);
+    assert_eq!(Task::current(), Ok(Some(*task)));
+
+    if let Some(int) = int {
+        int.pend().unwrap();
+    } else {
+        log::warn!("No interrupt lines defined, skipping a portion of the test");
+        seq.expect_and_replace(1, 2);
+    }
+
+    // Let the timer fire while no task is ready to run
+    seq.expect_and_replace(2, 3);
+    timer.start().unwrap();
+    System::park().unwrap();
+
+    seq.expect_and_replace(4, 5);
+    assert_eq!(Task::current(), Ok(Some(*task)));
+
+    D::success();
+}
+
+fn isr<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { task, seq, .. } = D::app();
+
+    seq.expect_and_replace(1, 2);
+
+    // `task` was interrupted by this interrupt handler
+    assert_eq!(Task::current(), Ok(Some(*task)));
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { task, seq, .. } = D::app();
+
+    seq.expect_and_replace(3, 4);
+
+    // The only task is in the Waiting state
+    assert_eq!(Task::<System>::current(), Ok(None));
+
+    task.unpark_exact().unwrap();
+}
@@ -0,0 +1,73 @@
+//! Performs floating-point computation in two tasks, switching between them
+//! in the middle of the computation. Checks that the floating-point state of
+//! each task is preserved across context switches.
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+};
+
+use super::Driver;
+
+pub struct App<System> {
+    task1: Task<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task2_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        App { task1 }
+    }
+}
+
+const NUM_ROUNDS: usize = 16;
+
+static SEED1: f32 = 1.25;
+static SEED2: f32 = -42.0;
+
+/// Perform some floating-point computation, calling `yield_` between rounds.
+/// The intermediate values are likely to be held in callee-saved
+/// floating-point registers across the calls to `yield_`.
+fn compute(seed: &f32, mut yield_: impl FnMut()) -> [f32; 4] {
+    // Defeat constant propagation
+    let seed = unsafe { core::ptr::read_volatile(seed) };
+
+    let mut x = [seed, seed * 2.0, seed + 1.5, seed * 0.75];
+    for _ in 0..NUM_ROUNDS {
+        x[0] = x[0] * 1.0625 + x[3];
+        x[1] = x[1] * 0.9375 - x[0];
+        x[2] = x[2] / 1.5 + x[1];
+        x[3] = x[3] * 0.5 + x[2] * 0.125;
+        yield_();
+    }
+    x
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // `park` blocks, switching to `task2`
+    let actual = compute(&SEED1, || System::park().unwrap());
+    let expected = compute(&SEED1, || {});
+    log::trace!("task1: actual = {:?}, expected = {:?}", actual, expected);
+    assert_eq!(actual, expected);
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let task1 = D::app().task1;
+
+    // `unpark_exact` switches to `task1`
+    let actual = compute(&SEED2, || task1.unpark_exact().unwrap());
+    let expected = compute(&SEED2, || {});
+    log::trace!("task2: actual = {:?}, expected = {:?}", actual, expected);
+    assert_eq!(actual, expected);
+
+    D::success();
+}
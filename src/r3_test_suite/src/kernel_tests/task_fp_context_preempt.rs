@@ -0,0 +1,153 @@
+//! Computes `f64` dot products in two tasks, which are preempted by a timer
+//! in the middle of the computation. Checks that the floating-point state of
+//! each task is preserved across preemption.
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, Timer},
+    prelude::*,
+    time::Duration,
+    utils::Init,
+};
+
+use super::Driver;
+
+const NUM_TASKS: usize = 2;
+
+pub struct App<System> {
+    tasks: [Task<System>; NUM_TASKS],
+    state: Hunk<System, State>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Timer::build()
+            .delay(Duration::from_millis(0))
+            .period(Duration::from_millis(10))
+            .start(timer_body::<System, D>)
+            .active(true)
+            .finish(b);
+
+        let task0 = Task::build()
+            .active(true)
+            .start(worker_body::<System, D>)
+            .priority(2)
+            .param(0)
+            .finish(b);
+        let task1 = Task::build()
+            .active(true)
+            .start(worker_body::<System, D>)
+            .priority(3)
+            .param(1)
+            .finish(b);
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App {
+            tasks: [task0, task1],
+            state,
+        }
+    }
+}
+
+struct State {
+    /// The number of times the computation was completed by each task.
+    run_count: [AtomicUsize; NUM_TASKS],
+    stop: AtomicBool,
+    /// The index of the task currently given a higher priority. Only accessed
+    /// by the timer handler.
+    cur_task: AtomicUsize,
+    /// The number of timer ticks so far. Only accessed by the timer handler.
+    time: AtomicUsize,
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        run_count: Init::INIT,
+        stop: Init::INIT,
+        cur_task: Init::INIT,
+        time: Init::INIT,
+    };
+}
+
+static SEEDS: [f64; NUM_TASKS] = [1.25, -42.0];
+
+const LEN: usize = 256;
+
+/// Compute the dot product of two vectors generated from `seed`.
+#[inline(never)]
+fn dot_product(seed: f64) -> f64 {
+    let mut sum = 0.0;
+    for i in 0..LEN {
+        let x = seed + i as f64 * 0.5;
+        let y = 1.0 / (i as f64 + 1.0);
+        sum += x * y;
+    }
+    sum
+}
+
+fn worker_body<System: Kernel, D: Driver<App<System>>>(worker_id: usize) {
+    let state = &*D::app().state;
+
+    // Defeat constant propagation
+    let seed = unsafe { core::ptr::read_volatile(&SEEDS[worker_id]) };
+
+    let expected = dot_product(seed);
+    let run_count = &state.run_count[worker_id];
+
+    while !state.stop.load(Ordering::Relaxed) {
+        let actual = dot_product(seed);
+        if actual.to_bits() != expected.to_bits() {
+            state.stop.store(true, Ordering::Relaxed);
+            panic!(
+                "[{}] output validation failed: expected = {:?}, actual = {:?}",
+                worker_id, expected, actual
+            );
+        }
+
+        // Note: Some targets don't support CAS atomics. Non-atomic load/store
+        //       suffices because `run_count` is only written by this task.
+        run_count.store(run_count.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+    }
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { state, tasks } = D::app();
+
+    if state.stop.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let time = state.time.load(Ordering::Relaxed) + 1;
+    state.time.store(time, Ordering::Relaxed);
+
+    // Switch the running task
+    let cur_task = state.cur_task.load(Ordering::Relaxed);
+    let new_task = (cur_task + 1) % NUM_TASKS;
+    tasks[cur_task].set_priority(3).unwrap();
+    tasks[new_task].set_priority(2).unwrap();
+    state.cur_task.store(new_task, Ordering::Relaxed);
+
+    // Wait for several ticks to catch any bugs in context switching
+    if time < 50 {
+        return;
+    }
+
+    let run_count = [
+        state.run_count[0].load(Ordering::Relaxed),
+        state.run_count[1].load(Ordering::Relaxed),
+    ];
+    log::debug!("run_count = {:?}", run_count);
+
+    if run_count.iter().any(|&x| x == 0) {
+        if time > 1000 {
+            state.stop.store(true, Ordering::Relaxed);
+            panic!("Timeout");
+        }
+        return;
+    }
+
+    state.stop.store(true, Ordering::Relaxed);
+    D::success();
+}
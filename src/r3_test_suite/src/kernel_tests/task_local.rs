@@ -0,0 +1,96 @@
+//! Checks that each task has its own value of a [`r3::kernel::TaskLocal`].
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, TaskLocal},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task2: Task<System>,
+    local: TaskLocal<System, u32>,
+    local_opt: TaskLocal<System, Option<u64>>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        // Task-local storage slots can be defined before tasks
+        let local = TaskLocal::build().finish(b);
+        let local_opt = TaskLocal::build().finish(b);
+
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App {
+            task2,
+            local,
+            local_opt,
+            seq,
+        }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(0, 1);
+
+    // The initial values are `Init::INIT`
+    assert_eq!(app.local.get(), 0);
+    assert_eq!(app.local_opt.take(), None);
+
+    app.local.set(1);
+    app.local_opt.set(Some(0x1_0000_0001));
+
+    app.task2.activate().unwrap(); // giving the control to `task2`
+
+    // `task2`'s writes didn't affect our values
+    app.seq.expect_and_replace(2, 3);
+    assert_eq!(app.local.get(), 1);
+    assert_eq!(app.local_opt.replace(None), Some(0x1_0000_0001));
+    assert_eq!(app.local_opt.get(), None);
+
+    // Activate `task2` again. The value persists across activations.
+    app.task2.activate().unwrap();
+
+    app.seq.expect_and_replace(4, 5);
+    assert_eq!(app.local.get(), 1);
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    match app.seq.get() {
+        1 => {
+            app.seq.expect_and_replace(1, 2);
+
+            // We can't see `task1`'s values
+            assert_eq!(app.local.get(), 0);
+            assert_eq!(app.local_opt.get(), None);
+
+            app.local.set(2);
+            app.local_opt.set(Some(2));
+        }
+        3 => {
+            app.seq.expect_and_replace(3, 4);
+            assert_eq!(app.local.replace(3), 2);
+            assert_eq!(app.local_opt.get(), Some(2));
+        }
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,104 @@
+//! Runs three busy tasks having the same priority with round-robin scheduling
+//! enabled and checks that each task gets approximately one third of the CPU
+//! time. A higher-priority task (`task0`) ends the measurement after
+//! `WINDOW_MS` milliseconds.
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    time::Duration,
+    utils::Init,
+};
+
+use super::Driver;
+use crate::utils::time::KernelTimeExt;
+
+const NUM_TASKS: usize = 3;
+
+/// The length of the measurement window, measured in milliseconds.
+const WINDOW_MS: u32 = 300;
+
+pub struct App<System> {
+    state: Hunk<System, State>,
+}
+
+struct State {
+    stop: AtomicBool,
+    run_count: [AtomicUsize; NUM_TASKS],
+}
+
+impl Init for State {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        stop: Init::INIT,
+        run_count: Init::INIT,
+    };
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        b.round_robin_quantum(Duration::from_millis(10));
+
+        Task::build()
+            .start(task0_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+
+        let mut i = 0;
+        while i < NUM_TASKS {
+            Task::build()
+                .start(worker_body::<System, D>)
+                .param(i)
+                .priority(1)
+                .active(true)
+                .finish(b);
+            i += 1;
+        }
+
+        let state = Hunk::<_, State>::build().finish(b);
+
+        App { state }
+    }
+}
+
+fn task0_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let state = &*D::app().state;
+
+    System::sleep_ms(WINDOW_MS);
+    state.stop.store(true, Ordering::Relaxed);
+
+    let mut run_count = [0; NUM_TASKS];
+    for (x, y) in state.run_count.iter().zip(run_count.iter_mut()) {
+        *y = x.load(Ordering::Relaxed);
+    }
+    log::debug!("run_count = {:?}", run_count);
+
+    // Each task should get approximately one third of the CPU time. The
+    // tolerance is wide to account for the jitter in the timer and the
+    // varying speed of the workload.
+    let total: usize = run_count.iter().sum();
+    for (i, &count) in run_count.iter().enumerate() {
+        assert!(
+            count * 5 >= total && count * 2 <= total,
+            "tasks[{}] ran for {} iterations out of {}",
+            i,
+            count,
+            total
+        );
+    }
+
+    D::success();
+}
+
+fn worker_body<System: Kernel, D: Driver<App<System>>>(worker_id: usize) {
+    let state = &*D::app().state;
+    let run_count = &state.run_count[worker_id];
+
+    while !state.stop.load(Ordering::Relaxed) {
+        // Note: Some targets don't support CAS atomics. Non-atomic load/store
+        //       suffices because `run_count` is only written by this task.
+        run_count.store(run_count.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
+    }
+}
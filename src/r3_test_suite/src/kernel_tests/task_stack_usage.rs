@@ -0,0 +1,120 @@
+//! Measures the stack usage of a task by `Task::stack_usage`.
+//!
+//! 1. (`seq`: 0 → 1) `task1` activates `task2`.
+//! 2. (`seq`: 1 → 2) `task2` consumes some stack space and exits.
+//! 3. (`seq`: 2 → 3) `task1` checks `task2`'s stack usage and re-activates
+//!    `task2`.
+//! 4. (`seq`: 3 → 4) `task2` exits without consuming much stack space.
+//! 5. (`seq`: 4 → 5) `task1` checks that the stack usage was reset by the
+//!    re-activation.
+//!
+use core::sync::atomic::{AtomicBool, Ordering};
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, PortThreading, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+/// The size of the buffer `task2` places on the stack.
+const BUFFER_SIZE: usize = 256;
+
+pub struct App<System> {
+    task2: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+    consume_stack: Hunk<System, AtomicBool>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+        let consume_stack = Hunk::<_, AtomicBool>::build().finish(b);
+
+        App {
+            task2,
+            seq,
+            consume_stack,
+        }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task2,
+        seq,
+        consume_stack,
+    } = D::app();
+
+    seq.expect_and_replace(0, 1);
+    consume_stack.store(true, Ordering::Relaxed);
+    task2.activate().unwrap(); // preempted by `task2`
+
+    seq.expect_and_replace(2, 3);
+    let usage1 = task2.stack_usage().unwrap();
+    log::debug!("stack usage (1) = {}", usage1);
+
+    if usage1 == 0 {
+        // The port doesn't run tasks on `StackHunk`
+        log::warn!("The port doesn't use the task stack, skipping the test");
+        D::success();
+        return;
+    }
+
+    assert!(
+        usage1 >= BUFFER_SIZE,
+        "stack usage = {} (expected >= {})",
+        usage1,
+        BUFFER_SIZE
+    );
+    assert!(usage1 <= System::STACK_DEFAULT_SIZE);
+
+    // The measurement is reset on activation
+    consume_stack.store(false, Ordering::Relaxed);
+    task2.activate().unwrap(); // preempted by `task2`
+
+    seq.expect_and_replace(4, 5);
+    let usage2 = task2.stack_usage().unwrap();
+    log::debug!("stack usage (2) = {}", usage2);
+    assert!(
+        usage2 < usage1,
+        "stack usage = {} (expected < {})",
+        usage2,
+        usage1
+    );
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        seq, consume_stack, ..
+    } = D::app();
+
+    if consume_stack.load(Ordering::Relaxed) {
+        seq.expect_and_replace(1, 2);
+        consume_stack_space();
+    } else {
+        seq.expect_and_replace(3, 4);
+    }
+}
+
+#[inline(never)]
+fn consume_stack_space() {
+    let mut buffer = [0u8; BUFFER_SIZE];
+    for (i, x) in buffer.iter_mut().enumerate() {
+        // Safety: `x` is a valid reference
+        unsafe { core::ptr::write_volatile(x, i as u8 ^ 0x5a) };
+    }
+}
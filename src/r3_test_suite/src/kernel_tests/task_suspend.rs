@@ -0,0 +1,94 @@
+//! Suspends and resumes a task in various states.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, ResumeTaskError, SuspendTaskError, Task},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::{time::KernelTimeExt, SeqTracker};
+
+pub struct App<System> {
+    task2: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(2)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { task2, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { task2, seq } = D::app();
+
+    seq.expect_and_replace(0, 1);
+
+    // `task2` is Dormant
+    assert_eq!(task2.suspend(), Err(SuspendTaskError::BadObjectState));
+    assert_eq!(task2.resume(), Err(ResumeTaskError::BadObjectState));
+
+    // Ready → Suspended
+    task2.activate().unwrap();
+    task2.suspend().unwrap();
+
+    // It's already suspended
+    assert_eq!(task2.suspend(), Err(SuspendTaskError::BadObjectState));
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(task2.suspend(), Err(SuspendTaskError::BadContext));
+    assert_eq!(task2.resume(), Err(ResumeTaskError::BadContext));
+    unsafe { System::release_cpu_lock() }.unwrap();
+
+    // A suspended task is not scheduled even if the processor is idle
+    System::sleep_ms(20);
+    seq.expect_and_replace(1, 2);
+
+    // Suspended → Ready
+    task2.resume().unwrap();
+    assert_eq!(task2.resume(), Err(ResumeTaskError::BadObjectState));
+
+    // `task2` runs and suspends itself
+    System::sleep_ms(20);
+    seq.expect_and_replace(3, 4);
+
+    // `task2` returns from `suspend` and parks itself
+    task2.resume().unwrap();
+    System::sleep_ms(20);
+    seq.expect_and_replace(5, 6);
+
+    // `task2` is Waiting
+    assert_eq!(task2.suspend(), Err(SuspendTaskError::BadObjectState));
+
+    task2.unpark().unwrap();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, .. } = D::app();
+
+    seq.expect_and_replace(2, 3);
+
+    // Running → Suspended
+    let task2 = Task::<System>::current().unwrap().unwrap();
+    task2.suspend().unwrap();
+
+    seq.expect_and_replace(4, 5);
+
+    System::park().unwrap();
+
+    seq.expect_and_replace(6, 7);
+    D::success();
+}
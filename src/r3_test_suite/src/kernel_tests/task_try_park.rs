@@ -0,0 +1,57 @@
+//! Checks the behavior of `Kernel::try_park` and the saturation of park tokens.
+use r3::{
+    kernel::{cfg::CfgBuilder, Task, TryParkError, UnparkExactError},
+    prelude::*,
+};
+
+use super::Driver;
+
+pub struct App<System> {
+    task: Task<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task = Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        App { task }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let task = D::app().task;
+
+    // The token is initially absent
+    assert_eq!(System::try_park(), Err(TryParkError::Timeout));
+
+    // Give a token. The second call fails because the token count saturates
+    // at one.
+    task.unpark_exact().unwrap();
+    assert_eq!(task.unpark_exact(), Err(UnparkExactError::QueueOverflow));
+    task.unpark().unwrap();
+
+    // Consume the token
+    assert_eq!(System::try_park(), Ok(()));
+    assert_eq!(System::try_park(), Err(TryParkError::Timeout));
+
+    // `try_park` doesn't block even if there's no token, so it can be called
+    // repeatedly
+    for _ in 0..3 {
+        assert_eq!(System::try_park(), Err(TryParkError::Timeout));
+    }
+
+    // CPU Lock active
+    task.unpark_exact().unwrap();
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(System::try_park(), Err(TryParkError::BadContext));
+    unsafe { System::release_cpu_lock().unwrap() };
+
+    // The token wasn't consumed by the failed call
+    System::park().unwrap();
+
+    D::success();
+}
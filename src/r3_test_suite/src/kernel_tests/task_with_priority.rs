@@ -0,0 +1,100 @@
+//! Temporarily changes the current task's priority by `Kernel::with_priority`.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, WithPriorityError},
+    prelude::*,
+};
+
+use super::Driver;
+use crate::utils::SeqTracker;
+
+pub struct App<System> {
+    task1: Task<System>,
+    task2: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let task1 = Task::build()
+            .start(task1_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+        let task2 = Task::build()
+            .start(task2_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+        Task::build()
+            .start(task3_body::<System, D>)
+            .priority(2)
+            .active(true)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { task1, task2, seq }
+    }
+}
+
+fn task1_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let app = D::app();
+
+    app.seq.expect_and_replace(1, 2);
+
+    // Raise the priority. `task2` can't preempt `task1` in the scope.
+    let output = System::with_priority(0, || {
+        app.task2.unpark_exact().unwrap();
+        app.seq.expect_and_replace(2, 3);
+        assert_eq!(app.task1.priority().unwrap(), 0);
+        assert_eq!(app.task1.effective_priority().unwrap(), 0);
+        42
+    });
+    assert_eq!(output, Ok(42));
+
+    // `task2` preempted `task1` at the end of the scope
+    app.seq.expect_and_replace(4, 5);
+    assert_eq!(app.task1.priority().unwrap(), 1);
+
+    // Lower the priority. `task3` preempts `task1` at the beginning of the
+    // scope.
+    System::with_priority(3, || {
+        app.seq.expect_and_replace(6, 7);
+        assert_eq!(app.task1.priority().unwrap(), 3);
+    })
+    .unwrap();
+
+    app.seq.expect_and_replace(7, 8);
+    assert_eq!(app.task1.priority().unwrap(), 1);
+
+    // Out-of-range priority
+    assert_eq!(
+        System::with_priority(4, || unreachable!()),
+        Err(WithPriorityError::BadParam)
+    );
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(
+        System::with_priority(0, || unreachable!()),
+        Err(WithPriorityError::BadContext)
+    );
+    unsafe { System::release_cpu_lock().unwrap() };
+
+    assert_eq!(app.task1.priority().unwrap(), 1);
+
+    D::success();
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().seq.expect_and_replace(0, 1);
+
+    System::park().unwrap(); // blocks, switching to `task1`
+
+    D::app().seq.expect_and_replace(3, 4);
+}
+
+fn task3_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    D::app().seq.expect_and_replace(5, 6);
+}
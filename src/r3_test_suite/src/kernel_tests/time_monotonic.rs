@@ -0,0 +1,115 @@
+//! Checks that the monotonic time (`Kernel::monotonic_time`) advances steadily
+//! and isn't affected by `set_time` or `adjust_time`. Also checks that it can
+//! be read from an interrupt context, a startup hook, and a task with CPU Lock
+//! active.
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, StartupHook, Task, Timer},
+    prelude::*,
+    time::{Duration, Time},
+};
+
+use super::Driver;
+use crate::utils::{time::KernelTimeExt, SeqTracker};
+
+pub struct App<System> {
+    timer: Timer<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        StartupHook::build()
+            .start(startup_hook::<System, D>)
+            .finish(b);
+
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(1)
+            .active(true)
+            .finish(b);
+
+        let timer = Timer::build()
+            .delay(Duration::from_millis(50))
+            .start(timer_body::<System, D>)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { timer, seq }
+    }
+}
+
+fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // CPU Lock active
+    let mono = System::monotonic_time();
+    log::trace!("monotonic_time = {:?}", mono);
+    assert_eq!(mono.as_secs(), 0);
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer } = D::app();
+
+    // Because this task is activated at boot, the monotonic time should be
+    // very close to zero
+    let mono0 = System::monotonic_time();
+    log::trace!("monotonic_time = {:?}", mono0);
+    assert_eq!(mono0.as_secs(), 0);
+
+    // Changing the system time doesn't affect the monotonic time
+    System::set_time(Time::from_millis(114514)).unwrap();
+    System::adjust_time(Duration::from_millis(-200)).unwrap();
+    System::adjust_time(Duration::from_millis(400)).unwrap();
+
+    let mono1 = System::monotonic_time();
+    log::trace!("monotonic_time = {:?}", mono1);
+    assert!(mono1 >= mono0);
+    assert!(mono1 - mono0 < core::time::Duration::from_millis(100));
+
+    // `monotonic_time` can be called with CPU Lock active. It doesn't
+    // deactivate CPU Lock, so `release_cpu_lock` succeeds.
+    System::acquire_cpu_lock().unwrap();
+    let mono2 = System::monotonic_time();
+    unsafe { System::release_cpu_lock().unwrap() };
+    log::trace!("monotonic_time = {:?}", mono2);
+    assert!(mono2 >= mono1);
+
+    // The monotonic time advances along with the real time
+    System::sleep(Duration::from_millis(100)).unwrap();
+
+    let mono3 = System::monotonic_time();
+    log::trace!("monotonic_time = {:?}", mono3);
+    assert!(mono3 - mono2 >= core::time::Duration::from_millis(100));
+
+    // The upper bound is extended by `MAX_CONTEXT_SWITCH_LATENCY_US` because
+    // waking up from `sleep_ms` involves a context switch, which can take much
+    // longer than 10 milliseconds on a hosted port.
+    System::sleep_ms(10);
+    let mono4 = System::monotonic_time();
+    log::trace!("monotonic_time = {:?}", mono4);
+    let elapsed = mono4 - mono3;
+    let max_elapsed = core::time::Duration::from_millis(20)
+        + core::time::Duration::from_micros(D::MAX_CONTEXT_SWITCH_LATENCY_US as u64);
+    assert!(
+        elapsed >= core::time::Duration::from_millis(10) && elapsed <= max_elapsed,
+        "elapsed = {:?} (expected = 10ms..={:?})",
+        elapsed,
+        max_elapsed
+    );
+
+    seq.expect_and_replace(0, 1);
+    timer.start().unwrap();
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, .. } = D::app();
+
+    seq.expect_and_replace(1, 2);
+
+    // `monotonic_time` is available in an interrupt context
+    let mono = System::monotonic_time();
+    log::trace!("monotonic_time = {:?}", mono);
+    assert!(mono >= core::time::Duration::from_millis(150));
+
+    D::success();
+}
@@ -0,0 +1,81 @@
+//! Blocks the current task until specific system times using
+//! `Kernel::sleep_until`.
+use core::marker::PhantomData;
+use r3::{
+    kernel::{cfg::CfgBuilder, StartupHook, Task},
+    prelude::*,
+    time::{Duration, Time},
+};
+
+use super::Driver;
+use crate::utils::time::KernelTimeExt;
+
+pub struct App<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        StartupHook::build()
+            .start(startup_hook::<System, D>)
+            .finish(b);
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+
+        App {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+fn startup_hook<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    // Not a task context
+    assert_eq!(
+        System::sleep_until(Time::from_millis(0)),
+        Err(r3::kernel::SleepUntilError::BadContext)
+    );
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    System::set_time_ms(0);
+
+    // Sleep until a future time
+    System::sleep_until(Time::from_millis(200)).unwrap();
+    System::assert_time_ms_range(200..300);
+
+    // The specified time is already past; this should return immediately
+    System::sleep_until(Time::from_millis(100)).unwrap();
+    System::assert_time_ms_range(200..300);
+
+    // CPU Lock active
+    System::acquire_cpu_lock().unwrap();
+    assert_eq!(
+        System::sleep_until(Time::from_millis(400)),
+        Err(r3::kernel::SleepUntilError::BadContext)
+    );
+    unsafe { System::release_cpu_lock().unwrap() };
+
+    // Too far in the future
+    let now = System::time().unwrap();
+    assert_eq!(
+        System::sleep_until(now + Duration::MAX + Duration::from_secs(1)),
+        Err(r3::kernel::SleepUntilError::BadParam)
+    );
+
+    // The system time wraps around during the sleep
+    let now = Time::from_micros(0xffff_ffff_fffe_0000);
+    System::set_time(now).unwrap();
+    let wake_up_time = Time::from_micros(0x20000);
+    log::trace!("sleeping until {:?}", wake_up_time);
+    System::sleep_until(wake_up_time).unwrap();
+
+    let now_got = System::time().unwrap();
+    log::trace!("time = {:?} (expected >= {:?})", now_got, wake_up_time);
+    assert!(now_got.as_micros() >= wake_up_time.as_micros());
+    assert!(now_got.as_micros() < wake_up_time.as_micros() + 100_000);
+
+    D::success();
+}
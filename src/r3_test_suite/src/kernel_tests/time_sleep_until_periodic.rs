@@ -0,0 +1,57 @@
+//! Runs a periodic loop driven by `Kernel::sleep_until` and checks that the
+//! wake-up times don't drift even though each iteration spends some time
+//! before going to sleep.
+use core::marker::PhantomData;
+use r3::{
+    kernel::{cfg::CfgBuilder, Task},
+    prelude::*,
+    time::{Duration, Time},
+};
+
+use super::Driver;
+use crate::utils::time::KernelTimeExt;
+
+pub struct App<System> {
+    _phantom: PhantomData<System>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Task::build()
+            .start(task_body::<System, D>)
+            .priority(0)
+            .active(true)
+            .finish(b);
+
+        App {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+const PERIOD_MS: u32 = 100;
+const WORK_MS: u32 = 50;
+const NUM_ITERATIONS: u32 = 5;
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    System::set_time_ms(0);
+
+    let mut deadline = Time::from_millis(0);
+
+    for i in 1..=NUM_ITERATIONS {
+        // Spend some time doing "work". Sleeping for `PERIOD_MS` relatively
+        // would accumulate `WORK_MS` of drift in each iteration.
+        let work_end_ms = System::time_ms() + WORK_MS;
+        while System::time_ms() < work_end_ms {}
+
+        deadline += Duration::from_millis(PERIOD_MS as _);
+        System::sleep_until(deadline).unwrap();
+
+        // The drift doesn't accumulate
+        let deadline_ms = PERIOD_MS * i;
+        log::trace!("iteration {}: deadline = {}ms", i, deadline_ms);
+        System::assert_time_ms_range(deadline_ms..deadline_ms + WORK_MS);
+    }
+
+    D::success();
+}
@@ -0,0 +1,112 @@
+//! Configures `Timer` as a periodic timer, clears its period by
+//! `Timer::clear_period`, and checks that it stops firing after the tick that
+//! was already scheduled.
+//!
+//! ```text
+//!       __         __          __                  __
+//! Task |__|       |__|        |__|                |__|
+//!      0→1        2→3         4→5                 5→6
+//!                _           _
+//! Timer callback|_|         |_|
+//!               1→2         3→4
+//!      ├──┬──┬──┼──┬──┬──┬──┼──┬──┬──┬──┬──┬──┬──┼──────
+//!      ↑ 200ms  ↑   300ms   ↑       600ms
+//! system boot   ↑
+//!         period ← None
+//! ```
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::{time::KernelTimeExt, SeqTracker};
+
+pub struct App<System> {
+    timer: Timer<System>,
+    task: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let timer = Timer::build()
+            .active(true)
+            .delay(Duration::from_millis(200))
+            .period(Duration::from_millis(300))
+            .start(timer_body::<System, D>)
+            .finish(b);
+
+        let task = Task::build()
+            .active(true)
+            .start(task_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { timer, task, seq }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer, .. } = D::app();
+
+    // Expected current time
+    let mut now = 0u32;
+
+    macro_rules! check_time {
+        () => {
+            System::assert_time_ms_range(now..now + 100);
+        };
+    }
+
+    seq.expect_and_replace(0, 1);
+
+    // First tick
+    System::park().unwrap();
+    seq.expect_and_replace(2, 3);
+    now += 200; // delay
+    check_time!();
+
+    // Second tick. It was scheduled before the period was cleared.
+    System::park().unwrap();
+    seq.expect_and_replace(4, 5);
+    now += 300; // period
+    check_time!();
+
+    // The period was cleared, so the timer shouldn't fire anymore
+    System::sleep_ms(600);
+    seq.expect_and_replace(5, 6);
+    now += 600;
+    check_time!();
+
+    // The timer is still active
+    assert!(timer.is_active().unwrap());
+    timer.stop().unwrap();
+
+    D::success();
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task, timer, seq, ..
+    } = D::app();
+
+    match seq.get() {
+        1 => {
+            seq.expect_and_replace(1, 2);
+            // The next tick is already scheduled with the current period, so
+            // this takes effect after that
+            timer.clear_period().unwrap();
+            task.unpark_exact().unwrap();
+        }
+        3 => {
+            seq.expect_and_replace(3, 4);
+            task.unpark_exact().unwrap();
+        }
+        _ => unreachable!(),
+    }
+}
@@ -165,11 +165,19 @@ fn timer1_body<System: Kernel, D: Driver<App<System>>>(param: usize) {
     // Invalid ID
     let bad_timer: Timer<System> = unsafe { Timer::from_id(NonZeroUsize::new(42).unwrap()) };
     assert_eq!(bad_timer.start(), Err(r3::kernel::StartTimerError::BadId));
+    assert_eq!(
+        bad_timer.is_active(),
+        Err(r3::kernel::QueryTimerError::BadId)
+    );
 
     // Disallowed with CPU Lock acitve
     System::acquire_cpu_lock().unwrap();
     assert_eq!(timer1.start(), Err(r3::kernel::StartTimerError::BadContext));
     assert_eq!(timer1.stop(), Err(r3::kernel::StopTimerError::BadContext));
+    assert_eq!(
+        timer1.is_active(),
+        Err(r3::kernel::QueryTimerError::BadContext)
+    );
     assert_eq!(
         timer1.set_delay(None),
         Err(r3::kernel::SetTimerDelayError::BadContext)
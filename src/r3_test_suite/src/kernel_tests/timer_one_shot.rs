@@ -0,0 +1,77 @@
+//! Configures `Timer` as a one-shot timer and checks that it fires only once
+//! until the delay is reset.
+//!
+//! ```text
+//!       __                     __                    __
+//! Task |__|                   |__|                  |__|
+//!      0→1                    2→3                   4→5
+//!                 _                      _
+//! Timer callback |_|                    |_|
+//!                1→2                    3→4
+//!      ├──┬──┬──┼──┬──┬──┬──┬──┼──┬──┬──┼──┬──┬──┬──┬──┤
+//!      ↑ 300ms                 ↑ 300ms
+//! system boot             delay ← 300ms
+//! ```
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::{time::KernelTimeExt, SeqTracker};
+
+pub struct App<System> {
+    timer: Timer<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        let timer = Timer::build()
+            .delay(Duration::from_millis(300))
+            .active(true)
+            .start(timer_body::<System, D>)
+            .finish(b);
+
+        Task::build()
+            .active(true)
+            .start(task_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { timer, seq }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer, .. } = D::app();
+
+    seq.expect_and_replace(0, 1);
+
+    // The first (and only) tick happens at 300ms. The timer doesn't fire
+    // again because the period is infinite.
+    System::sleep_ms(900);
+    seq.expect_and_replace(2, 3);
+
+    // Reschedule the timer
+    timer.set_delay(Some(Duration::from_millis(300))).unwrap();
+
+    System::sleep_ms(900);
+    seq.expect_and_replace(4, 5);
+
+    D::success();
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, .. } = D::app();
+
+    match seq.get() {
+        1 => seq.expect_and_replace(1, 2),
+        3 => seq.expect_and_replace(3, 4),
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,134 @@
+//! Starts a one-shot timer from another timer's callback function, resets it
+//! while it's running, and checks that it fires at expected moments.
+//!
+//! ```text
+//!       __                         __     __             __
+//! Task |__|                       |__|   |__|           |__|
+//!      0→1                        3→4    4→5            6→7
+//!               _
+//! Timer A      |_|
+//!              1→2                 _                    _
+//! Timer B                         |_|                  |_|
+//!                                 2→3                  5→6
+//!      ├──┬──┬──┼──┬──┬──┬──┬──┬──┼──┬──┬──┼──┬──┬──┬──┬──┬──┤
+//!      ↑ 200ms  ↑      300ms      ↑ 200ms  ↑      300ms
+//! system boot start B          reset B  reset B
+//! ```
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::{time::KernelTimeExt, SeqTracker};
+
+pub struct App<System> {
+    timer_b: Timer<System>,
+    task: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        Timer::build()
+            .active(true)
+            .delay(Duration::from_millis(200))
+            .start(timer_a_body::<System, D>)
+            .finish(b);
+
+        let timer_b = Timer::build()
+            .delay(Duration::from_millis(300))
+            .start(timer_b_body::<System, D>)
+            .finish(b);
+
+        let task = Task::build()
+            .active(true)
+            .start(task_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { timer_b, task, seq }
+    }
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer_b, .. } = D::app();
+
+    // Expected current time
+    let mut now = 0;
+
+    macro_rules! check_time {
+        () => {
+            System::assert_time_ms_range(now..now + 100);
+        };
+    }
+
+    seq.expect_and_replace(0, 1);
+
+    // Stopping a Dormant timer has no effect
+    assert!(!timer_b.is_active().unwrap());
+    timer_b.stop().unwrap();
+    assert!(!timer_b.is_active().unwrap());
+
+    // Resetting a Dormant timer doesn't start it
+    timer_b.reset().unwrap();
+    assert!(!timer_b.is_active().unwrap());
+
+    // Wait until Timer B fires
+    System::park().unwrap();
+    seq.expect_and_replace(3, 4);
+    now += 500;
+    check_time!();
+
+    // Timer B has an infinite period, so it doesn't fire again unless the
+    // delay is reset
+    assert!(timer_b.is_active().unwrap());
+    timer_b.reset().unwrap();
+
+    System::sleep_ms(200);
+    now += 200;
+    check_time!();
+
+    // Push back the next tick
+    seq.expect_and_replace(4, 5);
+    timer_b.reset().unwrap();
+
+    System::park().unwrap();
+    seq.expect_and_replace(6, 7);
+    now += 300;
+    check_time!();
+
+    // Stopping a timer twice is okay
+    timer_b.stop().unwrap();
+    timer_b.stop().unwrap();
+    assert!(!timer_b.is_active().unwrap());
+
+    D::success();
+}
+
+fn timer_a_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer_b, .. } = D::app();
+
+    seq.expect_and_replace(1, 2);
+
+    // Start Timer B. Starting an Active timer has no effect.
+    timer_b.start().unwrap();
+    timer_b.start().unwrap();
+    assert!(timer_b.is_active().unwrap());
+}
+
+fn timer_b_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { task, seq, .. } = D::app();
+
+    match seq.get() {
+        2 => seq.expect_and_replace(2, 3),
+        5 => seq.expect_and_replace(5, 6),
+        i => unreachable!("{}", i),
+    }
+
+    task.unpark_exact().unwrap();
+}
@@ -0,0 +1,117 @@
+//! Reschedules a `Timer` by `Timer::set_delay` from another task and from the
+//! timer's callback function.
+//!
+//! ```text
+//!       __    __          __             __       __
+//! Task |__|  |__|        |__|           |__|     |__|
+//!      0→1   1→2         2→3            4→5      6→7
+//!                                      _        _
+//! Timer callback                      |_|      |_|
+//!                                     3→4      5→6
+//!      ├──┬──┼──┬──┬──┬──┼──┬──┬──┬──┼──┬──┬──┼──┬──┤
+//!      ↑ 200ms    400ms       300ms      200ms
+//! system boot  ↑           ↑            ↑
+//!          delay ← ∞  delay ← 300ms  delay ← 200ms
+//!                                    (in callback)
+//! ```
+use r3::{
+    hunk::Hunk,
+    kernel::{cfg::CfgBuilder, Task, Timer},
+    prelude::*,
+    time::Duration,
+};
+
+use super::Driver;
+use crate::utils::{time::KernelTimeExt, SeqTracker};
+
+pub struct App<System> {
+    timer: Timer<System>,
+    task: Task<System>,
+    seq: Hunk<System, SeqTracker>,
+}
+
+impl<System: Kernel> App<System> {
+    pub const fn new<D: Driver<Self>>(b: &mut CfgBuilder<System>) -> Self {
+        // This timer would fire at 400ms if it were not rescheduled
+        let timer = Timer::build()
+            .delay(Duration::from_millis(400))
+            .period(Duration::from_millis(100))
+            .active(true)
+            .start(timer_body::<System, D>)
+            .finish(b);
+
+        let task = Task::build()
+            .active(true)
+            .start(task_body::<System, D>)
+            .priority(2)
+            .finish(b);
+
+        Task::build()
+            .active(true)
+            .start(task2_body::<System, D>)
+            .priority(1)
+            .finish(b);
+
+        let seq = Hunk::<_, SeqTracker>::build().finish(b);
+
+        App { timer, task, seq }
+    }
+}
+
+fn task2_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer, .. } = D::app();
+
+    seq.expect_and_replace(0, 1);
+
+    System::sleep_ms(200);
+
+    // Stop the timer from another task before it fires
+    seq.expect_and_replace(1, 2);
+    timer.set_delay(None).unwrap();
+}
+
+fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App { seq, timer, .. } = D::app();
+
+    // Wait past the original expiration time
+    System::sleep_ms(600);
+    seq.expect_and_replace(2, 3);
+    System::assert_time_ms_range(600..700);
+
+    // Restart the timer
+    timer.set_delay(Some(Duration::from_millis(300))).unwrap();
+
+    // First tick
+    System::park().unwrap();
+    seq.expect_and_replace(4, 5);
+    System::assert_time_ms_range(900..1000);
+
+    // Second tick, rescheduled by the callback function
+    System::park().unwrap();
+    seq.expect_and_replace(6, 7);
+    System::assert_time_ms_range(1100..1200);
+
+    timer.stop().unwrap();
+
+    D::success();
+}
+
+fn timer_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
+    let App {
+        task, timer, seq, ..
+    } = D::app();
+
+    match seq.get() {
+        3 => {
+            seq.expect_and_replace(3, 4);
+            // Override the period-based rescheduling
+            timer.set_delay(Some(Duration::from_millis(200))).unwrap();
+        }
+        5 => {
+            seq.expect_and_replace(5, 6);
+        }
+        _ => unreachable!(),
+    }
+
+    task.unpark_exact().unwrap();
+}
@@ -70,6 +70,7 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     // Start the timer
     check_time!();
     seq.expect_and_replace(1, 2);
+    assert!(timer.is_active().unwrap());
     timer.set_delay(Some(Duration::from_millis(500))).unwrap();
 
     System::sleep_ms(200);
@@ -78,6 +79,7 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     // Suspend the timer
     seq.expect_and_replace(2, 3);
     timer.stop().unwrap();
+    assert!(!timer.is_active().unwrap());
     check_time!();
 
     System::sleep_ms(400);
@@ -86,6 +88,7 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     // Resume the timer
     seq.expect_and_replace(3, 4);
     timer.start().unwrap();
+    assert!(timer.is_active().unwrap());
     check_time!();
 
     // Tick
@@ -94,6 +97,9 @@ fn task_body<System: Kernel, D: Driver<App<System>>>(_: usize) {
     now += 300;
     check_time!();
 
+    // A one-shot timer stays in the Active state after firing
+    assert!(timer.is_active().unwrap());
+
     D::success();
 }
 
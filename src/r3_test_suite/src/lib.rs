@@ -96,6 +96,15 @@ pub mod kernel_tests {
         /// [`INTERRUPT_PRIORITIES`]: Self::INTERRUPT_PRIORITIES
         ///
         const UNMANAGED_INTERRUPT_PRIORITIES: &'static [InterruptPriority] = &[];
+
+        /// The upper bound of the 99th percentile of the context switch
+        /// latency, measured in microseconds. Used by the `latency_probe` test.
+        const MAX_CONTEXT_SWITCH_LATENCY_US: u32 = 1000;
+
+        /// The upper bound of the 99th percentile of the interrupt latency,
+        /// measured in microseconds. Used by the `interrupt_latency_probe`
+        /// test.
+        const MAX_INTERRUPT_LATENCY_US: u32 = 1000;
     }
 
     macro_rules! define_kernel_tests {
@@ -184,43 +193,70 @@ pub mod kernel_tests {
         (mod basic {}, "basic"),
         (mod compute_atomics_round_robin {}, "compute_atomics_round_robin"),
         (mod compute_round_robin {}, "compute_round_robin"),
+        (mod context_query {}, "context_query"),
         (mod cpu_lock {}, "cpu_lock"),
+        (mod deferred_work {}, "deferred_work"),
+        #[cfg(feature = "event_group_64bit")]
+        (mod event_group_64bit {}, "event_group_64bit"),
         (mod event_group_interrupt {}, "event_group_interrupt"),
         (mod event_group_misc {}, "event_group_misc"),
         (mod event_group_order_fifo {}, "event_group_order_fifo"),
         (mod event_group_order_task_priority {}, "event_group_order_task_priority"),
         (mod event_group_poll {}, "event_group_poll"),
+        (mod event_group_poll_task_set {}, "event_group_poll_task_set"),
         (mod event_group_reorder_task_priority {}, "event_group_reorder_task_priority"),
         (mod event_group_set_and_dispatch {}, "event_group_set_and_dispatch"),
         (mod event_group_timeout {}, "event_group_timeout"),
+        (mod event_group_wait_any {}, "event_group_wait_any"),
         (mod event_group_wait_types {}, "event_group_wait_types"),
+        (mod future_block_on {}, "future_block_on"),
+        (mod hunk_misc {}, "hunk_misc"),
         (mod interrupt_disallowed_services {}, "interrupt_disallowed_services"),
         (mod interrupt_during_boot {}, "interrupt_during_boot"),
+        (mod interrupt_handler_order {}, "interrupt_handler_order"),
         (mod interrupt_handler_priority {}, "interrupt_handler_priority"),
+        (mod interrupt_latency_probe {}, "interrupt_latency_probe"),
         (mod interrupt_misc {}, "interrupt_misc"),
         (mod interrupt_priority {}, "interrupt_priority"),
         (mod interrupt_task_activate {}, "interrupt_task_activate"),
+        (mod interrupt_trigger_mode {}, "interrupt_trigger_mode"),
         (mod interrupt_unmanaged {}, "interrupt_unmanaged"),
+        (mod latency_probe {}, "latency_probe"),
         (mod mutex_misc {}, "mutex_misc"),
         (mod mutex_nesting {}, "mutex_nesting"),
         (mod mutex_protect_priority_by_ceiling {}, "mutex_protect_priority_by_ceiling"),
         (mod mutex_timeout {}, "mutex_timeout"),
         (mod mutex_unlock_and_dispatch {}, "mutex_unlock_and_dispatch"),
+        (mod notification_misc {}, "notification_misc"),
         (mod priority_boost {}, "priority_boost"),
+        (mod sched_stress {}, "sched_stress"),
         (mod semaphore_interrupt_handler {}, "semaphore_interrupt_handler"),
         (mod semaphore_misc {}, "semaphore_misc"),
         (mod semaphore_signal_and_dispatch {}, "semaphore_signal_and_dispatch"),
         (mod semaphore_timeout {}, "semaphore_timeout"),
+        (mod semaphore_wait_n {}, "semaphore_wait_n"),
+        (mod software_interrupt_semaphore {}, "software_interrupt_semaphore"),
         (mod startup_hook_disallowed_services {}, "startup_hook_disallowed_services"),
         (mod startup_hook_pend_interrupt {}, "startup_hook_pend_interrupt"),
         (mod startup_hook_priority {}, "startup_hook_priority"),
+        (mod sync_condvar_producer_consumer {}, "sync_condvar_producer_consumer"),
+        (mod sync_memory_pool_misc {}, "sync_memory_pool_misc"),
         (mod sync_mutex_lock_and_dispatch {}, "sync_mutex_lock_and_dispatch"),
         (mod sync_mutex_misc {}, "sync_mutex_misc"),
+        (mod sync_queue_misc {}, "sync_queue_misc"),
         (mod sync_recursive_mutex_lock_and_dispatch {}, "sync_recursive_mutex_lock_and_dispatch"),
         (mod sync_recursive_mutex_misc {}, "sync_recursive_mutex_misc"),
+        (mod sync_rwlock_readers_writer {}, "sync_rwlock_readers_writer"),
+        (mod sync_semaphore_misc {}, "sync_semaphore_misc"),
         (mod task_activate_and_dispatch {}, "task_activate_and_dispatch"),
         (mod task_activate_and_do_not_dispatch {}, "task_activate_and_do_not_dispatch"),
         (mod task_cpu_lock_reset {}, "task_cpu_lock_reset"),
+        #[cfg(feature = "task_stats")]
+        (mod task_cpu_time {}, "task_cpu_time"),
+        (mod task_current {}, "task_current"),
+        (mod task_fp_context {}, "task_fp_context"),
+        (mod task_fp_context_preempt {}, "task_fp_context_preempt"),
+        (mod task_local {}, "task_local"),
         (mod task_misc {}, "task_misc"),
         (mod task_park {}, "task_park"),
         #[cfg(feature = "priority_boost")]
@@ -229,18 +265,33 @@ pub mod kernel_tests {
         (mod task_priority_boost_reset {}, "task_priority_boost_reset"),
         (mod task_priority_reset {}, "task_priority_reset"),
         (mod task_queue_fifo {}, "task_queue_fifo"),
+        (mod task_round_robin {}, "task_round_robin"),
         (mod task_set_priority {}, "task_set_priority"),
+        #[cfg(feature = "stack_usage")]
+        (mod task_stack_usage {}, "task_stack_usage"),
+        (mod task_suspend {}, "task_suspend"),
         (mod task_take_interrupt_at_return {}, "task_take_interrupt_at_return"),
+        (mod task_try_park {}, "task_try_park"),
+        (mod task_with_priority {}, "task_with_priority"),
         (mod time_adjust_event {}, "time_adjust_event"),
         #[cfg(feature = "priority_boost")]
         (mod time_adjust_limits {}, "time_adjust_limits"),
         (mod time_misc {}, "time_misc"),
+        (mod time_monotonic {}, "time_monotonic"),
         (mod time_set_event {}, "time_set_event"),
         #[cfg(feature = "system_time")]
+        (mod time_sleep_until {}, "time_sleep_until"),
+        #[cfg(feature = "system_time")]
+        (mod time_sleep_until_periodic {}, "time_sleep_until_periodic"),
+        #[cfg(feature = "system_time")]
         (mod time_stress {}, "time_stress"),
+        (mod timer_clear_period {}, "timer_clear_period"),
         (mod timer_misc {}, "timer_misc"),
+        (mod timer_one_shot {}, "timer_one_shot"),
         (mod timer_overdue {}, "timer_overdue"),
         (mod timer_periodic {}, "timer_periodic"),
+        (mod timer_reset {}, "timer_reset"),
+        (mod timer_set_delay {}, "timer_set_delay"),
         (mod timer_stop {}, "timer_stop"),
         (mod timer_zero_period {}, "timer_zero_period"),
     }
@@ -365,6 +416,12 @@ pub mod kernel_benchmarks {
         fn mark_end(int: crate::utils::benchmark::Interval);
 
         fn main_task() -> r3::kernel::Task<System>;
+
+        /// Forwarded from [`Driver::INTERRUPT_LINES`].
+        const INTERRUPT_LINES: &'static [InterruptNum];
+
+        /// Forwarded from [`Driver::INTERRUPT_PRIORITIES`].
+        const INTERRUPT_PRIORITIES: &'static [InterruptPriority];
     }
 
     /// Define an `App` type using [the benchmark
@@ -449,6 +506,10 @@ pub mod kernel_benchmarks {
                 fn main_task() -> r3::kernel::Task<System> {
                     <Self as benchmark::Bencher<System>>::main_task()
                 }
+
+                const INTERRUPT_LINES: &'static [r3::kernel::InterruptNum] = D::INTERRUPT_LINES;
+                const INTERRUPT_PRIORITIES: &'static [r3::kernel::InterruptPriority] =
+                    D::INTERRUPT_PRIORITIES;
             }
         };
     }
@@ -525,6 +586,7 @@ pub mod kernel_benchmarks {
 
     define_kernel_benchmarks! {
         [$]
+        (mod interrupt_dispatch {}, "interrupt_dispatch"),
         (mod mutex_ceiling {}, "mutex_ceiling"),
         (mod mutex_none {}, "mutex_none"),
         (mod port {}, "port"),